@@ -304,6 +304,10 @@ impl<T> Analyzed<T> {
             }
         };
         self.post_visit_expressions_in_identities_mut(algebraic_visitor);
+        self.intermediate_columns
+            .values_mut()
+            .flat_map(|(_, definitions)| definitions)
+            .for_each(|e| e.post_visit_expressions_mut(algebraic_visitor));
     }
 
     /// Removes the given set of trait impls, identified by their index
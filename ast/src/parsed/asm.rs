@@ -32,23 +32,48 @@ pub struct ASMModule {
 #[derive(Debug, Clone, PartialEq, Eq, From)]
 pub enum ModuleStatement {
     SymbolDefinition(SymbolDefinition),
-    PilStatement(PilStatement),
+    PilStatement(Visibility, PilStatement),
 }
 
 impl ModuleStatement {
     pub fn defined_names(&self) -> Box<dyn Iterator<Item = &String> + '_> {
         match self {
             ModuleStatement::SymbolDefinition(d) => Box::new(once(&d.name)),
-            ModuleStatement::PilStatement(s) => {
+            ModuleStatement::PilStatement(_, s) => {
                 Box::new(s.symbol_definition_names().map(|(name, _)| name))
             }
         }
     }
+
+    /// Returns the visibility of the symbols defined by this statement.
+    pub fn visibility(&self) -> Visibility {
+        match self {
+            ModuleStatement::SymbolDefinition(d) => d.visibility,
+            ModuleStatement::PilStatement(visibility, _) => *visibility,
+        }
+    }
+}
+
+/// The visibility of a symbol defined at module level.
+/// Private symbols can only be accessed from inside the module they are
+/// defined in (including its sub-modules).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Visibility {
+    Public,
+    #[default]
+    Private,
+}
+
+impl Visibility {
+    pub fn is_public(&self) -> bool {
+        *self == Visibility::Public
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SymbolDefinition {
     pub name: String,
+    pub visibility: Visibility,
     pub value: SymbolValue,
 }
 
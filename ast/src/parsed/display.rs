@@ -40,14 +40,28 @@ impl Display for ModuleStatement {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         match self {
             ModuleStatement::SymbolDefinition(symbol_def) => write!(f, "{symbol_def}"),
-            ModuleStatement::PilStatement(s) => write!(f, "{s}"),
+            ModuleStatement::PilStatement(visibility, s) => write!(f, "{visibility}{s}"),
+        }
+    }
+}
+
+impl Display for Visibility {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Visibility::Public => write!(f, "pub "),
+            Visibility::Private => Ok(()),
         }
     }
 }
 
 impl Display for SymbolDefinition {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        let SymbolDefinition { name, value } = self;
+        let SymbolDefinition {
+            name,
+            visibility,
+            value,
+        } = self;
+        write!(f, "{visibility}")?;
         match value {
             SymbolValue::Machine(m) => {
                 write!(f, "machine {name}{m}")
@@ -406,13 +420,39 @@ impl<E: Display> Display for StatementInsideBlock<E> {
         match self {
             StatementInsideBlock::LetStatement(s) => write!(f, "{s}"),
             StatementInsideBlock::Expression(e) => write!(f, "{e};"),
+            StatementInsideBlock::Assignment(a) => write!(f, "{a}"),
+            StatementInsideBlock::While(w) => write!(f, "{w}"),
+            StatementInsideBlock::For(l) => write!(f, "{l}"),
         }
     }
 }
 
+impl<E: Display> Display for AssignmentInsideBlock<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{} = {};", self.target, self.value)
+    }
+}
+
+impl<E: Display> Display for WhileLoop<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "while {} {}", self.condition, self.body)
+    }
+}
+
+impl<E: Display> Display for ForLoop<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "for {} in {} {}", self.pattern, self.iterable, self.body)
+    }
+}
+
 impl<E: Display> Display for LetStatementInsideBlock<E> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        write!(f, "let {}", self.pattern)?;
+        write!(
+            f,
+            "let {}{}",
+            if self.mutable { "mut " } else { "" },
+            self.pattern
+        )?;
         if let Some(ty) = &self.ty {
             write!(f, ": {ty}")?;
         }
@@ -26,7 +26,9 @@ pub trait Folder {
                     SymbolValue::Module(module) => self.fold_module(module).map(From::from),
                 }
                 .map(|value| ModuleStatement::SymbolDefinition(SymbolDefinition { value, ..d })),
-                ModuleStatement::PilStatement(s) => self.fold_pil_statement(s).map(From::from),
+                ModuleStatement::PilStatement(visibility, s) => self
+                    .fold_pil_statement(s)
+                    .map(|s| ModuleStatement::PilStatement(visibility, s)),
             })
             .collect::<Result<Vec<_>, _>>()?;
 
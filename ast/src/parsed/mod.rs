@@ -1315,6 +1315,13 @@ pub enum StatementInsideBlock<E = Expression<NamespacedPolynomialReference>> {
     // TODO add a source ref here.
     LetStatement(LetStatementInsideBlock<E>),
     Expression(E),
+    /// Assignment to a mutable local variable.
+    /// The parser never creates this variant, since assignments are
+    /// syntactically identical to identities. They are distinguished during
+    /// symbol resolution, when it is known which local variables are mutable.
+    Assignment(AssignmentInsideBlock<E>),
+    While(WhileLoop<E>),
+    For(ForLoop<E>),
 }
 
 impl<E> Children<E> for StatementInsideBlock<E> {
@@ -1322,6 +1329,9 @@ impl<E> Children<E> for StatementInsideBlock<E> {
         match self {
             StatementInsideBlock::LetStatement(l) => Box::new(l.children()),
             StatementInsideBlock::Expression(e) => Box::new(once(e)),
+            StatementInsideBlock::Assignment(a) => Box::new(a.children()),
+            StatementInsideBlock::While(w) => Box::new(w.children()),
+            StatementInsideBlock::For(f) => Box::new(f.children()),
         }
     }
 
@@ -1329,6 +1339,9 @@ impl<E> Children<E> for StatementInsideBlock<E> {
         match self {
             StatementInsideBlock::LetStatement(l) => Box::new(l.children_mut()),
             StatementInsideBlock::Expression(e) => Box::new(once(e)),
+            StatementInsideBlock::Assignment(a) => Box::new(a.children_mut()),
+            StatementInsideBlock::While(w) => Box::new(w.children_mut()),
+            StatementInsideBlock::For(f) => Box::new(f.children_mut()),
         }
     }
 }
@@ -1336,6 +1349,9 @@ impl<E> Children<E> for StatementInsideBlock<E> {
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct LetStatementInsideBlock<E = Expression<NamespacedPolynomialReference>> {
     pub pattern: Pattern,
+    /// If true, the variable can be re-assigned. Only allowed
+    /// if the pattern is a single variable.
+    pub mutable: bool,
     pub ty: Option<Type<u64>>,
     pub value: Option<E>,
 }
@@ -1356,6 +1372,60 @@ impl<E> From<LetStatementInsideBlock<E>> for StatementInsideBlock<E> {
     }
 }
 
+/// Assignment of a new value to a mutable local variable.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AssignmentInsideBlock<E = Expression<NamespacedPolynomialReference>> {
+    /// The assigned variable, always a reference to a local variable.
+    pub target: E,
+    pub value: E,
+}
+
+impl<E> Children<E> for AssignmentInsideBlock<E> {
+    fn children(&self) -> Box<dyn Iterator<Item = &E> + '_> {
+        Box::new([&self.target, &self.value].into_iter())
+    }
+
+    fn children_mut(&mut self) -> Box<dyn Iterator<Item = &mut E> + '_> {
+        Box::new([&mut self.target, &mut self.value].into_iter())
+    }
+}
+
+/// A loop `while <condition> { <body> }`. The body has to be of type `()`.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct WhileLoop<E = Expression<NamespacedPolynomialReference>> {
+    pub condition: Box<E>,
+    pub body: Box<E>,
+}
+
+impl<E> Children<E> for WhileLoop<E> {
+    fn children(&self) -> Box<dyn Iterator<Item = &E> + '_> {
+        Box::new([self.condition.as_ref(), self.body.as_ref()].into_iter())
+    }
+
+    fn children_mut(&mut self) -> Box<dyn Iterator<Item = &mut E> + '_> {
+        Box::new([self.condition.as_mut(), self.body.as_mut()].into_iter())
+    }
+}
+
+/// A loop `for <pattern> in <array> { <body> }` that executes the body once for every
+/// element of the array. The body has to be of type `()`.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ForLoop<E = Expression<NamespacedPolynomialReference>> {
+    pub pattern: Pattern,
+    pub iterable: Box<E>,
+    pub body: Box<E>,
+}
+
+impl<E> Children<E> for ForLoop<E> {
+    fn children(&self) -> Box<dyn Iterator<Item = &E> + '_> {
+        Box::new([self.iterable.as_ref(), self.body.as_ref()].into_iter())
+    }
+
+    fn children_mut(&mut self) -> Box<dyn Iterator<Item = &mut E> + '_> {
+        Box::new([self.iterable.as_mut(), self.body.as_mut()].into_iter())
+    }
+}
+
 /// The definition of a function (excluding its name):
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
 pub enum FunctionDefinition {
@@ -20,29 +20,29 @@ use crate::{Backend, BackendFactory, BackendOptions, Error, Proof};
 use self::sub_prover::RunStatus;
 
 /// Maps each size to the corresponding verification key.
-type VerificationKeyBySize = BTreeMap<DegreeType, Vec<u8>>;
+pub(crate) type VerificationKeyBySize = BTreeMap<DegreeType, Vec<u8>>;
 
 /// A composite verification key that contains a verification key for each machine separately.
 #[derive(Serialize, Deserialize)]
-struct CompositeVerificationKey {
+pub(crate) struct CompositeVerificationKey {
     /// Verification keys for each machine (if available, otherwise None), sorted by machine name.
-    verification_keys: Vec<Option<VerificationKeyBySize>>,
+    pub(crate) verification_keys: Vec<Option<VerificationKeyBySize>>,
 }
 
 /// A proof for a single machine.
 #[derive(Serialize, Deserialize)]
-struct MachineProof {
+pub(crate) struct MachineProof {
     /// The (dynamic) size of the machine.
-    size: DegreeType,
+    pub(crate) size: DegreeType,
     /// The proof for the machine.
-    proof: Vec<u8>,
+    pub(crate) proof: Vec<u8>,
 }
 
 /// A composite proof that contains a proof for each machine separately, sorted by machine name.
 #[derive(Serialize, Deserialize)]
-struct CompositeProof {
+pub(crate) struct CompositeProof {
     /// Machine proofs, sorted by machine name.
-    proofs: Vec<MachineProof>,
+    pub(crate) proofs: Vec<MachineProof>,
 }
 
 pub(crate) struct CompositeBackendFactory<F: FieldElement, B: BackendFactory<F>> {
@@ -80,12 +80,17 @@ impl<F: FieldElement, B: BackendFactory<F>> BackendFactory<F> for CompositeBacke
 
         let pils = powdr_backend_utils::split_pil(&pil);
 
-        // Read the setup once (if any) to pass to all backends.
-        let setup_bytes = setup.map(|setup| {
-            let mut setup_data = Vec::new();
-            setup.read_to_end(&mut setup_data).unwrap();
-            setup_data
-        });
+        // Read the setup once (if any) to pass to all backends. If none is
+        // provided, generate a single setup for the largest size of any machine,
+        // so that backends do not generate their own setup for every size.
+        let setup_bytes = match setup {
+            Some(setup) => {
+                let mut setup_data = Vec::new();
+                setup.read_to_end(&mut setup_data).unwrap();
+                Some(setup_data)
+            }
+            None => self.generate_shared_setup(&pil)?,
+        };
 
         // Read all provided verification keys
         let verification_keys = verification_key
@@ -168,6 +173,25 @@ impl<F: FieldElement, B: BackendFactory<F>> BackendFactory<F> for CompositeBacke
     }
 }
 
+impl<F: FieldElement, B: BackendFactory<F>> CompositeBackendFactory<F, B> {
+    /// Generates a setup large enough for all machines, or returns `None` if the
+    /// backend does not use a setup.
+    fn generate_shared_setup(&self, pil: &Analyzed<F>) -> Result<Option<Vec<u8>>, Error> {
+        let Some(max_size) = pil.degree_ranges().iter().map(|range| range.max).max() else {
+            return Ok(None);
+        };
+        let mut setup = Vec::new();
+        match self.factory.generate_setup(max_size, &mut setup) {
+            Ok(()) => {
+                log::info!("Generated a setup of size {max_size} shared by all machines.");
+                Ok(Some(setup))
+            }
+            Err(Error::NoSetupAvailable) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
 fn log_machine_stats<T: FieldElement>(machine_name: &str, pil: &Analyzed<T>) {
     let num_witness_columns = pil.commitment_count();
     let num_fixed_columns = pil.constant_count();
@@ -221,7 +245,7 @@ pub(crate) struct CompositeBackend<F> {
 /// # Panics
 /// Panics if the machine PIL contains definitions with different degrees, or if the machine
 /// already has a degree set that is different from the provided degree.
-fn set_size<F: Clone>(pil: Arc<Analyzed<F>>, degree: DegreeType) -> Arc<Analyzed<F>> {
+pub(crate) fn set_size<F: Clone>(pil: Arc<Analyzed<F>>, degree: DegreeType) -> Arc<Analyzed<F>> {
     let current_ranges = pil.degree_ranges();
     assert!(
         current_ranges.len() <= 1,
@@ -50,10 +50,16 @@ impl ProofType {
     }
 }
 
+/// The base 2 logarithm of the blowup factor of the low-degree extension.
+const LOG_BLOWUP: usize = 1;
+
+/// The largest constraint degree whose quotient fits into the low-degree extension.
+pub(crate) const MAX_CONSTRAINT_DEGREE: usize = (1 << LOG_BLOWUP) + 1;
+
 fn create_stark_struct(degree: DegreeType, hash_type: &str) -> StarkStruct {
     assert!(degree > 1);
     let n_bits = (DegreeType::BITS - (degree - 1).leading_zeros()) as usize;
-    let n_bits_ext = n_bits + 1;
+    let n_bits_ext = n_bits + LOG_BLOWUP;
 
     let steps = (2..=n_bits_ext)
         .rev()
@@ -10,8 +10,8 @@ use halo2_proofs::{
 use halo2_wrong_ecc::{
     integer::rns::Rns,
     maingate::{
-        MainGate, MainGateConfig, MainGateInstructions, RangeChip, RangeConfig, RangeInstructions,
-        RegionCtx,
+        AssignedValue, MainGate, MainGateConfig, MainGateInstructions, RangeChip, RangeConfig,
+        RangeInstructions, RegionCtx,
     },
     EccConfig,
 };
@@ -34,7 +34,7 @@ use snark_verifier::{
         self,
         halo2::{compile, transcript::evm::EvmTranscript, Config},
     },
-    util::arithmetic::{fe_to_limbs, PrimeField},
+    util::arithmetic::{fe_from_limbs, fe_to_limbs, CurveAffine, PrimeField},
     verifier::{self, plonk::PlonkProtocol, SnarkVerifier},
 };
 
@@ -136,12 +136,17 @@ impl SnarkWitness {
     }
 }
 
+/// Verifies the snarks in-circuit and returns their assigned instances, in the
+/// order of the snarks, together with the accumulator.
 pub fn aggregate<'a>(
     svk: &Svk,
     loader: &Rc<Halo2Loader<'a>>,
     snarks: &[SnarkWitness],
     as_proof: Value<&'_ [u8]>,
-) -> KzgAccumulator<G1Affine, Rc<Halo2Loader<'a>>> {
+) -> (
+    Vec<AssignedValue<Fr>>,
+    KzgAccumulator<G1Affine, Rc<Halo2Loader<'a>>>,
+) {
     let assign_instances = |instances: &[Vec<Value<Fr>>]| {
         instances
             .iter()
@@ -154,6 +159,7 @@ pub fn aggregate<'a>(
             .collect_vec()
     };
 
+    let mut assigned_instances = Vec::new();
     let accumulators = snarks
         .iter()
         .flat_map(|snark| {
@@ -164,7 +170,15 @@ pub fn aggregate<'a>(
             let proof =
                 PlonkSuccinctVerifier::read_proof(svk, &protocol, &instances, &mut transcript)
                     .unwrap();
-            PlonkSuccinctVerifier::verify(svk, &protocol, &instances, &proof).unwrap()
+            let accumulators =
+                PlonkSuccinctVerifier::verify(svk, &protocol, &instances, &proof).unwrap();
+            assigned_instances.extend(
+                instances
+                    .into_iter()
+                    .flatten()
+                    .map(|instance| instance.into_assigned()),
+            );
+            accumulators
         })
         .collect_vec();
 
@@ -174,7 +188,7 @@ pub fn aggregate<'a>(
         As::verify(&Default::default(), &accumulators, &proof).unwrap()
     };
 
-    accumulator
+    (assigned_instances, accumulator)
 }
 
 #[derive(Clone)]
@@ -258,9 +272,13 @@ impl AggregationCircuit {
         )
         .expect("Aggregated proof should be valid");
         let KzgAccumulator { lhs, rhs } = accumulator;
+        // The accumulator limbs, followed by the instances of the snarks.
         let instances = [lhs.x, lhs.y, rhs.x, rhs.y]
             .map(fe_to_limbs::<_, _, LIMBS, BITS>)
-            .concat();
+            .into_iter()
+            .flatten()
+            .chain(snarks.iter().flat_map(|snark| snark.instances.concat()))
+            .collect();
 
         Self {
             svk,
@@ -289,8 +307,10 @@ impl AggregationCircuit {
         (0..4 * LIMBS).map(|idx| (0, idx)).collect()
     }
 
-    pub fn num_instance() -> Vec<usize> {
-        vec![4 * LIMBS]
+    /// The number of instances: the accumulator limbs, followed by the
+    /// `num_publics` instances of the aggregated snarks.
+    pub fn num_instance(num_publics: usize) -> Vec<usize> {
+        vec![4 * LIMBS + num_publics]
     }
 
     pub fn instances(&self) -> Vec<Vec<Fr>> {
@@ -338,14 +358,15 @@ impl Circuit<Fr> for AggregationCircuit {
 
         range_chip.load_table(&mut layouter)?;
 
-        let accumulator_limbs = layouter.assign_region(
+        let (accumulator_limbs, instances) = layouter.assign_region(
             || "",
             |region| {
                 let ctx = RegionCtx::new(region, 0);
 
                 let ecc_chip = config.ecc_chip();
                 let loader = Halo2Loader::new(ecc_chip, ctx);
-                let accumulator = aggregate(&self.svk, &loader, &self.snarks, self.as_proof());
+                let (instances, accumulator) =
+                    aggregate(&self.svk, &loader, &self.snarks, self.as_proof());
 
                 let accumulator_limbs = [accumulator.lhs, accumulator.rhs]
                     .iter()
@@ -358,18 +379,42 @@ impl Circuit<Fr> for AggregationCircuit {
                     .into_iter()
                     .flatten();
 
-                Ok(accumulator_limbs)
+                Ok((accumulator_limbs, instances))
             },
         )?;
 
-        for (row, limb) in accumulator_limbs.enumerate() {
-            main_gate.expose_public(layouter.namespace(|| ""), limb, row)?;
+        for (row, cell) in accumulator_limbs.chain(instances).enumerate() {
+            main_gate.expose_public(layouter.namespace(|| ""), cell, row)?;
         }
 
         Ok(())
     }
 }
 
+/// Decides the KZG accumulator whose limbs are the instances of the aggregation
+/// circuit, i.e. checks the pairing that all aggregated proofs were reduced to.
+pub fn decide_accumulator(params: &ParamsKZG<Bn256>, limbs: &[Fr]) -> Result<(), String> {
+    if limbs.len() != 4 * LIMBS {
+        return Err(format!(
+            "Expected {} accumulator limbs, got {}",
+            4 * LIMBS,
+            limbs.len()
+        ));
+    }
+    let [lhs_x, lhs_y, rhs_x, rhs_y] = [0, 1, 2, 3].map(|i| {
+        fe_from_limbs::<_, Fq, LIMBS, BITS>(limbs[i * LIMBS..(i + 1) * LIMBS].try_into().unwrap())
+    });
+    let point = |x, y| {
+        Option::from(G1Affine::from_xy(x, y))
+            .ok_or_else(|| "Accumulator is not on the curve".to_string())
+    };
+    As::decide(
+        &(params.get_g()[0], params.g2(), params.s_g2()).into(),
+        KzgAccumulator::new(point(lhs_x, lhs_y)?, point(rhs_x, rhs_y)?),
+    )
+    .map_err(|e| format!("Accumulator is invalid: {e:?}"))
+}
+
 pub fn gen_aggregation_solidity_verifier(
     params: &ParamsKZG<Bn256>,
     vk: &VerifyingKey<G1Affine>,
@@ -404,3 +449,26 @@ pub fn gen_aggregation_evm_verifier(
     let sol = gen_aggregation_solidity_verifier(params, vk, num_instance, accumulator_indices);
     evm::compile_solidity(&sol)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn limbs(lhs: G1Affine, rhs: G1Affine) -> Vec<Fr> {
+        let lhs = lhs.coordinates().unwrap();
+        let rhs = rhs.coordinates().unwrap();
+        [*lhs.x(), *lhs.y(), *rhs.x(), *rhs.y()]
+            .map(fe_to_limbs::<_, _, LIMBS, BITS>)
+            .concat()
+    }
+
+    #[test]
+    fn decide() {
+        let params = ParamsKZG::<Bn256>::new(4);
+        let g = params.get_g();
+        // e(s * G, H) = e(G, s * H)
+        assert!(decide_accumulator(&params, &limbs(g[1], g[0])).is_ok());
+        assert!(decide_accumulator(&params, &limbs(g[0], g[1])).is_err());
+        assert!(decide_accumulator(&params, &limbs(g[1], g[0])[1..]).is_err());
+    }
+}
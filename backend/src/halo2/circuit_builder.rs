@@ -82,7 +82,8 @@ pub(crate) struct PowdrCircuit<'a, T> {
     witgen_callback: Option<WitgenCallback<T>>,
 }
 
-fn get_publics<T: FieldElement>(analyzed: &Analyzed<T>) -> Vec<(String, usize)> {
+/// The column and row of each public value, in the order of the instance column.
+pub(crate) fn get_publics<T: FieldElement>(analyzed: &Analyzed<T>) -> Vec<(String, usize)> {
     let mut publics = analyzed
         .public_declarations
         .values()
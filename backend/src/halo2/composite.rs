@@ -0,0 +1,464 @@
+//! Halo2 proofs for PILs with several machines of possibly variable size.
+//!
+//! All machines share a single universal setup, which each machine backend
+//! downsizes to its degree. With the "poseidon" and "snark_single" options,
+//! this is the generic composite backend. With "snark_aggr", the Poseidon proofs
+//! of all machines are aggregated into a single SNARK, so that a program with
+//! several machines can be verified on Ethereum by a single verifier.
+
+use std::{collections::BTreeMap, io, path::PathBuf, sync::Arc, time::Instant};
+
+use halo2_proofs::{
+    halo2curves::bn256::{Bn256, Fr, G1Affine},
+    halo2curves::ff::PrimeField,
+    plonk::{keygen_pk, keygen_vk, VerifyingKey},
+    poly::{commitment::Params, kzg::commitment::ParamsKZG},
+    SerdeFormat,
+};
+use itertools::Itertools;
+use powdr_ast::analyzed::Analyzed;
+use powdr_executor::{constant_evaluator::VariablySizedColumn, witgen::WitgenCallback};
+use powdr_number::{Bn254Field, DegreeType};
+use serde::{Deserialize, Serialize};
+use snark_verifier::{
+    system::halo2::{compile, transcript::evm::EvmTranscript, Config},
+    verifier::plonk::PlonkProtocol,
+};
+
+use crate::composite::{
+    set_size, CompositeBackendFactory, CompositeProof, CompositeVerificationKey,
+};
+use crate::field_filter::generalize_factory;
+use crate::{Backend, BackendFactory, BackendOptions, Error, Proof};
+
+use super::{
+    aggregation,
+    circuit_builder::{convert_field, get_publics, PowdrCircuit},
+    prover::{degree_bits, gen_proof, generate_setup, verify_inner},
+    Bn254Factory, Halo2Proof, ProofType,
+};
+
+/// An aggregated proof of all machines.
+#[derive(Serialize, Deserialize)]
+struct AggregatedProof {
+    /// The sizes of the machines, sorted by machine name.
+    sizes: Vec<DegreeType>,
+    /// The proof of the aggregation circuit.
+    proof: Vec<u8>,
+    /// The limbs of the accumulator, as returned by `Fr::to_repr`. The public
+    /// values of the aggregation circuit are these limbs, followed by the public
+    /// values of the machines.
+    accumulator: Vec<[u8; 32]>,
+}
+
+struct Bn254CompositeFactory;
+
+impl BackendFactory<Bn254Field> for Bn254CompositeFactory {
+    fn create(
+        &self,
+        pil: Arc<Analyzed<Bn254Field>>,
+        fixed: Arc<Vec<(String, VariablySizedColumn<Bn254Field>)>>,
+        output_dir: Option<PathBuf>,
+        setup: Option<&mut dyn io::Read>,
+        proving_key: Option<&mut dyn io::Read>,
+        verification_key: Option<&mut dyn io::Read>,
+        verification_app_key: Option<&mut dyn io::Read>,
+        options: BackendOptions,
+    ) -> Result<Box<dyn Backend<Bn254Field>>, Error> {
+        match ProofType::from(options.clone()) {
+            ProofType::Poseidon | ProofType::SnarkSingle => {
+                CompositeBackendFactory::new(Bn254Factory).create(
+                    pil,
+                    fixed,
+                    output_dir,
+                    setup,
+                    proving_key,
+                    verification_key,
+                    verification_app_key,
+                    options,
+                )
+            }
+            ProofType::SnarkAggr => {
+                if proving_key.is_some() {
+                    return Err(Error::NoProvingKeyAvailable);
+                }
+                if verification_app_key.is_some() {
+                    return Err(Error::BackendError(
+                        "Aggregated composite proofs do not use an app verification key"
+                            .to_string(),
+                    ));
+                }
+                Ok(Box::new(Halo2CompositeAggr::new(
+                    pil,
+                    fixed,
+                    output_dir,
+                    setup,
+                    verification_key,
+                )?))
+            }
+        }
+    }
+
+    fn generate_setup(&self, size: DegreeType, output: &mut dyn io::Write) -> Result<(), Error> {
+        Bn254Factory.generate_setup(size, output)
+    }
+}
+
+generalize_factory!(Halo2CompositeFactory <- Bn254CompositeFactory, [Bn254Field]);
+
+/// Aggregates the Poseidon proofs of all machines into a single SNARK.
+///
+/// The aggregation circuit depends on the sizes of the machines, so its
+/// verification key is derived from the machine verification keys for the
+/// sizes of a given proof.
+///
+/// The public values of the machines are public values of the aggregation
+/// circuit as well, sorted by machine name and, within a machine, in the order
+/// of its instance column.
+struct Halo2CompositeAggr {
+    /// The composite backend creating a Poseidon proof for each machine.
+    machines: Box<dyn Backend<Bn254Field>>,
+    /// The PIL of each machine, by machine name.
+    machine_pils: BTreeMap<String, Arc<Analyzed<Bn254Field>>>,
+    /// For each public value of the machines, in the order of the aggregation
+    /// circuit, its index in the public values of the whole PIL in source order.
+    public_indices: Vec<usize>,
+    /// The universal setup, downsized for the machine proofs.
+    params: ParamsKZG<Bn256>,
+}
+
+impl Halo2CompositeAggr {
+    fn new(
+        pil: Arc<Analyzed<Bn254Field>>,
+        fixed: Arc<Vec<(String, VariablySizedColumn<Bn254Field>)>>,
+        output_dir: Option<PathBuf>,
+        setup: Option<&mut dyn io::Read>,
+        verification_key: Option<&mut dyn io::Read>,
+    ) -> Result<Self, Error> {
+        let params = match setup {
+            Some(mut setup) => ParamsKZG::<Bn256>::read(&mut setup)?,
+            None => generate_setup(
+                pil.degree_ranges()
+                    .into_iter()
+                    .map(|range| range.max)
+                    .max()
+                    .unwrap_or_default(),
+            ),
+        };
+        let mut setup_bytes = Vec::new();
+        params.write(&mut setup_bytes)?;
+        let mut setup_reader = setup_bytes.as_slice();
+
+        let machine_pils: BTreeMap<_, _> = powdr_backend_utils::split_pil(&pil)
+            .into_iter()
+            .map(|(name, pil)| (name, Arc::new(pil)))
+            .collect();
+
+        let publics_in_source_order = pil
+            .public_declarations_in_source_order()
+            .map(|(_, public)| (public.referenced_poly_name(), public.index as usize))
+            .collect_vec();
+        let public_indices = machine_pils
+            .values()
+            .flat_map(|pil| get_publics(pil))
+            .map(|public| {
+                publics_in_source_order
+                    .iter()
+                    .position(|p| *p == public)
+                    .unwrap()
+            })
+            .collect();
+
+        let machines = CompositeBackendFactory::new(Bn254Factory).create(
+            pil,
+            fixed,
+            output_dir,
+            Some(&mut setup_reader as &mut dyn io::Read),
+            None,
+            verification_key,
+            None,
+            "poseidon".to_string(),
+        )?;
+
+        Ok(Self {
+            machines,
+            machine_pils,
+            public_indices,
+            params,
+        })
+    }
+
+    /// Returns the protocol of each machine for the given sizes.
+    fn protocols(&self, sizes: &[DegreeType]) -> Result<Vec<PlonkProtocol<G1Affine>>, Error> {
+        let verification_keys: CompositeVerificationKey =
+            bincode::deserialize(&self.machines.verification_key_bytes()?)
+                .map_err(|e| format!("Failed to deserialize verification key: {e}"))?;
+        verification_keys
+            .verification_keys
+            .into_iter()
+            .zip_eq(self.machine_pils.values())
+            .zip_eq(sizes)
+            .map(|((keys, pil), size)| {
+                let key = keys
+                    .as_ref()
+                    .and_then(|keys| keys.get(size))
+                    .ok_or(Error::NoVerificationAvailable)?;
+                let vkey = VerifyingKey::<G1Affine>::read::<_, PowdrCircuit<Bn254Field>>(
+                    &mut key.as_slice(),
+                    SerdeFormat::Processed,
+                    set_size(pil.clone(), *size).into(),
+                )?;
+
+                let mut params = self.params.clone();
+                params.downsize(degree_bits(*size));
+                Ok(compile(
+                    &params,
+                    &vkey,
+                    Config::kzg().with_num_instance(vec![get_publics(pil).len()]),
+                ))
+            })
+            .collect()
+    }
+
+    fn aggregation_circuit_without_witness(
+        &self,
+        protocols: &[PlonkProtocol<G1Affine>],
+    ) -> aggregation::AggregationCircuit {
+        aggregation::AggregationCircuit::new_without_witness(
+            &self.params,
+            protocols
+                .iter()
+                .cloned()
+                .map(aggregation::Snark::new_without_witness),
+        )
+    }
+
+    fn aggregation_verification_key(
+        &self,
+        sizes: &[DegreeType],
+    ) -> Result<VerifyingKey<G1Affine>, Error> {
+        let circuit = self.aggregation_circuit_without_witness(&self.protocols(sizes)?);
+        keygen_vk(&self.params, &circuit).map_err(|e| Error::BackendError(e.to_string()))
+    }
+
+    /// The sizes to export the Ethereum verifier for. The aggregation circuit
+    /// depends on the machine sizes, so this fails unless every machine has a
+    /// single size.
+    fn verifier_sizes(&self) -> Result<Vec<DegreeType>, Error> {
+        self.machine_pils
+            .iter()
+            .map(|(name, pil)| match pil.degree_ranges().into_iter().exactly_one() {
+                Ok(range) if range.min == range.max => Ok(range.max),
+                _ => Err(Error::BackendError(format!(
+                    "Machine {name} has a variable size, but an Ethereum verifier for aggregated proofs can only be exported if every machine has a single size"
+                ))),
+            })
+            .collect()
+    }
+
+    /// Returns the public values of the aggregation circuit for the given
+    /// accumulator limbs and the public values of the whole PIL.
+    fn instances(&self, accumulator: Vec<Fr>, publics: &[Bn254Field]) -> Result<Vec<Fr>, Error> {
+        if publics.len() != self.public_indices.len() {
+            return Err(Error::BackendError(format!(
+                "Expected {} public values, got {}",
+                self.public_indices.len(),
+                publics.len()
+            )));
+        }
+        Ok(accumulator
+            .into_iter()
+            .chain(
+                self.public_indices
+                    .iter()
+                    .map(|i| convert_field(publics[*i])),
+            )
+            .collect())
+    }
+}
+
+impl Backend<Bn254Field> for Halo2CompositeAggr {
+    fn prove(
+        &self,
+        witness: &[(String, Vec<Bn254Field>)],
+        prev_proof: Option<Proof>,
+        witgen_callback: WitgenCallback<Bn254Field>,
+    ) -> Result<Proof, Error> {
+        // A previous proof is a composite Poseidon proof that only needs to be aggregated.
+        let machine_proofs = match prev_proof {
+            Some(proof) => proof,
+            None => self.machines.prove(witness, None, witgen_callback)?,
+        };
+        let machine_proofs: CompositeProof = bincode::deserialize(&machine_proofs)
+            .map_err(|e| format!("Failed to deserialize machine proofs: {e}"))?;
+        let sizes = machine_proofs.proofs.iter().map(|p| p.size).collect_vec();
+
+        log::info!("Generating VK and PK for aggregation snark...");
+        let protocols = self.protocols(&sizes)?;
+        let circuit = self.aggregation_circuit_without_witness(&protocols);
+        let vk = keygen_vk(&self.params, &circuit).map_err(|e| e.to_string())?;
+        let pk = keygen_pk(&self.params, vk.clone(), &circuit).map_err(|e| e.to_string())?;
+
+        let snarks = protocols
+            .into_iter()
+            .zip_eq(machine_proofs.proofs)
+            .map(|(protocol, machine_proof)| {
+                let proof: Halo2Proof = bincode::deserialize(&machine_proof.proof)
+                    .map_err(|e| format!("Failed to deserialize machine proof: {e}"))?;
+                let publics = proof
+                    .publics
+                    .iter()
+                    .map(|public| public.parse::<Bn254Field>().map(convert_field))
+                    .collect::<Result<Vec<Fr>, _>>()?;
+                Ok::<_, Error>(aggregation::Snark::new(
+                    protocol,
+                    vec![publics],
+                    proof.proof,
+                ))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        log::info!("Aggregating {} machine proofs...", snarks.len());
+        let start = Instant::now();
+        let circuit = aggregation::AggregationCircuit::new(&self.params, snarks);
+        let instances = circuit.instances();
+        let proof = gen_proof::<_, _, EvmTranscript<G1Affine, _, _, _>>(
+            &self.params,
+            &pk,
+            circuit,
+            &instances,
+        )?;
+        log::info!("Time taken: {:?}", start.elapsed());
+
+        verify_inner::<_, EvmTranscript<G1Affine, _, _, _>>(&vk, &self.params, &proof, &instances)?;
+
+        let proof = AggregatedProof {
+            sizes,
+            proof,
+            accumulator: instances[0]
+                .iter()
+                .take(aggregation::AggregationCircuit::accumulator_indices().len())
+                .map(|x| x.to_repr())
+                .collect(),
+        };
+        Ok(bincode::serialize(&proof).unwrap())
+    }
+
+    fn verify(&self, proof: &[u8], instances: &[Vec<Bn254Field>]) -> Result<(), Error> {
+        let [publics] = instances else {
+            return Err(Error::BackendError(format!(
+                "Expected a single vector of public values, got {}",
+                instances.len()
+            )));
+        };
+        let proof: AggregatedProof =
+            bincode::deserialize(proof).map_err(|e| format!("Failed to deserialize proof: {e}"))?;
+        let accumulator = proof
+            .accumulator
+            .iter()
+            .map(|bytes| Option::from(Fr::from_repr(*bytes)))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| Error::BackendError("Invalid accumulator".to_string()))?;
+
+        let vk = self.aggregation_verification_key(&proof.sizes)?;
+        verify_inner::<_, EvmTranscript<G1Affine, _, _, _>>(
+            &vk,
+            &self.params,
+            &proof.proof,
+            &[self.instances(accumulator.clone(), publics)?],
+        )?;
+        // The aggregation proof only shows that the accumulator was computed
+        // correctly, the machine proofs are valid if the accumulator is.
+        Ok(aggregation::decide_accumulator(&self.params, &accumulator)?)
+    }
+
+    fn export_setup(&self, mut output: &mut dyn io::Write) -> Result<(), Error> {
+        Ok(self.params.write(&mut output)?)
+    }
+
+    fn verification_key_bytes(&self) -> Result<Vec<u8>, Error> {
+        self.machines.verification_key_bytes()
+    }
+
+    fn export_ethereum_verifier(&self, output: &mut dyn io::Write) -> Result<(), Error> {
+        let vk = self.aggregation_verification_key(&self.verifier_sizes()?)?;
+        let verifier = aggregation::gen_aggregation_solidity_verifier(
+            &self.params,
+            &vk,
+            aggregation::AggregationCircuit::num_instance(self.public_indices.len()),
+            aggregation::AggregationCircuit::accumulator_indices(),
+        );
+        Ok(output.write_all(verifier.as_bytes())?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use powdr_pipeline::Pipeline;
+
+    use super::*;
+    use crate::halo2::prover::evm_verify;
+
+    #[test]
+    #[ignore = "Too slow"]
+    fn aggregate_with_publics() {
+        // The machines are sorted by name in the aggregation circuit, which
+        // differs from the source order of the publics.
+        let content = r#"
+        namespace Mul(16);
+            col witness x;
+            col witness y;
+            col witness z;
+            x - 2 = 0;
+            y - 3 = 0;
+            x * y = z;
+            public outz = z(15);
+
+        namespace Add(8);
+            col witness x;
+            col witness y;
+            col witness z;
+            x = 0;
+            y - 1 = 0;
+            x + y = z;
+            public outz = z(7);
+        "#;
+        let mut pipeline = Pipeline::<Bn254Field>::default().from_pil_string(content.to_string());
+        let pil = pipeline.compute_optimized_pil().unwrap();
+        let fixed = pipeline.compute_fixed_cols().unwrap();
+        let witgen_callback = pipeline.witgen_callback().unwrap();
+        let witness = pipeline.compute_witness().unwrap();
+        let publics = pipeline
+            .publics()
+            .unwrap()
+            .into_iter()
+            .map(|(_, value)| value.unwrap())
+            .collect_vec();
+        assert_eq!(publics, vec![6.into(), 1.into()]);
+
+        let backend = Halo2CompositeAggr::new(pil, fixed, None, None, None).unwrap();
+        let proof = backend.prove(&witness, None, witgen_callback).unwrap();
+        backend.verify(&proof, &[publics.clone()]).unwrap();
+        let forged = vec![publics[0], 2.into()];
+        assert!(backend.verify(&proof, &[forged]).is_err());
+
+        // The exported Ethereum verifier accepts the proof.
+        let proof: AggregatedProof = bincode::deserialize(&proof).unwrap();
+        let vk = backend
+            .aggregation_verification_key(&backend.verifier_sizes().unwrap())
+            .unwrap();
+        let deployment_code = aggregation::gen_aggregation_evm_verifier(
+            &backend.params,
+            &vk,
+            aggregation::AggregationCircuit::num_instance(backend.public_indices.len()),
+            aggregation::AggregationCircuit::accumulator_indices(),
+        );
+        let accumulator = proof
+            .accumulator
+            .iter()
+            .map(|bytes| Fr::from_repr(*bytes).unwrap())
+            .collect();
+        let instances = backend.instances(accumulator, &publics).unwrap();
+        evm_verify(deployment_code, vec![instances], &proof.proof);
+    }
+}
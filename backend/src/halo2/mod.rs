@@ -12,18 +12,27 @@ use powdr_executor::witgen::WitgenCallback;
 use powdr_number::{Bn254Field, DegreeType, FieldElement};
 use prover::{generate_setup, Halo2Prover};
 
+pub(crate) use composite::Halo2CompositeFactory;
+
 use serde::de::{self, Deserializer};
 use serde::ser::Serializer;
 use serde::{Deserialize, Serialize};
 
 mod aggregation;
 mod circuit_builder;
+mod composite;
 mod mock_prover;
 mod prover;
 
 use halo2_proofs::poly::commitment::Params;
 use halo2_proofs::SerdeFormat;
 
+/// The largest degree of polynomial identities. Halo2 supports any degree, but
+/// the extended domain grows with the largest constraint degree. The circuit
+/// multiplies every identity by a selector, so identities of degree 3 result
+/// in constraints of degree 4, which a lookup needs anyway.
+pub(crate) const MAX_CONSTRAINT_DEGREE: usize = 3;
+
 #[derive(Clone)]
 enum ProofType {
     /// Create a single proof for a given PIL using Poseidon transcripts.
@@ -32,7 +41,8 @@ enum ProofType {
     /// which can be verified directly on Ethereum.
     SnarkSingle,
     /// Create a recursive proof that compresses a Poseidon proof,
-    /// which can be verified directly on Ethereum.
+    /// which can be verified directly on Ethereum. With the composite
+    /// backend, the Poseidon proofs of all machines are compressed into one.
     SnarkAggr,
 }
 
@@ -79,7 +89,7 @@ impl BackendFactory<Bn254Field> for Bn254Factory {
         &self,
         pil: Arc<Analyzed<Bn254Field>>,
         fixed: Arc<Vec<(String, VariablySizedColumn<Bn254Field>)>>,
-        _output_dir: Option<PathBuf>,
+        output_dir: Option<PathBuf>,
         setup: Option<&mut dyn io::Read>,
         proving_key: Option<&mut dyn io::Read>,
         verification_key: Option<&mut dyn io::Read>,
@@ -100,6 +110,8 @@ impl BackendFactory<Bn254Field> for Bn254Factory {
         let mut halo2 = Box::new(Halo2Prover::new(pil, fixed, setup, proof_type)?);
         if let Some(vk) = verification_key {
             halo2.add_verification_key(vk);
+        } else if let Some(output_dir) = output_dir {
+            halo2.cache_verification_key_in(&output_dir)?;
         }
         if let Some(vk_app) = verification_app_key {
             halo2.add_verification_app_key(vk_app);
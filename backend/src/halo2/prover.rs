@@ -46,8 +46,11 @@ use super::{
 
 use itertools::Itertools;
 use rand::rngs::OsRng;
+use sha2::{Digest, Sha256};
 use std::{
+    fs,
     io::{self, Cursor},
+    path::{Path, PathBuf},
     sync::Arc,
     time::Instant,
 };
@@ -65,10 +68,12 @@ pub struct Halo2Prover {
     // That is, if proof type is "snark_aggr", this will be
     // the vkey of the "poseidon" proof.
     vkey_app: Option<VerifyingKey<G1Affine>>,
+    // File in which the verification key of a single proof is cached.
+    vkey_cache: Option<PathBuf>,
     proof_type: ProofType,
 }
 
-fn degree_bits(degree: DegreeType) -> u32 {
+pub(crate) fn degree_bits(degree: DegreeType) -> u32 {
     DegreeType::BITS - degree.leading_zeros() + 1
 }
 
@@ -101,10 +106,33 @@ impl Halo2Prover {
             params,
             vkey: None,
             vkey_app: None,
+            vkey_cache: None,
             proof_type,
         })
     }
 
+    /// Caches the verification key of single proofs in `dir`. The file name
+    /// contains the degree and a SHA-256 hash of the PIL, the fixed columns and
+    /// the setup, so a stale key is never read.
+    pub fn cache_verification_key_in(&mut self, dir: &Path) -> Result<(), io::Error> {
+        let mut hasher = Sha256::new();
+        hasher.update(self.analyzed.to_string());
+        for (name, values) in self.fixed.iter() {
+            hasher.update(name);
+            for value in values {
+                hasher.update(value.to_bytes_le());
+            }
+        }
+        self.params.write(&mut hasher)?;
+        let file_name = format!(
+            "vkey_{}_{}.bin",
+            self.analyzed.degree(),
+            hex::encode(hasher.finalize())
+        );
+        self.vkey_cache = Some(dir.join(file_name));
+        Ok(())
+    }
+
     pub fn proof_type(&self) -> ProofType {
         self.proof_type.clone()
     }
@@ -128,10 +156,7 @@ impl Halo2Prover {
         let circuit = PowdrCircuit::new(self.analyzed.clone(), &self.fixed);
 
         log::info!("Generating PK for snark...");
-        let vk = match self.vkey {
-            Some(ref vk) => vk.clone(),
-            None => keygen_vk(&self.params, &circuit).unwrap(),
-        };
+        let vk = self.verification_key()?;
         let pk = keygen_pk(&self.params, vk.clone(), &circuit).unwrap();
 
         log::info!("Generating proof...");
@@ -149,7 +174,7 @@ impl Halo2Prover {
         let duration = start.elapsed();
         log::info!("Time taken: {:?}", duration);
 
-        match self.verify_inner::<_, TR>(&vk, &self.params, &proof, &publics) {
+        match verify_inner::<_, TR>(&vk, &self.params, &proof, &publics) {
             Ok(_) => {}
             Err(e) => {
                 return Err(e.to_string());
@@ -272,7 +297,7 @@ impl Halo2Prover {
         let deployment_code = aggregation::gen_aggregation_evm_verifier(
             &self.params,
             &vk_aggr,
-            aggregation::AggregationCircuit::num_instance(),
+            aggregation::AggregationCircuit::num_instance(0),
             aggregation::AggregationCircuit::accumulator_indices(),
         );
 
@@ -292,7 +317,7 @@ impl Halo2Prover {
         let duration = start.elapsed();
         log::info!("Time taken: {:?}", duration);
 
-        match self.verify_inner::<_, EvmTranscript<G1Affine, _, _, _>>(
+        match verify_inner::<_, EvmTranscript<G1Affine, _, _, _>>(
             &vk_aggr,
             &self.params,
             &proof,
@@ -357,11 +382,38 @@ impl Halo2Prover {
         }
 
         match self.proof_type {
-            ProofType::Poseidon | ProofType::SnarkSingle => self.generate_verification_key_single(),
+            ProofType::Poseidon | ProofType::SnarkSingle => self.cached_verification_key_single(),
             ProofType::SnarkAggr => self.generate_verification_key_aggr(),
         }
     }
 
+    fn cached_verification_key_single(&self) -> Result<VerifyingKey<G1Affine>, String> {
+        let Some(path) = self.vkey_cache.as_ref() else {
+            return self.generate_verification_key_single();
+        };
+
+        if let Ok(bytes) = fs::read(path) {
+            match VerifyingKey::<G1Affine>::read::<_, PowdrCircuit<Bn254Field>>(
+                &mut bytes.as_slice(),
+                SerdeFormat::Processed,
+                self.analyzed.clone().into(),
+            ) {
+                Ok(vkey) => {
+                    log::info!("Using cached verification key {}", path.display());
+                    return Ok(vkey);
+                }
+                Err(e) => log::warn!(
+                    "Ignoring invalid cached verification key {}: {e}",
+                    path.display()
+                ),
+            }
+        }
+
+        let vkey = self.generate_verification_key_single()?;
+        fs::write(path, vkey.to_bytes(SerdeFormat::Processed)).map_err(|e| e.to_string())?;
+        Ok(vkey)
+    }
+
     fn generate_verification_key_single(&self) -> Result<VerifyingKey<G1Affine>, String> {
         let circuit = PowdrCircuit::new(self.analyzed.clone(), &self.fixed);
         keygen_vk(&self.params, &circuit).map_err(|e| e.to_string())
@@ -391,45 +443,6 @@ impl Halo2Prover {
         keygen_vk(&self.params, &agg_circuit).map_err(|e| e.to_string())
     }
 
-    fn verify_inner<
-        E: EncodedChallenge<G1Affine>,
-        TR: TranscriptReadBuffer<Cursor<Vec<u8>>, G1Affine, E>,
-    >(
-        &self,
-        vkey: &VerifyingKey<G1Affine>,
-        params: &ParamsKZG<Bn256>,
-        proof: &[u8],
-        instances: &[Vec<Fr>],
-    ) -> Result<(), String> {
-        let instances = instances
-            .iter()
-            .map(|instances| instances.as_slice())
-            .collect_vec();
-
-        let mut transcript = TR::init(Cursor::new(proof.to_owned()));
-
-        let res = verify_proof::<_, VerifierGWC<_>, _, TR, _>(
-            params.verifier_params(),
-            vkey,
-            AccumulatorStrategy::new(self.params.verifier_params()),
-            &[instances.as_slice()],
-            &mut transcript,
-        )
-        .map(|strategy| {
-            <AccumulatorStrategy<'_, _> as VerificationStrategy<'_, _, VerifierGWC<_>>>::finalize(
-                strategy,
-            )
-        });
-
-        match res {
-            Err(e) => Err(e.to_string()),
-            Ok(valid) => match valid {
-                true => Ok(()),
-                false => Err("Proof is invalid".to_string()),
-            },
-        }
-    }
-
     fn verify_common<
         E: EncodedChallenge<G1Affine>,
         TR: TranscriptReadBuffer<Cursor<Vec<u8>>, G1Affine, E>,
@@ -448,7 +461,7 @@ impl Halo2Prover {
             })
             .collect_vec();
 
-        self.verify_inner::<_, TR>(self.vkey.as_ref().unwrap(), &self.params, proof, &instances)
+        verify_inner::<_, TR>(self.vkey.as_ref().unwrap(), &self.params, proof, &instances)
     }
 
     pub fn verify_poseidon(
@@ -499,7 +512,7 @@ impl Halo2Prover {
         let verifier = aggregation::gen_aggregation_solidity_verifier(
             &self.params,
             &vk,
-            aggregation::AggregationCircuit::num_instance(),
+            aggregation::AggregationCircuit::num_instance(0),
             aggregation::AggregationCircuit::accumulator_indices(),
         );
 
@@ -507,7 +520,45 @@ impl Halo2Prover {
     }
 }
 
-fn gen_proof<
+pub(crate) fn verify_inner<
+    E: EncodedChallenge<G1Affine>,
+    TR: TranscriptReadBuffer<Cursor<Vec<u8>>, G1Affine, E>,
+>(
+    vkey: &VerifyingKey<G1Affine>,
+    params: &ParamsKZG<Bn256>,
+    proof: &[u8],
+    instances: &[Vec<Fr>],
+) -> Result<(), String> {
+    let instances = instances
+        .iter()
+        .map(|instances| instances.as_slice())
+        .collect_vec();
+
+    let mut transcript = TR::init(Cursor::new(proof.to_owned()));
+
+    let res = verify_proof::<_, VerifierGWC<_>, _, TR, _>(
+        params.verifier_params(),
+        vkey,
+        AccumulatorStrategy::new(params.verifier_params()),
+        &[instances.as_slice()],
+        &mut transcript,
+    )
+    .map(|strategy| {
+        <AccumulatorStrategy<'_, _> as VerificationStrategy<'_, _, VerifierGWC<_>>>::finalize(
+            strategy,
+        )
+    });
+
+    match res {
+        Err(e) => Err(e.to_string()),
+        Ok(valid) => match valid {
+            true => Ok(()),
+            false => Err("Proof is invalid".to_string()),
+        },
+    }
+}
+
+pub(crate) fn gen_proof<
     C: Circuit<Fr>,
     E: EncodedChallenge<G1Affine>,
     TW: TranscriptWriterBuffer<Vec<u8>, G1Affine, E>,
@@ -538,7 +589,7 @@ fn gen_proof<
     Ok(proof)
 }
 
-fn evm_verify(deployment_code: Vec<u8>, instances: Vec<Vec<Fr>>, proof: &[u8]) {
+pub(crate) fn evm_verify(deployment_code: Vec<u8>, instances: Vec<Vec<Fr>>, proof: &[u8]) {
     let calldata = encode_calldata_snark_verifier(&instances, proof);
     let gas_cost = deploy_and_call(deployment_code, calldata).unwrap();
     log::info!("Gas cost: {gas_cost}");
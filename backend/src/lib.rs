@@ -11,6 +11,9 @@ mod stwo;
 
 mod composite;
 mod field_filter;
+mod proof_container;
+
+pub use proof_container::ProofContainer;
 
 use powdr_ast::analyzed::Analyzed;
 use powdr_executor::{constant_evaluator::VariablySizedColumn, witgen::WitgenCallback};
@@ -69,9 +72,7 @@ impl BackendType {
             #[cfg(feature = "halo2")]
             BackendType::Halo2 => Box::new(halo2::Halo2ProverFactory),
             #[cfg(feature = "halo2")]
-            BackendType::Halo2Composite => Box::new(composite::CompositeBackendFactory::new(
-                halo2::Halo2ProverFactory,
-            )),
+            BackendType::Halo2Composite => Box::new(halo2::Halo2CompositeFactory),
             #[cfg(feature = "halo2")]
             BackendType::Halo2Mock => Box::new(halo2::Halo2MockFactory),
             #[cfg(feature = "halo2")]
@@ -111,6 +112,41 @@ impl BackendType {
             _ => panic!("Empty backend."),
         }
     }
+
+    /// The largest degree of polynomial identities the backend supports with the
+    /// given options, or `None` if it is unbounded. If a verification key is
+    /// given, the options it records take precedence, since the backend is
+    /// created with them.
+    ///
+    /// The limit only applies to polynomial identities, the degree of the
+    /// expressions in lookups and permutations is not reduced.
+    #[cfg_attr(not(feature = "plonky3"), allow(unused_variables))]
+    pub fn max_constraint_degree(
+        &self,
+        options: &BackendOptions,
+        verification_key: Option<&[u8]>,
+    ) -> Option<usize> {
+        match self {
+            #[cfg(feature = "halo2")]
+            BackendType::Halo2
+            | BackendType::Halo2Composite
+            | BackendType::Halo2Mock
+            | BackendType::Halo2MockComposite => Some(halo2::MAX_CONSTRAINT_DEGREE),
+            #[cfg(feature = "estark-polygon")]
+            BackendType::EStarkPolygon | BackendType::EStarkPolygonComposite => {
+                Some(estark::MAX_CONSTRAINT_DEGREE)
+            }
+            #[cfg(feature = "estark-starky")]
+            BackendType::EStarkStarky
+            | BackendType::EStarkStarkyComposite
+            | BackendType::EStarkDump
+            | BackendType::EStarkDumpComposite => Some(estark::MAX_CONSTRAINT_DEGREE),
+            #[cfg(feature = "plonky3")]
+            BackendType::Plonky3 => plonky3::max_constraint_degree(options, verification_key),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -5,20 +5,42 @@ use std::{io, path::PathBuf, sync::Arc};
 use powdr_ast::analyzed::Analyzed;
 use powdr_executor::{constant_evaluator::VariablySizedColumn, witgen::WitgenCallback};
 use powdr_number::{BabyBearField, GoldilocksField, KoalaBearField, Mersenne31Field};
-use powdr_plonky3::{Commitment, FieldElementMap, ProverData};
+use powdr_plonky3::{
+    Blake3Hash, Commitment, CommitmentHash, ExplicitPlonky3Options, FieldElementMap, HashFunction,
+    KeccakHash, Plonky3Options, Poseidon2Hash, ProverData,
+};
 use serde::{Deserialize, Serialize};
-use stark::Plonky3Prover;
+use stark::{KeyExportError, Plonky3Prover};
 
 use crate::{
     field_filter::generalize_factory, Backend, BackendFactory, BackendOptions, Error, Proof,
 };
 
+/// The largest constraint degree supported with the options recorded in the
+/// verification key or, without one, the given options. Returns `None` if they
+/// are invalid, which is reported when creating the backend.
+pub(crate) fn max_constraint_degree(
+    options: &BackendOptions,
+    verification_key: Option<&[u8]>,
+) -> Option<usize> {
+    match verification_key {
+        Some(vk) => stark::verifying_key_options(vk).ok(),
+        None => options.parse::<Plonky3Options>().ok(),
+    }
+    .map(|options| options.fri.max_constraint_degree())
+}
+
 struct RestrictedFactory;
 
-impl<T: FieldElementMap> BackendFactory<T> for RestrictedFactory
+impl<T> BackendFactory<T> for RestrictedFactory
 where
-    ProverData<T>: Send + Serialize + for<'a> Deserialize<'a>,
-    Commitment<T>: Send,
+    T: FieldElementMap<Poseidon2Hash> + FieldElementMap<KeccakHash> + FieldElementMap<Blake3Hash>,
+    ProverData<T, Poseidon2Hash>: Send + Serialize + for<'a> Deserialize<'a>,
+    Commitment<T, Poseidon2Hash>: Send,
+    ProverData<T, KeccakHash>: Send + Serialize + for<'a> Deserialize<'a>,
+    Commitment<T, KeccakHash>: Send,
+    ProverData<T, Blake3Hash>: Send + Serialize + for<'a> Deserialize<'a>,
+    Commitment<T, Blake3Hash>: Send,
 {
     fn create(
         &self,
@@ -29,7 +51,7 @@ where
         proving_key: Option<&mut dyn io::Read>,
         verification_key: Option<&mut dyn io::Read>,
         verification_app_key: Option<&mut dyn io::Read>,
-        _: BackendOptions,
+        options: BackendOptions,
     ) -> Result<Box<dyn crate::Backend<T>>, Error> {
         if setup.is_some() {
             return Err(Error::NoSetupAvailable);
@@ -38,28 +60,85 @@ where
             return Err(Error::NoAggregationAvailable);
         }
 
-        let mut p3 = Box::new(Plonky3Prover::new(pil.clone(), fixed));
+        let explicit_options: ExplicitPlonky3Options = options.parse()?;
 
-        match (proving_key, verification_key) {
-            (Some(pk), Some(vk)) => {
-                p3.set_proving_key(pk);
-                p3.set_verifying_key(vk);
+        // The verification key records the options it was generated with. They
+        // replace the defaults, but must agree with every explicitly set option.
+        let verification_key = verification_key
+            .map(|vk| {
+                let mut buf = vec![];
+                vk.read_to_end(&mut buf).map(|_| buf)
+            })
+            .transpose()?;
+        let options = match &verification_key {
+            Some(vk) => {
+                let vk_options = stark::verifying_key_options(vk)?;
+                if !explicit_options.is_compatible_with(&vk_options) {
+                    return Err(Error::BackendError(format!(
+                        "The verification key was generated with options {vk_options}, but {explicit_options} were requested"
+                    )));
+                }
+                vk_options
             }
-            _ => {
-                p3.setup();
+            None => explicit_options.apply_to(Plonky3Options::default())?,
+        };
+        let verification_key = verification_key.as_deref();
+
+        match options.hash {
+            HashFunction::Poseidon2 => create_prover::<T, Poseidon2Hash>(
+                pil,
+                fixed,
+                proving_key,
+                verification_key,
+                options,
+            ),
+            HashFunction::Keccak => {
+                create_prover::<T, KeccakHash>(pil, fixed, proving_key, verification_key, options)
+            }
+            HashFunction::Blake3 => {
+                create_prover::<T, Blake3Hash>(pil, fixed, proving_key, verification_key, options)
             }
         }
+    }
+}
 
-        Ok(p3)
+fn create_prover<T: FieldElementMap<H>, H: CommitmentHash>(
+    pil: Arc<Analyzed<T>>,
+    fixed: Arc<Vec<(String, VariablySizedColumn<T>)>>,
+    proving_key: Option<&mut dyn io::Read>,
+    verification_key: Option<&[u8]>,
+    options: Plonky3Options,
+) -> Result<Box<dyn crate::Backend<T>>, Error>
+where
+    ProverData<T, H>: Send + Serialize + for<'a> Deserialize<'a>,
+    Commitment<T, H>: Send,
+{
+    let mut p3 = Box::new(Plonky3Prover::<T, H>::new(pil, fixed, options));
+
+    log::info!(
+        "Plonky3 options: {options}, conjectured security: {} bits",
+        p3.conjectured_security_bits()
+    );
+
+    match (proving_key, verification_key) {
+        (Some(pk), Some(mut vk)) => {
+            p3.set_proving_key(pk);
+            p3.set_verifying_key(&mut vk);
+        }
+        _ => {
+            p3.setup();
+        }
     }
+
+    Ok(p3)
 }
 
 generalize_factory!(Factory <- RestrictedFactory, [BabyBearField, KoalaBearField, GoldilocksField, Mersenne31Field]);
 
-impl<T: FieldElementMap> Backend<T> for Plonky3Prover<T>
+impl<T: FieldElementMap<H>, H: CommitmentHash> Backend<T> for Plonky3Prover<T, H>
 where
-    ProverData<T>: Send + Serialize + for<'a> Deserialize<'a>,
-    Commitment<T>: Send,
+    ProverData<T, H>: Send + Serialize + for<'a> Deserialize<'a>,
+    Commitment<T, H>: Send,
 {
     fn verify(&self, proof: &[u8], instances: &[Vec<T>]) -> Result<(), Error> {
         assert_eq!(instances.len(), 1);
@@ -82,9 +161,10 @@ where
     }
 
     fn export_verification_key(&self, output: &mut dyn io::Write) -> Result<(), Error> {
-        let vk = self
-            .export_verifying_key()
-            .map_err(|e| Error::BackendError(e.to_string()))?;
+        let vk = self.export_verifying_key().map_err(|e| match e {
+            KeyExportError::NoVerificationKey => Error::NoVerificationAvailable,
+            e => Error::BackendError(e.to_string()),
+        })?;
         output.write_all(&vk).unwrap();
         Ok(())
     }
@@ -16,16 +16,18 @@ use powdr_ast::analyzed::Analyzed;
 use powdr_executor::witgen::WitgenCallback;
 
 use powdr_plonky3::{
-    prove, verify, Challenger, Commitment, ConstraintSystem, FieldElementMap, PowdrCircuit, Proof,
-    ProverData, StarkProvingKey, StarkVerifyingKey, TableProvingKey, TableProvingKeyCollection,
+    prove, verify, Challenge, Challenger, Commitment, CommitmentHash, ConstraintSystem,
+    FieldElementMap, Plonky3Options, PowdrCircuit, Proof, ProverData, StarkProvingKey,
+    StarkVerifyingKey, TableProvingKey, TableProvingKeyCollection,
 };
 
+use p3_field::Field;
 use p3_uni_stark::StarkGenericConfig;
 
-pub struct Plonky3Prover<T: FieldElementMap>
+pub struct Plonky3Prover<T: FieldElementMap<H>, H: CommitmentHash>
 where
-    ProverData<T>: Send,
-    Commitment<T>: Send,
+    ProverData<T, H>: Send,
+    Commitment<T, H>: Send,
 {
     /// The analyzed PIL
     analyzed: Arc<Analyzed<T>>,
@@ -37,6 +39,8 @@ where
     proving_key: Option<StarkProvingKey<T::Config>>,
     /// Verifying key
     verifying_key: Option<StarkVerifyingKey<T::Config>>,
+    /// The FRI parameters and the hash function
+    options: Plonky3Options,
 }
 
 pub enum KeyExportError {
@@ -53,15 +57,24 @@ impl fmt::Display for KeyExportError {
     }
 }
 
-impl<T: FieldElementMap> Plonky3Prover<T>
+/// Reads the options a serialized verifying key was generated with.
+pub fn verifying_key_options(verifying_key: &[u8]) -> Result<Plonky3Options, String> {
+    // the options are the first field of the key
+    bincode::deserialize(verifying_key)
+        .map_err(|e| format!("Failed to deserialize verifying key options: {e}"))
+}
+
+impl<T: FieldElementMap<H>, H: CommitmentHash> Plonky3Prover<T, H>
 where
-    ProverData<T>: Send + Serialize + for<'a> Deserialize<'a>,
-    Commitment<T>: Send,
+    ProverData<T, H>: Send + Serialize + for<'a> Deserialize<'a>,
+    Commitment<T, H>: Send,
 {
     pub fn new(
         analyzed: Arc<Analyzed<T>>,
         fixed: Arc<Vec<(String, VariablySizedColumn<T>)>>,
+        options: Plonky3Options,
     ) -> Self {
+        assert_eq!(options.hash, H::FUNCTION);
         Self {
             split: powdr_backend_utils::split_pil(&analyzed)
                 .into_iter()
@@ -74,6 +87,7 @@ where
             fixed,
             proving_key: None,
             verifying_key: None,
+            options,
         }
     }
 
@@ -82,7 +96,12 @@ where
     }
 
     pub fn set_verifying_key(&mut self, rdr: &mut dyn std::io::Read) {
-        self.verifying_key = Some(bincode::deserialize_from(rdr).unwrap());
+        let verifying_key: StarkVerifyingKey<T::Config> = bincode::deserialize_from(rdr).unwrap();
+        assert_eq!(
+            verifying_key.options, self.options,
+            "The verifying key was generated with different options"
+        );
+        self.verifying_key = Some(verifying_key);
     }
 
     pub fn export_proving_key(&self) -> Result<Vec<u8>, KeyExportError> {
@@ -104,11 +123,21 @@ where
     }
 }
 
-impl<T: FieldElementMap> Plonky3Prover<T>
+impl<T: FieldElementMap<H>, H: CommitmentHash> Plonky3Prover<T, H>
 where
-    ProverData<T>: Send,
-    Commitment<T>: Send,
+    ProverData<T, H>: Send,
+    Commitment<T, H>: Send,
 {
+    /// The conjectured security of the proofs in bits, see [`Plonky3Options::conjectured_security_bits`].
+    pub fn conjectured_security_bits(&self) -> usize {
+        self.options
+            .conjectured_security_bits(<Challenge<T, H> as Field>::bits())
+    }
+
+    fn config(&self) -> T::Config {
+        T::get_config(&self.options.fri)
+    }
+
     pub fn setup(&mut self) {
         let preprocessed: BTreeMap<String, TableProvingKeyCollection<T::Config>> = self
             .split
@@ -134,7 +163,7 @@ where
                                     .collect::<Vec<_>>();
 
                                 // get the config
-                                let config = T::get_config();
+                                let config = self.config();
 
                                 // commit to the fixed columns
                                 let pcs = config.pcs();
@@ -178,6 +207,7 @@ where
             .collect();
 
         let verifying_key = StarkVerifyingKey {
+            options: self.options,
             preprocessed: preprocessed
                 .iter()
                 .map(|(table_name, data)| {
@@ -206,13 +236,15 @@ where
         // here we need to clone the witness because the callback will modify it
         let witness = &mut witness.to_vec();
 
-        let circuit = PowdrCircuit::new(&self.split).with_witgen_callback(witgen_callback);
+        let circuit = PowdrCircuit::<T, H>::new(&self.split).with_witgen_callback(witgen_callback);
+
+        let config = self.config();
 
         let mut challenger = T::get_challenger();
 
         let proving_key = self.proving_key.as_ref();
 
-        let proof = prove(proving_key, &circuit, witness, &mut challenger);
+        let proof = prove(&config, proving_key, &circuit, witness, &mut challenger);
 
         let mut challenger = T::get_challenger();
 
@@ -240,7 +272,8 @@ where
             })
             .collect();
 
-        verify(
+        verify::<T, H>(
+            &config,
             verifying_key,
             &circuit
                 .split
@@ -260,6 +293,8 @@ where
         let proof: Proof<_> =
             bincode::deserialize(proof).map_err(|e| format!("Failed to deserialize proof: {e}"))?;
 
+        let config = self.config();
+
         let mut challenger = T::get_challenger();
 
         let verifying_key = self.verifying_key.as_ref();
@@ -284,7 +319,8 @@ where
                 instance_map.get_mut(namespace).unwrap()[*stage as usize].push(*value);
             });
 
-        verify(
+        verify::<T, H>(
+            &config,
             verifying_key,
             &self
                 .split
@@ -307,7 +343,10 @@ mod tests {
     use powdr_pipeline::Pipeline;
     use test_log::test;
 
-    use powdr_plonky3::{Commitment, FieldElementMap, ProverData};
+    use powdr_plonky3::{
+        Blake3Hash, Commitment, CommitmentHash, FieldElementMap, KeccakHash, Plonky3Options,
+        Poseidon2Hash, ProverData,
+    };
 
     /// Prove and verify execution over all supported fields
     fn run_test(pil: &str) {
@@ -315,15 +354,18 @@ mod tests {
     }
 
     fn run_test_publics(pil: &str, malicious_publics: &Option<Vec<usize>>) {
-        run_test_publics_aux::<GoldilocksField>(pil, malicious_publics);
-        run_test_publics_aux::<BabyBearField>(pil, malicious_publics);
-        run_test_publics_aux::<Mersenne31Field>(pil, malicious_publics);
+        run_test_publics_aux::<GoldilocksField, Poseidon2Hash>(pil, malicious_publics, "");
+        run_test_publics_aux::<BabyBearField, Poseidon2Hash>(pil, malicious_publics, "");
+        run_test_publics_aux::<Mersenne31Field, Poseidon2Hash>(pil, malicious_publics, "");
     }
 
-    fn run_test_publics_aux<F: FieldElementMap>(pil: &str, malicious_publics: &Option<Vec<usize>>)
-    where
-        ProverData<F>: Send + serde::Serialize + for<'a> serde::Deserialize<'a>,
-        Commitment<F>: Send,
+    fn run_test_publics_aux<F: FieldElementMap<H>, H: CommitmentHash>(
+        pil: &str,
+        malicious_publics: &Option<Vec<usize>>,
+        options: &str,
+    ) where
+        ProverData<F, H>: Send + serde::Serialize + for<'a> serde::Deserialize<'a>,
+        Commitment<F, H>: Send,
     {
         let mut pipeline = Pipeline::<F>::default().from_pil_string(pil.to_string());
 
@@ -332,7 +374,7 @@ mod tests {
         let witness = &mut pipeline.compute_witness().unwrap();
         let fixed = pipeline.compute_fixed_cols().unwrap();
 
-        let mut prover = Plonky3Prover::new(pil, fixed);
+        let mut prover = Plonky3Prover::<F, H>::new(pil, fixed, options.parse().unwrap());
         prover.setup();
         let proof = prover.prove(witness, witness_callback);
 
@@ -496,4 +538,46 @@ mod tests {
         let content = "namespace Global(8); pol fixed z = [0, 1]*; pol witness a; [a] in [z];";
         run_test(content);
     }
+
+    #[test]
+    fn hashes() {
+        let content = r#"
+        namespace Add(8);
+            col witness x;
+            col witness y;
+            col witness z;
+            x + y = z;
+        "#;
+        run_test_publics_aux::<GoldilocksField, KeccakHash>(content, &None, "hash=keccak");
+        run_test_publics_aux::<BabyBearField, KeccakHash>(content, &None, "hash=keccak");
+        run_test_publics_aux::<Mersenne31Field, KeccakHash>(content, &None, "hash=keccak");
+        run_test_publics_aux::<GoldilocksField, Blake3Hash>(content, &None, "hash=blake3");
+        run_test_publics_aux::<BabyBearField, Blake3Hash>(content, &None, "hash=blake3");
+    }
+
+    #[test]
+    fn fri_options() {
+        let content = "namespace Global(8); pol fixed z = [1, 2]*; pol witness a; a = z + 1;";
+        run_test_publics_aux::<BabyBearField, Poseidon2Hash>(
+            content,
+            &None,
+            "log_blowup=2,queries=50,pow_bits=20",
+        );
+    }
+
+    #[test]
+    fn options() {
+        let options: Plonky3Options = "hash=blake3, log_blowup=2,queries=50".parse().unwrap();
+        assert_eq!(options.hash, powdr_plonky3::HashFunction::Blake3);
+        assert_eq!(options.fri.log_blowup, 2);
+        assert_eq!(options.fri.num_queries, 50);
+        assert_eq!(options.fri.proof_of_work_bits, 16);
+        assert_eq!(options.conjectured_security_bits(124), 116);
+        assert_eq!(options.to_string().parse::<Plonky3Options>(), Ok(options));
+
+        assert_eq!("".parse(), Ok(Plonky3Options::default()));
+        assert!("hash=sha256".parse::<Plonky3Options>().is_err());
+        assert!("queries=0".parse::<Plonky3Options>().is_err());
+        assert!("blowup".parse::<Plonky3Options>().is_err());
+    }
 }
@@ -0,0 +1,162 @@
+//! A self-describing container for the proofs of all backends.
+//!
+//! The proof of each backend is in a backend specific format. The container
+//! records which backend, options and field produced it, for which PIL and
+//! verification key, and with which public values, so that a proof can be
+//! verified without knowing these out of band.
+
+use powdr_ast::analyzed::Analyzed;
+use powdr_number::{FieldElement, KnownField};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{BackendOptions, BackendType, Error, Proof};
+
+/// The bytes every serialized container starts with.
+const MAGIC: &[u8] = b"powdr-proof";
+
+/// The version of the container format. It is serialized right after the
+/// magic bytes, so that containers of other versions are rejected before they
+/// are decoded.
+const VERSION: u32 = 1;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ProofContainer {
+    /// The backend type, as accepted by `BackendType::from_str`.
+    pub backend: String,
+    pub backend_options: BackendOptions,
+    pub field: KnownField,
+    /// The hash of the PIL the proof is for, see `ProofContainer::pil_hash`.
+    pub pil_hash: String,
+    /// The hash of the verification key, if the backend has one, see
+    /// `ProofContainer::verification_key_hash`.
+    pub verification_key_hash: Option<String>,
+    pub publics: Vec<String>,
+    /// The proof in the format of the backend.
+    pub proof: Proof,
+}
+
+impl ProofContainer {
+    pub fn new<F: FieldElement>(
+        backend: BackendType,
+        backend_options: BackendOptions,
+        pil: &Analyzed<F>,
+        verification_key_hash: Option<String>,
+        publics: &[F],
+        proof: Proof,
+    ) -> Self {
+        Self {
+            backend: backend.to_string(),
+            backend_options,
+            field: F::known_field().expect("Proofs require a known field"),
+            pil_hash: Self::pil_hash(pil),
+            verification_key_hash,
+            publics: publics.iter().map(|p| p.to_string()).collect(),
+            proof,
+        }
+    }
+
+    /// Returns true if `bytes` are a serialized container, as opposed to a
+    /// proof in the format of a backend.
+    pub fn is_container(bytes: &[u8]) -> bool {
+        bytes.starts_with(MAGIC)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend(VERSION.to_le_bytes());
+        bincode::serialize_into(&mut bytes, self).unwrap();
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let rest = bytes
+            .strip_prefix(MAGIC)
+            .ok_or_else(|| Error::BackendError("Not a proof container".to_string()))?;
+        if rest.len() < 4 {
+            return Err(Error::BackendError("Truncated proof container".to_string()));
+        }
+        let version = u32::from_le_bytes(rest[..4].try_into().unwrap());
+        if version != VERSION {
+            return Err(Error::BackendError(format!(
+                "Unsupported proof container version {version}, expected {VERSION}"
+            )));
+        }
+        bincode::deserialize(&rest[4..])
+            .map_err(|e| Error::BackendError(format!("Invalid proof container: {e}")))
+    }
+
+    /// Returns the backend type, if it is available in this build.
+    pub fn backend_type(&self) -> Result<BackendType, Error> {
+        self.backend
+            .parse()
+            .map_err(|_| Error::BackendError(format!("Backend {} is not available", self.backend)))
+    }
+
+    pub fn publics<F: FieldElement>(&self) -> Result<Vec<F>, Error> {
+        self.publics
+            .iter()
+            .map(|p| p.parse().map_err(Error::BackendError))
+            .collect()
+    }
+
+    /// Returns the hash of the textual representation of a PIL.
+    pub fn pil_hash<F: FieldElement>(pil: &Analyzed<F>) -> String {
+        hash(pil.to_string().as_bytes())
+    }
+
+    pub fn verification_key_hash(verification_key: &[u8]) -> String {
+        hash(verification_key)
+    }
+}
+
+fn hash(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+#[cfg(test)]
+mod test {
+    use powdr_number::GoldilocksField;
+    use powdr_pil_analyzer::analyze_string;
+
+    use super::*;
+
+    fn container() -> ProofContainer {
+        let pil =
+            analyze_string::<GoldilocksField>("namespace N(4); col witness x; x = 1;").unwrap();
+        ProofContainer {
+            backend: "plonky3".to_string(),
+            backend_options: String::new(),
+            field: KnownField::GoldilocksField,
+            pil_hash: ProofContainer::pil_hash(&pil),
+            verification_key_hash: Some(ProofContainer::verification_key_hash(&[1, 2, 3])),
+            publics: vec!["7".to_string()],
+            proof: vec![4, 5, 6],
+        }
+    }
+
+    #[test]
+    fn roundtrip() {
+        let container = container();
+        let bytes = container.to_bytes();
+        assert!(ProofContainer::is_container(&bytes));
+        assert_eq!(ProofContainer::from_bytes(&bytes).unwrap(), container);
+        assert_eq!(
+            container.publics::<GoldilocksField>().unwrap(),
+            vec![GoldilocksField::from(7)]
+        );
+    }
+
+    #[test]
+    fn rejects_other_versions() {
+        let mut bytes = container().to_bytes();
+        bytes[MAGIC.len()] += 1;
+        assert!(ProofContainer::from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn raw_proofs_are_not_containers() {
+        assert!(!ProofContainer::is_container(&[4, 5, 6]));
+        assert!(ProofContainer::from_bytes(&[4, 5, 6]).is_err());
+    }
+}
@@ -24,8 +24,9 @@ fn main() {
         .write(2, &some_data.iter().sum::<u32>());
 
     // Fast dry run to test execution.
-    session.run();
+    // Fails if the guest panicked or exited with a non-zero exit code.
+    session.run().unwrap();
 
     // Uncomment to compute the proof.
-    //session.prove();
+    //session.prove().unwrap();
 }
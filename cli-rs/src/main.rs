@@ -9,9 +9,11 @@ use log::LevelFilter;
 
 use powdr::number::{
     BabyBearField, BigUint, Bn254Field, FieldElement, GoldilocksField, KnownField, KoalaBearField,
+    Mersenne31Field,
 };
+use powdr::riscv::exit::{ExitStatus, GuestError};
 use powdr::riscv::{CompilerOptions, RuntimeLibs};
-use powdr::riscv_executor::ProfilerOptions;
+use powdr::riscv_executor::{ExecutionError, ProfilerOptions};
 use powdr::Pipeline;
 
 use std::ffi::OsStr;
@@ -27,6 +29,8 @@ pub enum FieldArgument {
     Bb,
     #[strum(serialize = "kb")]
     Kb,
+    #[strum(serialize = "m31")]
+    M31,
     #[strum(serialize = "gl")]
     Gl,
     #[strum(serialize = "bn254")]
@@ -38,6 +42,7 @@ impl FieldArgument {
         match self {
             FieldArgument::Bb => KnownField::BabyBearField,
             FieldArgument::Kb => KnownField::KoalaBearField,
+            FieldArgument::M31 => KnownField::Mersenne31Field,
             FieldArgument::Gl => KnownField::GoldilocksField,
             FieldArgument::Bn254 => KnownField::Bn254Field,
         }
@@ -78,7 +83,9 @@ enum Commands {
         #[arg(default_value_t = String::from("."))]
         output_directory: String,
 
-        /// Comma-separated list of coprocessors.
+        /// Comma-separated list of built-in coprocessors: poseidon_gl, keccakf,
+        /// arith, sha256 and arith384. Custom coprocessors can only be enabled
+        /// with `powdr::riscv::CompilerOptions::with_custom`.
         #[arg(long)]
         coprocessors: Option<String>,
 
@@ -104,7 +111,9 @@ enum Commands {
         #[arg(default_value_t = String::from("."))]
         output_directory: String,
 
-        /// Comma-separated list of coprocessors.
+        /// Comma-separated list of built-in coprocessors: poseidon_gl, keccakf,
+        /// arith, sha256 and arith384. Custom coprocessors can only be enabled
+        /// with `powdr::riscv::CompilerOptions::with_custom`.
         #[arg(long)]
         coprocessors: Option<String>,
 
@@ -114,6 +123,8 @@ enum Commands {
         continuations: bool,
     },
     /// Executes a powdr-asm file with given inputs.
+    /// Fails if the guest panicked or exited with a non-zero exit code.
+    /// Programs that do not report an exit code only log a warning.
     Execute {
         /// input powdr-asm code compiled from Rust/RISCV
         file: String,
@@ -285,8 +296,7 @@ fn compile_rust(
 ) -> Result<(), Vec<String>> {
     let libs = coprocessors_to_options(coprocessors)?;
     let options = CompilerOptions::new(field, libs, continuations);
-    powdr::riscv::compile_rust(file_name, options, output_dir, true, None)
-        .ok_or_else(|| vec!["could not compile rust".to_string()])?;
+    powdr::riscv::compile_rust(file_name, options, output_dir, true, None).map_err(|e| vec![e])?;
 
     Ok(())
 }
@@ -301,7 +311,7 @@ fn compile_riscv_elf(
     let libs = coprocessors_to_options(coprocessors)?;
     let options = CompilerOptions::new(field, libs, continuations);
     powdr::riscv::compile_riscv_elf(input_file, Path::new(input_file), options, output_dir, true)
-        .ok_or_else(|| vec!["could not translate RISC-V executable".to_string()])?;
+        .map_err(|e| vec![e])?;
 
     Ok(())
 }
@@ -325,9 +335,17 @@ fn execute<F: FieldElement>(
         Ok(())
     };
 
-    match (witness, continuations) {
+    // Witness generation executes the program again, so with continuations we
+    // take the exit status written by the dry run.
+    let exit_status = match (witness, continuations) {
         (false, true) => {
-            powdr::riscv::continuations::rust_continuations_dry_run(&mut pipeline, profiling);
+            powdr::riscv::continuations::rust_continuations_dry_run(
+                &mut pipeline,
+                &Default::default(),
+                profiling,
+            )
+            .map_err(|e| guest_error(&pipeline, &e))?;
+            ExitStatus::take_from_pipeline(&pipeline)
         }
         (false, false) => {
             let program = pipeline.compute_asm_string().unwrap().clone();
@@ -337,25 +355,46 @@ fn execute<F: FieldElement>(
                 pipeline.data_callback().unwrap(),
                 &[],
                 powdr::riscv_executor::ExecMode::Fast,
+                // Custom coprocessors can't be enabled from the CLI.
+                &Default::default(),
                 profiling,
-            );
+            )
+            .map_err(|e| guest_error(&pipeline, &e))?;
             log::info!("Execution trace length: {}", trace.len);
+            ExitStatus::take_from_pipeline(&pipeline)
         }
         (true, true) => {
-            let dry_run =
-                powdr::riscv::continuations::rust_continuations_dry_run(&mut pipeline, profiling);
+            let dry_run = powdr::riscv::continuations::rust_continuations_dry_run(
+                &mut pipeline,
+                &Default::default(),
+                profiling,
+            )
+            .map_err(|e| guest_error(&pipeline, &e))?;
+            let exit_status = ExitStatus::take_from_pipeline(&pipeline);
             powdr::riscv::continuations::rust_continuations(
                 &mut pipeline,
                 generate_witness,
                 dry_run,
             )?;
+            exit_status
         }
         (true, false) => {
             generate_witness(&mut pipeline)?;
+            ExitStatus::take_from_pipeline(&pipeline)
+        }
+    };
+
+    match exit_status {
+        Some(exit_status) => exit_status.into_result().map_err(|e| vec![e.to_string()]),
+        None => {
+            log::warn!("The program terminated without reporting an exit code");
+            Ok(())
         }
     }
+}
 
-    Ok(())
+fn guest_error<F: FieldElement>(pipeline: &Pipeline<F>, error: &ExecutionError) -> Vec<String> {
+    vec![GuestError::from_execution_error(pipeline, error).to_string()]
 }
 
 fn coprocessors_to_options(coprocessors: Option<String>) -> Result<RuntimeLibs, Vec<String>> {
@@ -367,6 +406,8 @@ fn coprocessors_to_options(coprocessors: Option<String>) -> Result<RuntimeLibs,
                 "poseidon_gl" => libs = libs.with_poseidon(),
                 "keccakf" => libs = libs.with_keccak(),
                 "arith" => libs = libs.with_arith(),
+                "sha256" => libs = libs.with_sha256(),
+                "arith384" => libs = libs.with_arith384(),
                 _ => return Err(vec![format!("Invalid co-processor specified: {name}")]),
             }
         }
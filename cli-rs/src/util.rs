@@ -15,6 +15,7 @@ macro_rules! call_with_field {
         match $field {
             FieldArgument::Bb => $function::<BabyBearField>($($args),*),
             FieldArgument::Kb => $function::<KoalaBearField>($($args),*),
+            FieldArgument::M31 => $function::<Mersenne31Field>($($args),*),
             FieldArgument::Gl => $function::<GoldilocksField>($($args),*),
             FieldArgument::Bn254 => $function::<Bn254Field>($($args),*),
         }
@@ -6,10 +6,10 @@ use clap::{CommandFactory, Parser, Subcommand};
 use env_logger::fmt::Color;
 use env_logger::{Builder, Target};
 use log::{max_level, LevelFilter};
-use powdr::backend::BackendType;
+use powdr::backend::{BackendType, ProofContainer};
 use powdr::number::{buffered_write_file, read_polys_csv_file, CsvRenderMode};
 use powdr::number::{
-    BabyBearField, BigUint, Bn254Field, FieldElement, GoldilocksField, KoalaBearField,
+    BabyBearField, BigUint, Bn254Field, FieldElement, GoldilocksField, KnownField, KoalaBearField,
     Mersenne31Field,
 };
 use powdr::pipeline::test_runner;
@@ -151,6 +151,7 @@ enum Commands {
 
         /// Backend options. Halo2: "poseidon", "snark_single" or "snark_aggr".
         /// EStark and PilStarkCLI: "stark_gl", "stark_bn" or "snark_bn".
+        /// Plonky3: comma-separated "hash=poseidon2|keccak|blake3", "log_blowup=N", "queries=N", "pow_bits=N".
         #[arg(long)]
         backend_options: Option<String>,
 
@@ -192,6 +193,7 @@ enum Commands {
 
         /// Backend options. Halo2: "poseidon", "snark_single" or "snark_aggr".
         /// EStark and PilStarkCLI: "stark_gl", "stark_bn" or "snark_bn".
+        /// Plonky3: comma-separated "hash=poseidon2|keccak|blake3", "log_blowup=N", "queries=N", "pow_bits=N".
         #[arg(long)]
         backend_options: Option<String>,
 
@@ -221,19 +223,20 @@ enum Commands {
         #[arg(default_value_t = String::from("."))]
         dir: String,
 
-        /// The field to use
+        /// The field to use. Defaults to the field recorded in the proof, or "gl".
         #[arg(long)]
-        #[arg(default_value_t = FieldArgument::Gl)]
         #[arg(value_parser = clap_enum_variants!(FieldArgument))]
-        field: FieldArgument,
+        field: Option<FieldArgument>,
 
-        /// Generate a proof with a given backend.
+        /// Verify the proof with a given backend. Defaults to the backend recorded in the proof.
         #[arg(short, long)]
         #[arg(value_parser = clap_enum_variants!(BackendType))]
-        backend: BackendType,
+        backend: Option<BackendType>,
 
         /// Backend options. Halo2: "poseidon", "snark_single" or "snark_aggr".
         /// EStark and PilStarkCLI: "stark_gl", "stark_bn" or "snark_bn".
+        /// Plonky3: comma-separated "hash=poseidon2|keccak|blake3", "log_blowup=N", "queries=N", "pow_bits=N".
+        /// Defaults to the options recorded in the proof, which must match the given ones.
         #[arg(long)]
         backend_options: Option<String>,
 
@@ -242,6 +245,7 @@ enum Commands {
         proof: String,
 
         /// Comma-separated list of public inputs (numbers).
+        /// Defaults to the public values recorded in the proof.
         #[arg(long)]
         #[arg(default_value_t = String::new())]
         publics: String,
@@ -277,6 +281,7 @@ enum Commands {
 
         /// Backend options. Halo2: "poseidon", "snark_single" or "snark_aggr".
         /// EStark and PilStarkCLI: "stark_gl", "stark_bn" or "snark_bn".
+        /// Plonky3: comma-separated "hash=poseidon2|keccak|blake3", "log_blowup=N", "queries=N", "pow_bits=N".
         #[arg(long)]
         backend_options: Option<String>,
 
@@ -313,6 +318,7 @@ enum Commands {
 
         /// Backend options. Halo2: "poseidon", "snark_single" or "snark_aggr".
         /// EStark and PilStarkCLI: "stark_gl", "stark_bn" or "snark_bn".
+        /// Plonky3: comma-separated "hash=poseidon2|keccak|blake3", "log_blowup=N", "queries=N", "pow_bits=N".
         #[arg(long)]
         backend_options: Option<String>,
 
@@ -363,10 +369,30 @@ enum Commands {
         #[arg(default_value_t = FieldArgument::Gl)]
         #[arg(value_parser = clap_enum_variants!(FieldArgument))]
         field: FieldArgument,
+
+        /// Skip the given optimizer pass. Can be given multiple times.
+        #[arg(long = "disable-pass")]
+        disabled_passes: Vec<String>,
+
+        /// Run the given opt-in optimizer pass. Can be given multiple times.
+        #[arg(long = "enable-pass")]
+        enabled_passes: Vec<String>,
+
+        /// Write the PIL after every run of the given optimizer pass to the
+        /// output directory. Can be given multiple times.
+        #[arg(long)]
+        dump_after: Vec<String>,
+
+        /// Output directory for the PIL dumps and a JSON report of the columns
+        /// and identities removed by each pass.
+        #[arg(short, long)]
+        output_directory: Option<String>,
     },
 
     /// Executes all functions starting with `test_` in every module called
     /// `test` (or sub-module thereof) starting from the given module.
+    /// The tests can use the functions in `std::test` to run witness generation
+    /// on the given file and to check constraints on the resulting witness.
     Test {
         /// Input file.
         file: String,
@@ -449,9 +475,21 @@ fn run_command(command: Commands) {
             };
             Ok(())
         }
-        Commands::OptimizePIL { file, field } => {
-            call_with_field!(optimize_and_output::<field>(&file));
-            Ok(())
+        Commands::OptimizePIL {
+            file,
+            field,
+            disabled_passes,
+            enabled_passes,
+            dump_after,
+            output_directory,
+        } => {
+            call_with_field!(optimize_and_output::<field>(
+                &file,
+                disabled_passes,
+                enabled_passes,
+                dump_after,
+                output_directory
+            ))
         }
         Commands::Pil {
             file,
@@ -523,12 +561,14 @@ fn run_command(command: Commands) {
         } => {
             let pil = Path::new(&file);
             let dir = Path::new(&dir);
+            let proof = fs::read(proof).unwrap();
+            let field = field.unwrap_or_else(|| field_of_proof(&proof));
             call_with_field!(read_and_verify::<field>(
                 pil,
                 dir,
-                &backend,
+                backend,
                 backend_options,
-                proof,
+                &proof,
                 publics,
                 params,
                 vkey
@@ -739,46 +779,78 @@ fn read_and_prove<T: FieldElement>(
     Ok(())
 }
 
+/// Returns the field recorded in a proof container, or Goldilocks for other proofs.
+fn field_of_proof(proof: &[u8]) -> FieldArgument {
+    match ProofContainer::from_bytes(proof).map(|container| container.field) {
+        Ok(KnownField::BabyBearField) => FieldArgument::Bb,
+        Ok(KnownField::KoalaBearField) => FieldArgument::Kb,
+        Ok(KnownField::Mersenne31Field) => FieldArgument::M31,
+        Ok(KnownField::GoldilocksField) | Err(_) => FieldArgument::Gl,
+        Ok(KnownField::Bn254Field) => FieldArgument::Bn254,
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 fn read_and_verify<T: FieldElement>(
     file: &Path,
     dir: &Path,
-    backend_type: &BackendType,
+    backend_type: Option<BackendType>,
     backend_options: Option<String>,
-    proof: String,
+    proof: &[u8],
     publics: String,
     params: Option<String>,
     vkey: String,
 ) -> Result<(), Vec<String>> {
-    let proof = Path::new(&proof);
     let vkey = Path::new(&vkey).to_path_buf();
 
-    let proof = fs::read(proof).unwrap();
-    let publics = split_inputs(publics.as_str());
+    let container = ProofContainer::from_bytes(proof).ok();
+    let publics = match &container {
+        Some(container) if publics.trim().is_empty() => container
+            .publics()
+            .map_err(|e| vec![format!("Invalid public values in the proof: {e:?}")])?,
+        _ => split_inputs(publics.as_str()),
+    };
+    // Without explicit options, those recorded in the proof are used. Explicit
+    // options are checked against the recorded ones when verifying.
+    let backend_options =
+        backend_options.or_else(|| container.map(|container| container.backend_options));
 
     let mut pipeline = Pipeline::<T>::default()
         .from_file(file.to_path_buf())
         .read_constants(dir)
         .map_err(|e| vec![e])?
         .with_setup_file(params.map(PathBuf::from))
-        .with_vkey_file(Some(vkey))
-        .with_backend(*backend_type, backend_options);
+        .with_vkey_file(Some(vkey));
+    if let Some(backend_type) = backend_type {
+        pipeline = pipeline.with_backend(backend_type, backend_options);
+    }
 
-    pipeline.verify(&proof, &[publics])?;
+    pipeline.verify(proof, &[publics])?;
     log::info!("Proof is valid!");
 
     Ok(())
 }
 
 #[allow(clippy::print_stdout)]
-fn optimize_and_output<T: FieldElement>(file: &str) {
-    println!(
-        "{}",
-        Pipeline::<T>::default()
-            .from_file(PathBuf::from(file))
-            .compute_optimized_pil()
-            .unwrap()
-    );
+fn optimize_and_output<T: FieldElement>(
+    file: &str,
+    disabled_passes: Vec<String>,
+    enabled_passes: Vec<String>,
+    dump_after: Vec<String>,
+    output_directory: Option<String>,
+) -> Result<(), Vec<String>> {
+    let mut pipeline = Pipeline::<T>::default()
+        .from_file(PathBuf::from(file))
+        .with_disabled_optimizer_passes(disabled_passes)
+        .with_enabled_optimizer_passes(enabled_passes)
+        .with_dump_after_optimizer_passes(dump_after);
+    if let Some(output_directory) = output_directory {
+        pipeline = pipeline
+            .with_output(PathBuf::from(output_directory), true)
+            .with_optimization_report();
+    }
+    println!("{}", pipeline.compute_optimized_pil()?);
+    Ok(())
 }
 
 #[cfg(test)]
@@ -25,10 +25,11 @@ fn main() {
         .write(2, &preimg);
 
     // Fast dry run to test execution.
-    session.run();
+    // Fails if the guest panicked or exited with a non-zero exit code.
+    session.run().unwrap();
 
     // Uncomment to compute the proof.
-    //session.prove();
+    //session.prove().unwrap();
 }
 
 fn parse_hash(s: &str) -> [u8; 32] {
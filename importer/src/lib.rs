@@ -1,12 +1,14 @@
 #![deny(clippy::print_stdout)]
 
 mod module_loader;
+mod packages;
 mod path_canonicalizer;
 mod powdr_std;
 
 use std::path::PathBuf;
 
 pub use module_loader::load_module_files;
+use packages::add_packages;
 use path_canonicalizer::canonicalize_paths;
 use powdr_ast::parsed::asm::ASMProgram;
 use powdr_parser::parse_asm;
@@ -17,7 +19,8 @@ pub fn load_dependencies_and_resolve(
     path: Option<PathBuf>,
     module: ASMProgram,
 ) -> Result<ASMProgram, Error> {
-    load_module_files(path, module)
+    load_module_files(path.clone(), module)
+        .and_then(|module| add_packages(path.as_deref(), module))
         .and_then(add_std)
         .map_err(|e| SourceRef::default().with_error(e))
         .and_then(canonicalize_paths)
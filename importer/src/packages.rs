@@ -0,0 +1,574 @@
+//! Resolution of external packages declared in a `powdr.toml` manifest.
+//!
+//! A manifest declares the package itself and its dependencies on other
+//! packages on the local file system:
+//!
+//! ```toml
+//! [package]
+//! name = "my_project"
+//! version = "0.1.0"
+//!
+//! [dependencies]
+//! math = { path = "../math", version = "0.2" }
+//! ```
+//!
+//! Each dependency is a directory containing a `powdr.toml` and a `mod.asm`
+//! file, which is the root module of the package. All packages in the
+//! dependency graph are added to the main module under their name. Like
+//! `std`, a dependency can be referenced from any module of the package
+//! declaring it, e.g. `use math::Add;`, but packages that are not declared as
+//! dependencies are not visible.
+//!
+//! The resolved dependency graph is recorded in a `powdr.lock` file next to the
+//! manifest. As long as it matches the manifests, packages are loaded from the
+//! locations it records.
+
+use std::{
+    collections::BTreeMap,
+    env,
+    path::{Component, Path, PathBuf},
+};
+
+use powdr_ast::parsed::asm::{ASMModule, ASMProgram};
+use powdr_parser::parse_asm;
+use semver::{Version, VersionReq};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    load_module_files,
+    powdr_std::{add_external_modules, ExternalModule},
+};
+
+static MANIFEST_FILE: &str = "powdr.toml";
+static LOCK_FILE: &str = "powdr.lock";
+static MOD_FILE: &str = "mod.asm";
+/// If this environment variable is set, an out-of-date lock file is an error
+/// instead of being updated.
+static POWDR_LOCKED_ENV: &str = "POWDR_LOCKED";
+static LOCK_FILE_HEADER: &str = "# This file is automatically generated by powdr.\n\
+     # It is not intended for manual editing.\n";
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Manifest {
+    package: PackageInfo,
+    #[serde(default)]
+    dependencies: BTreeMap<String, Dependency>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PackageInfo {
+    name: String,
+    version: Version,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Dependency {
+    /// The path to the package, relative to the manifest declaring the dependency.
+    path: PathBuf,
+    version: VersionReq,
+}
+
+impl Manifest {
+    fn read(dir: &Path) -> Result<Self, String> {
+        let path = dir.join(MANIFEST_FILE);
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Could not read `{}`: {e}", path.display()))?;
+        toml::from_str(&content).map_err(|e| format!("Invalid `{}`: {e}", path.display()))
+    }
+}
+
+/// The contents of a lock file: the dependencies of the root package and all
+/// packages in the dependency graph, sorted by name.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct LockFile {
+    /// The names of the packages the root package depends on.
+    #[serde(default)]
+    dependencies: Vec<String>,
+    #[serde(default, rename = "package")]
+    packages: Vec<LockedPackage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct LockedPackage {
+    name: String,
+    version: Version,
+    /// The path to the package, relative to the root manifest.
+    path: PathBuf,
+    /// The names of the packages this package depends on.
+    dependencies: Vec<String>,
+}
+
+/// Looks for a `powdr.toml` manifest in the directory of `path` and its ancestors.
+/// If there is one, resolves its dependencies and adds the root modules of all
+/// packages to the program.
+pub fn add_packages(path: Option<&Path>, program: ASMProgram) -> Result<ASMProgram, String> {
+    let Some(manifest_dir) = path.and_then(find_manifest_dir) else {
+        return Ok(program);
+    };
+
+    let lock = load_lock_file(&manifest_dir, env::var(POWDR_LOCKED_ENV).is_ok())?;
+
+    let defined_names = program
+        .main
+        .statements
+        .iter()
+        .flat_map(|s| s.defined_names())
+        .collect::<Vec<_>>();
+    if let Some(p) = lock
+        .packages
+        .iter()
+        .find(|p| defined_names.contains(&&p.name))
+    {
+        return Err(format!(
+            "Package `{}` conflicts with a symbol of the same name in the main module",
+            p.name
+        ));
+    }
+
+    let modules = lock
+        .packages
+        .into_iter()
+        .map(|p| {
+            Ok(ExternalModule {
+                module: load_package(&manifest_dir.join(&p.path))?,
+                name: p.name,
+                dependencies: p.dependencies,
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    add_external_modules(program, modules, &lock.dependencies)
+}
+
+/// Returns the dependency graph recorded in the lock file if it matches the
+/// manifests. Otherwise, resolves the dependency graph from the manifests and
+/// updates the lock file.
+fn load_lock_file(manifest_dir: &Path, locked: bool) -> Result<LockFile, String> {
+    let existing = std::fs::read_to_string(manifest_dir.join(LOCK_FILE))
+        .ok()
+        .and_then(|content| toml::from_str::<LockFile>(&content).ok());
+    if let Some(lock) = existing.filter(|lock| lock_file_matches(manifest_dir, lock)) {
+        return Ok(lock);
+    }
+
+    let lock = resolve_packages(manifest_dir)?;
+    update_lock_file(manifest_dir, &lock, locked)?;
+    Ok(lock)
+}
+
+fn find_manifest_dir(path: &Path) -> Option<PathBuf> {
+    path.parent()?
+        .ancestors()
+        .find(|dir| dir.join(MANIFEST_FILE).is_file())
+        .map(Path::to_path_buf)
+}
+
+/// Returns whether the packages recorded in the lock file still exist with the
+/// recorded names, versions and dependencies, and satisfy all dependency
+/// declarations of the root package and the locked packages.
+fn lock_file_matches(manifest_dir: &Path, lock: &LockFile) -> bool {
+    let locked = lock
+        .packages
+        .iter()
+        .map(|p| (p.name.as_str(), p))
+        .collect::<BTreeMap<_, _>>();
+
+    let Ok(root) = Manifest::read(manifest_dir) else {
+        return false;
+    };
+    let mut manifests = vec![(PathBuf::new(), &lock.dependencies, root)];
+    for package in &lock.packages {
+        let Ok(manifest) = Manifest::read(&manifest_dir.join(&package.path)) else {
+            return false;
+        };
+        if manifest.package.name != package.name || manifest.package.version != package.version {
+            return false;
+        }
+        manifests.push((package.path.clone(), &package.dependencies, manifest));
+    }
+
+    manifests.into_iter().all(|(base, dependencies, manifest)| {
+        manifest.dependencies.keys().eq(dependencies.iter())
+            && manifest.dependencies.iter().all(|(name, dependency)| {
+                name != "std"
+                    && locked.get(name.as_str()).is_some_and(|package| {
+                        package.path == normalize(&base.join(&dependency.path))
+                            && dependency.version.matches(&package.version)
+                    })
+            })
+    })
+}
+
+/// Resolves the dependency graph of the manifest in `manifest_dir`.
+fn resolve_packages(manifest_dir: &Path) -> Result<LockFile, String> {
+    let root = Manifest::read(manifest_dir)?;
+    let root_dependencies = root.dependencies.keys().cloned().collect();
+
+    // The resolved packages by name, together with their canonical location.
+    let mut packages: BTreeMap<String, (LockedPackage, PathBuf)> = BTreeMap::new();
+    // The version requirements on each package, with the name of the requiring package.
+    let mut requirements: BTreeMap<String, Vec<(String, VersionReq)>> = BTreeMap::new();
+
+    let mut queue = vec![(root.package.name.clone(), PathBuf::new(), root.dependencies)];
+    while let Some((dependent, base, dependencies)) = queue.pop() {
+        for (name, dependency) in dependencies {
+            if name == "std" {
+                return Err(format!(
+                    "Package `{dependent}` depends on a package called `std`, which is reserved for the powdr standard library"
+                ));
+            }
+            requirements
+                .entry(name.clone())
+                .or_default()
+                .push((dependent.clone(), dependency.version));
+
+            let path = normalize(&base.join(&dependency.path));
+            let location = manifest_dir.join(&path).canonicalize().map_err(|_| {
+                format!(
+                    "Could not find package `{name}` required by `{dependent}` at `{}`",
+                    path.display()
+                )
+            })?;
+
+            if let Some((existing, existing_location)) = packages.get(&name) {
+                if *existing_location == location {
+                    continue;
+                }
+                let manifest = Manifest::read(&location)?;
+                return Err(if manifest.package.version == existing.version {
+                    format!(
+                        "Duplicate package `{name}` {}: found at `{}` and `{}`",
+                        existing.version,
+                        existing.path.display(),
+                        path.display()
+                    )
+                } else {
+                    format!(
+                        "Incompatible versions of package `{name}`: {} at `{}` and {} at `{}`",
+                        existing.version,
+                        existing.path.display(),
+                        manifest.package.version,
+                        path.display()
+                    )
+                });
+            }
+
+            let manifest = Manifest::read(&location)?;
+            if manifest.package.name != name {
+                return Err(format!(
+                    "Package `{dependent}` depends on `{name}`, but the package at `{}` is called `{}`",
+                    path.display(),
+                    manifest.package.name
+                ));
+            }
+            let package = LockedPackage {
+                name: name.clone(),
+                version: manifest.package.version,
+                path: path.clone(),
+                dependencies: manifest.dependencies.keys().cloned().collect(),
+            };
+            packages.insert(name.clone(), (package, location));
+            queue.push((name, path, manifest.dependencies));
+        }
+    }
+
+    for (name, requirements) in requirements {
+        let (package, _) = &packages[&name];
+        if let Some((dependent, requirement)) = requirements
+            .iter()
+            .find(|(_, requirement)| !requirement.matches(&package.version))
+        {
+            return Err(format!(
+                "Package `{dependent}` requires `{name}` version {requirement}, but found version {} at `{}`",
+                package.version,
+                package.path.display()
+            ));
+        }
+    }
+
+    Ok(LockFile {
+        dependencies: root_dependencies,
+        packages: packages.into_values().map(|(p, _)| p).collect(),
+    })
+}
+
+/// Writes the lock file if it is missing or the resolved dependencies differ
+/// from the ones it records. An up-to-date lock file is left untouched, and no
+/// lock file is created for a manifest without dependencies.
+/// If `locked` is set, a lock file that would change is an error instead.
+fn update_lock_file(manifest_dir: &Path, lock: &LockFile, locked: bool) -> Result<(), String> {
+    let path = manifest_dir.join(LOCK_FILE);
+    let existing = match std::fs::read_to_string(&path) {
+        Ok(content) => toml::from_str::<LockFile>(&content).ok(),
+        Err(_) if lock.packages.is_empty() => return Ok(()),
+        Err(_) => None,
+    };
+    if existing.as_ref() == Some(lock) {
+        return Ok(());
+    }
+    if locked {
+        return Err(format!(
+            "`{}` needs to be updated, but {POWDR_LOCKED_ENV} is set",
+            path.display()
+        ));
+    }
+    let content = toml::to_string(lock)
+        .map_err(|e| format!("Could not serialize `{}`: {e}", path.display()))?;
+    std::fs::write(&path, format!("{LOCK_FILE_HEADER}\n{content}"))
+        .map_err(|e| format!("Could not write `{}`: {e}", path.display()))
+}
+
+/// Loads the root module of the package in `dir` and all its submodules.
+fn load_package(dir: &Path) -> Result<ASMModule, String> {
+    let path = dir.join(MOD_FILE);
+    let source = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Could not read `{}`: {e}", path.display()))?;
+    let file_name = path
+        .to_str()
+        .ok_or_else(|| format!("Package path `{}` is not valid UTF-8", path.display()))?;
+    let program = parse_asm(Some(file_name), &source).map_err(|err| {
+        err.output_to_stderr();
+        format!("Error parsing `{}`", path.display())
+    })?;
+    Ok(load_module_files(Some(path), program)?.main)
+}
+
+/// Removes `.` components and resolves `..` components where possible,
+/// without accessing the file system.
+fn normalize(path: &Path) -> PathBuf {
+    let mut result = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match result.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    result.pop();
+                }
+                Some(Component::RootDir | Component::Prefix(_)) => {}
+                _ => result.push(".."),
+            },
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use powdr_ast::parsed::asm::{Module, ModuleStatement, SymbolDefinition, SymbolValue};
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn resolve(project: &str) -> Result<LockFile, String> {
+        resolve_packages(&Path::new("test_data/packages").join(project))
+    }
+
+    fn package(name: &str, version: &str, path: &str, dependencies: &[&str]) -> LockedPackage {
+        LockedPackage {
+            name: name.to_string(),
+            version: Version::parse(version).unwrap(),
+            path: PathBuf::from(path),
+            dependencies: dependencies.iter().map(|d| d.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn simple() {
+        let lock = resolve("simple").unwrap();
+        assert_eq!(
+            lock,
+            LockFile {
+                dependencies: vec!["math".to_string()],
+                packages: vec![
+                    package("math", "0.1.2", "../libs/math", &["utils"]),
+                    package("utils", "1.0.0", "../libs/utils", &[]),
+                ]
+            }
+        );
+
+        // The lock file in the repository is up to date.
+        let dir = Path::new("test_data/packages/simple");
+        update_lock_file(dir, &lock, true).unwrap();
+        assert_eq!(load_lock_file(dir, true).unwrap(), lock);
+    }
+
+    #[test]
+    fn outdated_lock_file() {
+        let dir = Path::new("test_data/packages/simple");
+        let lock = resolve("simple").unwrap();
+        assert!(lock_file_matches(dir, &lock));
+
+        let mut other_version = resolve("simple").unwrap();
+        other_version.packages[1].version = Version::parse("1.1.0").unwrap();
+        assert!(!lock_file_matches(dir, &other_version));
+
+        let mut other_path = resolve("simple").unwrap();
+        other_path.packages[1].path = PathBuf::from("../libs/utils_copy");
+        assert!(!lock_file_matches(dir, &other_path));
+
+        let mut missing_dependency = resolve("simple").unwrap();
+        missing_dependency.packages[0].dependencies.clear();
+        assert!(!lock_file_matches(dir, &missing_dependency));
+    }
+
+    #[test]
+    fn load_simple() {
+        let main_path = Path::new("test_data/packages/simple/main.asm");
+        let main = parse_asm(None, &std::fs::read_to_string(main_path).unwrap()).unwrap();
+        let main = load_module_files(Some(main_path.to_path_buf()), main).unwrap();
+        let program = add_packages(Some(main_path), main).unwrap();
+        let names = program
+            .main
+            .statements
+            .iter()
+            .flat_map(|s| s.defined_names())
+            .cloned()
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["quadruple", "sub", "x", "math", "utils"]);
+
+        // `utils` is only imported where it is declared as a dependency.
+        let imports = |module: &ASMModule| {
+            module
+                .statements
+                .iter()
+                .flat_map(|s| s.defined_names())
+                .filter(|name| *name == "math" || *name == "utils")
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        let submodule = |name: &str| {
+            program
+                .main
+                .statements
+                .iter()
+                .find_map(|s| match s {
+                    ModuleStatement::SymbolDefinition(SymbolDefinition {
+                        name: n,
+                        value: SymbolValue::Module(Module::Local(module)),
+                        ..
+                    }) if n == name => Some(module),
+                    _ => None,
+                })
+                .unwrap()
+        };
+        assert_eq!(imports(submodule("sub")), ["math"]);
+        assert_eq!(imports(submodule("math")), ["utils"]);
+        assert_eq!(imports(submodule("utils")), Vec::<String>::new());
+
+        crate::load_dependencies_and_resolve(
+            Some(main_path.to_path_buf()),
+            parse_asm(None, &std::fs::read_to_string(main_path).unwrap()).unwrap(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn undeclared_dependency() {
+        let main_path = Path::new("test_data/packages/undeclared/main.asm");
+        let main = parse_asm(None, &std::fs::read_to_string(main_path).unwrap()).unwrap();
+        assert!(crate::load_dependencies_and_resolve(Some(main_path.to_path_buf()), main).is_err());
+    }
+
+    #[test]
+    fn no_manifest() {
+        let program = parse_asm(None, "let x = 1;").unwrap();
+        let result = add_packages(Some(Path::new("/main.asm")), program.clone()).unwrap();
+        assert_eq!(result, program);
+    }
+
+    #[test]
+    fn incompatible_versions() {
+        assert_eq!(
+            resolve("incompatible"),
+            Err("Incompatible versions of package `utils`: 2.0.0 at `../libs/utils_v2` and 1.0.0 at `../libs/utils`".to_string())
+        );
+    }
+
+    #[test]
+    fn duplicate() {
+        assert_eq!(
+            resolve("duplicate"),
+            Err("Duplicate package `utils` 1.0.0: found at `../libs/utils_copy` and `../libs/utils`".to_string())
+        );
+    }
+
+    #[test]
+    fn version_mismatch() {
+        assert_eq!(
+            resolve("version_mismatch"),
+            Err("Package `version_mismatch` requires `math` version ^0.2, but found version 0.1.2 at `../libs/math`".to_string())
+        );
+    }
+
+    #[test]
+    fn wrong_name() {
+        assert_eq!(
+            resolve("wrong_name"),
+            Err("Package `wrong_name` depends on `maths`, but the package at `../libs/math` is called `math`".to_string())
+        );
+    }
+
+    #[test]
+    fn not_found() {
+        assert_eq!(
+            resolve("not_found"),
+            Err(
+                "Could not find package `geometry` required by `not_found` at `../libs/geometry`"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn lock_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let lock = LockFile {
+            dependencies: vec!["utils".to_string()],
+            packages: vec![package("utils", "1.0.0", "../libs/utils", &[])],
+        };
+        // A missing lock file cannot be created in locked mode.
+        assert!(update_lock_file(dir.path(), &lock, true).is_err());
+        update_lock_file(dir.path(), &lock, false).unwrap();
+        update_lock_file(dir.path(), &lock, true).unwrap();
+
+        let updated = LockFile {
+            dependencies: vec!["utils".to_string()],
+            packages: vec![package("utils", "1.1.0", "../libs/utils", &[])],
+        };
+        assert!(update_lock_file(dir.path(), &updated, true).is_err());
+        update_lock_file(dir.path(), &updated, false).unwrap();
+        let content = std::fs::read_to_string(dir.path().join(LOCK_FILE)).unwrap();
+        assert_eq!(toml::from_str::<LockFile>(&content).unwrap(), updated);
+
+        // An up-to-date lock file is not rewritten.
+        let edited = format!("# edited\n{content}");
+        std::fs::write(dir.path().join(LOCK_FILE), &edited).unwrap();
+        update_lock_file(dir.path(), &updated, false).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join(LOCK_FILE)).unwrap(),
+            edited
+        );
+    }
+
+    #[test]
+    fn no_lock_file_without_dependencies() {
+        let dir = tempfile::tempdir().unwrap();
+        update_lock_file(dir.path(), &LockFile::default(), true).unwrap();
+        update_lock_file(dir.path(), &LockFile::default(), false).unwrap();
+        assert!(!dir.path().join(LOCK_FILE).exists());
+    }
+
+    #[test]
+    fn normalize_paths() {
+        assert_eq!(normalize(Path::new("a/./b/../c")), PathBuf::from("a/c"));
+        assert_eq!(
+            normalize(Path::new("../a/../../b")),
+            PathBuf::from("../../b")
+        );
+        assert_eq!(normalize(Path::new("/../a")), PathBuf::from("/a"));
+    }
+}
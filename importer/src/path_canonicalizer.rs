@@ -14,10 +14,11 @@ use powdr_ast::parsed::{
     folder::Folder,
     types::{ExpressionInArrayLength, Type, TypeScheme},
     visitor::{Children, ExpressionVisitable},
-    ArrayLiteral, BinaryOperation, BlockExpression, EnumDeclaration, EnumVariant, Expression,
-    FunctionCall, IndexAccess, LambdaExpression, LetStatementInsideBlock, MatchArm,
-    MatchExpression, NamedExpression, NamedType, Pattern, PilStatement, StatementInsideBlock,
-    StructDeclaration, StructExpression, TraitDeclaration, UnaryOperation,
+    ArrayLiteral, AssignmentInsideBlock, BinaryOperation, BlockExpression, EnumDeclaration,
+    EnumVariant, Expression, ForLoop, FunctionCall, IndexAccess, LambdaExpression,
+    LetStatementInsideBlock, MatchArm, MatchExpression, NamedExpression, NamedType, Pattern,
+    PilStatement, StatementInsideBlock, StructDeclaration, StructExpression, TraitDeclaration,
+    UnaryOperation, WhileLoop,
 };
 use powdr_parser_util::{Error, SourceRef};
 
@@ -57,7 +58,11 @@ impl<'a> Folder for Canonicalizer<'a> {
                 .statements
                 .into_iter()
                 .filter_map(|statement| match statement {
-                    ModuleStatement::SymbolDefinition(SymbolDefinition { name, value }) => {
+                    ModuleStatement::SymbolDefinition(SymbolDefinition {
+                        name,
+                        visibility,
+                        value,
+                    }) => {
                         match value {
                             SymbolValue::Machine(m) => {
                                 // canonicalize the machine based on the same path, so we can reuse the same instance
@@ -80,15 +85,24 @@ impl<'a> Folder for Canonicalizer<'a> {
                                 .transpose(),
                             },
                         }
-                        .map(|value| value.map(|value| SymbolDefinition { name, value }.into()))
+                        .map(|value| {
+                            value.map(|value| {
+                                SymbolDefinition {
+                                    name,
+                                    visibility,
+                                    value,
+                                }
+                                .into()
+                            })
+                        })
                     }
-                    ModuleStatement::PilStatement(mut pil_statement) => {
+                    ModuleStatement::PilStatement(visibility, mut pil_statement) => {
                         canonicalize_inside_pil_statement(
                             &mut pil_statement,
                             &self.path,
                             self.paths,
                         );
-                        Some(Ok(ModuleStatement::PilStatement(pil_statement)))
+                        Some(Ok(ModuleStatement::PilStatement(visibility, pil_statement)))
                     }
                 })
                 .collect::<Result<_, _>>()?,
@@ -297,8 +311,15 @@ fn canonicalize_inside_expression(
             }
             Expression::BlockExpression(_, BlockExpression { statements, .. }) => {
                 for statement in statements {
-                    if let StatementInsideBlock::LetStatement(let_statement) = statement {
-                        canonicalize_inside_pattern(&mut let_statement.pattern, path, paths);
+                    match statement {
+                        StatementInsideBlock::LetStatement(LetStatementInsideBlock {
+                            pattern,
+                            ..
+                        })
+                        | StatementInsideBlock::For(ForLoop { pattern, .. }) => {
+                            canonicalize_inside_pattern(pattern, path, paths);
+                        }
+                        _ => {}
                     }
                 }
             }
@@ -428,12 +449,13 @@ fn check_path_try_prelude(
     symbol: SymbolPath,
     state: &mut State<'_>,
 ) -> Result<(), String> {
-    let path_to_check = location.join(symbol.clone());
-    match check_path(path_to_check.clone(), state) {
+    let path_to_check = location.clone().join(symbol.clone());
+    match check_path(path_to_check.clone(), &location, state) {
         Ok(()) => Ok(()),
         Err(error) => {
             match check_path_internal(
                 parse_absolute_path("::std::prelude").join(symbol),
+                &location,
                 state,
                 Default::default(),
             ) {
@@ -456,19 +478,24 @@ fn check_path_try_prelude(
 /// # Errors
 ///
 /// This function will return an error if the relative path does not resolve to anything
+/// or if it references a private symbol that is not accessible from `from`.
 fn check_path(
     // the path to check
     path: AbsoluteSymbolPath,
+    // the module from which the path is accessed
+    from: &AbsoluteSymbolPath,
     // the current state
     state: &mut State<'_>,
 ) -> Result<(), String> {
-    check_path_internal(path, state, Default::default())?;
+    check_path_internal(path, from, state, Default::default())?;
     Ok(())
 }
 
 fn check_path_internal<'a>(
     // the path to check
     path: AbsoluteSymbolPath,
+    // the module from which the path is accessed
+    from: &AbsoluteSymbolPath,
     // the current state
     state: &mut State<'a>,
     // the locations visited so far
@@ -500,11 +527,13 @@ fn check_path_internal<'a>(
                         .statements
                         .iter()
                         .find_map(|s| match s {
-                            ModuleStatement::SymbolDefinition(SymbolDefinition { name, value }) => {
-                                (name == member).then_some(value.as_ref())
-                            }
+                            ModuleStatement::SymbolDefinition(SymbolDefinition {
+                                name,
+                                visibility,
+                                value,
+                            }) => (name == member).then_some((*visibility, value.as_ref())),
                             // some pil statements introduce names
-                            ModuleStatement::PilStatement(s) => match s {
+                            ModuleStatement::PilStatement(visibility, s) => match s {
                                 PilStatement::EnumDeclaration(_, d) => (d.name == member)
                                     .then_some(SymbolValueRef::TypeDeclaration(
                                         TypeDeclaration::Enum(d),
@@ -519,14 +548,31 @@ fn check_path_internal<'a>(
                                 PilStatement::TraitDeclaration(_, d) => (d.name == member)
                                     .then_some(SymbolValueRef::TraitDeclaration(d)),
                                 _s => None,
-                            },
+                            }
+                            .map(|symbol| (*visibility, symbol)),
                         })
                         .ok_or_else(|| format!("symbol not found in `{location}`: `{member}`"))
-                        .and_then(|symbol| {
+                        .and_then(|(visibility, symbol)| {
+                            // private symbols are only accessible from inside the module
+                            // they are defined in and its sub-modules
+                            if !visibility.is_public() && from.common_prefix(&location) != location
+                            {
+                                return Err(format!(
+                                    "symbol `{member}` in `{location}` is private \
+                                    (items are private by default, declare it with `pub` \
+                                    to use it outside of `{location}`)"
+                                ));
+                            }
                             match symbol {
                                 SymbolValueRef::Import(p) => {
-                                    // if we found an import, check it and continue from there
-                                    check_path_internal(location.join(p.path.clone()), state, chain)
+                                    // if we found an import, check it and continue from there,
+                                    // resolving the imported path in the context of the importing module
+                                    check_path_internal(
+                                        location.clone().join(p.path.clone()),
+                                        &location,
+                                        state,
+                                        chain,
+                                    )
                                 }
                                 symbol => {
                                     // if we found any other symbol, continue from there
@@ -541,7 +587,8 @@ fn check_path_internal<'a>(
 
                         // redirect to `p`
                         check_path_internal(
-                            location.join(p.path.clone()).with_part(member),
+                            location.clone().join(p.path.clone()).with_part(member),
+                            &location,
                             state,
                             chain,
                         )
@@ -597,7 +644,7 @@ fn check_import(
     // the current state
     state: &mut State<'_>,
 ) -> Result<(), String> {
-    check_path(location.join(imported.path), state)
+    check_path(location.clone().join(imported.path), &location, state)
 }
 
 fn generate_path_map(program: &ASMProgram) -> Result<PathMap, Error> {
@@ -641,23 +688,25 @@ fn check_module(
         // start with the initial state
         // update the state
         match statement {
-            ModuleStatement::PilStatement(p) => {
+            ModuleStatement::PilStatement(_, p) => {
                 check_pil_statement_inside_module(location.clone(), p, state)?;
             }
-            ModuleStatement::SymbolDefinition(SymbolDefinition { name, value }) => match value {
-                SymbolValue::Machine(machine) => {
-                    check_machine(location.with_part(name), machine, state)?;
-                }
-                SymbolValue::Module(module) => {
-                    let m = match module {
-                        Module::External(_) => unreachable!(),
-                        Module::Local(m) => m,
-                    };
-                    check_module(location.with_part(name), m, state)?;
+            ModuleStatement::SymbolDefinition(SymbolDefinition { name, value, .. }) => {
+                match value {
+                    SymbolValue::Machine(machine) => {
+                        check_machine(location.with_part(name), machine, state)?;
+                    }
+                    SymbolValue::Module(module) => {
+                        let m = match module {
+                            Module::External(_) => unreachable!(),
+                            Module::Local(m) => m,
+                        };
+                        check_module(location.with_part(name), m, state)?;
+                    }
+                    SymbolValue::Import(s) => check_import(location.clone(), s.clone(), state)
+                        .map_err(|e| SourceRef::default().with_error(e))?,
                 }
-                SymbolValue::Import(s) => check_import(location.clone(), s.clone(), state)
-                    .map_err(|e| SourceRef::default().with_error(e))?,
-            },
+            }
         }
     }
     Ok(())
@@ -732,7 +781,7 @@ fn check_machine(
     }
     for param in &m.params.0 {
         let path: SymbolPath = param.ty.clone().unwrap();
-        check_path(module_location.clone().join(path), state)
+        check_path(module_location.clone().join(path), &module_location, state)
             .map_err(|e| param.source.with_error(e))?
     }
     if let Some(degree) = &m.properties.degree {
@@ -747,8 +796,12 @@ fn check_machine(
     for statement in &m.statements {
         match statement {
             MachineStatement::Submachine(source_ref, path, _, args) => {
-                check_path(module_location.clone().join(path.clone()), state)
-                    .map_err(|e| source_ref.with_error(e))?;
+                check_path(
+                    module_location.clone().join(path.clone()),
+                    &module_location,
+                    state,
+                )
+                .map_err(|e| source_ref.with_error(e))?;
                 args.iter().try_for_each(|expr| {
                     check_expression(
                         &module_location,
@@ -916,6 +969,7 @@ fn check_expression(
                 match statement {
                     StatementInsideBlock::LetStatement(LetStatementInsideBlock {
                         pattern,
+                        mutable: _,
                         ty,
                         value,
                     }) => {
@@ -930,6 +984,24 @@ fn check_expression(
                     StatementInsideBlock::Expression(expr) => {
                         check_expression(location, expr, state, type_vars, &local_variables)?;
                     }
+                    StatementInsideBlock::Assignment(AssignmentInsideBlock { target, value }) => {
+                        check_expression(location, target, state, type_vars, &local_variables)?;
+                        check_expression(location, value, state, type_vars, &local_variables)?;
+                    }
+                    StatementInsideBlock::While(WhileLoop { condition, body }) => {
+                        check_expression(location, condition, state, type_vars, &local_variables)?;
+                        check_expression(location, body, state, type_vars, &local_variables)?;
+                    }
+                    StatementInsideBlock::For(ForLoop {
+                        pattern,
+                        iterable,
+                        body,
+                    }) => {
+                        check_expression(location, iterable, state, type_vars, &local_variables)?;
+                        let mut local_variables = local_variables.clone();
+                        local_variables.extend(check_pattern(location, pattern, state)?);
+                        check_expression(location, body, state, type_vars, &local_variables)?;
+                    }
                 }
             }
             match expr {
@@ -1280,6 +1352,38 @@ mod tests {
         expect("cycle", Err("Cycle detected in `use` statements: `::module::Machine` -> `::other_module::submodule::MyMachine` -> `::Machine` -> `::module::Machine`"), false)
     }
 
+    #[test]
+    fn private_symbol() {
+        expect(
+            "private_symbol",
+            Err("symbol `Foo` in `::submodule` is private (items are private by default, declare it with `pub` to use it outside of `::submodule`)"),
+            false,
+        )
+    }
+
+    #[test]
+    fn private_import() {
+        expect(
+            "private_import",
+            Err("symbol `Foo` in `::submodule` is private (items are private by default, declare it with `pub` to use it outside of `::submodule`)"),
+            false,
+        )
+    }
+
+    #[test]
+    fn private_module() {
+        expect(
+            "private_module",
+            Err("symbol `internal` in `::submodule` is private (items are private by default, declare it with `pub` to use it outside of `::submodule`)"),
+            false,
+        )
+    }
+
+    #[test]
+    fn private_access() {
+        expect("private_access", Ok(()), false)
+    }
+
     #[test]
     fn import_after_usage() {
         expect("import_after_usage", Ok(()), false)
@@ -1,4 +1,5 @@
 use std::{
+    collections::HashSet,
     env,
     path::{Path, PathBuf},
 };
@@ -6,7 +7,7 @@ use std::{
 use powdr_ast::parsed::{
     asm::{
         ASMModule, ASMProgram, Import, Module, ModuleStatement, Part, SymbolDefinition, SymbolPath,
-        SymbolValue,
+        SymbolValue, Visibility,
     },
     folder::Folder,
 };
@@ -54,68 +55,122 @@ fn load_std() -> ASMModule {
 }
 
 pub fn add_std(program: ASMProgram) -> Result<ASMProgram, String> {
-    StdAdder().fold_program(program)
+    add_external_modules(
+        program,
+        vec![ExternalModule {
+            name: "std".to_string(),
+            module: load_std(),
+            dependencies: vec!["std".to_string()],
+        }],
+        &["std".to_string()],
+    )
 }
 
-struct StdAdder();
-
-type Error = String;
+/// A module that is not part of the program itself, like the standard library
+/// or a package.
+pub struct ExternalModule {
+    pub name: String,
+    pub module: ASMModule,
+    /// The names of the external modules this module can refer to.
+    pub dependencies: Vec<String>,
+}
 
-impl Folder for StdAdder {
-    type Error = Error;
+/// Adds the given modules to the main module of the program under their name.
+/// The modules of the program can refer to the external modules in `visible`,
+/// and each external module to the ones in its `dependencies`: this is done by
+/// adding `use super::<name>;` to all modules that do not define a symbol of
+/// the same name.
+pub fn add_external_modules(
+    program: ASMProgram,
+    modules: Vec<ExternalModule>,
+    visible: &[String],
+) -> Result<ASMProgram, String> {
+    // The external modules are defined in the main module, so it does not need to import them.
+    let mounted = modules.iter().map(|m| m.name.clone()).collect();
+    let mut statements = ExternalModuleAdder {
+        names: visible.to_vec(),
+    }
+    .fold_module_statements(program.main.statements, mounted)?;
 
-    fn fold_program(&mut self, p: ASMProgram) -> Result<ASMProgram, Self::Error> {
-        // Add `std` to the main module
-        let mut main = p.main;
-        main.statements
-            .push(ModuleStatement::SymbolDefinition(SymbolDefinition {
-                name: "std".to_string(),
-                value: SymbolValue::Module(Module::Local(load_std())),
-            }));
-
-        // Recurse
-        let main = self.fold_module_value(main)?;
-        Ok(ASMProgram { main })
+    for ExternalModule {
+        name,
+        module,
+        dependencies,
+    } in modules
+    {
+        let module = ExternalModuleAdder {
+            names: dependencies,
+        }
+        .fold_module_value(module)?;
+        statements.push(ModuleStatement::SymbolDefinition(SymbolDefinition {
+            name,
+            visibility: Visibility::Public,
+            value: SymbolValue::Module(Module::Local(module)),
+        }));
     }
 
-    fn fold_module_value(&mut self, module: ASMModule) -> Result<ASMModule, Self::Error> {
+    Ok(ASMProgram {
+        main: ASMModule { statements },
+    })
+}
+
+struct ExternalModuleAdder {
+    names: Vec<String>,
+}
+
+impl ExternalModuleAdder {
+    /// Folds the statements of a module and adds `use super::<name>;` for all
+    /// external modules that are neither defined by the statements nor in `defined_names`.
+    fn fold_module_statements(
+        &mut self,
+        statements: Vec<ModuleStatement>,
+        mut defined_names: HashSet<String>,
+    ) -> Result<Vec<ModuleStatement>, Error> {
         // This block is identical to Folder::fold_module_value.
         // Unfortunately, there is no way to call the super method from here.
-        let mut statements = module
-            .statements
+        let mut statements = statements
             .into_iter()
             .map(|s| match s {
                 ModuleStatement::SymbolDefinition(d) => match d.value {
                     SymbolValue::Machine(machine) => self.fold_machine(machine).map(From::from),
                     SymbolValue::Import(import) => {
-                        StdAdder::fold_import(self, import).map(From::from)
+                        ExternalModuleAdder::fold_import(self, import).map(From::from)
                     }
                     SymbolValue::Module(module) => self.fold_module(module).map(From::from),
                 }
                 .map(|value| ModuleStatement::SymbolDefinition(SymbolDefinition { value, ..d })),
-                ModuleStatement::PilStatement(pil) => self.fold_pil_statement(pil).map(From::from),
+                ModuleStatement::PilStatement(visibility, pil) => self
+                    .fold_pil_statement(pil)
+                    .map(|pil| ModuleStatement::PilStatement(visibility, pil)),
             })
             .collect::<Result<Vec<_>, _>>()?;
 
-        // Check whether the module already has a definition for `std`
-        // (E.g. the main module)
-        let has_std = statements
-            .iter()
-            .flat_map(|m| m.defined_names())
-            .any(|n| n == "std");
-
-        if !has_std {
-            // If not, add `use super::std;`
-            let std_import_path =
-                SymbolPath::from_parts([Part::Super, Part::Named("std".to_string())]);
-            statements.push(ModuleStatement::SymbolDefinition(SymbolDefinition {
-                name: "std".to_string(),
-                value: SymbolValue::Import(Import {
-                    path: std_import_path,
-                }),
-            }));
+        // Check which of the external modules the module already has a definition for
+        defined_names.extend(statements.iter().flat_map(|m| m.defined_names()).cloned());
+
+        // For all others, add `use super::<name>;`
+        for name in &self.names {
+            if !defined_names.contains(name) {
+                let import_path = SymbolPath::from_parts([Part::Super, Part::Named(name.clone())]);
+                statements.push(ModuleStatement::SymbolDefinition(SymbolDefinition {
+                    name: name.clone(),
+                    visibility: Visibility::Private,
+                    value: SymbolValue::Import(Import { path: import_path }),
+                }));
+            }
         }
 
+        Ok(statements)
+    }
+}
+
+type Error = String;
+
+impl Folder for ExternalModuleAdder {
+    type Error = Error;
+
+    fn fold_module_value(&mut self, module: ASMModule) -> Result<ASMModule, Self::Error> {
+        let statements = self.fold_module_statements(module.statements, HashSet::new())?;
         Ok(ASMModule { statements })
     }
 }
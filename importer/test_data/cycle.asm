@@ -1,11 +1,11 @@
-use module::Machine;
+pub use module::Machine;
 
-mod module {
-    use super::other_module::submodule::MyMachine as Machine;
+pub mod module {
+    pub use super::other_module::submodule::MyMachine as Machine;
 }
 
-mod other_module {
-    mod submodule {
-        use super::super::Machine as MyMachine;
+pub mod other_module {
+    pub mod submodule {
+        pub use super::super::Machine as MyMachine;
     }
 }
\ No newline at end of file
@@ -1,3 +1,3 @@
-machine A with degree: N {
+pub machine A with degree: N {
     
 }
\ No newline at end of file
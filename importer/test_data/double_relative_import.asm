@@ -1,8 +1,8 @@
-use submodule::subbbb::Foo as Foo;
-mod submodule {
-    use subbbb::Foo as Foo;
-    mod subbbb {
-        machine Foo {
+pub use submodule::subbbb::Foo as Foo;
+pub mod submodule {
+    pub use subbbb::Foo as Foo;
+    pub mod subbbb {
+        pub machine Foo {
         }
     }
 }
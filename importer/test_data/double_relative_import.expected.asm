@@ -1,6 +1,6 @@
-mod submodule {
-    mod subbbb {
-        machine Foo {
+pub mod submodule {
+    pub mod subbbb {
+        pub machine Foo {
         }
     }
 }
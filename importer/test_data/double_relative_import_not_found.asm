@@ -1,6 +1,6 @@
-use submodule::subbbb::Foo as Foo1;
-mod submodule {
-    use subbbb::Foo as Foo0;
-    mod subbbb {
+pub use submodule::subbbb::Foo as Foo1;
+pub mod submodule {
+    pub use subbbb::Foo as Foo0;
+    pub mod subbbb {
     }
 }
@@ -1,4 +1,4 @@
-machine Foo {
+pub machine Foo {
 }
-machine Foo {
+pub machine Foo {
 }
@@ -1,6 +1,6 @@
-mod submodule {
-    machine Foo {
+pub mod submodule {
+    pub machine Foo {
     }
-    mod Foo {
+    pub mod Foo {
     }
 }
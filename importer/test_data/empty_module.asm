@@ -1,2 +1,2 @@
-mod module {
+pub mod module {
 }
@@ -1,9 +1,9 @@
-machine Foo {
+pub machine Foo {
 }
 
-mod module {
-    machine Bar {
+pub mod module {
+    pub machine Bar {
         Foo foo;
     }
-    use super::Foo;
+    pub use super::Foo;
 }
\ No newline at end of file
@@ -1,7 +1,7 @@
-machine Foo {
+pub machine Foo {
 }
-mod module {
-    machine Bar {
+pub mod module {
+    pub machine Bar {
         ::Foo foo;
     }
 }
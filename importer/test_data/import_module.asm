@@ -1,8 +1,8 @@
-use submodule as alias;
-mod submodule {
-    machine Foo {
+pub use submodule as alias;
+pub mod submodule {
+    pub machine Foo {
     }
 }
-machine Foo {
+pub machine Foo {
     alias::Foo foo;
 }
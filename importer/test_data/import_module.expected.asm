@@ -1,7 +1,7 @@
-mod submodule {
-    machine Foo {
+pub mod submodule {
+    pub machine Foo {
     }
 }
-machine Foo {
+pub machine Foo {
     ::submodule::Foo foo;
 }
@@ -1,8 +1,8 @@
-use submodule::alias::Foo as Foo;
-mod submodule {
-    use subbbb as alias;
-    mod subbbb {
-        machine Foo {
+pub use submodule::alias::Foo as Foo;
+pub mod submodule {
+    pub use subbbb as alias;
+    pub mod subbbb {
+        pub machine Foo {
         }
     }
 }
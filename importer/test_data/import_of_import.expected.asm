@@ -1,11 +1,11 @@
-machine Bar {
+pub machine Bar {
     ::submodule::subbbb::Foo a;
     ::submodule::subbbb::Foo b;
     ::submodule::subbbb::Foo c;
 }
-mod submodule {
-    mod subbbb {
-        machine Foo {
+pub mod submodule {
+    pub mod subbbb {
+        pub machine Foo {
         }
     }
 }
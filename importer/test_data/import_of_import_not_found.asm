@@ -1,6 +1,6 @@
-use submodule::Foo as Foo;
-mod submodule {
-    use subbbb::Foo as Foo;
-    mod subbbb {
+pub use submodule::Foo as Foo;
+pub mod submodule {
+    pub use subbbb::Foo as Foo;
+    pub mod subbbb {
     }
 }
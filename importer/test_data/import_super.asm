@@ -1,9 +1,9 @@
-machine Machine {
+pub machine Machine {
 }
-mod submodule {
-    mod subbbb {
-        use super::super::Machine as Machine0;
-        machine M {
+pub mod submodule {
+    pub mod subbbb {
+        pub use super::super::Machine as Machine0;
+        pub machine M {
             Machine0 m;
         }
     }
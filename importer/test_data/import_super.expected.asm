@@ -1,8 +1,8 @@
-machine Machine {
+pub machine Machine {
 }
-mod submodule {
-    mod subbbb {
-        machine M {
+pub mod submodule {
+    pub mod subbbb {
+        pub machine M {
             Machine m;
         }
     }
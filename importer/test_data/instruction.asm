@@ -1,6 +1,6 @@
-let identity: expr -> expr = |expr| expr;
+pub let identity: expr -> expr = |expr| expr;
 
-machine Id {
+pub machine Id {
     operation id<0> x, y;
 
     col witness x;
@@ -8,7 +8,7 @@ machine Id {
     x = y;
 }
 
-machine Main {
+pub machine Main {
 
     Id id;
 
@@ -1,11 +1,11 @@
-let identity: expr -> expr = |expr| expr;
-machine Id {
+pub let identity: expr -> expr = |expr| expr;
+pub machine Id {
     operation id<0> x, y;
     pol commit x;
     pol commit y;
     x = y;
 }
-machine Main {
+pub machine Main {
     ::Id id;
     reg pc[@pc];
     reg X[<=];
@@ -1 +1 @@
-mod A;
\ No newline at end of file
+pub mod A;
\ No newline at end of file
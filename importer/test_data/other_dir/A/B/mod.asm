@@ -1,3 +1,3 @@
-machine Machine {
+pub machine Machine {
     
 }
\ No newline at end of file
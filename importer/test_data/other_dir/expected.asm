@@ -1,11 +1,11 @@
-mod A {
-    mod B {
-        machine Machine {
+pub mod A {
+    pub mod B {
+        pub machine Machine {
             
         }
     }
 
-    machine Machine {
+    pub machine Machine {
 
     }
 }
\ No newline at end of file
@@ -0,0 +1,3 @@
+use utils::double;
+
+pub let quadruple: int -> int = |x| double(double(x));
@@ -0,0 +1 @@
+pub let double: int -> int = |x| x * 2;
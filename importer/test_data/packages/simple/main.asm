@@ -0,0 +1,7 @@
+use math::quadruple;
+
+mod sub {
+    let y: int = math::quadruple(3);
+}
+
+let x: int = quadruple(2);
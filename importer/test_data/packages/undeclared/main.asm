@@ -0,0 +1,4 @@
+mod sub {
+    // `utils` is a dependency of `math`, but not of this package.
+    let y: int = utils::double(3);
+}
@@ -1,10 +1,10 @@
-mod std {
-    mod prelude {
-        let x: int = 1;
+pub mod std {
+    pub mod prelude {
+        pub let x: int = 1;
     }
 }
 
-let y = module::x;
+pub let y = module::x;
 
-mod module {
+pub mod module {
 }
\ No newline at end of file
@@ -0,0 +1,15 @@
+machine Main {
+    submodule::Foo f;
+}
+mod submodule {
+    pub machine Foo {
+        internal::Bar b;
+    }
+    mod internal {
+        pub machine Bar {
+            super::super::Baz b;
+        }
+    }
+}
+machine Baz {
+}
@@ -0,0 +1,15 @@
+machine Main {
+    ::submodule::Foo f;
+}
+mod submodule {
+    pub machine Foo {
+        ::submodule::internal::Bar b;
+    }
+    mod internal {
+        pub machine Bar {
+            ::Baz b;
+        }
+    }
+}
+machine Baz {
+}
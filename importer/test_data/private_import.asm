@@ -0,0 +1,10 @@
+pub mod submodule {
+    use super::other::Foo;
+}
+pub mod other {
+    pub machine Foo {
+    }
+}
+pub machine Main {
+    submodule::Foo f;
+}
@@ -0,0 +1,9 @@
+pub mod submodule {
+    mod internal {
+        pub machine Foo {
+        }
+    }
+}
+pub machine Main {
+    submodule::internal::Foo f;
+}
@@ -0,0 +1,7 @@
+mod submodule {
+    machine Foo {
+    }
+}
+pub machine Main {
+    submodule::Foo f;
+}
@@ -1,5 +1,5 @@
-use submodule::Foo;
-mod submodule {
-    machine Foo {
+pub use submodule::Foo;
+pub mod submodule {
+    pub machine Foo {
     }
 }
@@ -1,4 +1,4 @@
-mod submodule {
-    machine Foo {
+pub mod submodule {
+    pub machine Foo {
     }
 }
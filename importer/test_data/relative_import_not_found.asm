@@ -1,3 +1,3 @@
-use submodule::Foo;
-mod submodule {
+pub use submodule::Foo;
+pub mod submodule {
 }
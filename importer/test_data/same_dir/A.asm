@@ -1,5 +1,5 @@
-mod B;
+pub mod B;
 
-machine Machine {
+pub machine Machine {
 
 }
\ No newline at end of file
@@ -1,9 +1,9 @@
-mod std {
-    mod prelude {
-        let x: int = 1;
+pub mod std {
+    pub mod prelude {
+        pub let x: int = 1;
     }
 }
 
-mod module {
-    let y = x;
+pub mod module {
+    pub let y = x;
 }
\ No newline at end of file
@@ -1,8 +1,8 @@
-mod std {
-    mod prelude {
-        let x: int = 1;
+pub mod std {
+    pub mod prelude {
+        pub let x: int = 1;
     }
 }
-mod module {
-    let y = std::prelude::x;
+pub mod module {
+    pub let y = std::prelude::x;
 }
@@ -1,15 +1,15 @@
-mod other {
-    enum E1 {
+pub mod other {
+    pub enum E1 {
         A,
     }
-    struct S1 {
+    pub struct S1 {
         a: int,
         b: other::E1,
     }
-    struct S2 {
+    pub struct S2 {
         x: std::prelude::Option<int>,
         y: other::S1,
     }
 }
-let s1 = other::S1{ a: 1, b: other::E1::A };
-let s2 = other::S2{ x: std::prelude::Option::Some(1), y: s1 };
+pub let s1 = other::S1{ a: 1, b: other::E1::A };
+pub let s2 = other::S2{ x: std::prelude::Option::Some(1), y: s1 };
@@ -1,8 +1,8 @@
-mod bar {
-    machine Bar {
+pub mod bar {
+    pub machine Bar {
     }
 }
-use bar::Bar as LocalBar;
-machine Foo {
+pub use bar::Bar as LocalBar;
+pub machine Foo {
     LocalBar foo;
 }
@@ -1,7 +1,7 @@
-mod bar {
-    machine Bar {
+pub mod bar {
+    pub machine Bar {
     }
 }
-machine Foo {
+pub machine Foo {
     ::bar::Bar foo;
 }
@@ -1,3 +1,3 @@
-machine Foo {
+pub machine Foo {
     Bar foo;
 }
@@ -1,5 +1,5 @@
-use submodule::Foo::Bar as Bar;
-mod submodule {
-    machine Foo {
+pub use submodule::Foo::Bar as Bar;
+pub mod submodule {
+    pub machine Foo {
     }
 }
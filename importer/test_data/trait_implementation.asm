@@ -1,20 +1,20 @@
-mod other {
-    enum E0 {
+pub mod other {
+    pub enum E0 {
 
     }
-    enum E1 {
+    pub enum E1 {
         A,
     }
-    enum E2 {
+    pub enum E2 {
         A
     }
 }
 
-use other::E0;
-use other::E1;
-use other::E2;
+pub use other::E0;
+pub use other::E1;
+pub use other::E2;
 
-trait Foo<F> {
+pub trait Foo<F> {
     foo: E1 -> E2,
 }
 
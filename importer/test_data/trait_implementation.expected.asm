@@ -1,14 +1,14 @@
-mod other {
-    enum E0 {
+pub mod other {
+    pub enum E0 {
     }
-    enum E1 {
+    pub enum E1 {
         A,
     }
-    enum E2 {
+    pub enum E2 {
         A,
     }
 }
-trait Foo<F> {
+pub trait Foo<F> {
     foo: other::E1 -> other::E2,
 }
 impl Foo<other::E0[3]> {
@@ -1,17 +1,17 @@
-mod m {
-    trait X<T> {
+pub mod m {
+    pub trait X<T> {
         a: -> T,
     }
 }
 
-use m::X;
-use m::X::a;
+pub use m::X;
+pub use m::X::a;
 
-trait Foo<F> {
+pub trait Foo<F> {
     foo: int -> F,
 }
 
-let t = || {
+pub let t = || {
     let g: int = a();
     let h: int = X::a();
     let i = m::X::a();
@@ -1,12 +1,12 @@
-mod m {
-    trait X<T> {
+pub mod m {
+    pub trait X<T> {
         a: -> T,
     }
 }
-trait Foo<F> {
+pub trait Foo<F> {
     foo: int -> F,
 }
-let t = || {
+pub let t = || {
     let g: int = m::X::a();
     let h: int = m::X::a();
     let i = m::X::a();
@@ -1,11 +1,11 @@
-machine Bar {
+pub machine Bar {
 }
-mod b {
-    use super::Bar;
+pub mod b {
+    pub use super::Bar;
 }
-mod a {
-    use super::b as b;
-    machine M {
+pub mod a {
+    pub use super::b as b;
+    pub machine M {
         b::Bar bar;
     }
 }
@@ -1,9 +1,9 @@
-machine Bar {
+pub machine Bar {
 }
-mod b {
+pub mod b {
 }
-mod a {
-    machine M {
+pub mod a {
+    pub machine M {
         ::Bar bar;
     }
 }
@@ -8,10 +8,10 @@ use powdr_ast::{
         display::quote,
         types::{ArrayType, FunctionType, TupleType, Type, TypeScheme},
         visitor::AllChildren,
-        ArrayLiteral, BinaryOperation, BinaryOperator, BlockExpression, EnumDeclaration,
-        FunctionCall, IfExpression, IndexAccess, LambdaExpression, LetStatementInsideBlock,
-        MatchArm, MatchExpression, Number, Pattern, StatementInsideBlock, TypeDeclaration,
-        UnaryOperation,
+        ArrayLiteral, AssignmentInsideBlock, BinaryOperation, BinaryOperator, BlockExpression,
+        EnumDeclaration, ForLoop, FunctionCall, IfExpression, IndexAccess, LambdaExpression,
+        LetStatementInsideBlock, MatchArm, MatchExpression, Number, Pattern, StatementInsideBlock,
+        TypeDeclaration, UnaryOperation, WhileLoop,
     },
 };
 use powdr_number::{BigInt, BigUint, FieldElement, LargeInt};
@@ -406,7 +406,28 @@ impl<'a, T: FieldElement> CodeGenerator<'a, T> {
         var_height: usize,
     ) -> Result<(String, usize), String> {
         Ok(match s {
-            StatementInsideBlock::LetStatement(LetStatementInsideBlock { pattern, ty, value }) => {
+            StatementInsideBlock::LetStatement(LetStatementInsideBlock {
+                pattern: Pattern::Variable(_, name),
+                mutable: true,
+                ty,
+                value: Some(value),
+            }) => {
+                let value = self.format_expr(value, var_height)?;
+                let ty = ty
+                    .as_ref()
+                    .map(|ty| format!(": {}", map_type(ty)))
+                    .unwrap_or_default();
+                (
+                    format!("let mut {name}{ty} = ({value}).clone();"),
+                    var_height + 1,
+                )
+            }
+            StatementInsideBlock::LetStatement(LetStatementInsideBlock {
+                pattern,
+                ty,
+                value,
+                ..
+            }) => {
                 let Some(value) = value else {
                     return Err(format!(
                         "Column creating 'let'-statements not yet supported: {s}"
@@ -432,6 +453,47 @@ impl<'a, T: FieldElement> CodeGenerator<'a, T> {
             StatementInsideBlock::Expression(e) => {
                 (format!("{};", self.format_expr(e, var_height)?), var_height)
             }
+            StatementInsideBlock::Assignment(AssignmentInsideBlock { target, value }) => {
+                let target = match target {
+                    Expression::IndexAccess(_, IndexAccess { array, index }) => format!(
+                        "{}[usize::try_from({}).unwrap()]",
+                        self.format_expr(array, var_height)?,
+                        self.format_expr(index, var_height)?
+                    ),
+                    _ => self.format_expr(target, var_height)?,
+                };
+                (
+                    format!(
+                        "{target} = ({}).clone();",
+                        self.format_expr(value, var_height)?
+                    ),
+                    var_height,
+                )
+            }
+            StatementInsideBlock::While(WhileLoop { condition, body }) => (
+                format!(
+                    "while {} {{ {}; }}",
+                    self.format_expr(condition, var_height)?,
+                    self.format_expr(body, var_height)?
+                ),
+                var_height,
+            ),
+            StatementInsideBlock::For(ForLoop {
+                pattern,
+                iterable,
+                body,
+            }) => {
+                let var_name = "item__";
+                let (vars, code) = check_pattern(var_name, pattern)?;
+                (
+                    format!(
+                        "for {var_name} in ({}).iter().cloned() {{ let {vars} = (|{var_name}| {code})({var_name}).unwrap(); {}; }}",
+                        self.format_expr(iterable, var_height)?,
+                        self.format_expr(body, var_height + pattern.variables().count())?
+                    ),
+                    var_height,
+                )
+            }
         })
     }
 
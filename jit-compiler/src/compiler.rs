@@ -124,6 +124,13 @@ impl<T> std::ops::Index<usize> for PilVec<T> {
         &self.0[index]
     }
 }
+impl<T: Clone> std::ops::IndexMut<usize> for PilVec<T> {
+    #[inline]
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        // Only copies the vector if it is shared.
+        &mut std::sync::Arc::make_mut(&mut self.0)[index]
+    }
+}
     
 
 trait Add {
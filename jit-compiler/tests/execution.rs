@@ -391,3 +391,51 @@ fn generic() {
     assert_eq!(q.call(2), 23);
     assert_eq!(q.call(3), 24);
 }
+
+#[test]
+fn loops() {
+    let input = "
+        let sum_to: int -> int = |n| {
+            let mut i: int = 0;
+            let mut sum: int = 0;
+            while i <= n {
+                sum = sum + i;
+                i = i + 1;
+            }
+            sum
+        };
+        let sum_of_pairs: int -> int = |n| {
+            let mut result: int = 0;
+            for (a, b) in [(n, 1), (2, n)] {
+                result = result + a * b;
+            }
+            result
+        };
+    ";
+    let sum_to = compile_fun(input, "sum_to");
+    assert_eq!(sum_to.call(0), 0);
+    assert_eq!(sum_to.call(10), 55);
+
+    let sum_of_pairs = compile_fun(input, "sum_of_pairs");
+    assert_eq!(sum_of_pairs.call(3), 9);
+}
+
+#[test]
+fn array_element_assignment() {
+    let input = "
+        let squares: int -> int = |n| {
+            let mut arr: int[] = [0, 0, 0, 0, 0];
+            let copy: int[] = arr;
+            let mut i: int = 0;
+            while i < 5 {
+                arr[i] = i * i;
+                i = i + 1;
+            }
+            arr[n] + copy[n]
+        };
+    ";
+    let squares = compile_fun(input, "squares");
+    assert_eq!(squares.call(0), 0);
+    assert_eq!(squares.call(3), 9);
+    assert_eq!(squares.call(4), 16);
+}
@@ -4,8 +4,8 @@ use powdr_ast::parsed::asm::{
 };
 use powdr_ast::parsed::visitor::Children;
 use powdr_ast::parsed::{
-    BlockExpression, Expression, LambdaExpression, LetStatementInsideBlock, MatchExpression,
-    PILFile, Pattern, PilStatement, SourceReference, StatementInsideBlock,
+    BlockExpression, Expression, ForLoop, LambdaExpression, LetStatementInsideBlock,
+    MatchExpression, PILFile, Pattern, PilStatement, SourceReference, StatementInsideBlock,
 };
 use powdr_parser_util::SourceRef;
 
@@ -92,7 +92,7 @@ impl ClearSourceRefs for ModuleStatement {
                 }
                 SymbolValue::Module(Module::External(_)) | SymbolValue::Import(_) => {}
             },
-            ModuleStatement::PilStatement(s) => {
+            ModuleStatement::PilStatement(_, s) => {
                 s.clear_source_refs();
             }
         }
@@ -159,10 +159,11 @@ impl<E> ClearSourceRefs for BlockExpression<E> {
     fn clear_source_refs(&mut self) {
         for s in &mut self.statements {
             match s {
-                StatementInsideBlock::LetStatement(LetStatementInsideBlock { pattern, .. }) => {
-                    pattern.clear_source_refs()
-                }
-                StatementInsideBlock::Expression(_) => {}
+                StatementInsideBlock::LetStatement(LetStatementInsideBlock { pattern, .. })
+                | StatementInsideBlock::For(ForLoop { pattern, .. }) => pattern.clear_source_refs(),
+                StatementInsideBlock::Expression(_)
+                | StatementInsideBlock::Assignment(_)
+                | StatementInsideBlock::While(_) => {}
             }
         }
     }
@@ -865,6 +865,7 @@ fn try_closure_to_expression<T: FieldElement>(
 
             Ok(LetStatementInsideBlock {
                 pattern: Pattern::Variable(SourceRef::unknown(), (*name).clone()),
+                mutable: false,
                 // We do not know the type.
                 ty: None,
                 value: Some(expr),
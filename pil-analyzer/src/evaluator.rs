@@ -16,10 +16,10 @@ use powdr_ast::{
     parsed::{
         display::quote,
         types::{ArrayType, Type, TypeScheme},
-        ArrayLiteral, BinaryOperation, BinaryOperator, BlockExpression, EnumDeclaration,
-        FunctionCall, IfExpression, IndexAccess, LambdaExpression, LetStatementInsideBlock,
-        MatchArm, MatchExpression, Number, Pattern, StatementInsideBlock, UnaryOperation,
-        UnaryOperator,
+        ArrayLiteral, AssignmentInsideBlock, BinaryOperation, BinaryOperator, BlockExpression,
+        EnumDeclaration, ForLoop, FunctionCall, IfExpression, IndexAccess, LambdaExpression,
+        LetStatementInsideBlock, MatchArm, MatchExpression, Number, Pattern, StatementInsideBlock,
+        UnaryOperation, UnaryOperator, WhileLoop,
     },
 };
 use powdr_number::{BigInt, BigUint, FieldElement, LargeInt};
@@ -399,7 +399,7 @@ fn none_value<'a, T>() -> Value<'a, T> {
     })
 }
 
-const BUILTINS: [(&str, BuiltinFunction); 20] = [
+const BUILTINS: [(&str, BuiltinFunction); 25] = [
     ("std::array::len", BuiltinFunction::ArrayLen),
     ("std::check::panic", BuiltinFunction::Panic),
     ("std::convert::expr", BuiltinFunction::ToExpr),
@@ -432,6 +432,17 @@ const BUILTINS: [(&str, BuiltinFunction); 20] = [
         "std::prover::output_to_channel",
         BuiltinFunction::OutputToChannel,
     ),
+    ("std::test::run_witgen", BuiltinFunction::RunWitgen),
+    ("std::test::witness_value", BuiltinFunction::WitnessValue),
+    ("std::test::public_value", BuiltinFunction::PublicValue),
+    (
+        "std::test::expect_witgen_failure",
+        BuiltinFunction::ExpectWitgenFailure,
+    ),
+    (
+        "std::test::expect_constraint_failure",
+        BuiltinFunction::ExpectConstraintFailure,
+    ),
 ];
 
 #[derive(Clone, Copy, Debug)]
@@ -481,6 +492,22 @@ pub enum BuiltinFunction {
     InputFromChannel,
     /// std::prover::output_to_channel: int, fe -> (), outputs a field element to an output channel
     OutputToChannel,
+    /// std::test::run_witgen: fe[] -> (), runs witness generation for the program under test
+    /// using the given prover inputs.
+    RunWitgen,
+    /// std::test::witness_value: string, int -> fe, returns the value of a witness column
+    /// at a given row of the witness generated by the last call to run_witgen.
+    WitnessValue,
+    /// std::test::public_value: string -> fe, returns the value of a public
+    /// in the witness generated by the last call to run_witgen.
+    PublicValue,
+    /// std::test::expect_witgen_failure: fe[] -> (), runs witness generation using the given
+    /// prover inputs and fails if it succeeds.
+    ExpectWitgenFailure,
+    /// std::test::expect_constraint_failure: (string, int, fe)[] -> (), modifies the witness
+    /// generated by the last call to run_witgen at the given (column, row) cells
+    /// and fails if the modified witness still satisfies all constraints.
+    ExpectConstraintFailure,
 }
 
 impl<'a, T: Display> Display for Value<'a, T> {
@@ -776,6 +803,39 @@ pub trait SymbolLookup<'a, T: FieldElement> {
             "Tried to output to channel outside of prover function.".to_string(),
         ))
     }
+
+    fn run_witgen(&mut self, _inputs: Vec<T>) -> Result<(), EvalError> {
+        Err(EvalError::Unsupported(
+            "Witness generation is only available in the test runner.".to_string(),
+        ))
+    }
+
+    fn witness_value(&self, _column: &str, _row: usize) -> Result<T, EvalError> {
+        Err(EvalError::Unsupported(
+            "Witness values are only available in the test runner.".to_string(),
+        ))
+    }
+
+    fn public_value(&self, _name: &str) -> Result<T, EvalError> {
+        Err(EvalError::Unsupported(
+            "Public values are only available in the test runner.".to_string(),
+        ))
+    }
+
+    fn expect_witgen_failure(&mut self, _inputs: Vec<T>) -> Result<(), EvalError> {
+        Err(EvalError::Unsupported(
+            "Witness generation is only available in the test runner.".to_string(),
+        ))
+    }
+
+    fn expect_constraint_failure(
+        &mut self,
+        _modifications: Vec<(String, usize, T)>,
+    ) -> Result<(), EvalError> {
+        Err(EvalError::Unsupported(
+            "Constraint checking is only available in the test runner.".to_string(),
+        ))
+    }
 }
 
 /// Operations to be performed by the evaluator.
@@ -791,6 +851,19 @@ enum Operation<'a, T> {
     SetEnvironment(Vec<Arc<Value<'a, T>>>, HashMap<String, Type>),
     /// Evaluate a let statement, adding matched pattern variables to the local variables.
     LetStatement(&'a LetStatementInsideBlock<Expression>),
+    /// Replace the value of a mutable local variable or of an element of it
+    /// by the topmost value on the value stack.
+    Assignment(&'a AssignmentInsideBlock<Expression>),
+    /// Evaluate the condition of a while loop and, if it is true,
+    /// evaluate the body and the loop again.
+    While(&'a WhileLoop<Expression>),
+    /// Start a for loop over the array on top of the value stack.
+    For(&'a ForLoop<Expression>),
+    /// Evaluate the body of a for loop for the item at the given index
+    /// of the array and then continue with the next index.
+    ForIteration(&'a ForLoop<Expression>, Arc<Value<'a, T>>, usize),
+    /// Remove the topmost value from the value stack.
+    DiscardValue,
     /// Add a constraint to the constraint set.
     AddProofItem,
 }
@@ -852,6 +925,18 @@ impl<'a, 'b, T: FieldElement, S: SymbolLookup<'a, T>> Evaluator<'a, 'b, T, S> {
                     self.type_args = new_type_args;
                 }
                 Operation::LetStatement(s) => self.evaluate_let_statement(s)?,
+                Operation::Assignment(a) => self.evaluate_assignment(a)?,
+                Operation::While(w) => self.evaluate_while_iteration(w),
+                Operation::For(f) => {
+                    let array = self.value_stack.pop().unwrap();
+                    self.evaluate_for_iteration(f, array, 0)
+                }
+                Operation::ForIteration(f, array, index) => {
+                    self.evaluate_for_iteration(f, array, index)
+                }
+                Operation::DiscardValue => {
+                    self.value_stack.pop().unwrap();
+                }
                 Operation::AddProofItem => {
                     let result = self.value_stack.pop().unwrap();
                     match result.as_ref() {
@@ -970,6 +1055,22 @@ impl<'a, 'b, T: FieldElement, S: SymbolLookup<'a, T>> Evaluator<'a, 'b, T, S> {
                             self.op_stack.push(Operation::AddProofItem);
                             self.op_stack.push(Operation::Expand(expr));
                         }
+                        StatementInsideBlock::Assignment(a) => {
+                            self.op_stack.push(Operation::Assignment(a));
+                            self.op_stack.push(Operation::Expand(&a.value));
+                            if let Expression::IndexAccess(_, IndexAccess { index, .. }) = &a.target
+                            {
+                                self.op_stack.push(Operation::Expand(index));
+                            }
+                        }
+                        StatementInsideBlock::While(w) => {
+                            self.op_stack.push(Operation::While(w));
+                            self.op_stack.push(Operation::Expand(&w.condition));
+                        }
+                        StatementInsideBlock::For(f) => {
+                            self.op_stack.push(Operation::For(f));
+                            self.op_stack.push(Operation::Expand(&f.iterable));
+                        }
                     }
                 }
             }
@@ -1010,6 +1111,85 @@ impl<'a, 'b, T: FieldElement, S: SymbolLookup<'a, T>> Evaluator<'a, 'b, T, S> {
         Ok(())
     }
 
+    /// Assigns the topmost value on the value stack to the target. If the target
+    /// is an array element, the index is the second value on the value stack.
+    /// The array is only copied if its value is shared with other variables.
+    fn evaluate_assignment(
+        &mut self,
+        a: &'a AssignmentInsideBlock<Expression>,
+    ) -> Result<(), EvalError> {
+        let value = self.value_stack.pop().unwrap();
+        match &a.target {
+            Expression::Reference(_, Reference::LocalVar(id, _)) => {
+                self.local_vars[*id as usize] = value;
+            }
+            Expression::IndexAccess(_, IndexAccess { array, .. }) => {
+                let Expression::Reference(_, Reference::LocalVar(id, _)) = array.as_ref() else {
+                    unreachable!("Can only assign to elements of local variables.")
+                };
+                let index = self.value_stack.pop().unwrap().try_to_integer()?;
+                let Value::Array(items) = Arc::make_mut(&mut self.local_vars[*id as usize]) else {
+                    unreachable!("Type error: Expected array in element assignment.")
+                };
+                let len = items.len();
+                let item = usize::try_from(&index)
+                    .ok()
+                    .and_then(|i| items.get_mut(i))
+                    .ok_or_else(|| {
+                        EvalError::OutOfBounds(format!(
+                            "Index access out of bounds: Tried to assign element {index} of array of size {len} in: {}.",
+                            a.target
+                        ))
+                    })?;
+                *item = value;
+            }
+            _ => unreachable!("Can only assign to local variables."),
+        }
+        Ok(())
+    }
+
+    /// Consumes the value of the loop condition and schedules the next iteration
+    /// of the loop if it is true.
+    fn evaluate_while_iteration(&mut self, w: &'a WhileLoop<Expression>) {
+        let condition = self.value_stack.pop().unwrap();
+        let Value::Bool(condition) = condition.as_ref() else {
+            unreachable!("Type error: Expected bool as loop condition.")
+        };
+        if *condition {
+            self.op_stack.push(Operation::While(w));
+            self.op_stack.push(Operation::Expand(&w.condition));
+            self.op_stack.push(Operation::DiscardValue);
+            self.op_stack.push(Operation::Expand(&w.body));
+        }
+    }
+
+    /// Binds the pattern to the item at `index` and schedules the body
+    /// and the next iteration, if there are items left in the array.
+    fn evaluate_for_iteration(
+        &mut self,
+        f: &'a ForLoop<Expression>,
+        array: Arc<Value<'a, T>>,
+        index: usize,
+    ) {
+        let Value::Array(items) = array.as_ref() else {
+            unreachable!("Type error: Expected array in for loop.")
+        };
+        let Some(item) = items.get(index).cloned() else {
+            return;
+        };
+        self.op_stack
+            .push(Operation::ForIteration(f, array, index + 1));
+        self.op_stack
+            .push(Operation::TruncateLocals(self.local_vars.len()));
+        self.op_stack.push(Operation::DiscardValue);
+        self.op_stack.push(Operation::Expand(&f.body));
+        self.local_vars.extend(
+            Value::try_match_pattern(&item, &f.pattern).unwrap_or_else(|| {
+                panic!("Irrefutable pattern did not match: {} = {item}", f.pattern)
+            }),
+        );
+    }
+
     fn evaluate_reference(
         &mut self,
         reference: &'a Reference,
@@ -1430,6 +1610,11 @@ fn evaluate_builtin_function<'a, T: FieldElement>(
         BuiltinFunction::TryEval => 1,
         BuiltinFunction::InputFromChannel => 2,
         BuiltinFunction::OutputToChannel => 2,
+        BuiltinFunction::RunWitgen => 1,
+        BuiltinFunction::WitnessValue => 2,
+        BuiltinFunction::PublicValue => 1,
+        BuiltinFunction::ExpectWitgenFailure => 1,
+        BuiltinFunction::ExpectConstraintFailure => 1,
     };
 
     if arguments.len() != params {
@@ -1542,6 +1727,49 @@ fn evaluate_builtin_function<'a, T: FieldElement>(
             )?;
             Value::Tuple(vec![]).into()
         }
+        BuiltinFunction::RunWitgen => {
+            let inputs = field_element_array(&arguments.pop().unwrap())?;
+            symbols.run_witgen(inputs)?;
+            Value::Tuple(vec![]).into()
+        }
+        BuiltinFunction::WitnessValue => {
+            let row = row_index(&arguments.pop().unwrap())?;
+            let column = string_value(&arguments.pop().unwrap())?;
+            Value::FieldElement(symbols.witness_value(column, row)?).into()
+        }
+        BuiltinFunction::PublicValue => {
+            let name = string_value(&arguments.pop().unwrap())?;
+            Value::FieldElement(symbols.public_value(name)?).into()
+        }
+        BuiltinFunction::ExpectWitgenFailure => {
+            let inputs = field_element_array(&arguments.pop().unwrap())?;
+            symbols.expect_witgen_failure(inputs)?;
+            Value::Tuple(vec![]).into()
+        }
+        BuiltinFunction::ExpectConstraintFailure => {
+            let modifications = match arguments.pop().unwrap().as_ref() {
+                Value::Array(items) => items
+                    .iter()
+                    .map(|item| match item.as_ref() {
+                        Value::Tuple(item) if item.len() == 3 => Ok((
+                            string_value(&item[0])?.clone(),
+                            row_index(&item[1])?,
+                            item[2].try_to_field_element()?,
+                        )),
+                        v => Err(EvalError::TypeError(format!(
+                            "Expected (string, int, fe), but got {v}: {}",
+                            v.type_formatted()
+                        ))),
+                    })
+                    .collect::<Result<_, EvalError>>()?,
+                v => Err(EvalError::TypeError(format!(
+                    "Expected array of cell modifications, but got {v}: {}",
+                    v.type_formatted()
+                )))?,
+            };
+            symbols.expect_constraint_failure(modifications)?;
+            Value::Tuple(vec![]).into()
+        }
         BuiltinFunction::SetHint => {
             let expr = arguments.pop().unwrap();
             let col = arguments.pop().unwrap();
@@ -1589,6 +1817,32 @@ fn evaluate_builtin_function<'a, T: FieldElement>(
     })
 }
 
+fn string_value<'a, 'b, T: FieldElement>(value: &'b Value<'a, T>) -> Result<&'b String, EvalError> {
+    match value {
+        Value::String(s) => Ok(s),
+        v => Err(EvalError::TypeError(format!(
+            "Expected string, but got {v}: {}",
+            v.type_formatted()
+        ))),
+    }
+}
+
+fn row_index<T: FieldElement>(value: &Value<'_, T>) -> Result<usize, EvalError> {
+    let row = value.try_to_integer()?;
+    usize::try_from(&row).map_err(|_| EvalError::OutOfBounds(format!("Invalid row index {row}")))
+}
+
+/// Converts an array value into a vector of field elements.
+fn field_element_array<T: FieldElement>(value: &Value<'_, T>) -> Result<Vec<T>, EvalError> {
+    match value {
+        Value::Array(items) => items.iter().map(|v| v.try_to_field_element()).collect(),
+        v => Err(EvalError::TypeError(format!(
+            "Expected array of field elements, but got {v}: {}",
+            v.type_formatted()
+        ))),
+    }
+}
+
 pub fn evaluate_binary_operation_field<'a, T: FieldElement>(
     left: T,
     op: BinaryOperator,
@@ -2,11 +2,11 @@ use core::panic;
 use powdr_ast::{
     analyzed::{Expression, PolynomialReference, Reference},
     parsed::{
-        self, asm::SymbolPath, types::Type, ArrayExpression, ArrayLiteral, BinaryOperation,
-        BlockExpression, IfExpression, LambdaExpression, LetStatementInsideBlock, MatchArm,
-        MatchExpression, NamedExpression, NamespacedPolynomialReference, Number, Pattern,
-        SelectedExpressions, StatementInsideBlock, StructExpression, SymbolCategory,
-        UnaryOperation,
+        self, asm::SymbolPath, types::Type, ArrayExpression, ArrayLiteral, AssignmentInsideBlock,
+        BinaryOperation, BinaryOperator, BlockExpression, ForLoop, IfExpression, IndexAccess,
+        LambdaExpression, LetStatementInsideBlock, MatchArm, MatchExpression, NamedExpression,
+        NamespacedPolynomialReference, Number, Pattern, SelectedExpressions, StatementInsideBlock,
+        StructExpression, SymbolCategory, UnaryOperation, WhileLoop,
     },
 };
 
@@ -27,6 +27,11 @@ pub struct ExpressionProcessor<'a, D: AnalysisDriver> {
     type_vars: &'a HashSet<&'a String>,
     local_variables: HashMap<String, u64>,
     local_variable_counter: u64,
+    /// The IDs of all local variables declared with `let mut`.
+    mutable_local_variables: HashSet<u64>,
+    /// Local variables with an ID below this value are captured by the
+    /// current lambda expression and thus cannot be assigned to.
+    lambda_boundary: u64,
 }
 
 impl<'a, D: AnalysisDriver> ExpressionProcessor<'a, D> {
@@ -36,6 +41,8 @@ impl<'a, D: AnalysisDriver> ExpressionProcessor<'a, D> {
             type_vars,
             local_variables: Default::default(),
             local_variable_counter: 0,
+            mutable_local_variables: Default::default(),
+            lambda_boundary: 0,
         }
     }
 
@@ -325,6 +332,8 @@ impl<'a, D: AnalysisDriver> ExpressionProcessor<'a, D> {
         }: LambdaExpression,
     ) -> LambdaExpression<Expression> {
         let previous_local_vars = self.save_local_variables();
+        let previous_lambda_boundary = self.lambda_boundary;
+        self.lambda_boundary = self.local_variable_counter;
 
         let params = params
             .into_iter()
@@ -338,6 +347,7 @@ impl<'a, D: AnalysisDriver> ExpressionProcessor<'a, D> {
         }
         let body = Box::new(self.process_expression(*body));
 
+        self.lambda_boundary = previous_lambda_boundary;
         self.reset_local_variables(previous_local_vars);
         LambdaExpression {
             kind,
@@ -357,23 +367,7 @@ impl<'a, D: AnalysisDriver> ExpressionProcessor<'a, D> {
 
         let processed_statements = statements
             .into_iter()
-            .map(|statement| match statement {
-                StatementInsideBlock::LetStatement(LetStatementInsideBlock { pattern, ty, value }) => {
-                    let value = value.map(|v| self.process_expression(v));
-                    let pattern = self.process_pattern(pattern);
-                    let ty = ty.map(|ty| self.process_number_type(ty));
-                    if value.is_none() && !matches!(pattern, Pattern::Variable(_, _)) {
-                        panic!("Let statement without value requires a single variable, but got {pattern}.");
-                    }
-                    if !pattern.is_irrefutable() {
-                        panic!("Let statement requires an irrefutable pattern, but {pattern} is refutable.");
-                    }
-                    StatementInsideBlock::LetStatement(LetStatementInsideBlock { pattern, ty, value })
-                }
-                StatementInsideBlock::Expression(expr) => {
-                    StatementInsideBlock::Expression(self.process_expression(expr))
-                }
-            })
+            .map(|statement| self.process_statement_inside_block(statement))
             .collect::<Vec<_>>();
 
         let processed_expr = expr.map(|expr| Box::new(self.process_expression(*expr)));
@@ -388,6 +382,126 @@ impl<'a, D: AnalysisDriver> ExpressionProcessor<'a, D> {
         )
     }
 
+    fn process_statement_inside_block(
+        &mut self,
+        statement: StatementInsideBlock,
+    ) -> StatementInsideBlock<Expression> {
+        match statement {
+            StatementInsideBlock::LetStatement(LetStatementInsideBlock {
+                pattern,
+                mutable,
+                ty,
+                value,
+            }) => {
+                let value = value.map(|v| self.process_expression(v));
+                let pattern = self.process_pattern(pattern);
+                let ty = ty.map(|ty| self.process_number_type(ty));
+                if value.is_none() && !matches!(pattern, Pattern::Variable(_, _)) {
+                    panic!("Let statement without value requires a single variable, but got {pattern}.");
+                }
+                if !pattern.is_irrefutable() {
+                    panic!("Let statement requires an irrefutable pattern, but {pattern} is refutable.");
+                }
+                if mutable {
+                    let Pattern::Variable(_, name) = &pattern else {
+                        panic!(
+                            "Mutable let statement requires a single variable, but got {pattern}."
+                        );
+                    };
+                    if value.is_none() {
+                        panic!("Mutable variable {name} requires an initial value.");
+                    }
+                    self.mutable_local_variables
+                        .insert(self.local_variables[name]);
+                }
+                StatementInsideBlock::LetStatement(LetStatementInsideBlock {
+                    pattern,
+                    mutable,
+                    ty,
+                    value,
+                })
+            }
+            StatementInsideBlock::Expression(parsed::Expression::BinaryOperation(
+                _,
+                BinaryOperation {
+                    left,
+                    op: BinaryOperator::Identity,
+                    right,
+                },
+            )) if self.is_assignment_target(&left) => {
+                // The parser cannot distinguish between identities and assignments.
+                let target = self.process_expression(*left);
+                let variable = match &target {
+                    Expression::IndexAccess(_, IndexAccess { array, .. }) => array.as_ref(),
+                    e => e,
+                };
+                let Expression::Reference(_, Reference::LocalVar(id, name)) = variable else {
+                    unreachable!()
+                };
+                if *id < self.lambda_boundary {
+                    panic!("Cannot assign to captured variable {name} inside a lambda expression.");
+                }
+                StatementInsideBlock::Assignment(AssignmentInsideBlock {
+                    target,
+                    value: self.process_expression(*right),
+                })
+            }
+            StatementInsideBlock::Expression(expr) => {
+                StatementInsideBlock::Expression(self.process_expression(expr))
+            }
+            StatementInsideBlock::Assignment(_) => {
+                unreachable!("Assignments are not created by the parser.")
+            }
+            StatementInsideBlock::While(WhileLoop { condition, body }) => {
+                StatementInsideBlock::While(WhileLoop {
+                    condition: Box::new(self.process_expression(*condition)),
+                    body: Box::new(self.process_expression(*body)),
+                })
+            }
+            StatementInsideBlock::For(ForLoop {
+                pattern,
+                iterable,
+                body,
+            }) => {
+                let iterable = Box::new(self.process_expression(*iterable));
+                let vars = self.save_local_variables();
+                let pattern = self.process_pattern(pattern);
+                if !pattern.is_irrefutable() {
+                    panic!("For loop requires an irrefutable pattern, but {pattern} is refutable.");
+                }
+                let body = Box::new(self.process_expression(*body));
+                self.reset_local_variables(vars);
+                StatementInsideBlock::For(ForLoop {
+                    pattern,
+                    iterable,
+                    body,
+                })
+            }
+        }
+    }
+
+    /// Returns true if the expression is a reference to a mutable local variable
+    /// or an element of an array stored in a mutable local variable.
+    fn is_assignment_target(&self, expr: &parsed::Expression) -> bool {
+        match expr {
+            parsed::Expression::IndexAccess(_, IndexAccess { array, .. }) => {
+                self.is_mutable_local_variable(array)
+            }
+            e => self.is_mutable_local_variable(e),
+        }
+    }
+
+    /// Returns true if the expression is a reference to a mutable local variable.
+    fn is_mutable_local_variable(&self, expr: &parsed::Expression) -> bool {
+        let parsed::Expression::Reference(_, reference) = expr else {
+            return false;
+        };
+        reference
+            .try_to_identifier()
+            .and_then(|name| self.local_variables.get(name))
+            .is_some_and(|id| self.mutable_local_variables.contains(id))
+    }
+
     pub fn process_namespaced_polynomial_reference(
         &mut self,
         reference: NamespacedPolynomialReference,
@@ -416,16 +530,19 @@ impl<'a, D: AnalysisDriver> ExpressionProcessor<'a, D> {
         LocalVariableState {
             local_variables: self.local_variables.clone(),
             local_variable_counter: self.local_variable_counter,
+            mutable_local_variables: self.mutable_local_variables.clone(),
         }
     }
 
     fn reset_local_variables(&mut self, state: LocalVariableState) {
         self.local_variables = state.local_variables;
         self.local_variable_counter = state.local_variable_counter;
+        self.mutable_local_variables = state.mutable_local_variables;
     }
 }
 
 struct LocalVariableState {
     pub local_variables: HashMap<String, u64>,
     pub local_variable_counter: u64,
+    pub mutable_local_variables: HashSet<u64>,
 }
@@ -9,7 +9,8 @@ use std::sync::Arc;
 use crate::structural_checks::check_structs_fields;
 use itertools::Itertools;
 use powdr_ast::parsed::asm::{
-    parse_absolute_path, AbsoluteSymbolPath, ModuleStatement, SymbolPath,
+    parse_absolute_path, AbsoluteSymbolPath, ModuleStatement, SymbolDefinition, SymbolPath,
+    Visibility,
 };
 use powdr_ast::parsed::types::Type;
 use powdr_ast::parsed::visitor::{AllChildren, Children};
@@ -184,16 +185,21 @@ impl PILAnalyzer {
             let missing_symbols = module
                 .statements
                 .into_iter()
-                .filter_map(|s| {
-                    match &s {
-                        ModuleStatement::SymbolDefinition(s) => {
-                            missing_symbols.contains(&s.name.as_str())
-                        }
-                        ModuleStatement::PilStatement(s) => s
-                            .symbol_definition_names()
-                            .any(|(name, _)| missing_symbols.contains(&name.as_str())),
+                .filter_map(|s| match &s {
+                    ModuleStatement::SymbolDefinition(d) => {
+                        missing_symbols.contains(&d.name.as_str()).then(|| {
+                            // Visibility modifiers are not allowed inside namespaces.
+                            let d = SymbolDefinition {
+                                visibility: Visibility::Private,
+                                ..d.clone()
+                            };
+                            vec![format!("{d}")]
+                        })
                     }
-                    .then_some(vec![format!("{s}")])
+                    ModuleStatement::PilStatement(_, s) => s
+                        .symbol_definition_names()
+                        .any(|(name, _)| missing_symbols.contains(&name.as_str()))
+                        .then(|| vec![format!("{s}")]),
                 })
                 .flatten()
                 .join("\n");
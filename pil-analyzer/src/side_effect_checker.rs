@@ -165,6 +165,11 @@ lazy_static! {
         ("std::prover::try_eval", FunctionKind::Query),
         ("std::prover::input_from_channel", FunctionKind::Query),
         ("std::prover::output_to_channel", FunctionKind::Query),
+        ("std::test::run_witgen", FunctionKind::Query),
+        ("std::test::witness_value", FunctionKind::Query),
+        ("std::test::public_value", FunctionKind::Query),
+        ("std::test::expect_witgen_failure", FunctionKind::Query),
+        ("std::test::expect_constraint_failure", FunctionKind::Query),
     ]
     .into_iter()
     .collect();
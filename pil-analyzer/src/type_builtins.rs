@@ -65,7 +65,15 @@ lazy_static! {
         ),
         ("std::prover::provide_value", ("", "expr, int, fe -> ()")),
         ("std::prover::input_from_channel", ("", "int, int -> fe")),
-        ("std::prover::output_to_channel", ("", "int, fe -> ()"))
+        ("std::prover::output_to_channel", ("", "int, fe -> ()")),
+        ("std::test::run_witgen", ("", "fe[] -> ()")),
+        ("std::test::witness_value", ("", "string, int -> fe")),
+        ("std::test::public_value", ("", "string -> fe")),
+        ("std::test::expect_witgen_failure", ("", "fe[] -> ()")),
+        (
+            "std::test::expect_constraint_failure",
+            ("", "(string, int, fe)[] -> ()")
+        )
     ]
     .into_iter()
     .map(|(name, (vars, ty))| { (name.to_string(), parse_type_scheme(vars, ty)) })
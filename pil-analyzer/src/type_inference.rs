@@ -7,9 +7,10 @@ use powdr_ast::{
         display::format_type_scheme_around_name,
         types::{ArrayType, FunctionType, TupleType, Type, TypeBounds, TypeScheme},
         visitor::ExpressionVisitable,
-        ArrayLiteral, BinaryOperation, BlockExpression, FunctionCall, FunctionKind, IndexAccess,
-        LambdaExpression, LetStatementInsideBlock, MatchArm, MatchExpression, Number, Pattern,
-        SourceReference, StatementInsideBlock, UnaryOperation,
+        ArrayLiteral, AssignmentInsideBlock, BinaryOperation, BlockExpression, ForLoop,
+        FunctionCall, FunctionKind, IndexAccess, LambdaExpression, LetStatementInsideBlock,
+        MatchArm, MatchExpression, Number, Pattern, SourceReference, StatementInsideBlock,
+        UnaryOperation, WhileLoop,
     },
 };
 use powdr_parser_util::{Error, SourceRef};
@@ -432,6 +433,7 @@ impl TypeChecker {
                         StatementInsideBlock::LetStatement(LetStatementInsideBlock {
                             ty,
                             pattern,
+                            mutable: _,
                             value: _,
                         }) => {
                             if !self.update_local_type(ty.as_mut().unwrap(), type_var_mapping) {
@@ -441,7 +443,10 @@ impl TypeChecker {
                                 )));
                             }
                         }
-                        StatementInsideBlock::Expression(_) => {}
+                        StatementInsideBlock::Expression(_)
+                        | StatementInsideBlock::Assignment(_)
+                        | StatementInsideBlock::While(_)
+                        | StatementInsideBlock::For(_) => {}
                     }
                 }
             }
@@ -695,6 +700,7 @@ impl TypeChecker {
                     match statement {
                         StatementInsideBlock::LetStatement(LetStatementInsideBlock {
                             pattern,
+                            mutable: _,
                             ty,
                             value,
                         }) => {
@@ -719,6 +725,37 @@ impl TypeChecker {
                         StatementInsideBlock::Expression(expr) => {
                             self.expect_type_with_flexibility(&self.statement_type(), expr)?;
                         }
+                        StatementInsideBlock::Assignment(AssignmentInsideBlock {
+                            target,
+                            value,
+                        }) => {
+                            let target_type = self.infer_type_of_expression(target)?;
+                            self.expect_type(&target_type, value)?;
+                        }
+                        StatementInsideBlock::While(WhileLoop { condition, body }) => {
+                            self.expect_type(&Type::Bool, condition)?;
+                            self.expect_type(&Type::empty_tuple(), body)?;
+                        }
+                        StatementInsideBlock::For(ForLoop {
+                            pattern,
+                            iterable,
+                            body,
+                        }) => {
+                            let item_type = self.unifier.new_type_var();
+                            self.expect_type(
+                                &Type::Array(ArrayType {
+                                    base: Box::new(item_type.clone()),
+                                    length: None,
+                                }),
+                                iterable,
+                            )?;
+                            let local_var_count = self.local_var_types.len();
+                            let result = self
+                                .expect_type_of_pattern(&item_type, pattern)
+                                .and_then(|_| self.expect_type(&Type::empty_tuple(), body));
+                            self.local_var_types.truncate(local_var_count);
+                            result?;
+                        }
                     }
                 }
                 let result = match expr {
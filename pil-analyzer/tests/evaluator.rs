@@ -647,3 +647,108 @@ fn test_trait_function_call_cross_impl() {
 
     assert_eq!(parse_and_evaluate_symbol(input, "F::r"), "6".to_string());
 }
+
+#[test]
+fn while_loop() {
+    let input = "
+        let f: int -> int = |n| {
+            let mut i: int = 0;
+            let mut sum: int = 0;
+            while i < n {
+                sum = sum + i;
+                i = i + 1;
+            }
+            sum
+        };
+        let x: int = f(10);
+        let y: int = f(0);
+    ";
+    assert_eq!(parse_and_evaluate_symbol(input, "x"), "45".to_string());
+    assert_eq!(parse_and_evaluate_symbol(input, "y"), "0".to_string());
+}
+
+#[test]
+fn for_loop() {
+    let input = "
+        let f: (int, int)[] -> int = |arr| {
+            let mut result: int = 1;
+            for (a, b) in arr {
+                let product = a * b;
+                result = result + product;
+            }
+            result
+        };
+        let x: int = f([(1, 2), (3, 4), (5, 6)]);
+        let y: int = f([]);
+    ";
+    assert_eq!(parse_and_evaluate_symbol(input, "x"), "45".to_string());
+    assert_eq!(parse_and_evaluate_symbol(input, "y"), "1".to_string());
+}
+
+#[test]
+fn nested_loops() {
+    let input = "
+        let x: int = {
+            let mut count: int = 0;
+            for i in [1, 2, 3] {
+                let mut j: int = 0;
+                while j < i {
+                    count = count + 1;
+                    j = j + 1;
+                }
+            }
+            let result = count * 10;
+            result
+        };
+    ";
+    assert_eq!(parse_and_evaluate_symbol(input, "x"), "60".to_string());
+}
+
+#[test]
+#[should_panic = "Cannot assign to captured variable x inside a lambda expression."]
+fn assign_to_captured_variable() {
+    let input = "
+        let f: int -> int = |n| {
+            let mut x: int = n;
+            let g = |_| { x = 2; };
+            g(1);
+            x
+        };
+    ";
+    analyze_string::<GoldilocksField>(input).unwrap();
+}
+
+#[test]
+fn array_element_assignment() {
+    let input = "
+        let f: int[] -> (int[], int[]) = |arr| {
+            let mut a = arr;
+            let b = a;
+            a[1] = 7;
+            let mut i = 0;
+            while i < 3 {
+                a[i] = a[i] * 2;
+                i = i + 1;
+            }
+            (a, b)
+        };
+        let x: (int[], int[]) = f([1, 2, 3]);
+    ";
+    assert_eq!(
+        parse_and_evaluate_symbol(input, "x"),
+        "([2, 14, 6], [1, 2, 3])".to_string()
+    );
+}
+
+#[test]
+#[should_panic = "Tried to assign element 3 of array of size 3"]
+fn array_element_assignment_out_of_bounds() {
+    let input = "
+        let x: int[] = {
+            let mut a = [1, 2, 3];
+            a[3] = 4;
+            a
+        };
+    ";
+    parse_and_evaluate_symbol(input, "x");
+}
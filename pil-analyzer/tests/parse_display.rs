@@ -570,6 +570,35 @@ fn let_inside_block_complex_type() {
     assert_eq!(formatted, expected);
 }
 
+#[test]
+fn mutable_variables_and_loops() {
+    let input = "
+    let f: int[] -> int = |arr| {
+        let mut s: int = 0;
+        for x in arr {
+            s = s + x;
+        }
+        while s > 100 {
+            s = s - 100;
+        }
+        s
+    };
+    ";
+    let formatted = analyze_string(input).to_string();
+    let expected = "    let f: int[] -> int = |arr| {
+        let mut s: int = 0_int;
+        for x in arr {
+            s = s + x;
+        }
+        while s > 100_int {
+            s = s - 100_int;
+        }
+        s
+    };
+";
+    assert_eq!(formatted, expected);
+}
+
 #[test]
 #[should_panic = "Variable already defined: t"]
 fn let_inside_block_redefine() {
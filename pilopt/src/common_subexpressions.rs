@@ -0,0 +1,168 @@
+//! Extraction of common sub-expressions into intermediate columns.
+//!
+//! Sub-expressions that occur several times in the polynomial identities are
+//! defined once as an intermediate column and referenced from there. This does
+//! not change the constraints, but backends and witness generation only need to
+//! evaluate the sub-expression once per row.
+
+use std::collections::BTreeMap;
+
+use powdr_ast::analyzed::{
+    AlgebraicExpression, AlgebraicReference, Analyzed, DegreeRange, Identity, PolyID,
+    PolynomialIdentity, PolynomialType, StatementIdentifier, Symbol, SymbolKind,
+};
+use powdr_ast::parsed::visitor::{AllChildren, ExpressionVisitable};
+use powdr_number::FieldElement;
+use powdr_parser_util::SourceRef;
+
+use crate::degree::{degree_with, intermediate_degrees, reference_degree};
+
+/// Extracts sub-expressions that occur at least twice in polynomial identities
+/// into new intermediate columns. Only sub-expressions of degree at most
+/// `max_degree` that reference at least two columns are extracted, larger ones first.
+pub(crate) fn extract_common_subexpressions<T: FieldElement>(
+    pil_file: &mut Analyzed<T>,
+    max_degree: usize,
+) {
+    let intermediate_degrees = intermediate_degrees(pil_file);
+    let is_candidate = |e: &AlgebraicExpression<T>| {
+        matches!(e, AlgebraicExpression::BinaryOperation(_))
+            && e.all_children()
+                .filter(|e| matches!(e, AlgebraicExpression::Reference(_)))
+                .count()
+                >= 2
+            && !e.all_children().any(|e| {
+                matches!(
+                    e,
+                    AlgebraicExpression::PublicReference(_) | AlgebraicExpression::Challenge(_)
+                )
+            })
+            && degree_with(e, &|r| reference_degree(r, &intermediate_degrees)) <= max_degree
+    };
+
+    let mut counts = BTreeMap::<_, usize>::new();
+    for expression in polynomial_identity_expressions(pil_file) {
+        for e in expression.all_children().filter(|e| is_candidate(e)) {
+            *counts.entry(e).or_default() += 1;
+        }
+    }
+    let mut candidates = counts
+        .into_iter()
+        .filter(|(_, count)| *count >= 2)
+        .map(|(e, _)| e.clone())
+        .collect::<Vec<_>>();
+    candidates.sort_by_key(|e| std::cmp::Reverse(e.all_children().count()));
+
+    let mut names = pil_file
+        .definitions
+        .keys()
+        .chain(pil_file.intermediate_columns.keys())
+        .cloned()
+        .collect::<Vec<_>>();
+    // The names of the intermediate columns created so far.
+    let mut extracted: Vec<String> = vec![];
+    for candidate in candidates {
+        // Occurrences inside larger extracted sub-expressions only remain
+        // once, in the definition of the intermediate column.
+        let occurrences = polynomial_identity_expressions(pil_file)
+            .chain(
+                extracted
+                    .iter()
+                    .map(|name| &pil_file.intermediate_columns[name].1[0]),
+            )
+            .map(|e| e.all_children().filter(|e| *e == &candidate).count())
+            .sum::<usize>();
+        if occurrences < 2 {
+            continue;
+        }
+
+        let namespace = candidate.all_children().find_map(|e| match e {
+            AlgebraicExpression::Reference(r) => r.name.rsplit_once("::").map(|(ns, _)| ns),
+            _ => None,
+        });
+        let prefix = namespace.map(|ns| format!("{ns}::")).unwrap_or_default();
+        let name = (0..)
+            .map(|i| format!("{prefix}_subexpression_{i}"))
+            .find(|name| !names.contains(name))
+            .unwrap();
+        let degree = namespace.and_then(|ns| namespace_degree(pil_file, ns));
+        names.push(name.clone());
+        let symbol = Symbol {
+            id: pil_file.intermediate_count() as u64,
+            source: SourceRef::unknown(),
+            absolute_name: name.clone(),
+            stage: None,
+            kind: SymbolKind::Poly(PolynomialType::Intermediate),
+            length: None,
+            degree,
+        };
+        let reference = AlgebraicExpression::Reference(AlgebraicReference {
+            name: name.clone(),
+            poly_id: PolyID::from(&symbol),
+            next: false,
+        });
+        log::trace!("Extracting {name} = {candidate}.");
+
+        let mut replace = |e: &mut AlgebraicExpression<T>| {
+            if *e == candidate {
+                *e = reference.clone();
+            }
+        };
+        // Define the column right before the first identity using it.
+        let mut first_use = None;
+        for (index, identity) in pil_file.identities.iter_mut().enumerate() {
+            if let Identity::Polynomial(PolynomialIdentity { expression, .. }) = identity {
+                if expression.all_children().any(|e| *e == candidate) {
+                    first_use.get_or_insert(StatementIdentifier::ProofItem(index));
+                    expression.pre_visit_expressions_mut(&mut replace);
+                }
+            }
+        }
+        for other in &extracted {
+            let definition = &mut pil_file.intermediate_columns.get_mut(other).unwrap().1[0];
+            if definition.all_children().any(|e| *e == candidate) {
+                first_use =
+                    first_use.or_else(|| Some(StatementIdentifier::Definition(other.clone())));
+                definition.pre_visit_expressions_mut(&mut replace);
+            }
+        }
+        let position = first_use
+            .and_then(|first_use| pil_file.source_order.iter().position(|s| *s == first_use))
+            .unwrap_or(pil_file.source_order.len());
+        pil_file
+            .source_order
+            .insert(position, StatementIdentifier::Definition(name.clone()));
+        pil_file
+            .intermediate_columns
+            .insert(name.clone(), (symbol, vec![candidate]));
+        extracted.push(name);
+    }
+}
+
+fn polynomial_identity_expressions<T>(
+    pil_file: &Analyzed<T>,
+) -> impl Iterator<Item = &AlgebraicExpression<T>> {
+    pil_file
+        .identities
+        .iter()
+        .filter_map(|identity| match identity {
+            Identity::Polynomial(PolynomialIdentity { expression, .. }) => Some(expression),
+            _ => None,
+        })
+}
+
+/// The degree of the columns in the given namespace.
+fn namespace_degree<T>(pil_file: &Analyzed<T>, namespace: &str) -> Option<DegreeRange> {
+    pil_file
+        .definitions
+        .values()
+        .map(|(symbol, _)| symbol)
+        .filter(|symbol| matches!(symbol.kind, SymbolKind::Poly(_)))
+        .find(|symbol| {
+            symbol
+                .absolute_name
+                .rsplit_once("::")
+                .is_some_and(|(ns, _)| ns == namespace)
+        })
+        .and_then(|symbol| symbol.degree)
+}
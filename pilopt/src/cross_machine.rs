@@ -0,0 +1,541 @@
+//! Optimizations across machine boundaries.
+//!
+//! After linking, every instance of a small table machine like
+//! `std::machines::range::Byte` lives in its own namespace with its own fixed
+//! columns, and several callers may look up the same values in them. Identical
+//! fixed columns are merged into one, which turns lookups into different
+//! instances into identical lookups. Those, and lookups that are implied by
+//! other lookups, are removed, and lookups that share a key column are combined
+//! into a single multi-column lookup. Submachines that only consist of
+//! polynomial identities are inlined into their callers. Machines that end up
+//! unreferenced are removed by `remove_unreferenced_definitions`.
+
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use powdr_ast::analyzed::{
+    AlgebraicExpression, AlgebraicReference, Analyzed, Expression, FunctionValueDefinition,
+    Identity, LookupIdentity, PolyID, PolynomialIdentity, PolynomialReference, PolynomialType,
+    Reference, SelectedExpressions,
+};
+use powdr_ast::parsed::visitor::{AllChildren, ExpressionVisitable};
+use powdr_ast::parsed::{
+    BinaryOperation, BinaryOperator, LambdaExpression, Number, Pattern, TypedExpression,
+};
+use powdr_number::FieldElement;
+
+use crate::degree::{degree_with, intermediate_degrees, reference_degree};
+
+/// Replaces references to fixed columns by references to an identical fixed
+/// column in another namespace, if they are only used as lookup tables.
+///
+/// Two fixed columns are identical if they have the same size range and the same
+/// definition. A lookup is only changed if its right side still references a
+/// single namespace afterwards.
+pub(crate) fn merge_identical_fixed_columns<T: FieldElement>(pil_file: &mut Analyzed<T>) {
+    let candidates = table_columns(pil_file);
+    let mut representatives = HashMap::new();
+    let mut replacements: HashMap<PolyID, AlgebraicReference> = HashMap::new();
+    for (symbol, definition) in pil_file.constant_polys_in_source_order() {
+        let id = PolyID::from(symbol);
+        let Some(definition) = definition.as_ref().filter(|_| candidates.contains(&id)) else {
+            continue;
+        };
+        let reference = AlgebraicReference {
+            name: symbol.absolute_name.clone(),
+            poly_id: id,
+            next: false,
+        };
+        match representatives.entry((symbol.degree, definition.to_string())) {
+            Entry::Vacant(entry) => {
+                entry.insert(reference);
+            }
+            Entry::Occupied(entry) => {
+                replacements.insert(id, entry.get().clone());
+            }
+        }
+    }
+
+    for identity in &mut pil_file.identities {
+        let Identity::Lookup(LookupIdentity { right, .. }) = identity else {
+            continue;
+        };
+        let namespaces = right
+            .all_children()
+            .filter_map(|e| match e {
+                AlgebraicExpression::Reference(r) => {
+                    Some(namespace(&replacements.get(&r.poly_id).unwrap_or(r).name))
+                }
+                _ => None,
+            })
+            .collect::<BTreeSet<_>>();
+        if namespaces.len() > 1 {
+            continue;
+        }
+        right.post_visit_expressions_mut(&mut |e| {
+            if let AlgebraicExpression::Reference(r) = e {
+                if let Some(replacement) = replacements.get(&r.poly_id) {
+                    log::trace!("Replacing {} by identical {}.", r.name, replacement.name);
+                    r.name = replacement.name.clone();
+                    r.poly_id = replacement.poly_id;
+                }
+            }
+        });
+    }
+}
+
+/// Removes lookups that are implied by another lookup with the same selectors and
+/// combines lookups with the same selectors that look up the same value in a key
+/// column of the same table.
+///
+/// A key column is a fixed column like `|i| i` or `|i| i & 0xff` of size 256. Since it has a different
+/// value in every row, `[k, a] in [KEY, A]` and `[k, b] in [KEY, B]` both hold if
+/// and only if `[k, a, b] in [KEY, A, B]` holds. Lookups without a common key
+/// column are not combined, since that would require their values to occur in
+/// the same row of the table.
+pub(crate) fn merge_lookups<T: FieldElement>(pil_file: &mut Analyzed<T>) {
+    let key_columns = key_columns(pil_file);
+    let is_key = |(_, r): &(AlgebraicExpression<T>, AlgebraicExpression<T>)| match r {
+        AlgebraicExpression::Reference(r) => !r.next && key_columns.contains(&r.poly_id),
+        _ => false,
+    };
+
+    let mut to_remove = BTreeSet::new();
+    for i in 0..pil_file.identities.len() {
+        if to_remove.contains(&i) {
+            continue;
+        }
+        for j in (0..pil_file.identities.len()).filter(|j| *j != i) {
+            if to_remove.contains(&j) {
+                continue;
+            }
+            let (Some((left_i, right_i)), Some((left_j, right_j))) = (
+                lookup_sides(&pil_file.identities[i]),
+                lookup_sides(&pil_file.identities[j]),
+            ) else {
+                continue;
+            };
+            if left_i.selector != left_j.selector || right_i.selector != right_j.selector {
+                continue;
+            }
+            let pairs_i = pairs(left_i, right_i);
+            let pairs_j = pairs(left_j, right_j);
+            // Keep the first of two identical lookups.
+            if pairs_j.is_subset(&pairs_i) && (pairs_j != pairs_i || i < j) {
+                log::trace!(
+                    "Removing lookup implied by another lookup: {}",
+                    pil_file.identities[j]
+                );
+                to_remove.insert(j);
+                continue;
+            }
+            if !pairs_i.intersection(&pairs_j).any(is_key)
+                || namespaces(left_i).union(&namespaces(left_j)).count() > 1
+                || namespaces(right_i).union(&namespaces(right_j)).count() > 1
+            {
+                continue;
+            }
+            let additional_pairs = left_j
+                .expressions
+                .iter()
+                .cloned()
+                .zip(right_j.expressions.iter().cloned())
+                .filter(|pair| !pairs_i.contains(pair))
+                .collect::<Vec<_>>();
+            let Identity::Lookup(LookupIdentity { left, right, .. }) = &mut pil_file.identities[i]
+            else {
+                unreachable!()
+            };
+            for (l, r) in additional_pairs {
+                left.expressions.push(l);
+                right.expressions.push(r);
+            }
+            log::trace!("Combined lookups into {}", pil_file.identities[i]);
+            to_remove.insert(j);
+        }
+    }
+    pil_file.remove_identities(&to_remove);
+}
+
+/// Inlines submachines that only consist of witness columns constrained by
+/// polynomial identities and are only connected to through lookups.
+///
+/// A lookup `s $ [a, b, c] in [x, y, z]` into such a machine with identities
+/// `P(x, y, z) = 0` is replaced by `s * P(a, b, c) = 0` in the calling machine.
+/// This is only done if every column of the submachine occurs on the right side
+/// of every lookup into it, none of them is referenced with a next reference,
+/// and the degree of the inlined identities does not exceed `max_degree`.
+pub(crate) fn inline_trivial_submachines<T: FieldElement>(
+    pil_file: &mut Analyzed<T>,
+    max_degree: usize,
+) {
+    // Inlining a submachine can make its caller trivial, so we inline one at a time.
+    while let Some(namespace) =
+        trivial_submachines(pil_file)
+            .into_iter()
+            .find_map(|(namespace, submachine)| {
+                inline_submachine(pil_file, &namespace, submachine, max_degree).then_some(namespace)
+            })
+    {
+        log::trace!("Inlined trivial submachine {namespace}.");
+    }
+}
+
+/// The identities of a trivial submachine, by index.
+#[derive(Default)]
+struct TrivialSubmachine {
+    polynomial_identities: Vec<usize>,
+    lookups: Vec<usize>,
+}
+
+/// Returns the namespaces that only have witness columns without hints, whose
+/// columns are only referenced by polynomial identities within the namespace and on
+/// the right side of lookups with a constant selector, without next references.
+fn trivial_submachines<T: FieldElement>(
+    pil_file: &Analyzed<T>,
+) -> BTreeMap<String, TrivialSubmachine> {
+    let mut excluded = pil_file
+        .public_declarations
+        .values()
+        .map(|p| namespace(&p.polynomial.name).to_string())
+        .collect::<HashSet<_>>();
+    let definitions = pil_file
+        .definitions
+        .values()
+        .filter_map(|(_, definition)| definition.as_ref());
+    for e in definitions.flat_map(|d| d.all_children()).chain(
+        pil_file
+            .prover_functions
+            .iter()
+            .flat_map(|f| f.all_children()),
+    ) {
+        if let Expression::Reference(_, Reference::Poly(PolynomialReference { name, .. })) = e {
+            excluded.insert(namespace(name).to_string());
+        }
+    }
+    for (symbol, definitions) in pil_file.intermediate_columns.values() {
+        excluded.insert(namespace(&symbol.absolute_name).to_string());
+        for r in definitions.iter().flat_map(|d| d.all_children()) {
+            if let AlgebraicExpression::Reference(r) = r {
+                excluded.insert(namespace(&r.name).to_string());
+            }
+        }
+    }
+    for (symbol, definition) in pil_file.committed_polys_in_source_order() {
+        if symbol.is_array() || definition.is_some() {
+            excluded.insert(namespace(&symbol.absolute_name).to_string());
+        }
+    }
+
+    let mut submachines = BTreeMap::<String, TrivialSubmachine>::new();
+    for (index, identity) in pil_file.identities.iter().enumerate() {
+        match identity {
+            Identity::Polynomial(PolynomialIdentity { expression, .. }) => {
+                let namespaces = expression
+                    .all_children()
+                    .filter_map(|e| match e {
+                        AlgebraicExpression::Reference(r) => Some(r),
+                        _ => None,
+                    })
+                    .map(|r| {
+                        let is_local = !r.next && r.poly_id.ptype == PolynomialType::Committed;
+                        (namespace(&r.name), is_local)
+                    })
+                    .collect::<BTreeSet<_>>();
+                match namespaces.iter().collect::<Vec<_>>().as_slice() {
+                    [] => {}
+                    [(namespace, true)] => submachines
+                        .entry(namespace.to_string())
+                        .or_default()
+                        .polynomial_identities
+                        .push(index),
+                    namespaces => {
+                        excluded.extend(namespaces.iter().map(|(n, _)| n.to_string()));
+                    }
+                }
+            }
+            Identity::Lookup(LookupIdentity { left, right, .. }) => {
+                let columns = right
+                    .expressions
+                    .iter()
+                    .map(|e| match e {
+                        AlgebraicExpression::Reference(r)
+                            if !r.next && r.poly_id.ptype == PolynomialType::Committed =>
+                        {
+                            Some(r.poly_id)
+                        }
+                        _ => None,
+                    })
+                    .collect::<Option<BTreeSet<_>>>();
+                let left_namespaces = namespaces(left);
+                let right_namespaces = namespaces(right);
+                // The columns of the caller cannot be removed while it is referenced here.
+                excluded.extend(left_namespaces.iter().map(|n| n.to_string()));
+                match (
+                    columns,
+                    right_namespaces.iter().collect::<Vec<_>>().as_slice(),
+                ) {
+                    (Some(columns), [namespace])
+                        if columns.len() == right.expressions.len()
+                            && right.selector == AlgebraicExpression::Number(T::one())
+                            && !left_namespaces.contains(*namespace) =>
+                    {
+                        submachines
+                            .entry(namespace.to_string())
+                            .or_default()
+                            .lookups
+                            .push(index)
+                    }
+                    _ => excluded.extend(right_namespaces.iter().map(|n| n.to_string())),
+                }
+            }
+            _ => {
+                excluded.extend(identity.all_children().filter_map(|e| match e {
+                    AlgebraicExpression::Reference(r) => Some(namespace(&r.name).to_string()),
+                    _ => None,
+                }));
+            }
+        }
+    }
+    submachines.retain(|namespace, submachine| {
+        !excluded.contains(namespace) && !submachine.lookups.is_empty()
+    });
+    submachines
+}
+
+/// Inlines the submachine in `namespace_name` into its callers, if every column
+/// of it is on the right side of every lookup into it and the degree bound is not
+/// exceeded. Returns true on success, and leaves the PIL file unchanged otherwise.
+fn inline_submachine<T: FieldElement>(
+    pil_file: &mut Analyzed<T>,
+    namespace_name: &str,
+    submachine: TrivialSubmachine,
+    max_degree: usize,
+) -> bool {
+    let polynomial_identities = submachine
+        .polynomial_identities
+        .iter()
+        .map(|index| match &pil_file.identities[*index] {
+            Identity::Polynomial(PolynomialIdentity { expression, .. }) => expression,
+            _ => unreachable!(),
+        })
+        .collect::<Vec<_>>();
+    let intermediate_degrees = intermediate_degrees(pil_file);
+
+    let mut new_identities = vec![];
+    for index in &submachine.lookups {
+        let Identity::Lookup(LookupIdentity {
+            source,
+            left,
+            right,
+            ..
+        }) = &pil_file.identities[*index]
+        else {
+            unreachable!()
+        };
+        let substitutions = right
+            .expressions
+            .iter()
+            .zip(&left.expressions)
+            .map(|(r, l)| match r {
+                AlgebraicExpression::Reference(r) => (r.poly_id, l),
+                _ => unreachable!(),
+            })
+            .collect::<HashMap<_, _>>();
+        for expression in &polynomial_identities {
+            let mut inlined = (*expression).clone();
+            let mut complete = true;
+            inlined.post_visit_expressions_mut(&mut |e| {
+                if let AlgebraicExpression::Reference(r) = e {
+                    match substitutions.get(&r.poly_id) {
+                        Some(value) => *e = (*value).clone(),
+                        None => complete = false,
+                    }
+                }
+            });
+            if !complete {
+                return false;
+            }
+            let inlined = crate::simplify_expression(left.selector.clone() * inlined);
+            if degree_with(&inlined, &|r| reference_degree(r, &intermediate_degrees)) > max_degree {
+                return false;
+            }
+            new_identities.push((inlined, source.clone()));
+        }
+    }
+
+    let columns = pil_file
+        .committed_polys_in_source_order()
+        .map(|(symbol, _)| &symbol.absolute_name)
+        .filter(|name| namespace(name) == namespace_name)
+        .cloned()
+        .collect();
+    pil_file.remove_identities(
+        &submachine
+            .polynomial_identities
+            .into_iter()
+            .chain(submachine.lookups)
+            .collect(),
+    );
+    pil_file.remove_definitions(&columns);
+    for (expression, source) in new_identities {
+        pil_file.append_polynomial_identity(expression, source);
+    }
+    true
+}
+
+/// Returns the non-array fixed columns that are only referenced on the right side
+/// of lookups.
+fn table_columns<T: FieldElement>(pil_file: &Analyzed<T>) -> HashSet<PolyID> {
+    let mut referenced_names = pil_file
+        .public_declarations
+        .values()
+        .map(|p| p.polynomial.name.clone())
+        .collect::<HashSet<_>>();
+    let definitions = pil_file
+        .definitions
+        .values()
+        .filter_map(|(_, definition)| definition.as_ref());
+    for e in definitions.flat_map(|d| d.all_children()).chain(
+        pil_file
+            .prover_functions
+            .iter()
+            .flat_map(|f| f.all_children()),
+    ) {
+        if let Expression::Reference(_, Reference::Poly(PolynomialReference { name, .. })) = e {
+            referenced_names.insert(name.clone());
+        }
+    }
+
+    let mut referenced_ids = HashSet::new();
+    let mut add_reference = |e: &AlgebraicExpression<T>| {
+        if let AlgebraicExpression::Reference(r) = e {
+            referenced_ids.insert(r.poly_id);
+        }
+    };
+    for identity in &pil_file.identities {
+        match identity {
+            Identity::Lookup(LookupIdentity { left, .. }) => {
+                left.pre_visit_expressions(&mut add_reference)
+            }
+            _ => identity.pre_visit_expressions(&mut add_reference),
+        }
+    }
+    for (_, definitions) in pil_file.intermediate_columns.values() {
+        for definition in definitions {
+            definition.pre_visit_expressions(&mut add_reference);
+        }
+    }
+
+    pil_file
+        .constant_polys_in_source_order()
+        .filter(|(symbol, _)| {
+            !symbol.is_array() && !referenced_names.contains(&symbol.absolute_name)
+        })
+        .map(|(symbol, _)| symbol.into())
+        .filter(|id| !referenced_ids.contains(id))
+        .collect()
+}
+
+/// Returns the non-array fixed columns of a fixed size that have a different
+/// value in every row. Those are the columns defined as `|i| i`, `|i| i % m` or
+/// `|i| i & m` that do not wrap around within their size, like the tables of
+/// `std::machines::range::{Byte, Bit2, ...}`.
+fn key_columns<T: FieldElement>(pil_file: &Analyzed<T>) -> HashSet<PolyID> {
+    pil_file
+        .constant_polys_in_source_order()
+        .filter(|(symbol, definition)| {
+            let Some(size) = symbol
+                .degree
+                .filter(|degree| degree.min == degree.max)
+                .map(|degree| degree.max)
+            else {
+                return false;
+            };
+            let Some(FunctionValueDefinition::Expression(TypedExpression {
+                e: Expression::LambdaExpression(_, LambdaExpression { params, body, .. }),
+                ..
+            })) = definition
+            else {
+                return false;
+            };
+            let [Pattern::Variable(_, param)] = params.as_slice() else {
+                return false;
+            };
+            let is_param =
+                |e: &Expression| matches!(e, Expression::Reference(_, Reference::LocalVar(_, name)) if name == param);
+            let is_key = match body.as_ref() {
+                e if is_param(e) => true,
+                Expression::BinaryOperation(_, BinaryOperation { left, op, right })
+                    if is_param(left) =>
+                {
+                    match (op, integer_value(right)) {
+                        (BinaryOperator::Mod, Some(modulus)) => size <= modulus,
+                        (BinaryOperator::BinaryAnd, Some(mask)) => {
+                            let bits = size.next_power_of_two() - 1;
+                            mask & bits == bits
+                        }
+                        _ => false,
+                    }
+                }
+                _ => false,
+            };
+            !symbol.is_array() && is_key
+        })
+        .map(|(symbol, _)| symbol.into())
+        .collect()
+}
+
+/// Evaluates an expression built from integer literals, `+`, `-`, `*` and `**`.
+fn integer_value(e: &Expression) -> Option<u64> {
+    match e {
+        Expression::Number(_, Number { value, .. }) => u64::try_from(value).ok(),
+        Expression::BinaryOperation(_, BinaryOperation { left, op, right }) => {
+            let (left, right) = (integer_value(left)?, integer_value(right)?);
+            match op {
+                BinaryOperator::Add => left.checked_add(right),
+                BinaryOperator::Sub => left.checked_sub(right),
+                BinaryOperator::Mul => left.checked_mul(right),
+                BinaryOperator::Pow => left.checked_pow(right.try_into().ok()?),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn lookup_sides<T>(
+    identity: &Identity<T>,
+) -> Option<(&SelectedExpressions<T>, &SelectedExpressions<T>)> {
+    match identity {
+        Identity::Lookup(LookupIdentity { left, right, .. }) => Some((left, right)),
+        _ => None,
+    }
+}
+
+/// Returns the pairs of looked up expressions and table columns of a lookup.
+fn pairs<T: FieldElement>(
+    left: &SelectedExpressions<T>,
+    right: &SelectedExpressions<T>,
+) -> BTreeSet<(AlgebraicExpression<T>, AlgebraicExpression<T>)> {
+    left.expressions
+        .iter()
+        .cloned()
+        .zip(right.expressions.iter().cloned())
+        .collect()
+}
+
+fn namespaces<T>(side: &SelectedExpressions<T>) -> BTreeSet<&str> {
+    side.all_children()
+        .filter_map(|e| match e {
+            AlgebraicExpression::Reference(r) => Some(namespace(&r.name)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the namespace of an absolute column name.
+fn namespace(name: &str) -> &str {
+    name.rsplit_once("::")
+        .map_or("", |(namespace, _)| namespace)
+}
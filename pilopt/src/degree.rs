@@ -0,0 +1,101 @@
+//! Degrees of algebraic expressions.
+
+use std::collections::HashMap;
+
+use powdr_ast::analyzed::{
+    AlgebraicBinaryOperation, AlgebraicBinaryOperator, AlgebraicExpression, AlgebraicReference,
+    AlgebraicUnaryOperation, Analyzed, PolyID, PolynomialType,
+};
+use powdr_ast::parsed::visitor::AllChildren;
+use powdr_number::FieldElement;
+
+/// The degree of an expression, counting exponentiation by constants.
+/// All references have degree one.
+pub(crate) fn degree<T: FieldElement>(e: &AlgebraicExpression<T>) -> usize {
+    degree_with(e, &|_| 1)
+}
+
+/// The degree of an expression, counting exponentiation by constants.
+/// The degree of references is given by `reference_degree`.
+pub(crate) fn degree_with<T: FieldElement>(
+    e: &AlgebraicExpression<T>,
+    reference_degree: &impl Fn(&AlgebraicReference) -> usize,
+) -> usize {
+    match e {
+        AlgebraicExpression::Reference(r) => reference_degree(r),
+        AlgebraicExpression::BinaryOperation(AlgebraicBinaryOperation {
+            left,
+            op: AlgebraicBinaryOperator::Mul,
+            right,
+        }) => degree_with(left, reference_degree) + degree_with(right, reference_degree),
+        AlgebraicExpression::BinaryOperation(AlgebraicBinaryOperation {
+            left,
+            op: AlgebraicBinaryOperator::Pow,
+            right,
+        }) => match right.as_ref() {
+            AlgebraicExpression::Number(n) => {
+                degree_with(left, reference_degree).saturating_mul(n.to_degree() as usize)
+            }
+            _ => unreachable!("Exponents must be constants."),
+        },
+        AlgebraicExpression::BinaryOperation(AlgebraicBinaryOperation { left, right, .. }) => {
+            degree_with(left, reference_degree).max(degree_with(right, reference_degree))
+        }
+        AlgebraicExpression::UnaryOperation(AlgebraicUnaryOperation { expr, .. }) => {
+            degree_with(expr, reference_degree)
+        }
+        AlgebraicExpression::PublicReference(_)
+        | AlgebraicExpression::Challenge(_)
+        | AlgebraicExpression::Number(_) => 0,
+    }
+}
+
+/// The degrees of all intermediate columns, with other intermediate columns inlined.
+pub(crate) fn intermediate_degrees<T: FieldElement>(
+    pil_file: &Analyzed<T>,
+) -> HashMap<PolyID, usize> {
+    let definitions = pil_file
+        .intermediate_polys_in_source_order()
+        .flat_map(|(symbol, definitions)| {
+            symbol.array_elements().map(|(_, id)| id).zip(definitions)
+        })
+        .collect::<HashMap<_, _>>();
+    let mut degrees = HashMap::new();
+    for id in definitions.keys() {
+        intermediate_degree(*id, &definitions, &mut degrees);
+    }
+    degrees
+}
+
+fn intermediate_degree<T: FieldElement>(
+    id: PolyID,
+    definitions: &HashMap<PolyID, &AlgebraicExpression<T>>,
+    degrees: &mut HashMap<PolyID, usize>,
+) -> usize {
+    if let Some(degree) = degrees.get(&id) {
+        return *degree;
+    }
+    let definition = definitions[&id];
+    // Compute the degrees of the referenced intermediate columns first.
+    for e in definition.all_children() {
+        if let AlgebraicExpression::Reference(r) = e {
+            if r.poly_id.ptype == PolynomialType::Intermediate {
+                intermediate_degree(r.poly_id, definitions, degrees);
+            }
+        }
+    }
+    let degree = degree_with(definition, &|r| reference_degree(r, degrees));
+    degrees.insert(id, degree);
+    degree
+}
+
+/// The degree of a reference, given the degrees of the intermediate columns.
+pub(crate) fn reference_degree(
+    r: &AlgebraicReference,
+    intermediate_degrees: &HashMap<PolyID, usize>,
+) -> usize {
+    match r.poly_id.ptype {
+        PolynomialType::Intermediate => intermediate_degrees[&r.poly_id],
+        PolynomialType::Committed | PolynomialType::Constant => 1,
+    }
+}
@@ -0,0 +1,374 @@
+//! Reduction of the degree of polynomial identities.
+//!
+//! Backends bound the degree of the constraints they can prove efficiently.
+//! This pass rewrites every polynomial identity whose degree exceeds a given
+//! maximum by replacing sub-expressions with new witness columns `c`, each
+//! constrained by `c = e` for the sub-expression `e` it replaces. Witness
+//! generation solves these constraints like any other, since they are affine
+//! in the new column.
+//!
+//! For each identity, the sub-expressions are chosen such that the number of
+//! new columns is minimal, not taking into account that identical
+//! sub-expressions share a column.
+//!
+//! The expressions and selectors of lookups and permutations are not reduced.
+//! Backends prove them with separate arguments whose degree grows with the
+//! degree of these expressions, so a lookup of high degree can still exceed
+//! the limit of a backend.
+
+use std::collections::{BTreeMap, HashMap};
+
+use powdr_ast::analyzed::{
+    AlgebraicBinaryOperation, AlgebraicBinaryOperator, AlgebraicExpression, AlgebraicReference,
+    AlgebraicUnaryOperation, Analyzed, DegreeRange, Identity, PolyID, PolynomialIdentity,
+    PolynomialType, StatementIdentifier, Symbol, SymbolKind,
+};
+use powdr_ast::parsed::visitor::AllChildren;
+use powdr_number::FieldElement;
+use powdr_parser_util::SourceRef;
+
+use crate::degree::degree;
+
+/// Cost of an impossible reduction, e.g. bringing a column to degree zero.
+const IMPOSSIBLE: usize = usize::MAX / 2;
+
+/// Introduces new witness columns such that no polynomial identity has a
+/// degree larger than `max_degree`. Lookups and permutations are left unchanged.
+///
+/// # Panics
+///
+/// Panics if `max_degree` is less than 2, since the constraint of a new column
+/// on a product already has degree 2.
+pub fn reduce_degree<T: FieldElement>(pil_file: &mut Analyzed<T>, max_degree: usize) {
+    assert!(
+        max_degree >= 2,
+        "Cannot reduce the constraint degree below 2, requested {max_degree}."
+    );
+
+    let inlined = pil_file.identities_with_inlined_intermediate_polynomials();
+    let mut reducer = DegreeReducer::new(pil_file, max_degree);
+    for (identity, inlined) in pil_file.identities.iter_mut().zip(inlined) {
+        let (
+            Identity::Polynomial(PolynomialIdentity { expression, .. }),
+            Identity::Polynomial(PolynomialIdentity {
+                expression: inlined,
+                ..
+            }),
+        ) = (identity, inlined)
+        else {
+            continue;
+        };
+        if degree(&inlined) > max_degree {
+            *expression = reducer.reduce(expand_powers(inlined, max_degree));
+        }
+    }
+
+    let added = reducer.extracted.len();
+    for (expression, (name, symbol)) in reducer.into_columns() {
+        let reference = AlgebraicReference {
+            name: name.clone(),
+            poly_id: PolyID::from(&symbol),
+            next: false,
+        };
+        pil_file.definitions.insert(name.clone(), (symbol, None));
+        pil_file
+            .source_order
+            .push(StatementIdentifier::Definition(name));
+        pil_file.append_polynomial_identity(
+            AlgebraicExpression::Reference(reference) - expression,
+            SourceRef::unknown(),
+        );
+    }
+    if added > 0 {
+        log::info!(
+            "Added {added} witness columns to reduce the constraint degree to {max_degree}."
+        );
+    }
+}
+
+/// Replaces `e**n` by a product of powers of `e` wherever the power exceeds
+/// `max_degree`, so that partial products can be extracted. The largest power
+/// of `e` that fits into `max_degree` is repeated, so that it only needs one column.
+fn expand_powers<T: FieldElement>(
+    e: AlgebraicExpression<T>,
+    max_degree: usize,
+) -> AlgebraicExpression<T> {
+    match e {
+        AlgebraicExpression::BinaryOperation(AlgebraicBinaryOperation { left, op, right }) => {
+            let left = expand_powers(*left, max_degree);
+            let right = expand_powers(*right, max_degree);
+            match (op, &right) {
+                (AlgebraicBinaryOperator::Pow, AlgebraicExpression::Number(n))
+                    if degree(&left) > 0
+                        && degree(&left).saturating_mul(n.to_degree() as usize) > max_degree =>
+                {
+                    let exponent = n.to_degree();
+                    let chunk = (max_degree / degree(&left)) as u64;
+                    if chunk < 2 {
+                        balanced_product(&left, exponent)
+                    } else {
+                        let repeated = balanced_product(&power(&left, chunk), exponent / chunk);
+                        match exponent % chunk {
+                            0 => repeated,
+                            remainder => repeated * power(&left, remainder),
+                        }
+                    }
+                }
+                _ => AlgebraicExpression::new_binary(left, op, right),
+            }
+        }
+        AlgebraicExpression::UnaryOperation(AlgebraicUnaryOperation { op, expr }) => {
+            AlgebraicExpression::new_unary(op, expand_powers(*expr, max_degree))
+        }
+        e => e,
+    }
+}
+
+fn power<T: FieldElement>(base: &AlgebraicExpression<T>, exponent: u64) -> AlgebraicExpression<T> {
+    match exponent {
+        1 => base.clone(),
+        _ => AlgebraicExpression::new_binary(
+            base.clone(),
+            AlgebraicBinaryOperator::Pow,
+            AlgebraicExpression::Number(exponent.into()),
+        ),
+    }
+}
+
+/// The product of `count` factors `factor`, as a balanced tree.
+fn balanced_product<T: FieldElement>(
+    factor: &AlgebraicExpression<T>,
+    count: u64,
+) -> AlgebraicExpression<T> {
+    match count {
+        0 => AlgebraicExpression::Number(T::one()),
+        1 => factor.clone(),
+        _ => balanced_product(factor, count / 2) * balanced_product(factor, count - count / 2),
+    }
+}
+
+/// A new witness column: its name and symbol.
+type Column = (String, Symbol);
+
+struct DegreeReducer<T> {
+    max_degree: usize,
+    /// The sub-expressions that have been replaced, with the columns replacing them.
+    extracted: BTreeMap<AlgebraicExpression<T>, (AlgebraicReference, Column)>,
+    /// The stage of each witness column.
+    stages: HashMap<PolyID, u32>,
+    /// The degree of each namespace.
+    degrees: HashMap<String, Option<DegreeRange>>,
+    /// The names of all existing symbols.
+    names: Vec<String>,
+    next_id: u64,
+}
+
+impl<T: FieldElement> DegreeReducer<T> {
+    fn new(pil_file: &Analyzed<T>, max_degree: usize) -> Self {
+        let stages = pil_file
+            .committed_polys_in_source_order()
+            .flat_map(|(symbol, _)| {
+                let stage = symbol.stage.unwrap_or_default();
+                symbol.array_elements().map(move |(_, id)| (id, stage))
+            })
+            .collect();
+        let degrees = pil_file
+            .definitions
+            .values()
+            .filter(|(symbol, _)| matches!(symbol.kind, SymbolKind::Poly(_)))
+            .filter_map(|(symbol, _)| {
+                let (namespace, _) = symbol.absolute_name.rsplit_once("::")?;
+                Some((namespace.to_string(), symbol.degree))
+            })
+            .collect();
+        Self {
+            max_degree,
+            extracted: Default::default(),
+            stages,
+            degrees,
+            names: pil_file
+                .definitions
+                .keys()
+                .chain(pil_file.intermediate_columns.keys())
+                .cloned()
+                .collect(),
+            next_id: pil_file.commitment_count() as u64,
+        }
+    }
+
+    fn reduce(&mut self, e: AlgebraicExpression<T>) -> AlgebraicExpression<T> {
+        self.rewrite(e, self.max_degree)
+    }
+
+    /// For each `k` up to the maximum degree, the minimal number of columns
+    /// needed to bring the degree of `e` down to at most `k`.
+    fn costs(&self, e: &AlgebraicExpression<T>) -> Vec<usize> {
+        if self.extracted.contains_key(e) {
+            let mut costs = vec![0; self.max_degree + 1];
+            costs[0] = IMPOSSIBLE;
+            return costs;
+        }
+        let own = self.costs_without_extraction(e);
+        // Alternatively, we can replace `e` by a column of degree one.
+        let extract = own[self.max_degree].saturating_add(1).min(IMPOSSIBLE);
+        own.into_iter()
+            .enumerate()
+            .map(|(k, cost)| if k == 0 { cost } else { cost.min(extract) })
+            .collect()
+    }
+
+    fn costs_without_extraction(&self, e: &AlgebraicExpression<T>) -> Vec<usize> {
+        let max = self.max_degree;
+        match e {
+            AlgebraicExpression::Reference(_) => {
+                let mut costs = vec![0; max + 1];
+                costs[0] = IMPOSSIBLE;
+                costs
+            }
+            AlgebraicExpression::PublicReference(_)
+            | AlgebraicExpression::Challenge(_)
+            | AlgebraicExpression::Number(_) => vec![0; max + 1],
+            AlgebraicExpression::BinaryOperation(AlgebraicBinaryOperation {
+                left,
+                op: AlgebraicBinaryOperator::Mul,
+                right,
+            }) => {
+                let (left, right) = (self.costs(left), self.costs(right));
+                (0..=max)
+                    .map(|k| {
+                        (0..=k)
+                            .map(|k_left| left[k_left] + right[k - k_left])
+                            .min()
+                            .unwrap()
+                            .min(IMPOSSIBLE)
+                    })
+                    .collect()
+            }
+            AlgebraicExpression::BinaryOperation(AlgebraicBinaryOperation {
+                op: AlgebraicBinaryOperator::Pow,
+                ..
+            }) => {
+                // Powers exceeding the maximum degree have been expanded, so the
+                // remaining ones can only be reduced further by extracting them.
+                let d = degree(e);
+                (0..=max)
+                    .map(|k| if d <= k { 0 } else { IMPOSSIBLE })
+                    .collect()
+            }
+            AlgebraicExpression::BinaryOperation(AlgebraicBinaryOperation {
+                left, right, ..
+            }) => {
+                let (left, right) = (self.costs(left), self.costs(right));
+                left.iter()
+                    .zip(right)
+                    .map(|(l, r)| (l + r).min(IMPOSSIBLE))
+                    .collect()
+            }
+            AlgebraicExpression::UnaryOperation(AlgebraicUnaryOperation { expr, .. }) => {
+                self.costs(expr)
+            }
+        }
+    }
+
+    /// Rewrites `e` to degree at most `k` with the minimal number of new columns.
+    fn rewrite(&mut self, e: AlgebraicExpression<T>, k: usize) -> AlgebraicExpression<T> {
+        if k > 0 {
+            if let Some((reference, _)) = self.extracted.get(&e) {
+                return AlgebraicExpression::Reference(reference.clone());
+            }
+        }
+        if degree(&e) <= k {
+            return e;
+        }
+        let own = self.costs_without_extraction(&e);
+        if k > 0 && own[self.max_degree] + 1 < own[k] {
+            let e = self.rewrite(e, self.max_degree);
+            return AlgebraicExpression::Reference(self.column_for(e));
+        }
+        match e {
+            AlgebraicExpression::BinaryOperation(AlgebraicBinaryOperation {
+                left,
+                op: AlgebraicBinaryOperator::Mul,
+                right,
+            }) => {
+                let (left_costs, right_costs) = (self.costs(&left), self.costs(&right));
+                let k_left = (0..=k)
+                    .min_by_key(|k_left| left_costs[*k_left] + right_costs[k - k_left])
+                    .unwrap();
+                let left = self.rewrite(*left, k_left);
+                let right = self.rewrite(*right, k - k_left);
+                left * right
+            }
+            AlgebraicExpression::BinaryOperation(AlgebraicBinaryOperation { left, op, right }) => {
+                let left = self.rewrite(*left, k);
+                let right = self.rewrite(*right, k);
+                AlgebraicExpression::new_binary(left, op, right)
+            }
+            AlgebraicExpression::UnaryOperation(AlgebraicUnaryOperation { op, expr }) => {
+                AlgebraicExpression::new_unary(op, self.rewrite(*expr, k))
+            }
+            _ => unreachable!("Leaves always have degree at most one."),
+        }
+    }
+
+    /// Returns the column replacing `e`, creating it if needed.
+    fn column_for(&mut self, e: AlgebraicExpression<T>) -> AlgebraicReference {
+        if let Some((reference, _)) = self.extracted.get(&e) {
+            return reference.clone();
+        }
+        let namespace = e
+            .all_children()
+            .find_map(|e| match e {
+                AlgebraicExpression::Reference(r) => {
+                    r.name.rsplit_once("::").map(|(ns, _)| ns.to_string())
+                }
+                _ => None,
+            })
+            .expect("Expressions of positive degree reference a column.");
+        let stage = e
+            .all_children()
+            .filter_map(|e| match e {
+                AlgebraicExpression::Reference(r) => self.stages.get(&r.poly_id).copied(),
+                AlgebraicExpression::Challenge(c) => Some(c.stage + 1),
+                _ => None,
+            })
+            .max()
+            .unwrap_or_default();
+        let name = (0..)
+            .map(|i| format!("{namespace}::_degree_reduction_{i}"))
+            .find(|name| !self.names.contains(name))
+            .unwrap();
+        self.names.push(name.clone());
+
+        let symbol = Symbol {
+            id: self.next_id,
+            source: SourceRef::unknown(),
+            absolute_name: name.clone(),
+            stage: (stage > 0).then_some(stage),
+            kind: SymbolKind::Poly(PolynomialType::Committed),
+            length: None,
+            degree: self.degrees.get(&namespace).copied().flatten(),
+        };
+        self.next_id += 1;
+        let reference = AlgebraicReference {
+            name: name.clone(),
+            poly_id: PolyID::from(&symbol),
+            next: false,
+        };
+        self.stages.insert(reference.poly_id, stage);
+        self.extracted
+            .insert(e, (reference.clone(), (name, symbol)));
+        reference
+    }
+
+    /// Returns the extracted expressions and their columns, in the order the columns were created.
+    fn into_columns(self) -> Vec<(AlgebraicExpression<T>, Column)> {
+        let mut columns = self
+            .extracted
+            .into_iter()
+            .map(|(e, (_, column))| (e, column))
+            .collect::<Vec<_>>();
+        columns.sort_by_key(|(_, (_, symbol))| symbol.id);
+        columns
+    }
+}
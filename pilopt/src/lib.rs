@@ -3,6 +3,7 @@
 
 use std::cmp::Ordering;
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt::{self, Display, Formatter};
 
 use powdr_ast::analyzed::{
     AlgebraicBinaryOperation, AlgebraicBinaryOperator, AlgebraicExpression, AlgebraicReference,
@@ -15,30 +16,235 @@ use powdr_ast::parsed::visitor::{AllChildren, Children, ExpressionVisitable};
 use powdr_ast::parsed::Number;
 use powdr_number::{BigUint, FieldElement};
 
+mod common_subexpressions;
+mod cross_machine;
+mod degree;
+mod degree_reduction;
+mod linear_substitution;
 mod referenced_symbols;
+mod report;
 
+use common_subexpressions::extract_common_subexpressions;
+use cross_machine::{inline_trivial_submachines, merge_identical_fixed_columns, merge_lookups};
+pub use degree_reduction::reduce_degree;
+use linear_substitution::substitute_determined_witness_columns;
 use referenced_symbols::{ReferencedSymbols, SymbolReference};
+use report::Snapshot;
+pub use report::{
+    OptimizationReport, PassReport, ReportedColumn, ReportedIdentity, ReportedSubstitution,
+};
+
+/// The state passed to the optimizer passes.
+struct PassContext<T> {
+    /// Passes that substitute expressions into polynomial identities do not
+    /// increase their degree beyond this.
+    max_degree: usize,
+    /// Witness columns replaced by an expression in the current pass.
+    substitutions: Vec<ReportedSubstitution<T>>,
+}
+
+/// Declares the passes run by the optimizer as a single table of names and functions.
+macro_rules! passes {
+    ($($name:literal => $pass:expr,)*) => {
+        /// The passes run by the optimizer, in order. Some of them run several times.
+        pub const PASSES: &[&str] = &[$($name),*];
+
+        /// The functions of the passes in [`PASSES`], in the same order.
+        fn pass_functions<T: FieldElement>() -> Vec<fn(&mut Analyzed<T>, &mut PassContext<T>)> {
+            vec![$({
+                let pass: fn(&mut Analyzed<T>, &mut PassContext<T>) = $pass;
+                pass
+            }),*]
+        }
+    };
+}
+
+passes! {
+    "remove_unreferenced_definitions" => |pil_file, _| remove_unreferenced_definitions(pil_file),
+    "remove_constant_fixed_columns" => |pil_file, _| remove_constant_fixed_columns(pil_file),
+    "simplify_identities" => |pil_file, _| simplify_identities(pil_file),
+    "extract_constant_lookups" => |pil_file, _| extract_constant_lookups(pil_file),
+    "remove_constant_witness_columns" => |pil_file, context| {
+        context.substitutions = remove_constant_witness_columns(pil_file)
+    },
+    "simplify_identities" => |pil_file, _| simplify_identities(pil_file),
+    "remove_trivial_identities" => |pil_file, _| remove_trivial_identities(pil_file),
+    "remove_duplicate_identities" => |pil_file, _| remove_duplicate_identities(pil_file),
+    "inline_trivial_submachines" => |pil_file, context| {
+        inline_trivial_submachines(pil_file, context.max_degree)
+    },
+    "merge_identical_fixed_columns" => |pil_file, _| merge_identical_fixed_columns(pil_file),
+    "merge_lookups" => |pil_file, _| merge_lookups(pil_file),
+    "substitute_determined_witness_columns" => |pil_file, context| {
+        context.substitutions =
+            substitute_determined_witness_columns(pil_file, context.max_degree)
+    },
+    "simplify_identities" => |pil_file, _| simplify_identities(pil_file),
+    "remove_trivial_identities" => |pil_file, _| remove_trivial_identities(pil_file),
+    "remove_duplicate_identities" => |pil_file, _| remove_duplicate_identities(pil_file),
+    "extract_common_subexpressions" => |pil_file, context| {
+        extract_common_subexpressions(pil_file, context.max_degree)
+    },
+    "remove_unreferenced_definitions" => |pil_file, _| remove_unreferenced_definitions(pil_file),
+}
+
+/// The passes in [`PASSES`] that only run if they are enabled in
+/// [`OptimizerOptions::enabled_passes`].
+pub const OPT_IN_PASSES: &[&str] = &[
+    "inline_trivial_submachines",
+    "merge_identical_fixed_columns",
+    "merge_lookups",
+    "substitute_determined_witness_columns",
+    "extract_common_subexpressions",
+];
+
+/// Options for [`optimize_with_options`].
+#[derive(Clone, Debug, Default)]
+pub struct OptimizerOptions {
+    /// Passes that substitute expressions into polynomial identities do not increase
+    /// their degree beyond this. If `None`, the maximal degree of the input is used.
+    pub max_degree: Option<usize>,
+    /// Names of passes in [`PASSES`] that are skipped. Disabling a name disables
+    /// every run of that pass.
+    pub disabled_passes: BTreeSet<String>,
+    /// Names of passes in [`OPT_IN_PASSES`] that are run. Disabling a pass takes
+    /// precedence over enabling it.
+    pub enabled_passes: BTreeSet<String>,
+    /// Whether to report the columns and identities each pass removed or added,
+    /// and the expressions witness columns were replaced by. This compares the
+    /// PIL file before and after every pass.
+    pub report: bool,
+}
 
-pub fn optimize<T: FieldElement>(mut pil_file: Analyzed<T>) -> Analyzed<T> {
-    let col_count_pre = (pil_file.commitment_count(), pil_file.constant_count());
-    remove_unreferenced_definitions(&mut pil_file);
-    remove_constant_fixed_columns(&mut pil_file);
-    simplify_identities(&mut pil_file);
-    extract_constant_lookups(&mut pil_file);
-    remove_constant_witness_columns(&mut pil_file);
-    simplify_identities(&mut pil_file);
-    remove_trivial_identities(&mut pil_file);
-    remove_duplicate_identities(&mut pil_file);
-    remove_unreferenced_definitions(&mut pil_file);
-    let col_count_post = (pil_file.commitment_count(), pil_file.constant_count());
+/// Optimizes the PIL file without increasing the maximal degree of its polynomial identities.
+pub fn optimize<T: FieldElement>(pil_file: Analyzed<T>) -> Analyzed<T> {
+    optimize_with_options(pil_file, &Default::default(), |_, _, _| {})
+        .unwrap()
+        .0
+}
+
+/// Optimizes the PIL file. Passes that substitute expressions into polynomial
+/// identities do not increase their degree beyond `max_degree`.
+pub fn optimize_with_max_degree<T: FieldElement>(
+    pil_file: Analyzed<T>,
+    max_degree: usize,
+) -> Analyzed<T> {
+    let options = OptimizerOptions {
+        max_degree: Some(max_degree),
+        ..Default::default()
+    };
+    optimize_with_options(pil_file, &options, |_, _, _| {})
+        .unwrap()
+        .0
+}
+
+/// Optimizes the PIL file, running all passes in [`PASSES`] that are not disabled
+/// and, for the passes in [`OPT_IN_PASSES`], enabled.
+/// If requested in `options`, also returns a report of the changes each pass made.
+/// `after_pass` is called with the index in [`PASSES`] and the name of every pass
+/// that ran, and the PIL file after it.
+///
+/// Returns an error if a disabled or enabled pass is not in [`PASSES`].
+pub fn optimize_with_options<T: FieldElement>(
+    mut pil_file: Analyzed<T>,
+    options: &OptimizerOptions,
+    mut after_pass: impl FnMut(usize, &str, &Analyzed<T>),
+) -> Result<(Analyzed<T>, Option<OptimizationReport<T>>), String> {
+    if let Some(unknown) = options
+        .disabled_passes
+        .iter()
+        .chain(&options.enabled_passes)
+        .find(|name| !PASSES.contains(&name.as_str()))
+    {
+        return Err(format!("Unknown optimizer pass: {unknown}"));
+    }
+    let mut context = PassContext {
+        max_degree: options
+            .max_degree
+            .unwrap_or_else(|| max_polynomial_identity_degree(&pil_file)),
+        substitutions: vec![],
+    };
+
+    let statistics_pre = Statistics::from(&pil_file);
+    let mut pass_statistics = String::new();
+    let mut report = options.report.then(OptimizationReport::default);
+    for (index, (name, pass)) in PASSES.iter().zip(pass_functions()).enumerate() {
+        let opted_out = OPT_IN_PASSES.contains(name) && !options.enabled_passes.contains(*name);
+        if opted_out || options.disabled_passes.contains(*name) {
+            if let Some(report) = &mut report {
+                report.passes.push(PassReport::disabled(name));
+            }
+            continue;
+        }
+        let before = Statistics::from(&pil_file);
+        let snapshot = report.is_some().then(|| Snapshot::new(&pil_file));
+        pass(&mut pil_file, &mut context);
+        let after = Statistics::from(&pil_file);
+        if after != before {
+            pass_statistics += &format!("\n    {name}: {before} -> {after}");
+        }
+        if let (Some(report), Some(snapshot)) = (&mut report, snapshot) {
+            report.passes.push(snapshot.report(
+                name,
+                Snapshot::new(&pil_file),
+                std::mem::take(&mut context.substitutions),
+            ));
+        }
+        after_pass(index, name, &pil_file);
+    }
+    let statistics_post = Statistics::from(&pil_file);
     log::info!(
-        "Removed {} witness and {} fixed columns. Total count now: {} witness and {} fixed columns.",
-        col_count_pre.0 - col_count_post.0,
-        col_count_pre.1 - col_count_post.1,
-        col_count_post.0,
-        col_count_post.1
+        "Removed {} witness and {} fixed columns. Total count now: {} witness and {} fixed columns.{pass_statistics}",
+        statistics_pre.witness - statistics_post.witness,
+        statistics_pre.fixed - statistics_post.fixed,
+        statistics_post.witness,
+        statistics_post.fixed,
     );
+    Ok((pil_file, report))
+}
+
+fn max_polynomial_identity_degree<T: FieldElement>(pil_file: &Analyzed<T>) -> usize {
     pil_file
+        .identities_with_inlined_intermediate_polynomials()
+        .iter()
+        .filter_map(|identity| match identity {
+            Identity::Polynomial(PolynomialIdentity { expression, .. }) => {
+                Some(degree::degree(expression))
+            }
+            _ => None,
+        })
+        .max()
+        .unwrap_or(1)
+}
+
+/// The number of columns and identities, logged before and after each pass.
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Statistics {
+    witness: usize,
+    fixed: usize,
+    intermediate: usize,
+    identities: usize,
+}
+
+impl<T: FieldElement> From<&Analyzed<T>> for Statistics {
+    fn from(pil_file: &Analyzed<T>) -> Self {
+        Self {
+            witness: pil_file.commitment_count(),
+            fixed: pil_file.constant_count(),
+            intermediate: pil_file.intermediate_count(),
+            identities: pil_file.identities.len(),
+        }
+    }
+}
+
+impl Display for Statistics {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} witness, {} fixed, {} intermediate columns, {} identities",
+            self.witness, self.fixed, self.intermediate, self.identities
+        )
+    }
 }
 
 /// Removes all definitions that are not referenced by an identity, public declaration
@@ -376,7 +582,9 @@ fn extract_constant_lookups<T: FieldElement>(pil_file: &mut Analyzed<T>) {
 
 /// Identifies witness columns that are constrained to a single value, replaces every
 /// reference to this column by the value and deletes the column.
-fn remove_constant_witness_columns<T: FieldElement>(pil_file: &mut Analyzed<T>) {
+fn remove_constant_witness_columns<T: FieldElement>(
+    pil_file: &mut Analyzed<T>,
+) -> Vec<ReportedSubstitution<T>> {
     let mut constant_polys = pil_file
         .identities
         .iter()
@@ -397,7 +605,15 @@ fn remove_constant_witness_columns<T: FieldElement>(pil_file: &mut Analyzed<T>)
         .collect::<HashSet<PolyID>>();
     constant_polys.retain(|((_, id), _)| columns.contains(id));
 
+    let substitutions = constant_polys
+        .iter()
+        .map(|((name, _), value)| ReportedSubstitution {
+            name: name.clone(),
+            value: AlgebraicExpression::Number(T::checked_from(value.clone()).unwrap()),
+        })
+        .collect();
     substitute_polynomial_references(pil_file, constant_polys);
+    substitutions
 }
 
 /// Substitutes all references to certain polynomials by the given field elements.
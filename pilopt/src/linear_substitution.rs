@@ -0,0 +1,287 @@
+//! Removal of witness columns that are determined by a polynomial identity.
+//!
+//! An identity like `x = a + 2 * b` determines the witness column `x`. If `x`
+//! only occurs in polynomial identities, it can be replaced by `a + 2 * b`
+//! everywhere and the identity can be removed. More generally, this works for
+//! every identity that is a sum of terms, one of them being `x` or `-x`, where
+//! `x` does not occur in any other term.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use powdr_ast::analyzed::{
+    AlgebraicBinaryOperation, AlgebraicBinaryOperator, AlgebraicExpression, AlgebraicReference,
+    AlgebraicUnaryOperation, AlgebraicUnaryOperator, Analyzed, Expression, Identity, PolyID,
+    PolynomialIdentity, PolynomialReference, PolynomialType, Reference,
+};
+use powdr_ast::parsed::visitor::{AllChildren, ExpressionVisitable};
+use powdr_number::FieldElement;
+
+use crate::degree::{degree_with, intermediate_degrees, reference_degree};
+use crate::ReportedSubstitution;
+
+/// Substitutes witness columns determined by a polynomial identity, as long as
+/// this does not increase the degree of any identity beyond `max_degree`.
+///
+/// Only columns that are not referenced by other kinds of identities, public
+/// declarations, prover functions or intermediate columns are substituted, and
+/// only if the expression they are replaced by references another witness column.
+/// Returns the substituted columns and the expressions they were replaced by.
+pub(crate) fn substitute_determined_witness_columns<T: FieldElement>(
+    pil_file: &mut Analyzed<T>,
+    max_degree: usize,
+) -> Vec<ReportedSubstitution<T>> {
+    let mut candidates = candidate_columns(pil_file);
+    let intermediate_degrees = intermediate_degrees(pil_file);
+    let degree = |e: &AlgebraicExpression<T>| {
+        degree_with(e, &|r| reference_degree(r, &intermediate_degrees))
+    };
+
+    // The indices of the polynomial identities each column occurs in.
+    let mut occurrences: HashMap<PolyID, BTreeSet<usize>> = HashMap::new();
+    let mut next_referenced = HashSet::new();
+    for (index, identity) in pil_file.identities.iter().enumerate() {
+        if let Identity::Polynomial(PolynomialIdentity { expression, .. }) = identity {
+            for r in references(expression) {
+                occurrences.entry(r.poly_id).or_default().insert(index);
+                if r.next {
+                    next_referenced.insert(r.poly_id);
+                }
+            }
+        }
+    }
+
+    let mut identities_to_remove = BTreeSet::new();
+    let mut columns_to_remove = BTreeSet::new();
+    let mut substitutions = vec![];
+    for index in 0..pil_file.identities.len() {
+        let Identity::Polynomial(PolynomialIdentity { expression, .. }) =
+            &pil_file.identities[index]
+        else {
+            continue;
+        };
+        let Some((column, value)) = determined_column(expression, &candidates) else {
+            continue;
+        };
+        let id = column.poly_id;
+        let shifted_value = if next_referenced.contains(&id) {
+            match shift(&value) {
+                Some(shifted) => Some(shifted),
+                None => continue,
+            }
+        } else {
+            None
+        };
+
+        let other_occurrences = occurrences
+            .get(&id)
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(|i| *i != index)
+            .collect::<Vec<_>>();
+        let value_degree = degree(&value);
+        let increases_degree = other_occurrences.iter().any(|i| {
+            let expression = polynomial_identity_expression(&pil_file.identities[*i]);
+            let substituted_degree = degree_with(expression, &|r| {
+                if r.poly_id == id {
+                    value_degree
+                } else {
+                    reference_degree(r, &intermediate_degrees)
+                }
+            });
+            substituted_degree > degree(expression).max(max_degree)
+        });
+        if increases_degree {
+            continue;
+        }
+
+        log::trace!("Substituting {} = {value}.", column.name);
+        let value_references = references(&value).map(|r| r.poly_id).collect::<Vec<_>>();
+        for i in other_occurrences {
+            let Identity::Polynomial(PolynomialIdentity { expression, .. }) =
+                &mut pil_file.identities[i]
+            else {
+                unreachable!()
+            };
+            expression.post_visit_expressions_mut(&mut |e| {
+                if let AlgebraicExpression::Reference(r) = e {
+                    if r.poly_id == id {
+                        *e = match (r.next, &shifted_value) {
+                            (true, Some(shifted)) => shifted.clone(),
+                            _ => value.clone(),
+                        };
+                    }
+                }
+            });
+            for value_id in &value_references {
+                occurrences.entry(*value_id).or_default().insert(i);
+            }
+        }
+        for value_id in &value_references {
+            if let Some(o) = occurrences.get_mut(value_id) {
+                o.remove(&index);
+            }
+            if shifted_value.is_some() {
+                next_referenced.insert(*value_id);
+            }
+        }
+        occurrences.remove(&id);
+        candidates.remove(&id);
+        identities_to_remove.insert(index);
+        columns_to_remove.insert(column.name.clone());
+        substitutions.push(ReportedSubstitution {
+            name: column.name,
+            value,
+        });
+    }
+
+    pil_file.remove_identities(&identities_to_remove);
+    pil_file.remove_definitions(&columns_to_remove);
+    substitutions
+}
+
+/// Returns the non-array witness columns without hints that are only referenced
+/// in polynomial identities.
+fn candidate_columns<T: FieldElement>(pil_file: &Analyzed<T>) -> HashSet<PolyID> {
+    let mut referenced_names = pil_file
+        .public_declarations
+        .values()
+        .map(|p| p.polynomial.name.clone())
+        .collect::<HashSet<_>>();
+    for fun in &pil_file.prover_functions {
+        for e in fun.all_children() {
+            if let Expression::Reference(_, Reference::Poly(PolynomialReference { name, .. })) = e {
+                referenced_names.insert(name.clone());
+            }
+        }
+    }
+
+    let mut referenced_ids = HashSet::new();
+    for identity in &pil_file.identities {
+        if !matches!(identity, Identity::Polynomial(_)) {
+            identity.pre_visit_expressions(&mut |e: &AlgebraicExpression<T>| {
+                if let AlgebraicExpression::Reference(r) = e {
+                    referenced_ids.insert(r.poly_id);
+                }
+            });
+        }
+    }
+    for (_, definitions) in pil_file.intermediate_columns.values() {
+        for definition in definitions {
+            referenced_ids.extend(references(definition).map(|r| r.poly_id));
+        }
+    }
+
+    pil_file
+        .committed_polys_in_source_order()
+        .filter(|(symbol, value)| {
+            !symbol.is_array()
+                && value.is_none()
+                && !referenced_names.contains(&symbol.absolute_name)
+        })
+        .map(|(symbol, _)| symbol.into())
+        .filter(|id| !referenced_ids.contains(id))
+        .collect()
+}
+
+/// If the identity `expression = 0` determines one of the candidate columns,
+/// returns the column and the expression it is equal to.
+fn determined_column<T: FieldElement>(
+    expression: &AlgebraicExpression<T>,
+    candidates: &HashSet<PolyID>,
+) -> Option<(AlgebraicReference, AlgebraicExpression<T>)> {
+    let mut terms = vec![];
+    additive_terms(expression, false, &mut terms);
+    terms.iter().enumerate().find_map(|(i, (negated, term))| {
+        let AlgebraicExpression::Reference(column) = term else {
+            return None;
+        };
+        if column.next || !candidates.contains(&column.poly_id) {
+            return None;
+        }
+        let others = || {
+            terms
+                .iter()
+                .enumerate()
+                .filter(move |(j, _)| *j != i)
+                .map(|(_, term)| term)
+        };
+        if others().any(|(_, t)| references(t).any(|r| r.poly_id == column.poly_id))
+            || !others().any(|(_, t)| t.contains_witness_ref())
+        {
+            return None;
+        }
+        // `column` has the opposite sign of the sum of the other terms.
+        let mut value_terms = others().map(|(n, t)| (n == negated, (*t).clone()));
+        let (first_negated, first) = value_terms.next()?;
+        let first = if first_negated {
+            AlgebraicExpression::new_unary(AlgebraicUnaryOperator::Minus, first)
+        } else {
+            first
+        };
+        let value = value_terms.fold(first, |acc, (n, t)| if n { acc - t } else { acc + t });
+        Some((column.clone(), value))
+    })
+}
+
+/// Splits an expression into terms that are added up, each with a flag
+/// indicating whether it is negated.
+fn additive_terms<'a, T>(
+    e: &'a AlgebraicExpression<T>,
+    negated: bool,
+    terms: &mut Vec<(bool, &'a AlgebraicExpression<T>)>,
+) {
+    match e {
+        AlgebraicExpression::BinaryOperation(AlgebraicBinaryOperation {
+            left,
+            op: AlgebraicBinaryOperator::Add,
+            right,
+        }) => {
+            additive_terms(left, negated, terms);
+            additive_terms(right, negated, terms);
+        }
+        AlgebraicExpression::BinaryOperation(AlgebraicBinaryOperation {
+            left,
+            op: AlgebraicBinaryOperator::Sub,
+            right,
+        }) => {
+            additive_terms(left, negated, terms);
+            additive_terms(right, !negated, terms);
+        }
+        AlgebraicExpression::UnaryOperation(AlgebraicUnaryOperation {
+            op: AlgebraicUnaryOperator::Minus,
+            expr,
+        }) => additive_terms(expr, !negated, terms),
+        _ => terms.push((negated, e)),
+    }
+}
+
+/// Returns the expression with all references to the next row, or `None`
+/// if that is not possible because it already references the next row or
+/// intermediate columns.
+fn shift<T: Clone>(e: &AlgebraicExpression<T>) -> Option<AlgebraicExpression<T>> {
+    if references(e).any(|r| r.next || r.poly_id.ptype == PolynomialType::Intermediate) {
+        return None;
+    }
+    let mut shifted = e.clone();
+    shifted.post_visit_expressions_mut(&mut |e| {
+        if let AlgebraicExpression::Reference(r) = e {
+            r.next = true;
+        }
+    });
+    Some(shifted)
+}
+
+fn references<T>(e: &AlgebraicExpression<T>) -> impl Iterator<Item = &AlgebraicReference> {
+    e.all_children().filter_map(|e| match e {
+        AlgebraicExpression::Reference(r) => Some(r),
+        _ => None,
+    })
+}
+
+fn polynomial_identity_expression<T>(identity: &Identity<T>) -> &AlgebraicExpression<T> {
+    match identity {
+        Identity::Polynomial(PolynomialIdentity { expression, .. }) => expression,
+        _ => unreachable!(),
+    }
+}
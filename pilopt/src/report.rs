@@ -0,0 +1,210 @@
+//! Report of the changes each optimization pass made.
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+use powdr_ast::analyzed::{AlgebraicExpression, Analyzed, Identity, PolynomialType, SymbolKind};
+use powdr_ast::parsed::SourceReference;
+use powdr_number::FieldElement;
+use powdr_parser_util::SourceRef;
+use serde::{Serialize, Serializer};
+
+/// The changes made by all passes of an optimizer run, in the order they ran.
+#[derive(Clone, Debug, Serialize)]
+#[serde(bound = "T: Display")]
+pub struct OptimizationReport<T> {
+    pub passes: Vec<PassReport<T>>,
+}
+
+impl<T> Default for OptimizationReport<T> {
+    fn default() -> Self {
+        Self { passes: vec![] }
+    }
+}
+
+/// The changes made by a single run of a pass.
+#[derive(Clone, Debug, Serialize)]
+#[serde(bound = "T: Display")]
+pub struct PassReport<T> {
+    pub name: String,
+    /// False if the pass did not run because it was disabled or, for a pass
+    /// in [`crate::OPT_IN_PASSES`], not enabled.
+    pub enabled: bool,
+    pub removed_columns: Vec<ReportedColumn>,
+    pub added_columns: Vec<ReportedColumn>,
+    pub removed_identities: Vec<ReportedIdentity>,
+    pub added_identities: Vec<ReportedIdentity>,
+    /// Identities that were changed in place, as they are after the pass.
+    pub modified_identities: Vec<ReportedIdentity>,
+    /// Witness columns the pass replaced by an expression, in the order they were replaced.
+    pub substituted_columns: Vec<ReportedSubstitution<T>>,
+}
+
+/// A witness column that was replaced by an expression in the other columns.
+/// The expression can reference columns that are substituted by later passes.
+#[derive(Clone, Debug, Serialize)]
+#[serde(bound = "T: Display")]
+pub struct ReportedSubstitution<T> {
+    pub name: String,
+    #[serde(serialize_with = "serialize_display")]
+    pub value: AlgebraicExpression<T>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ReportedColumn {
+    pub name: String,
+    /// One of "witness", "fixed" or "intermediate".
+    pub kind: &'static str,
+    #[serde(serialize_with = "serialize_source")]
+    pub source: SourceRef,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ReportedIdentity {
+    pub id: u64,
+    pub identity: String,
+    #[serde(serialize_with = "serialize_source")]
+    pub source: SourceRef,
+}
+
+/// The columns and identities of a PIL file before a pass.
+pub(crate) struct Snapshot<T> {
+    columns: BTreeMap<String, ReportedColumn>,
+    identities: BTreeMap<u64, Identity<T>>,
+}
+
+impl<T: FieldElement> Snapshot<T> {
+    pub(crate) fn new(pil_file: &Analyzed<T>) -> Self {
+        let columns = pil_file
+            .definitions
+            .iter()
+            .filter_map(|(name, (symbol, _))| match symbol.kind {
+                SymbolKind::Poly(ptype) => Some((name, symbol, ptype)),
+                SymbolKind::Other() => None,
+            })
+            .chain(
+                pil_file
+                    .intermediate_columns
+                    .iter()
+                    .map(|(name, (symbol, _))| (name, symbol, PolynomialType::Intermediate)),
+            )
+            .map(|(name, symbol, ptype)| {
+                let column = ReportedColumn {
+                    name: name.clone(),
+                    kind: match ptype {
+                        PolynomialType::Committed => "witness",
+                        PolynomialType::Constant => "fixed",
+                        PolynomialType::Intermediate => "intermediate",
+                    },
+                    source: symbol.source.clone(),
+                };
+                (name.clone(), column)
+            })
+            .collect();
+        let identities = pil_file
+            .identities
+            .iter()
+            .map(|identity| (identity.id(), identity.clone()))
+            .collect();
+        Self {
+            columns,
+            identities,
+        }
+    }
+
+    /// Compares the snapshot taken before the pass to the snapshot after it.
+    pub(crate) fn report(
+        self,
+        name: &str,
+        after: Snapshot<T>,
+        substituted_columns: Vec<ReportedSubstitution<T>>,
+    ) -> PassReport<T> {
+        PassReport {
+            name: name.to_string(),
+            enabled: true,
+            removed_columns: missing_columns(&self.columns, &after.columns),
+            added_columns: missing_columns(&after.columns, &self.columns),
+            removed_identities: missing_identities(&self.identities, &after.identities),
+            added_identities: missing_identities(&after.identities, &self.identities),
+            modified_identities: after
+                .identities
+                .iter()
+                .filter(|(id, identity)| {
+                    self.identities
+                        .get(id)
+                        .is_some_and(|before| before != *identity)
+                })
+                .map(|(id, identity)| reported_identity(*id, identity))
+                .collect(),
+            substituted_columns,
+        }
+    }
+}
+
+impl<T> PassReport<T> {
+    pub(crate) fn disabled(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            enabled: false,
+            removed_columns: vec![],
+            added_columns: vec![],
+            removed_identities: vec![],
+            added_identities: vec![],
+            modified_identities: vec![],
+            substituted_columns: vec![],
+        }
+    }
+}
+
+/// Returns the columns in `a` that are not in `b`.
+fn missing_columns(
+    a: &BTreeMap<String, ReportedColumn>,
+    b: &BTreeMap<String, ReportedColumn>,
+) -> Vec<ReportedColumn> {
+    a.iter()
+        .filter(|(name, _)| !b.contains_key(*name))
+        .map(|(_, column)| column.clone())
+        .collect()
+}
+
+/// Returns the identities in `a` that are not in `b`.
+fn missing_identities<T: FieldElement>(
+    a: &BTreeMap<u64, Identity<T>>,
+    b: &BTreeMap<u64, Identity<T>>,
+) -> Vec<ReportedIdentity> {
+    a.iter()
+        .filter(|(id, _)| !b.contains_key(*id))
+        .map(|(id, identity)| reported_identity(*id, identity))
+        .collect()
+}
+
+fn reported_identity<T: FieldElement>(id: u64, identity: &Identity<T>) -> ReportedIdentity {
+    ReportedIdentity {
+        id,
+        identity: identity.to_string(),
+        source: identity.source_reference().clone(),
+    }
+}
+
+/// Serializes a source reference without the contents of the file.
+fn serialize_source<S: Serializer>(source: &SourceRef, serializer: S) -> Result<S::Ok, S::Error> {
+    #[derive(Serialize)]
+    struct Location<'a> {
+        file_name: Option<&'a str>,
+        start: usize,
+        end: usize,
+    }
+    Location {
+        file_name: source.file_name.as_deref(),
+        start: source.start,
+        end: source.end,
+    }
+    .serialize(serializer)
+}
+
+fn serialize_display<S: Serializer>(
+    value: &impl Display,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
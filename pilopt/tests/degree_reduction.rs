@@ -0,0 +1,138 @@
+use powdr_number::GoldilocksField;
+use powdr_pil_analyzer::analyze_string;
+
+use powdr_pilopt::reduce_degree;
+use pretty_assertions::assert_eq;
+
+fn reduce(input: &str, max_degree: usize) -> String {
+    let mut pil = analyze_string::<GoldilocksField>(input).unwrap();
+    reduce_degree(&mut pil, max_degree);
+    pil.to_string()
+}
+
+#[test]
+fn low_degree_unchanged() {
+    let input = r#"namespace N(65536);
+    col witness a;
+    col witness b;
+    col witness c;
+    N::a * N::b * N::c = 0;
+"#;
+    assert_eq!(reduce(input, 3), input);
+}
+
+#[test]
+fn product() {
+    let input = r#"namespace N(65536);
+    col witness a;
+    col witness b;
+    col witness c;
+    col witness d;
+    col witness e;
+    N::a * N::b * N::c * N::d * N::e = 0;
+"#;
+    let expectation = r#"namespace N(65536);
+    col witness a;
+    col witness b;
+    col witness c;
+    col witness d;
+    col witness e;
+    N::_degree_reduction_0 * N::d * N::e = 0;
+    col witness _degree_reduction_0;
+    N::_degree_reduction_0 = N::a * N::b * N::c;
+"#;
+    assert_eq!(reduce(input, 3), expectation);
+}
+
+#[test]
+fn shared_subexpression() {
+    let input = r#"namespace N(65536);
+    col witness a;
+    col witness b;
+    col witness c;
+    col witness d;
+    (N::a + N::b) * (N::a + N::b) * N::c = N::d;
+    (N::a + N::b) * (N::a + N::b) * N::d = N::c;
+"#;
+    let expectation = r#"namespace N(65536);
+    col witness a;
+    col witness b;
+    col witness c;
+    col witness d;
+    N::_degree_reduction_0 * N::c = N::d;
+    N::_degree_reduction_0 * N::d = N::c;
+    col witness _degree_reduction_0;
+    N::_degree_reduction_0 = (N::a + N::b) * (N::a + N::b);
+"#;
+    assert_eq!(reduce(input, 2), expectation);
+}
+
+#[test]
+fn power() {
+    let input = r#"namespace N(65536);
+    col witness x;
+    col witness y;
+    N::y = N::x ** 7;
+"#;
+    let expectation = r#"namespace N(65536);
+    col witness x;
+    col witness y;
+    N::y = N::_degree_reduction_0 * N::_degree_reduction_0 * N::x;
+    col witness _degree_reduction_0;
+    N::_degree_reduction_0 = N::x ** 3;
+"#;
+    assert_eq!(reduce(input, 3), expectation);
+}
+
+#[test]
+fn intermediate_columns() {
+    let input = r#"namespace N(65536);
+    col witness a;
+    col witness b;
+    col cube = N::a * N::a * N::a;
+    N::cube * N::b * N::b = 1;
+"#;
+    let expectation = r#"namespace N(65536);
+    col witness a;
+    col witness b;
+    col cube = N::a * N::a * N::a;
+    N::_degree_reduction_0 * N::b * N::b = 1;
+    col witness _degree_reduction_0;
+    N::_degree_reduction_0 = N::a * N::a * N::a;
+"#;
+    assert_eq!(reduce(input, 3), expectation);
+}
+
+#[test]
+fn stage() {
+    let input = r#"namespace N(65536);
+    col witness a;
+    col witness stage(1) b;
+    let alpha: expr = std::prelude::challenge(0, 1);
+    N::a * N::a * N::b * N::b = N::alpha;
+"#;
+    let expectation = r#"namespace N(65536);
+    col witness a;
+    col witness stage(1) b;
+    let alpha: expr = std::prelude::challenge(0_int, 1_int);
+    N::_degree_reduction_1 * N::b = std::prelude::challenge(0, 1);
+    col witness _degree_reduction_0;
+    N::_degree_reduction_0 = N::a * N::a;
+    col witness stage(1) _degree_reduction_1;
+    N::_degree_reduction_1 = N::_degree_reduction_0 * N::b;
+"#;
+    assert_eq!(reduce(input, 2), expectation);
+}
+
+#[test]
+fn lookups_and_permutations_unchanged() {
+    let input = r#"namespace N(65536);
+    col fixed t(i) { i };
+    col witness a;
+    col witness b;
+    col witness c;
+    N::a * N::b $ [N::a * N::b * N::c] in [N::t];
+    N::a * N::b $ [N::a * N::b * N::c] is [N::t];
+"#;
+    assert_eq!(reduce(input, 2), input);
+}
@@ -1,9 +1,21 @@
 use powdr_number::GoldilocksField;
 use powdr_pil_analyzer::analyze_string;
 
-use powdr_pilopt::optimize;
+use powdr_ast::analyzed::Analyzed;
+use powdr_pilopt::{optimize, optimize_with_options, OptimizerOptions, OPT_IN_PASSES, PASSES};
 use pretty_assertions::assert_eq;
 
+/// Optimizes the PIL file with all passes in [`OPT_IN_PASSES`] enabled.
+fn optimize_with_opt_in_passes(pil_file: Analyzed<GoldilocksField>) -> Analyzed<GoldilocksField> {
+    let options = OptimizerOptions {
+        enabled_passes: OPT_IN_PASSES.iter().map(|p| p.to_string()).collect(),
+        ..Default::default()
+    };
+    optimize_with_options(pil_file, &options, |_, _, _| {})
+        .unwrap()
+        .0
+}
+
 #[test]
 fn replace_fixed() {
     let input = r#"namespace N(65536);
@@ -281,3 +293,489 @@ fn enum_ref_by_trait() {
     let optimized = optimize(analyze_string::<GoldilocksField>(input).unwrap()).to_string();
     assert_eq!(optimized, expectation);
 }
+
+#[test]
+fn substitute_determined_columns() {
+    let input = r#"namespace N(65536);
+    col witness a;
+    col witness b;
+    col witness c;
+    col witness x;
+    col witness y;
+    x = a + 2 * b;
+    y = x - c;
+    y' * b = a;
+"#;
+    let expectation = r#"namespace N(65536);
+    col witness a;
+    col witness b;
+    col witness c;
+    (N::a' + 2 * N::b' - N::c') * N::b = N::a;
+"#;
+    let optimized =
+        optimize_with_opt_in_passes(analyze_string::<GoldilocksField>(input).unwrap()).to_string();
+    assert_eq!(optimized, expectation);
+}
+
+#[test]
+fn substitution_respects_degree() {
+    let input = r#"namespace N(65536);
+    col witness a;
+    col witness b;
+    col witness x;
+    col witness y;
+    x = a * b;
+    y = a + b;
+    x * y = 1;
+"#;
+    let expectation = r#"namespace N(65536);
+    col witness a;
+    col witness b;
+    col witness x;
+    N::x = N::a * N::b;
+    N::x * (N::a + N::b) = 1;
+"#;
+    let optimized =
+        optimize_with_opt_in_passes(analyze_string::<GoldilocksField>(input).unwrap()).to_string();
+    assert_eq!(optimized, expectation);
+}
+
+#[test]
+fn keep_columns_in_lookups() {
+    let input = r#"namespace N(65536);
+    col fixed BYTE(i) { i & 0xff };
+    col witness a;
+    col witness b;
+    col witness x;
+    x = a + b;
+    [x] in [BYTE];
+    [a] in [BYTE];
+    [b] in [BYTE];
+"#;
+    let expectation = r#"namespace N(65536);
+    col fixed BYTE(i) { i & 255_int };
+    col witness a;
+    col witness b;
+    col witness x;
+    N::x = N::a + N::b;
+    [N::x] in [N::BYTE];
+    [N::a] in [N::BYTE];
+    [N::b] in [N::BYTE];
+"#;
+    let optimized =
+        optimize_with_opt_in_passes(analyze_string::<GoldilocksField>(input).unwrap()).to_string();
+    assert_eq!(optimized, expectation);
+}
+
+#[test]
+fn common_subexpressions() {
+    let input = r#"namespace N(65536);
+    col fixed sel = [0, 1]*;
+    col witness a;
+    col witness b;
+    col witness c;
+    sel * (a + b) * c = 0;
+    (1 - sel) * (a + b) * c = 1;
+    (a + b) * b = c;
+"#;
+    let expectation = r#"namespace N(65536);
+    col fixed sel = [0_fe, 1_fe]*;
+    col witness a;
+    col witness b;
+    col witness c;
+    col _subexpression_0 = N::a + N::b;
+    N::sel * N::_subexpression_0 * N::c = 0;
+    (1 - N::sel) * N::_subexpression_0 * N::c = 1;
+    N::_subexpression_0 * N::b = N::c;
+"#;
+    let optimized =
+        optimize_with_opt_in_passes(analyze_string::<GoldilocksField>(input).unwrap()).to_string();
+    assert_eq!(optimized, expectation);
+}
+
+#[test]
+fn replace_fixed_with_opt_in_passes() {
+    let input = r#"namespace N(65536);
+    col fixed one = [1]*;
+    col fixed zero = [0]*;
+    col witness X;
+    col witness Y;
+    X * one = X * zero - zero + Y;
+    one * Y = zero * Y + 7 * X;
+"#;
+    let expectation = r#"namespace N(65536);
+    col witness Y;
+    N::Y = 7 * N::Y;
+"#;
+    let optimized =
+        optimize_with_opt_in_passes(analyze_string::<GoldilocksField>(input).unwrap()).to_string();
+    assert_eq!(optimized, expectation);
+}
+
+#[test]
+fn replace_lookup_with_opt_in_passes() {
+    let input = r#"namespace N(65536);
+    col fixed one = [1]*;
+    col fixed zero = [0]*;
+    col fixed two = [2]*;
+    col fixed cnt(i) { i };
+    col witness X;
+    col witness Y;
+    col witness W;
+    col witness Z;
+    col witness A;
+    (1 - A) $ [ X, Y, A ] in [ zero, one, cnt ];
+    [ Y, W, Z, A ] in (1 + A) $ [ cnt, zero, two, one ];
+    [ W, Z ] in (1 + A) $ [ zero, one ];
+"#;
+    let expectation = r#"namespace N(65536);
+    col fixed cnt(i) { i };
+    col witness X;
+    col witness Y;
+    col witness A;
+    1 - N::A $ [N::A] in [N::cnt];
+    [N::Y] in 1 + N::A $ [N::cnt];
+    (1 - N::A) * N::X = 0;
+    (1 - N::A) * N::Y = 1;
+    N::A = 1 + N::A;
+    (1 + N::A) * 2 = 1 + N::A;
+"#;
+    let optimized =
+        optimize_with_opt_in_passes(analyze_string::<GoldilocksField>(input).unwrap()).to_string();
+    assert_eq!(optimized, expectation);
+}
+
+#[test]
+fn merge_identical_fixed_tables() {
+    let input = r#"namespace N(65536);
+    col witness x;
+    col witness y;
+    [x] in [bit2::BIT2];
+    [x] in [bit2_1::BIT2];
+    [y] in [bit2_1::BIT2];
+namespace bit2(4);
+    col fixed BIT2(i) { i % 4 };
+namespace bit2_1(4);
+    col fixed BIT2(i) { i % 4 };
+"#;
+    let expectation = r#"namespace N(65536);
+    col witness x;
+    col witness y;
+    [N::x] in [bit2::BIT2];
+    [N::y] in [bit2::BIT2];
+namespace bit2(4);
+    col fixed BIT2(i) { i % 4_int };
+"#;
+    let optimized =
+        optimize_with_opt_in_passes(analyze_string::<GoldilocksField>(input).unwrap()).to_string();
+    assert_eq!(optimized, expectation);
+}
+
+#[test]
+fn keep_fixed_tables_of_different_size() {
+    let input = r#"namespace N(65536);
+    col witness x;
+    [x] in [bit2::BIT2];
+    [x] in [bit2_1::BIT2];
+namespace bit2(4);
+    col fixed BIT2(i) { i % 4 };
+namespace bit2_1(8);
+    col fixed BIT2(i) { i % 4 };
+"#;
+    let expectation = r#"namespace N(65536);
+    col witness x;
+    [N::x] in [bit2::BIT2];
+    [N::x] in [bit2_1::BIT2];
+namespace bit2(4);
+    col fixed BIT2(i) { i % 4_int };
+namespace bit2_1(8);
+    col fixed BIT2(i) { i % 4_int };
+"#;
+    let optimized =
+        optimize_with_opt_in_passes(analyze_string::<GoldilocksField>(input).unwrap()).to_string();
+    assert_eq!(optimized, expectation);
+}
+
+#[test]
+fn merge_lookups_on_key_column() {
+    let input = r#"namespace N(65536);
+    col witness a;
+    col witness b;
+    col witness c;
+    col witness s;
+    s $ [a, b] in [T::KEY, T::LOW];
+    s $ [a, c] in [T::KEY, T::HIGH];
+    s $ [a] in [T::KEY];
+    [b, c] in [T::LOW, T::HIGH];
+namespace T(256);
+    col fixed KEY(i) { i };
+    col fixed LOW(i) { i % 16 };
+    col fixed HIGH(i) { i / 16 };
+"#;
+    let expectation = r#"namespace N(65536);
+    col witness a;
+    col witness b;
+    col witness c;
+    col witness s;
+    N::s $ [N::a, N::b, N::c] in [T::KEY, T::LOW, T::HIGH];
+    [N::b, N::c] in [T::LOW, T::HIGH];
+namespace T(256);
+    col fixed KEY(i) { i };
+    col fixed LOW(i) { i % 16_int };
+    col fixed HIGH(i) { i / 16_int };
+"#;
+    let optimized =
+        optimize_with_opt_in_passes(analyze_string::<GoldilocksField>(input).unwrap()).to_string();
+    assert_eq!(optimized, expectation);
+}
+
+#[test]
+fn merge_lookups_on_range_table() {
+    let input = r#"namespace N(65536);
+    col witness a;
+    col witness b;
+    col witness c;
+    [a, b] in [byte::BYTE, byte::LOW];
+    [a, c] in [byte::BYTE, byte::HIGH];
+    [b, a] in [wide::BYTE, wide::LOW];
+    [b, c] in [wide::BYTE, wide::HIGH];
+namespace byte(256);
+    col fixed BYTE(i) { i & 0xff };
+    col fixed LOW(i) { i % 16 };
+    col fixed HIGH(i) { i / 16 };
+namespace wide(512);
+    col fixed BYTE(i) { i & 0xff };
+    col fixed LOW(i) { i % 16 };
+    col fixed HIGH(i) { i / 16 };
+"#;
+    // `i & 0xff` has a different value in every row of a table of size 256,
+    // but not in a table of size 512.
+    let expectation = r#"namespace N(65536);
+    col witness a;
+    col witness b;
+    col witness c;
+    [N::a, N::b, N::c] in [byte::BYTE, byte::LOW, byte::HIGH];
+    [N::b, N::a] in [wide::BYTE, wide::LOW];
+    [N::b, N::c] in [wide::BYTE, wide::HIGH];
+namespace byte(256);
+    col fixed BYTE(i) { i & 255_int };
+    col fixed LOW(i) { i % 16_int };
+    col fixed HIGH(i) { i / 16_int };
+namespace wide(512);
+    col fixed BYTE(i) { i & 255_int };
+    col fixed LOW(i) { i % 16_int };
+    col fixed HIGH(i) { i / 16_int };
+"#;
+    let optimized =
+        optimize_with_opt_in_passes(analyze_string::<GoldilocksField>(input).unwrap()).to_string();
+    assert_eq!(optimized, expectation);
+}
+
+#[test]
+fn inline_trivial_submachine() {
+    let input = r#"namespace N(65536);
+    col witness sel;
+    col witness a;
+    col witness b;
+    col witness c;
+    sel * (1 - sel) = 0;
+    sel $ [a, b, c] in [add::x, add::y, add::z];
+namespace add(65536);
+    col witness x;
+    col witness y;
+    col witness z;
+    z = x + y;
+"#;
+    let expectation = r#"namespace N(65536);
+    col witness sel;
+    col witness a;
+    col witness b;
+    col witness c;
+    N::sel * (1 - N::sel) = 0;
+    N::sel * (N::c - (N::a + N::b)) = 0;
+"#;
+    let optimized =
+        optimize_with_opt_in_passes(analyze_string::<GoldilocksField>(input).unwrap()).to_string();
+    assert_eq!(optimized, expectation);
+}
+
+#[test]
+fn keep_submachine_with_internal_columns() {
+    let input = r#"namespace N(65536);
+    col witness sel;
+    col witness a;
+    col witness b;
+    sel $ [a, b] in [sq::x, sq::z];
+namespace sq(65536);
+    col witness x;
+    col witness y;
+    col witness z;
+    y = x * x;
+    z = y * x;
+"#;
+    let expectation = r#"namespace N(65536);
+    col witness sel;
+    col witness a;
+    col witness b;
+    N::sel $ [N::a, N::b] in [sq::x, sq::z];
+namespace sq(65536);
+    col witness x;
+    col witness y;
+    col witness z;
+    sq::y = sq::x * sq::x;
+    sq::z = sq::y * sq::x;
+"#;
+    let optimized =
+        optimize_with_opt_in_passes(analyze_string::<GoldilocksField>(input).unwrap()).to_string();
+    assert_eq!(optimized, expectation);
+}
+
+#[test]
+fn disabled_passes() {
+    let input = r#"namespace N(65536);
+    col fixed one = [1]*;
+    col fixed cnt(i) { i };
+    col witness X;
+    [X * one] in [cnt];
+"#;
+    let options = OptimizerOptions {
+        report: true,
+        ..Default::default()
+    };
+    let (optimized, report) = optimize_with_options(
+        analyze_string::<GoldilocksField>(input).unwrap(),
+        &options,
+        |_, _, _| {},
+    )
+    .unwrap();
+    let report = report.unwrap();
+    let expectation = r#"namespace N(65536);
+    col fixed cnt(i) { i };
+    col witness X;
+    [N::X] in [N::cnt];
+"#;
+    assert_eq!(optimized.to_string(), expectation);
+    assert_eq!(report.passes.len(), PASSES.len());
+    let removed = &report.passes.last().unwrap().removed_columns;
+    assert_eq!(removed.len(), 1);
+    assert_eq!(removed[0].name, "N::one");
+    assert_eq!(removed[0].kind, "fixed");
+
+    let options = OptimizerOptions {
+        disabled_passes: ["remove_constant_fixed_columns".to_string()].into(),
+        report: true,
+        ..Default::default()
+    };
+    let mut passes_run = vec![];
+    let (optimized, report) = optimize_with_options(
+        analyze_string::<GoldilocksField>(input).unwrap(),
+        &options,
+        |index, name, _| passes_run.push((index, name.to_string())),
+    )
+    .unwrap();
+    let report = report.unwrap();
+    let expectation = r#"namespace N(65536);
+    col fixed one = [1_fe]*;
+    col fixed cnt(i) { i };
+    col witness X;
+    [N::X * N::one] in [N::cnt];
+"#;
+    assert_eq!(optimized.to_string(), expectation);
+    assert!(!report.passes[1].enabled);
+    assert_eq!(passes_run.len(), PASSES.len() - 1 - OPT_IN_PASSES.len());
+    assert!(passes_run.iter().all(|(index, _)| *index != 1));
+}
+
+#[test]
+fn disable_every_run_of_a_pass() {
+    let input = r#"namespace N(65536);
+    col witness X;
+    X * 1 = 0;
+"#;
+    let options = OptimizerOptions {
+        disabled_passes: ["simplify_identities".to_string()].into(),
+        ..Default::default()
+    };
+    let mut passes_run = vec![];
+    let (_, report) = optimize_with_options(
+        analyze_string::<GoldilocksField>(input).unwrap(),
+        &options,
+        |_, name, _| passes_run.push(name.to_string()),
+    )
+    .unwrap();
+    assert!(report.is_none());
+    assert!(!passes_run.is_empty());
+    assert!(passes_run.iter().all(|name| name != "simplify_identities"));
+
+    let options = OptimizerOptions {
+        disabled_passes: ["no_such_pass".to_string()].into(),
+        ..Default::default()
+    };
+    let result = optimize_with_options(
+        analyze_string::<GoldilocksField>(input).unwrap(),
+        &options,
+        |_, _, _| {},
+    );
+    assert_eq!(
+        result.err(),
+        Some("Unknown optimizer pass: no_such_pass".to_string())
+    );
+}
+
+#[test]
+fn opt_in_passes() {
+    let input = r#"namespace N(65536);
+    col witness a;
+    col witness x;
+    x = a + 1;
+    x * a = 2;
+"#;
+    let run = |options: &OptimizerOptions| {
+        let mut passes_run = vec![];
+        let (optimized, _) = optimize_with_options(
+            analyze_string::<GoldilocksField>(input).unwrap(),
+            options,
+            |_, name, _| passes_run.push(name.to_string()),
+        )
+        .unwrap();
+        (optimized.commitment_count(), passes_run)
+    };
+
+    // Opt-in passes do not run by default.
+    let (witness_count, passes_run) = run(&Default::default());
+    assert_eq!(witness_count, 2);
+    assert!(passes_run
+        .iter()
+        .all(|name| !OPT_IN_PASSES.contains(&name.as_str())));
+
+    let enabled_passes: std::collections::BTreeSet<_> =
+        OPT_IN_PASSES.iter().map(|p| p.to_string()).collect();
+    let (witness_count, passes_run) = run(&OptimizerOptions {
+        enabled_passes: enabled_passes.clone(),
+        ..Default::default()
+    });
+    assert_eq!(witness_count, 1);
+    assert_eq!(passes_run.len(), PASSES.len());
+
+    // Disabling a pass takes precedence over enabling it.
+    let (witness_count, _) = run(&OptimizerOptions {
+        enabled_passes,
+        disabled_passes: ["substitute_determined_witness_columns".to_string()].into(),
+        ..Default::default()
+    });
+    assert_eq!(witness_count, 2);
+
+    let options = OptimizerOptions {
+        enabled_passes: ["no_such_pass".to_string()].into(),
+        ..Default::default()
+    };
+    let result = optimize_with_options(
+        analyze_string::<GoldilocksField>(input).unwrap(),
+        &options,
+        |_, _, _| {},
+    );
+    assert_eq!(
+        result.err(),
+        Some("Unknown optimizer pass: no_such_pass".to_string())
+    );
+}
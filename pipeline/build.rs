@@ -11,6 +11,8 @@ fn main() {
     build_reparse_test("asm", "asm");
     build_reparse_test("pil", "pil");
     build_reparse_test("asm", "std");
+    build_translation_validation_test("asm");
+    build_translation_validation_test("pil");
 }
 
 fn build_book_tests(kind: &str) {
@@ -21,6 +23,10 @@ fn build_reparse_test(kind: &str, dir: &str) {
     build_tests(kind, dir, "", "reparse")
 }
 
+fn build_translation_validation_test(kind: &str) {
+    build_tests(kind, kind, "", "translation_validation")
+}
+
 #[allow(clippy::print_stdout)]
 fn build_tests(kind: &str, dir: &str, sub_dir: &str, name: &str) {
     let sub_dir = if sub_dir.is_empty() {
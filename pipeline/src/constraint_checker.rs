@@ -0,0 +1,360 @@
+//! A simple checker that evaluates all identities of an analyzed PIL file
+//! on concrete fixed and witness column values.
+//!
+//! This is not meant as a replacement for a backend, it is rather a debugging
+//! and testing tool that works independently of any proof system.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use itertools::Itertools;
+use num_traits::Zero;
+use powdr_ast::analyzed::{
+    AlgebraicBinaryOperation, AlgebraicBinaryOperator, AlgebraicExpression,
+    AlgebraicUnaryOperation, AlgebraicUnaryOperator, Analyzed, Identity, SelectedExpressions,
+};
+use powdr_ast::parsed::visitor::{AllChildren, Children};
+use powdr_executor::{constant_evaluator::VariablySizedColumn, witgen::extract_publics};
+use powdr_number::{DegreeType, FieldElement};
+
+/// Checks that all identities in `pil` hold on the given fixed and witness columns.
+/// Returns a list of all violated identities (with the first violating row, if applicable).
+///
+/// Connect identities and identities referencing challenges are not supported and
+/// are skipped with a warning.
+pub fn check_constraints<T: FieldElement>(
+    pil: &Analyzed<T>,
+    fixed: &[(String, VariablySizedColumn<T>)],
+    witness: &[(String, Vec<T>)],
+) -> Result<(), Vec<String>> {
+    let errors = violated_identities(pil, fixed, witness)
+        .into_iter()
+        .map(|(_, error)| error)
+        .collect::<Vec<_>>();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Like [`check_constraints`], but returns the ID of each violated identity
+/// together with the error.
+pub fn violated_identities<T: FieldElement>(
+    pil: &Analyzed<T>,
+    fixed: &[(String, VariablySizedColumn<T>)],
+    witness: &[(String, Vec<T>)],
+) -> Vec<(u64, String)> {
+    let checker = ConstraintChecker::new(pil, fixed, witness);
+    pil.identities_with_inlined_intermediate_polynomials()
+        .iter()
+        .filter_map(|identity| {
+            checker
+                .check_identity(identity)
+                .err()
+                .map(|error| (identity.id(), error))
+        })
+        .collect()
+}
+
+/// Evaluates `expression` on the first `size` rows of the given fixed and witness
+/// columns. References to intermediate columns of `pil` are evaluated using their definitions.
+pub(crate) fn evaluate_on_rows<T: FieldElement>(
+    pil: &Analyzed<T>,
+    fixed: &[(String, VariablySizedColumn<T>)],
+    witness: &[(String, Vec<T>)],
+    expression: &AlgebraicExpression<T>,
+    size: DegreeType,
+) -> Result<Vec<T>, String> {
+    let checker = ConstraintChecker::new(pil, fixed, witness);
+    (0..size)
+        .map(|row| checker.evaluate(expression, row, size))
+        .collect()
+}
+
+struct ConstraintChecker<'a, T> {
+    fixed: HashMap<&'a str, &'a VariablySizedColumn<T>>,
+    witness: HashMap<&'a str, &'a [T]>,
+    intermediates: HashMap<String, &'a AlgebraicExpression<T>>,
+    publics: BTreeMap<String, Option<T>>,
+}
+
+impl<'a, T: FieldElement> ConstraintChecker<'a, T> {
+    fn new(
+        pil: &'a Analyzed<T>,
+        fixed: &'a [(String, VariablySizedColumn<T>)],
+        witness: &'a [(String, Vec<T>)],
+    ) -> Self {
+        Self {
+            fixed: fixed
+                .iter()
+                .map(|(name, col)| (name.as_str(), col))
+                .collect(),
+            witness: witness
+                .iter()
+                .map(|(name, col)| (name.as_str(), col.as_slice()))
+                .collect(),
+            intermediates: pil
+                .intermediate_polys_in_source_order()
+                .flat_map(|(symbol, definitions)| {
+                    symbol
+                        .array_elements()
+                        .map(|(name, _)| name)
+                        .zip(definitions)
+                })
+                .collect(),
+            publics: extract_publics(witness, pil).into_iter().collect(),
+        }
+    }
+
+    fn check_identity(&self, identity: &Identity<T>) -> Result<(), String> {
+        if identity
+            .children()
+            .flat_map(|e| e.all_children())
+            .any(|e| matches!(e, AlgebraicExpression::Challenge(_)))
+        {
+            log::warn!("Skipping identity referencing challenges: {identity}");
+            return Ok(());
+        }
+        match identity {
+            Identity::Polynomial(i) => {
+                let size = self.size_of([&i.expression])?;
+                (0..size).try_for_each(|row| {
+                    let value = self.evaluate(&i.expression, row, size)?;
+                    if value.is_zero() {
+                        Ok(())
+                    } else {
+                        Err(format!(
+                            "Identity {identity} ({}) is not satisfied in row {row}: {value} != 0",
+                            i.source
+                        ))
+                    }
+                })
+            }
+            Identity::Lookup(i) => {
+                let right = self
+                    .selected_tuples(&i.right)?
+                    .into_iter()
+                    .collect::<HashSet<_>>();
+                match self
+                    .selected_tuples(&i.left)?
+                    .into_iter()
+                    .find(|tuple| !right.contains(tuple))
+                {
+                    None => Ok(()),
+                    Some(tuple) => Err(format!(
+                        "Lookup {identity} ({}) is not satisfied: ({}) is not contained in the right-hand side",
+                        i.source,
+                        tuple.iter().format(", ")
+                    )),
+                }
+            }
+            Identity::Permutation(i) => {
+                let mut counts: HashMap<Vec<T>, i64> = HashMap::new();
+                for tuple in self.selected_tuples(&i.left)? {
+                    *counts.entry(tuple).or_default() += 1;
+                }
+                for tuple in self.selected_tuples(&i.right)? {
+                    *counts.entry(tuple).or_default() -= 1;
+                }
+                match counts.into_iter().find(|(_, count)| *count != 0) {
+                    None => Ok(()),
+                    Some((tuple, count)) => Err(format!(
+                        "Permutation {identity} ({}) is not satisfied: ({}) has a multiplicity mismatch of {count}",
+                        i.source,
+                        tuple.iter().format(", ")
+                    )),
+                }
+            }
+            Identity::Connect(_) => {
+                log::warn!("Skipping connect identity: {identity}");
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns all tuples of the selected expressions on rows where the selector is non-zero.
+    fn selected_tuples(&self, selected: &SelectedExpressions<T>) -> Result<Vec<Vec<T>>, String> {
+        let size = self.size_of(selected.children())?;
+        (0..size)
+            .filter_map(|row| {
+                match self.evaluate(&selected.selector, row, size) {
+                    Ok(selector) if selector.is_zero() => return None,
+                    Err(e) => return Some(Err(e)),
+                    _ => {}
+                }
+                Some(
+                    selected
+                        .expressions
+                        .iter()
+                        .map(|e| self.evaluate(e, row, size))
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+
+    /// Determines the number of rows the given expressions have to be evaluated on,
+    /// which is the length of the referenced witness columns or, if there are none,
+    /// the unique size of the referenced fixed columns.
+    fn size_of<'b>(
+        &self,
+        exprs: impl IntoIterator<Item = &'b AlgebraicExpression<T>>,
+    ) -> Result<DegreeType, String>
+    where
+        T: 'b,
+    {
+        let references = exprs
+            .into_iter()
+            .flat_map(|e| e.all_children())
+            .filter_map(|e| match e {
+                AlgebraicExpression::Reference(r) => Some(r.name.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let witness_sizes = references
+            .iter()
+            .filter_map(|name| self.witness.get(name))
+            .map(|col| col.len() as DegreeType)
+            .unique()
+            .collect::<Vec<_>>();
+        match &witness_sizes[..] {
+            [size] => return Ok(*size),
+            [] => {}
+            _ => {
+                return Err(format!(
+                    "Referenced witness columns have different sizes: {}",
+                    witness_sizes.iter().format(", ")
+                ))
+            }
+        }
+        let fixed_sizes = references
+            .iter()
+            .filter_map(|name| self.fixed.get(name))
+            .map(|col| col.available_sizes())
+            .unique()
+            .collect::<Vec<_>>();
+        match &fixed_sizes[..] {
+            [] => Ok(1),
+            [sizes] if sizes.len() == 1 => Ok(*sizes.iter().next().unwrap()),
+            _ => Err(format!(
+                "Could not determine a unique size for the expressions referencing {}",
+                references.iter().format(", ")
+            )),
+        }
+    }
+
+    fn evaluate(
+        &self,
+        expr: &AlgebraicExpression<T>,
+        row: DegreeType,
+        size: DegreeType,
+    ) -> Result<T, String> {
+        Ok(match expr {
+            AlgebraicExpression::Reference(r) => {
+                let row = ((row + r.next as DegreeType) % size) as usize;
+                if let Some(col) = self.witness.get(r.name.as_str()) {
+                    col[row]
+                } else if let Some(col) = self.fixed.get(r.name.as_str()) {
+                    col.get_by_size(size).ok_or_else(|| {
+                        format!("Fixed column {} is not available in size {size}", r.name)
+                    })?[row]
+                } else if let Some(definition) = self.intermediates.get(&r.name) {
+                    self.evaluate(definition, row as DegreeType, size)?
+                } else {
+                    return Err(format!("Column {} not found", r.name));
+                }
+            }
+            AlgebraicExpression::PublicReference(name) => self
+                .publics
+                .get(name)
+                .cloned()
+                .flatten()
+                .ok_or_else(|| format!("Public {name} not available"))?,
+            AlgebraicExpression::Challenge(_) => unreachable!(),
+            AlgebraicExpression::Number(n) => *n,
+            AlgebraicExpression::BinaryOperation(AlgebraicBinaryOperation { left, op, right }) => {
+                let left = self.evaluate(left, row, size)?;
+                let right = self.evaluate(right, row, size)?;
+                match op {
+                    AlgebraicBinaryOperator::Add => left + right,
+                    AlgebraicBinaryOperator::Sub => left - right,
+                    AlgebraicBinaryOperator::Mul => left * right,
+                    AlgebraicBinaryOperator::Pow => left.pow(right.to_integer()),
+                }
+            }
+            AlgebraicExpression::UnaryOperation(AlgebraicUnaryOperation { op, expr }) => {
+                let value = self.evaluate(expr, row, size)?;
+                match op {
+                    AlgebraicUnaryOperator::Minus => -value,
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use powdr_executor::constant_evaluator;
+    use powdr_number::GoldilocksField;
+    use powdr_pil_analyzer::analyze_string;
+
+    use super::check_constraints;
+
+    fn check(input: &str, witness: &[(&str, Vec<u64>)]) -> Result<(), Vec<String>> {
+        let pil = analyze_string::<GoldilocksField>(input).unwrap();
+        let fixed = constant_evaluator::generate(&pil);
+        let witness = witness
+            .iter()
+            .map(|(name, values)| {
+                (
+                    name.to_string(),
+                    values.iter().map(|v| GoldilocksField::from(*v)).collect(),
+                )
+            })
+            .collect::<Vec<_>>();
+        check_constraints(&pil, &fixed, &witness)
+    }
+
+    #[test]
+    fn polynomial_identity() {
+        let input = "namespace N(4);
+            col witness x;
+            x' = x + 1;
+        ";
+        assert!(check(input, &[("N::x", vec![0, 1, 2, 3])]).is_err());
+        let input = "namespace N(4);
+            col fixed LAST = [0, 0, 0, 1];
+            col witness x;
+            (1 - LAST) * (x' - x - 1) = 0;
+        ";
+        assert!(check(input, &[("N::x", vec![0, 1, 2, 3])]).is_ok());
+        let errors = check(input, &[("N::x", vec![0, 1, 5, 3])]).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("row 1"));
+    }
+
+    #[test]
+    fn lookup_and_permutation() {
+        let input = "namespace N(4);
+            col fixed BYTE = [0, 1, 2, 3];
+            col witness x, y;
+            [x] in [BYTE];
+            [x] is [y];
+        ";
+        assert!(check(
+            input,
+            &[("N::x", vec![3, 2, 1, 0]), ("N::y", vec![0, 1, 2, 3])]
+        )
+        .is_ok());
+        assert!(check(
+            input,
+            &[("N::x", vec![3, 2, 1, 1]), ("N::y", vec![0, 1, 2, 3])]
+        )
+        .is_err());
+        assert!(check(
+            input,
+            &[("N::x", vec![4, 2, 1, 0]), ("N::y", vec![0, 1, 2, 4])]
+        )
+        .is_err());
+    }
+}
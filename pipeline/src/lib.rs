@@ -2,9 +2,11 @@
 
 #![deny(clippy::print_stdout)]
 
+pub mod constraint_checker;
 pub mod pipeline;
 pub mod test_runner;
 pub mod test_util;
+pub mod translation_validation;
 pub mod util;
 pub mod verify;
 
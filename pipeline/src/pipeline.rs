@@ -1,9 +1,9 @@
 use std::{
     borrow::Borrow,
-    collections::HashMap,
+    collections::{BTreeSet, HashMap},
     fmt::Display,
     fs,
-    io::{self, BufReader},
+    io::{self, BufReader, BufWriter},
     path::{Path, PathBuf},
     rc::Rc,
     sync::Arc,
@@ -19,7 +19,7 @@ use powdr_ast::{
     object::MachineInstanceGraph,
     parsed::{asm::ASMProgram, PILFile},
 };
-use powdr_backend::{Backend, BackendOptions, BackendType, Proof};
+use powdr_backend::{Backend, BackendOptions, BackendType, Proof, ProofContainer};
 use powdr_executor::{
     constant_evaluator::{self, VariablySizedColumn},
     witgen::{
@@ -28,6 +28,7 @@ use powdr_executor::{
     },
 };
 use powdr_number::{write_polys_csv_file, CsvRenderMode, FieldElement, ReadWrite};
+use powdr_pilopt::{OptimizationReport, OptimizerOptions};
 use powdr_schemas::SerializedAnalyzed;
 
 use crate::{
@@ -69,12 +70,16 @@ pub struct Artifacts<T: FieldElement> {
     analyzed_pil: Option<Analyzed<T>>,
     /// An optimized .pil file.
     optimized_pil: Option<Arc<Analyzed<T>>>,
+    /// The changes made by each optimizer pass.
+    optimization_report: Option<OptimizationReport<T>>,
     /// Fully evaluated fixed columns.
     fixed_cols: Option<Arc<VariablySizedColumns<T>>>,
     /// Generated witnesses.
     witness: Option<Arc<Columns<T>>>,
     /// Instantiated backend.
     backend: Option<Box<dyn Backend<T>>>,
+    /// The hash of the verification key of the backend, or None inside if it has none.
+    verification_key_hash: Option<Option<String>>,
     /// The proof (if successful).
     proof: Option<Proof>,
 }
@@ -102,6 +107,16 @@ struct Arguments<T: FieldElement> {
     backend: Option<BackendType>,
     /// Backend options
     backend_options: BackendOptions,
+    /// The maximum degree of polynomial identities. If None, the limit of the backend is used.
+    max_constraint_degree: Option<usize>,
+    /// Names of optimizer passes that are not run.
+    disabled_optimizer_passes: BTreeSet<String>,
+    /// Names of opt-in optimizer passes that are run.
+    enabled_optimizer_passes: BTreeSet<String>,
+    /// Names of optimizer passes after which the PIL is written to the output directory.
+    dump_after_optimizer_passes: BTreeSet<String>,
+    /// Whether to compute a report of the changes made by each optimizer pass.
+    optimization_report: bool,
     /// CSV render mode for witness generation.
     csv_render_mode: CsvRenderMode,
     /// Whether to export the witness as a CSV file.
@@ -160,11 +175,13 @@ impl<T: FieldElement> Clone for Artifacts<T> {
             pil_string: self.pil_string.clone(),
             analyzed_pil: self.analyzed_pil.clone(),
             optimized_pil: self.optimized_pil.clone(),
+            optimization_report: self.optimization_report.clone(),
             fixed_cols: self.fixed_cols.clone(),
             witness: self.witness.clone(),
             proof: self.proof.clone(),
             // Backend is not cloneable, so we clear it instead
             backend: None,
+            verification_key_hash: None,
         }
     }
 }
@@ -342,6 +359,55 @@ impl<T: FieldElement> Pipeline<T> {
         self
     }
 
+    /// Sets the maximum degree of polynomial identities. Identities of higher
+    /// degree are split into several using new witness columns when optimizing
+    /// the PIL. Without it, the limit of the backend is used, with the options
+    /// recorded in the verification key if one is set. So either has to be set
+    /// before the optimized PIL is computed.
+    pub fn with_max_constraint_degree(mut self, max_degree: Option<usize>) -> Self {
+        self.arguments.max_constraint_degree = max_degree;
+        self
+    }
+
+    /// Disables the given optimizer passes, see [`powdr_pilopt::PASSES`].
+    /// Disabling a pass disables every run of it.
+    pub fn with_disabled_optimizer_passes(
+        mut self,
+        passes: impl IntoIterator<Item = String>,
+    ) -> Self {
+        self.arguments.disabled_optimizer_passes.extend(passes);
+        self
+    }
+
+    /// Enables the given opt-in optimizer passes, see [`powdr_pilopt::OPT_IN_PASSES`].
+    /// Disabling a pass takes precedence over enabling it.
+    pub fn with_enabled_optimizer_passes(
+        mut self,
+        passes: impl IntoIterator<Item = String>,
+    ) -> Self {
+        self.arguments.enabled_optimizer_passes.extend(passes);
+        self
+    }
+
+    /// Writes the PIL after every run of the given optimizer passes to the
+    /// output directory, as `<name>_opt_<index>_<pass>.pil` with the index of
+    /// the pass in [`powdr_pilopt::PASSES`].
+    pub fn with_dump_after_optimizer_passes(
+        mut self,
+        passes: impl IntoIterator<Item = String>,
+    ) -> Self {
+        self.arguments.dump_after_optimizer_passes.extend(passes);
+        self
+    }
+
+    /// Computes a report of the columns and identities each optimizer pass
+    /// removed or added, see [`Self::optimization_report`]. It is written to the
+    /// output directory as `<name>_opt_report.json`.
+    pub fn with_optimization_report(mut self) -> Self {
+        self.arguments.optimization_report = true;
+        self
+    }
+
     pub fn with_setup_file(mut self, setup_file: Option<PathBuf>) -> Self {
         self.arguments.setup_file = setup_file;
         self.artifact.backend = None;
@@ -583,6 +649,19 @@ impl<T: FieldElement> Pipeline<T> {
         Ok(())
     }
 
+    fn maybe_write_optimization_report(
+        &self,
+        report: &OptimizationReport<T>,
+    ) -> Result<(), Vec<String>> {
+        if let Some(path) = self.path_if_should_write(|name| format!("{name}_opt_report.json"))? {
+            let file = fs::File::create(&path)
+                .map_err(|e| vec![format!("Error creating {}: {e}", path.to_str().unwrap())])?;
+            serde_json::to_writer_pretty(BufWriter::new(file), report)
+                .map_err(|e| vec![format!("Error writing {}: {e}", path.to_str().unwrap())])?;
+        }
+        Ok(())
+    }
+
     fn maybe_write_constants(
         &self,
         constants: &VariablySizedColumns<T>,
@@ -651,14 +730,15 @@ impl<T: FieldElement> Pipeline<T> {
         Ok(())
     }
 
-    fn maybe_write_proof(&self, proof: &Proof) -> Result<(), Vec<String>> {
+    fn maybe_write_proof(&mut self, proof: &Proof) -> Result<(), Vec<String>> {
         let fname = if self.arguments.existing_proof_file.is_some() {
             "proof_aggr.bin"
         } else {
             "proof.bin"
         };
         if let Some(path) = self.path_if_should_write(|name| format!("{name}_{fname}"))? {
-            fs::write(path, proof).unwrap();
+            let container = self.proof_container(proof.clone())?;
+            fs::write(path, container.to_bytes()).unwrap();
         }
 
         Ok(())
@@ -919,11 +999,61 @@ impl<T: FieldElement> Pipeline<T> {
             return Ok(optimized_pil.clone());
         }
 
+        let unknown_passes = self
+            .arguments
+            .dump_after_optimizer_passes
+            .iter()
+            .filter(|name| !powdr_pilopt::PASSES.contains(&name.as_str()))
+            .map(|name| format!("Unknown optimizer pass: {name}"))
+            .collect::<Vec<_>>();
+        if !unknown_passes.is_empty() {
+            return Err(unknown_passes);
+        }
+        // The backend is created with the options recorded in the verification
+        // key, so they also determine the degree bound.
+        let verification_key = match &self.arguments.vkey_file {
+            Some(path) if self.arguments.max_constraint_degree.is_none() => Some(
+                fs::read(path)
+                    .map_err(|e| vec![format!("Error reading {}: {e}", path.to_str().unwrap())])?,
+            ),
+            _ => None,
+        };
         self.compute_analyzed_pil()?;
         let analyzed_pil = self.artifact.analyzed_pil.take().unwrap();
 
         self.log("Optimizing pil...");
-        let optimized = powdr_pilopt::optimize(analyzed_pil);
+        let max_degree = self.arguments.max_constraint_degree.or_else(|| {
+            self.arguments.backend.and_then(|backend| {
+                backend.max_constraint_degree(
+                    &self.arguments.backend_options,
+                    verification_key.as_deref(),
+                )
+            })
+        });
+        let options = OptimizerOptions {
+            max_degree,
+            disabled_passes: self.arguments.disabled_optimizer_passes.clone(),
+            enabled_passes: self.arguments.enabled_optimizer_passes.clone(),
+            report: self.arguments.optimization_report,
+        };
+        let mut dump_result = Ok(());
+        let (mut optimized, report) =
+            powdr_pilopt::optimize_with_options(analyzed_pil, &options, |index, name, pil| {
+                if dump_result.is_ok() && self.arguments.dump_after_optimizer_passes.contains(name)
+                {
+                    dump_result = self.maybe_write_pil(pil, &format!("_opt_{index}_{name}"));
+                }
+            })
+            .map_err(|e| vec![e])?;
+        dump_result?;
+        if let Some(report) = &report {
+            self.maybe_write_optimization_report(report)?;
+        }
+        self.artifact.optimization_report = report;
+        if let Some(max_degree) = max_degree {
+            self.log(&format!("Reducing constraint degree to {max_degree}..."));
+            powdr_pilopt::reduce_degree(&mut optimized, max_degree);
+        }
         self.maybe_write_pil(&optimized, "_opt")?;
         self.maybe_write_pil_object(&optimized, "_opt")?;
 
@@ -936,6 +1066,66 @@ impl<T: FieldElement> Pipeline<T> {
         Ok(self.artifact.optimized_pil.as_ref().unwrap().clone())
     }
 
+    /// Returns the changes made by each optimizer pass, if the optimized PIL
+    /// was computed by this pipeline with [`Self::with_optimization_report`].
+    pub fn optimization_report(&self) -> Option<&OptimizationReport<T>> {
+        self.artifact.optimization_report.as_ref()
+    }
+
+    /// Checks that the optimizer did not change the semantics of the PIL on the
+    /// inputs of this pipeline. Generates a witness for the unoptimized PIL in
+    /// addition to the witness of this pipeline and checks the identities of the
+    /// unoptimized PIL, see [`crate::translation_validation`].
+    ///
+    /// The pipeline has to be created from an asm or PIL source.
+    pub fn validate_optimization(&mut self) -> Result<(), Vec<String>> {
+        if self.artifact.asm_file_path.is_none()
+            && self.artifact.asm_string.is_none()
+            && self.artifact.pil_file_path.is_none()
+            && self.artifact.pil_string.is_none()
+        {
+            return Err(vec![
+                "Validating the optimization requires an asm or PIL source.".to_string(),
+            ]);
+        }
+        let mut unoptimized = self
+            .clone()
+            .with_disabled_optimizer_passes(powdr_pilopt::PASSES.iter().map(|p| p.to_string()));
+        unoptimized.artifact = Artifacts {
+            asm_file_path: self.artifact.asm_file_path.clone(),
+            asm_string: self.artifact.asm_string.clone(),
+            pil_file_path: self.artifact.pil_file_path.clone(),
+            pil_string: self.artifact.pil_string.clone(),
+            ..Default::default()
+        };
+        unoptimized.output_dir = None;
+        unoptimized.arguments.dump_after_optimizer_passes.clear();
+
+        self.log("Generating witness for the unoptimized PIL...");
+        let original = unoptimized.compute_analyzed_pil()?.clone();
+        let original_witness = unoptimized.compute_witness()?;
+        let original_fixed = unoptimized.fixed_cols()?;
+
+        if self.artifact.optimized_pil.is_none() {
+            self.arguments.optimization_report = true;
+        }
+        let witness = self.compute_witness()?;
+        let report = self.optimization_report().ok_or_else(|| {
+            vec![
+                "Validating the optimization requires the report of the optimizer run, see `with_optimization_report`."
+                    .to_string(),
+            ]
+        })?;
+        self.log("Checking the identities of the unoptimized PIL...");
+        crate::translation_validation::validate(
+            &original,
+            &original_fixed,
+            &original_witness,
+            &witness,
+            report,
+        )
+    }
+
     pub fn compute_fixed_cols(&mut self) -> Result<Arc<VariablySizedColumns<T>>, Vec<String>> {
         if let Some(ref fixed_cols) = self.artifact.fixed_cols {
             return Ok(fixed_cols.clone());
@@ -1098,6 +1288,7 @@ impl<T: FieldElement> Pipeline<T> {
         self.log(&format!("Setup took {}s", start.elapsed().as_secs_f32()));
 
         self.artifact.backend = Some(backend);
+        self.artifact.verification_key_hash = None;
         Ok(self.artifact.backend.as_deref_mut().unwrap())
     }
 
@@ -1114,7 +1305,13 @@ impl<T: FieldElement> Pipeline<T> {
             .arguments
             .existing_proof_file
             .as_ref()
-            .map(|path| fs::read(path).unwrap());
+            .map(|path| fs::read(path).unwrap())
+            .map(|proof| match ProofContainer::is_container(&proof) {
+                true => ProofContainer::from_bytes(&proof).map(|container| container.proof),
+                false => Ok(proof),
+            })
+            .transpose()
+            .map_err(backend_error)?;
 
         self.setup_backend()?;
 
@@ -1146,6 +1343,30 @@ impl<T: FieldElement> Pipeline<T> {
         Ok(self.artifact.proof.as_ref().unwrap())
     }
 
+    /// Wraps a proof generated by this pipeline in a container that records
+    /// the backend, the PIL, the verification key and the public values.
+    pub fn proof_container(&mut self, proof: Proof) -> Result<ProofContainer, Vec<String>> {
+        let pil = self.compute_optimized_pil()?;
+        let publics = self
+            .publics()?
+            .into_iter()
+            .map(|(name, value)| value.ok_or_else(|| vec![format!("Unknown public value {name}")]))
+            .collect::<Result<Vec<_>, _>>()?;
+        let backend = self
+            .arguments
+            .backend
+            .ok_or_else(|| vec!["No backend selected".to_string()])?;
+        let verification_key_hash = self.verification_key_hash()?;
+        Ok(ProofContainer::new(
+            backend,
+            self.arguments.backend_options.clone(),
+            &pil,
+            verification_key_hash,
+            &publics,
+            proof,
+        ))
+    }
+
     pub fn output_dir(&self) -> &Option<PathBuf> {
         &self.output_dir
     }
@@ -1201,7 +1422,24 @@ impl<T: FieldElement> Pipeline<T> {
         }
     }
 
+    /// Verifies a proof. If the proof is a [ProofContainer], its backend is
+    /// used unless one is selected, in which case the container must record
+    /// the same backend and options. It is also checked that the container
+    /// matches this pipeline and the public values.
     pub fn verify(&mut self, proof: &[u8], instances: &[Vec<T>]) -> Result<(), Vec<String>> {
+        let container;
+        let proof = if ProofContainer::is_container(proof) {
+            container = ProofContainer::from_bytes(proof).map_err(backend_error)?;
+            self.check_proof_container(&container, instances)?;
+            container.proof.as_slice()
+        } else if self.arguments.backend.is_none() {
+            return Err(vec![
+                "No backend selected and the proof does not record its backend".to_string(),
+            ]);
+        } else {
+            proof
+        };
+
         let backend = self.setup_backend()?;
 
         let start = Instant::now();
@@ -1218,6 +1456,103 @@ impl<T: FieldElement> Pipeline<T> {
         }
     }
 
+    /// Selects the backend of the container if none is selected and checks
+    /// that the container was generated for this field, backend, backend
+    /// options, PIL, verification key and public values.
+    fn check_proof_container(
+        &mut self,
+        container: &ProofContainer,
+        instances: &[Vec<T>],
+    ) -> Result<(), Vec<String>> {
+        if T::known_field() != Some(container.field) {
+            return Err(vec![format!(
+                "The proof was generated for the {} field",
+                container.field
+            )]);
+        }
+
+        match self.arguments.backend {
+            None => {
+                self.arguments.backend = Some(container.backend_type().map_err(backend_error)?);
+                self.arguments.backend_options = container.backend_options.clone();
+                self.artifact.backend = None;
+            }
+            Some(backend) if backend.to_string() != container.backend => {
+                return Err(vec![format!(
+                    "The proof was generated by backend {}, not {backend}",
+                    container.backend
+                )]);
+            }
+            Some(_) if self.arguments.backend_options != container.backend_options => {
+                return Err(vec![format!(
+                    "The proof was generated with backend options \"{}\", not \"{}\"",
+                    container.backend_options, self.arguments.backend_options
+                )]);
+            }
+            Some(_) => {}
+        }
+
+        let pil = self.compute_optimized_pil()?;
+        if ProofContainer::pil_hash(&pil) != container.pil_hash {
+            return Err(vec![
+                "The proof was generated for a different PIL".to_string()
+            ]);
+        }
+
+        if container.publics::<T>().map_err(backend_error)? != instances.concat() {
+            return Err(vec![
+                "The public values do not match the ones of the proof".to_string()
+            ]);
+        }
+
+        // A container without a hash is only accepted by backends without a
+        // verification key, so the check cannot be bypassed by removing it.
+        match (
+            &container.verification_key_hash,
+            self.verification_key_hash()?,
+        ) {
+            (Some(hash), Some(expected)) if *hash != expected => {
+                return Err(vec![
+                    "The proof was generated with a different verification key".to_string(),
+                ]);
+            }
+            (None, Some(_)) => {
+                return Err(vec![
+                    "The proof does not record the hash of its verification key".to_string(),
+                ]);
+            }
+            (Some(_), None) => {
+                return Err(vec![format!(
+                    "The proof records a verification key, but backend {} has none",
+                    container.backend
+                )]);
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Returns the hash of the verification key of the backend, if it has one.
+    /// Exporting the key can be expensive, e.g. Halo2 generates it, so the
+    /// hash is only computed once per backend.
+    fn verification_key_hash(&mut self) -> Result<Option<String>, Vec<String>> {
+        if let Some(hash) = &self.artifact.verification_key_hash {
+            return Ok(hash.clone());
+        }
+        let mut verification_key = Vec::new();
+        let hash = match self
+            .setup_backend()?
+            .export_verification_key(&mut verification_key)
+        {
+            Ok(()) => Some(ProofContainer::verification_key_hash(&verification_key)),
+            Err(powdr_backend::Error::NoVerificationAvailable) => None,
+            Err(e) => return Err(backend_error(e)),
+        };
+        self.artifact.verification_key_hash = Some(hash.clone());
+        Ok(hash)
+    }
+
     pub fn export_backend_setup<W: io::Write>(&mut self, mut writer: W) -> Result<(), Vec<String>> {
         let backend = self.setup_backend()?;
         backend.export_setup(&mut writer).map_err(|e| match e {
@@ -1241,3 +1576,10 @@ fn output_pil_analysis_errors(errors: Vec<powdr_parser_util::Error>) -> Vec<Stri
         })
         .collect()
 }
+
+fn backend_error(e: powdr_backend::Error) -> Vec<String> {
+    match e {
+        powdr_backend::Error::BackendError(e) => vec![e],
+        e => vec![e.to_string()],
+    }
+}
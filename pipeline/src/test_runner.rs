@@ -1,22 +1,33 @@
-use std::{collections::BTreeSet, path::PathBuf, str::FromStr};
+use std::{
+    collections::{BTreeSet, HashMap},
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    str::FromStr,
+    sync::Arc,
+};
 
 use itertools::Itertools;
 
 use powdr_ast::{
-    analyzed::{Analyzed, FunctionValueDefinition},
+    analyzed::{AlgebraicExpression, Analyzed, FunctionValueDefinition, SolvedTraitImpls, Symbol},
     parsed::{
         asm::SymbolPath,
         types::{FunctionType, Type},
     },
 };
 use powdr_number::FieldElement;
-use powdr_pil_analyzer::evaluator::{self, SymbolLookup};
+use powdr_pil_analyzer::evaluator::{self, Definitions, EvalError, SymbolLookup, Value};
 
-use crate::Pipeline;
+use crate::{constraint_checker::check_constraints, pipeline::Columns, Pipeline};
 
 /// Executes all functions in the given file that start with `test_` and are
 /// inside a module called `test` (or a sub-module thereof).
 ///
+/// In contrast to `run_tests`, the tests can use the query functions in `std::test`
+/// to run witness generation on the program in the file and inspect the result.
+/// Witness generation always starts at the entry point of the file, i.e. the
+/// `Main` machine, and the inputs passed to it are provided on prover input channel 0.
+///
 /// @param include_std_tests: Whether to run the tests inside the standard library.
 pub fn run_from_file<F: FieldElement>(
     input: &str,
@@ -24,11 +35,10 @@ pub fn run_from_file<F: FieldElement>(
 ) -> Result<usize, Vec<String>> {
     let mut pipeline = Pipeline::<F>::default().from_file(PathBuf::from(&input));
 
-    let analyzed = pipeline.compute_analyzed_pil()?;
-    run_tests::<F>(analyzed, include_std_tests)
+    let analyzed = pipeline.compute_analyzed_pil()?.clone();
+    run_tests_impl::<F>(&analyzed, include_std_tests, Some(pipeline))
 }
 
-#[allow(clippy::print_stdout)]
 /// Executes all functions in the given file that start with `test_` and are
 /// inside a module called `test` (or a sub-module thereof).
 ///
@@ -39,9 +49,20 @@ pub fn run_tests<F: FieldElement>(
     analyzed: &Analyzed<F>,
     include_std_tests: bool,
 ) -> Result<usize, Vec<String>> {
-    let mut symbols = evaluator::Definitions {
+    run_tests_impl(analyzed, include_std_tests, None)
+}
+
+#[allow(clippy::print_stdout)]
+fn run_tests_impl<F: FieldElement>(
+    analyzed: &Analyzed<F>,
+    include_std_tests: bool,
+    pipeline: Option<Pipeline<F>>,
+) -> Result<usize, Vec<String>> {
+    let mut symbols = TestSymbols {
         definitions: &analyzed.definitions,
         solved_impls: &analyzed.solved_impls,
+        pipeline,
+        witness: None,
     };
 
     let mut errors = vec![];
@@ -82,6 +103,8 @@ pub fn run_tests<F: FieldElement>(
             " ".repeat(76 - name_len)
         };
         print!("{name}...");
+        // Every test starts without a witness.
+        symbols.witness = None;
         let function = symbols.lookup(name, &None).unwrap();
         match evaluator::evaluate_function_call::<F>(function, vec![], &mut symbols) {
             Err(e) => {
@@ -107,3 +130,150 @@ pub fn run_tests<F: FieldElement>(
         Err(vec![format!("{} test(s) failed.", errors.len())])
     }
 }
+
+/// Symbol lookup for test functions. In addition to the definitions,
+/// it provides access to witness generation and constraint checking
+/// for the program under test, if available.
+struct TestSymbols<'a, F: FieldElement> {
+    definitions: &'a HashMap<String, (Symbol, Option<FunctionValueDefinition>)>,
+    solved_impls: &'a SolvedTraitImpls,
+    /// The pipeline of the program under test, used as a template for witness generation.
+    pipeline: Option<Pipeline<F>>,
+    /// The witness and publics generated by the last call to `std::test::run_witgen`.
+    witness: Option<(Arc<Columns<F>>, Vec<(String, Option<F>)>)>,
+}
+
+impl<'a, F: FieldElement> TestSymbols<'a, F> {
+    /// Returns the template pipeline, with the fixed columns already computed.
+    fn pipeline(&mut self) -> Result<&Pipeline<F>, EvalError> {
+        let pipeline = self.pipeline.as_mut().ok_or_else(|| {
+            EvalError::Unsupported(
+                "Witness generation requires a program under test. Use `powdr test` on a file."
+                    .to_string(),
+            )
+        })?;
+        pipeline
+            .compute_fixed_cols()
+            .map_err(|e| EvalError::ProverError(e.join("\n")))?;
+        Ok(pipeline)
+    }
+
+    /// Runs witness generation on a copy of the template pipeline, catching any panics.
+    fn generate_witness(
+        &mut self,
+        inputs: Vec<F>,
+    ) -> Result<(Arc<Columns<F>>, Vec<(String, Option<F>)>), String> {
+        let mut pipeline = self
+            .pipeline()
+            .map_err(|e| e.to_string())?
+            .clone()
+            .with_prover_inputs(inputs);
+        panic::catch_unwind(AssertUnwindSafe(|| {
+            let witness = pipeline.compute_witness()?;
+            Ok((witness, pipeline.publics()?))
+        }))
+        .map_err(|e| {
+            e.downcast_ref::<String>()
+                .cloned()
+                .or_else(|| e.downcast_ref::<&str>().map(|s| s.to_string()))
+                .unwrap_or_else(|| "Witness generation panicked.".to_string())
+        })?
+        .map_err(|e: Vec<String>| e.join("\n"))
+    }
+
+    fn witness(&self) -> Result<&(Arc<Columns<F>>, Vec<(String, Option<F>)>), EvalError> {
+        self.witness.as_ref().ok_or_else(|| {
+            EvalError::FailedAssertion(
+                "No witness available, call std::test::run_witgen first.".to_string(),
+            )
+        })
+    }
+}
+
+impl<'a, F: FieldElement> SymbolLookup<'a, F> for TestSymbols<'a, F> {
+    fn lookup(
+        &mut self,
+        name: &'a str,
+        type_args: &Option<Vec<Type>>,
+    ) -> Result<Arc<Value<'a, F>>, EvalError> {
+        Definitions::lookup_with_symbols(self.definitions, self.solved_impls, name, type_args, self)
+    }
+
+    fn lookup_public_reference(&self, name: &str) -> Result<Arc<Value<'a, F>>, EvalError> {
+        Ok(Value::from(AlgebraicExpression::PublicReference(name.to_string())).into())
+    }
+
+    fn run_witgen(&mut self, inputs: Vec<F>) -> Result<(), EvalError> {
+        self.witness = None;
+        let witness = self
+            .generate_witness(inputs)
+            .map_err(|e| EvalError::FailedAssertion(format!("Witness generation failed: {e}")))?;
+        self.witness = Some(witness);
+        Ok(())
+    }
+
+    fn witness_value(&self, column: &str, row: usize) -> Result<F, EvalError> {
+        let (witness, _) = self.witness()?;
+        let (_, values) = witness
+            .iter()
+            .find(|(name, _)| name == column)
+            .ok_or_else(|| EvalError::SymbolNotFound(format!("Witness column {column}")))?;
+        values.get(row).cloned().ok_or_else(|| {
+            EvalError::OutOfBounds(format!(
+                "Row {row} of column {column} with {} rows",
+                values.len()
+            ))
+        })
+    }
+
+    fn public_value(&self, name: &str) -> Result<F, EvalError> {
+        let (_, publics) = self.witness()?;
+        publics
+            .iter()
+            .find(|(n, _)| n == name)
+            .ok_or_else(|| EvalError::SymbolNotFound(format!("Public {name}")))?
+            .1
+            .ok_or(EvalError::DataNotAvailable)
+    }
+
+    fn expect_witgen_failure(&mut self, inputs: Vec<F>) -> Result<(), EvalError> {
+        match self.generate_witness(inputs) {
+            Ok(_) => Err(EvalError::FailedAssertion(
+                "Expected witness generation to fail, but it succeeded.".to_string(),
+            )),
+            Err(_) => Ok(()),
+        }
+    }
+
+    fn expect_constraint_failure(
+        &mut self,
+        modifications: Vec<(String, usize, F)>,
+    ) -> Result<(), EvalError> {
+        let (witness, _) = self.witness()?;
+        let mut witness = witness.as_ref().clone();
+        for (column, row, value) in modifications {
+            let (_, values) = witness
+                .iter_mut()
+                .find(|(name, _)| *name == column)
+                .ok_or_else(|| EvalError::SymbolNotFound(format!("Witness column {column}")))?;
+            *values
+                .get_mut(row)
+                .ok_or_else(|| EvalError::OutOfBounds(format!("Row {row} of column {column}")))? =
+                value;
+        }
+        let pipeline = self.pipeline()?;
+        let pil = pipeline
+            .optimized_pil()
+            .map_err(|e| EvalError::ProverError(e.join("\n")))?;
+        let fixed = pipeline
+            .fixed_cols()
+            .map_err(|e| EvalError::ProverError(e.join("\n")))?;
+        match check_constraints(&pil, &fixed, &witness) {
+            Ok(()) => Err(EvalError::FailedAssertion(
+                "Expected the modified witness to violate a constraint, but all constraints hold."
+                    .to_string(),
+            )),
+            Err(_) => Ok(()),
+        }
+    }
+}
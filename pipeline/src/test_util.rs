@@ -2,10 +2,11 @@ use powdr_ast::analyzed::Analyzed;
 use powdr_backend::BackendType;
 use powdr_number::{
     buffered_write_file, BabyBearField, BigInt, Bn254Field, FieldElement, GoldilocksField,
-    KoalaBearField,
+    KoalaBearField, Mersenne31Field,
 };
 use powdr_pil_analyzer::evaluator::{self, SymbolLookup};
 use std::env;
+use std::panic::{self, AssertUnwindSafe};
 use std::path::PathBuf;
 
 use std::sync::Arc;
@@ -68,6 +69,10 @@ pub fn regular_test(file_name: &str, inputs: &[i32]) {
     let inputs_kb = inputs.iter().map(|x| KoalaBearField::from(*x)).collect();
     let pipeline_kb = make_prepared_pipeline(file_name, inputs_kb, vec![]);
     test_plonky3_pipeline(pipeline_kb);
+
+    let inputs_m31 = inputs.iter().map(|x| Mersenne31Field::from(*x)).collect();
+    let pipeline_m31 = make_prepared_pipeline(file_name, inputs_m31, vec![]);
+    test_plonky3_pipeline(pipeline_m31);
 }
 
 pub fn regular_test_without_small_field(file_name: &str, inputs: &[i32]) {
@@ -299,6 +304,41 @@ pub fn gen_halo2_proof(pipeline: Pipeline<Bn254Field>, backend: BackendVariant)
 #[cfg(not(feature = "halo2"))]
 pub fn gen_halo2_proof(_pipeline: Pipeline<Bn254Field>, _backend: BackendVariant) {}
 
+/// Aggregates the Halo2 proofs of all machines into a single SNARK and verifies it.
+#[cfg(feature = "halo2")]
+pub fn gen_halo2_composite_aggr_proof(pipeline: Pipeline<Bn254Field>) {
+    let mut pipeline =
+        pipeline.with_backend(BackendType::Halo2Composite, Some("snark_aggr".to_string()));
+
+    let proof = pipeline.compute_proof().unwrap().clone();
+    let publics: Vec<Bn254Field> = pipeline
+        .publics()
+        .unwrap()
+        .iter()
+        .map(|(_name, v)| v.expect("all publics should be known since we created a proof"))
+        .collect();
+    pipeline.verify(&proof, &[publics]).unwrap();
+}
+
+#[cfg(not(feature = "halo2"))]
+pub fn gen_halo2_composite_aggr_proof(_pipeline: Pipeline<Bn254Field>) {}
+
+/// Aggregates the Halo2 proofs of all machines into a single SNARK. This is
+/// slow, so like other Halo2 proofs, it only runs in the nightly tests.
+#[cfg(feature = "halo2")]
+pub fn test_halo2_composite_aggr(pipeline: Pipeline<Bn254Field>) {
+    let is_nightly_test = env::var("IS_NIGHTLY_TEST")
+        .map(|v| v == "true")
+        .unwrap_or(false);
+
+    if is_nightly_test && should_generate_proofs() {
+        gen_halo2_composite_aggr_proof(pipeline);
+    }
+}
+
+#[cfg(not(feature = "halo2"))]
+pub fn test_halo2_composite_aggr(_pipeline: Pipeline<Bn254Field>) {}
+
 #[cfg(feature = "plonky3")]
 pub fn test_plonky3<T: FieldElement>(file_name: &str, inputs: Vec<T>) {
     let backend = powdr_backend::BackendType::Plonky3;
@@ -321,6 +361,34 @@ pub fn test_plonky3<T: FieldElement>(file_name: &str, inputs: Vec<T>) {
 
     pipeline.verify(&proof, &[publics.clone()]).unwrap();
 
+    // The proof container records the backend, so a pipeline without one can verify it.
+    let container = pipeline.proof_container(proof.clone()).unwrap().to_bytes();
+    let mut pipeline_without_backend = Pipeline::default().from_file(resolve_test_file(file_name));
+    pipeline_without_backend
+        .verify(&container, &[publics.clone()])
+        .unwrap();
+    if !publics.is_empty() {
+        assert!(pipeline_without_backend
+            .verify(&container, &[vec![]])
+            .is_err());
+    }
+    // A pipeline selecting the backend with other options rejects it.
+    let mut pipeline_with_other_options = Pipeline::default()
+        .from_file(resolve_test_file(file_name))
+        .with_backend(backend, Some("log_blowup=2".to_string()));
+    assert!(pipeline_with_other_options
+        .verify(&container, &[publics.clone()])
+        .is_err());
+    // A container whose verification key hash was removed or added is rejected.
+    let mut tampered = pipeline.proof_container(proof.clone()).unwrap();
+    tampered.verification_key_hash = match tampered.verification_key_hash {
+        Some(_) => None,
+        None => Some(powdr_backend::ProofContainer::verification_key_hash(&[])),
+    };
+    assert!(pipeline_without_backend
+        .verify(&tampered.to_bytes(), &[publics.clone()])
+        .is_err());
+
     if pipeline.optimized_pil().unwrap().constant_count() > 0 {
         // Export verification Key
         let output_dir = pipeline.output_dir().as_ref().unwrap();
@@ -532,3 +600,28 @@ pub fn run_reparse_test_with_blacklist(file: &str, blacklist: &[&str]) {
         .compute_optimized_pil()
         .unwrap();
 }
+
+/// Validates the optimization of the given file, see [Pipeline::validate_optimization].
+/// Files for which witness generation on the unoptimized PIL fails with the prover
+/// input 0, because they need other inputs or are expected to fail, are skipped.
+pub fn run_translation_validation_test(file: &str) {
+    let pipeline = Pipeline::<GoldilocksField>::default()
+        .from_file(resolve_test_file(file))
+        .with_prover_inputs(vec![0.into()]);
+
+    let mut unoptimized = pipeline
+        .clone()
+        .with_disabled_optimizer_passes(powdr_pilopt::PASSES.iter().map(|p| p.to_string()));
+    let witness_generated =
+        panic::catch_unwind(AssertUnwindSafe(|| unoptimized.compute_witness().is_ok()))
+            .unwrap_or(false);
+    if !witness_generated {
+        return;
+    }
+
+    pipeline
+        .with_enabled_optimizer_passes(powdr_pilopt::OPT_IN_PASSES.iter().map(|p| p.to_string()))
+        .with_optimization_report()
+        .validate_optimization()
+        .unwrap();
+}
@@ -0,0 +1,215 @@
+//! Translation validation of the PIL optimizer.
+//!
+//! The optimizer removes columns and rewrites identities, so a witness for the
+//! optimized PIL cannot be checked against the unoptimized PIL directly.
+//! Instead, the witness for the optimized PIL is extended by the witness
+//! columns the optimizer removed, and the identities of the unoptimized PIL are
+//! checked on it. Columns the optimizer replaced by an expression are rebuilt by
+//! evaluating the expression on the optimized witness. Columns removed without
+//! a replacement, because they are unconstrained or belong to an inlined
+//! submachine, are taken from a witness generated for the unoptimized PIL on
+//! the same inputs. Columns are matched by name.
+
+use std::collections::{BTreeSet, HashMap};
+
+use powdr_ast::analyzed::{AlgebraicExpression, Analyzed};
+use powdr_ast::parsed::visitor::AllChildren;
+use powdr_executor::constant_evaluator::VariablySizedColumn;
+use powdr_number::{DegreeType, FieldElement};
+use powdr_pilopt::OptimizationReport;
+
+use crate::constraint_checker::{evaluate_on_rows, violated_identities};
+
+/// Checks that all identities of the unoptimized PIL `original` hold on
+/// `optimized_witness`, extended by the columns the optimizer removed.
+/// Columns replaced by an expression according to `report` are evaluated on
+/// `optimized_witness`, the others are taken from `original_witness`.
+/// `original_fixed` are the fixed columns of the unoptimized PIL.
+///
+/// Returns one error per violated identity. Each error names the first pass that
+/// removed or modified the identity or removed a column it references.
+pub fn validate<T: FieldElement>(
+    original: &Analyzed<T>,
+    original_fixed: &[(String, VariablySizedColumn<T>)],
+    original_witness: &[(String, Vec<T>)],
+    optimized_witness: &[(String, Vec<T>)],
+    report: &OptimizationReport<T>,
+) -> Result<(), Vec<String>> {
+    let substitutions = report
+        .passes
+        .iter()
+        .flat_map(|pass| &pass.substituted_columns)
+        .collect::<Vec<_>>();
+    let substituted = substitutions
+        .iter()
+        .map(|s| s.name.as_str())
+        .collect::<BTreeSet<_>>();
+    let optimized = optimized_witness
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<BTreeSet<_>>();
+    let sizes = original_witness
+        .iter()
+        .map(|(name, column)| (name.as_str(), column.len() as DegreeType))
+        .collect::<HashMap<_, _>>();
+
+    // Columns removed without a replacement are taken from the original witness.
+    let mut witness = optimized_witness
+        .iter()
+        .chain(original_witness.iter().filter(|(name, _)| {
+            !optimized.contains(name.as_str()) && !substituted.contains(name.as_str())
+        }))
+        .cloned()
+        .collect::<Vec<_>>();
+    // A substitution only references columns that exist when it is made, so
+    // evaluating them in reverse order only references known columns.
+    for substitution in substitutions.into_iter().rev() {
+        let size = sizes.get(substitution.name.as_str()).ok_or_else(|| {
+            vec![format!(
+                "Substituted column {} is not in the original witness",
+                substitution.name
+            )]
+        })?;
+        let column = evaluate_on_rows(
+            original,
+            original_fixed,
+            &witness,
+            &substitution.value,
+            *size,
+        )
+        .map_err(|e| {
+            vec![format!(
+                "Could not rebuild column {} = {}: {e}",
+                substitution.name, substitution.value
+            )]
+        })?;
+        witness.push((substitution.name.clone(), column));
+    }
+
+    let errors = violated_identities(original, original_fixed, &witness)
+        .into_iter()
+        .map(|(id, error)| match responsible_pass(original, id, report) {
+            Some((index, name)) => {
+                format!("{error}\nThis is probably caused by optimizer pass {index} ({name}).")
+            }
+            None => error,
+        })
+        .collect::<Vec<_>>();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Returns the index and name of the first pass that removed or modified the
+/// identity with the given ID or removed a column it references.
+fn responsible_pass<'a, T: FieldElement>(
+    original: &Analyzed<T>,
+    id: u64,
+    report: &'a OptimizationReport<T>,
+) -> Option<(usize, &'a str)> {
+    let identity = original.identities.iter().find(|i| i.id() == id)?;
+    let columns = identity
+        .all_children()
+        .filter_map(|e| match e {
+            AlgebraicExpression::Reference(r) => Some(r.name.as_str()),
+            _ => None,
+        })
+        .collect::<BTreeSet<_>>();
+    report
+        .passes
+        .iter()
+        .enumerate()
+        .find(|(_, pass)| {
+            pass.removed_identities
+                .iter()
+                .chain(&pass.modified_identities)
+                .any(|i| i.id == id)
+                || pass
+                    .removed_columns
+                    .iter()
+                    .any(|c| columns.contains(c.name.as_str()))
+        })
+        .map(|(index, pass)| (index, pass.name.as_str()))
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeSet;
+
+    use powdr_executor::constant_evaluator;
+    use powdr_number::GoldilocksField;
+    use powdr_pil_analyzer::analyze_string;
+    use powdr_pilopt::{optimize_with_options, OptimizerOptions, OPT_IN_PASSES};
+
+    use super::validate;
+
+    fn column(name: &str, values: [u64; 4]) -> (String, Vec<GoldilocksField>) {
+        (name.to_string(), values.map(GoldilocksField::from).to_vec())
+    }
+
+    #[test]
+    fn removed_columns_are_rebuilt_from_substitutions() {
+        let input = "namespace N(4);
+            col witness a;
+            col witness b;
+            col witness c;
+            col witness x;
+            col witness y;
+            y = 3;
+            x = a + 2 * b;
+            c = x * y;
+            a * b = 2 * b;
+        ";
+        let original = analyze_string::<GoldilocksField>(input).unwrap();
+        let fixed = constant_evaluator::generate(&original);
+        let options = OptimizerOptions {
+            enabled_passes: OPT_IN_PASSES.iter().map(|p| p.to_string()).collect(),
+            report: true,
+            ..Default::default()
+        };
+        let (optimized, report) =
+            optimize_with_options(original.clone(), &options, |_, _, _| {}).unwrap();
+        let report = report.unwrap();
+        assert_eq!(optimized.commitment_count(), 2);
+        let substituted = report
+            .passes
+            .iter()
+            .flat_map(|pass| &pass.substituted_columns)
+            .map(|s| s.name.as_str())
+            .collect::<BTreeSet<_>>();
+        assert_eq!(substituted, BTreeSet::from(["N::c", "N::x", "N::y"]));
+
+        // The removed columns are not taken from the original witness.
+        let original_witness = [
+            column("N::a", [0; 4]),
+            column("N::b", [0; 4]),
+            column("N::c", [0; 4]),
+            column("N::x", [0; 4]),
+            column("N::y", [0; 4]),
+        ];
+        let optimized_witness = [column("N::a", [2; 4]), column("N::b", [1, 0, 1, 0])];
+        assert_eq!(
+            validate(
+                &original,
+                &fixed,
+                &original_witness,
+                &optimized_witness,
+                &report
+            ),
+            Ok(())
+        );
+
+        let optimized_witness = [column("N::a", [3; 4]), column("N::b", [1; 4])];
+        let errors = validate(
+            &original,
+            &fixed,
+            &original_witness,
+            &optimized_witness,
+            &report,
+        )
+        .unwrap_err();
+        assert_eq!(errors.len(), 1);
+    }
+}
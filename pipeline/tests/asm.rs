@@ -4,11 +4,13 @@ use powdr_backend::BackendType;
 use powdr_executor::constant_evaluator::{self, get_uniquely_sized};
 use powdr_number::{Bn254Field, FieldElement, GoldilocksField};
 use powdr_pipeline::{
+    test_runner,
     test_util::{
         asm_string_to_pil, gen_estark_proof_with_backend_variant, make_prepared_pipeline,
         make_simple_prepared_pipeline, regular_test, regular_test_without_small_field,
-        resolve_test_file, run_pilcom_with_backend_variant, test_halo2,
-        test_halo2_with_backend_variant, test_pilcom, test_plonky3, BackendVariant,
+        resolve_test_file, run_pilcom_with_backend_variant, test_halo2, test_halo2_composite_aggr,
+        test_halo2_with_backend_variant, test_pilcom, test_plonky3, test_plonky3_pipeline,
+        BackendVariant,
     },
     util::{FixedPolySet, PolySet, WitnessPolySet},
     Pipeline,
@@ -133,6 +135,33 @@ fn vm_to_block_to_block() {
     test_halo2(make_simple_prepared_pipeline(f));
 }
 
+#[test]
+fn vm_to_block_substitution() {
+    let f = "asm/vm_to_block_substitution.asm";
+    // With the opt-in passes, `sum` and `diff` are substituted and `x + y` is extracted
+    // into an intermediate column, witness generation still has to solve the block
+    // machine's operations.
+    let mut pipeline = Pipeline::<GoldilocksField>::default()
+        .with_tmp_output()
+        .from_file(resolve_test_file(f))
+        .with_enabled_optimizer_passes(powdr_pilopt::OPT_IN_PASSES.iter().map(|p| p.to_string()));
+    let optimized = pipeline.compute_optimized_pil().unwrap();
+    let witness_columns = optimized
+        .committed_polys_in_source_order()
+        .map(|(symbol, _)| symbol.absolute_name.as_str())
+        .collect::<Vec<_>>();
+    assert!(!witness_columns.contains(&"main_arith::sum"));
+    assert!(!witness_columns.contains(&"main_arith::diff"));
+    assert!(optimized
+        .intermediate_polys_in_source_order()
+        .any(|(symbol, _)| symbol
+            .absolute_name
+            .starts_with("main_arith::_subexpression")));
+    test_plonky3_pipeline(pipeline);
+
+    regular_test(f, &[]);
+}
+
 #[test]
 fn block_to_block() {
     let f = "asm/block_to_block.asm";
@@ -160,6 +189,12 @@ fn block_to_block_with_bus_composite() {
     test_halo2_with_backend_variant(pipeline.clone(), BackendVariant::Composite);
 }
 
+#[test]
+fn vm_to_block_unique_interface_aggregated_halo2_proof() {
+    let f = "asm/vm_to_block_unique_interface.asm";
+    test_halo2_composite_aggr(make_simple_prepared_pipeline(f));
+}
+
 #[test]
 fn vm_instr_param_mapping() {
     let f = "asm/vm_instr_param_mapping.asm";
@@ -470,6 +505,15 @@ mod reparse {
     include!(concat!(env!("OUT_DIR"), "/asm_reparse_tests.rs"));
 }
 
+mod translation_validation {
+    use powdr_pipeline::test_util::run_translation_validation_test;
+    use test_log::test;
+    include!(concat!(
+        env!("OUT_DIR"),
+        "/asm_translation_validation_tests.rs"
+    ));
+}
+
 mod book {
     use super::*;
     use test_log::test;
@@ -775,3 +819,10 @@ fn expand_fixed_jit() {
         .collect::<Vec<_>>();
     assert_eq!(fixed_col_names, vec!["main::LAST"]);
 }
+
+#[test]
+fn native_tests() {
+    let f = resolve_test_file("asm/native_tests.asm");
+    let count = test_runner::run_from_file::<GoldilocksField>(f.to_str().unwrap(), false).unwrap();
+    assert_eq!(count, 3);
+}
@@ -7,8 +7,9 @@ use powdr_pipeline::{
         assert_proofs_fail_for_invalid_witnesses_halo2,
         assert_proofs_fail_for_invalid_witnesses_pilcom, gen_estark_proof,
         gen_estark_proof_with_backend_variant, make_prepared_pipeline,
-        make_simple_prepared_pipeline, regular_test, run_pilcom_with_backend_variant, test_halo2,
-        test_halo2_with_backend_variant, test_pilcom, test_plonky3, BackendVariant,
+        make_simple_prepared_pipeline, regular_test, resolve_test_file,
+        run_pilcom_with_backend_variant, test_halo2, test_halo2_with_backend_variant, test_pilcom,
+        test_plonky3, BackendVariant,
     },
     Pipeline,
 };
@@ -93,6 +94,103 @@ fn fibonacci() {
     test_plonky3::<GoldilocksField>(f, Default::default());
 }
 
+#[test]
+fn high_degree() {
+    let f = "pil/high_degree.pil";
+    regular_test(f, Default::default());
+    // Plonky3 reduces the degree to 3 with the default options.
+    test_plonky3::<GoldilocksField>(f, Default::default());
+
+    let mut pipeline = Pipeline::<GoldilocksField>::default()
+        .with_tmp_output()
+        .from_file(resolve_test_file(f))
+        .with_max_constraint_degree(Some(2));
+    pipeline.compute_witness().unwrap();
+    let pil = pipeline.optimized_pil().unwrap();
+    assert_eq!(pil.commitment_count(), 5);
+    test_pilcom(pipeline);
+}
+
+#[cfg(feature = "plonky3")]
+#[test]
+fn high_degree_with_verification_key() {
+    use powdr_backend::BackendType;
+    use powdr_number::buffered_write_file;
+
+    // With `log_blowup=2`, Plonky3 supports the degree 5 identity. A verifier
+    // only given the verification key has to reach the same PIL.
+    let f = "pil/high_degree.pil";
+    let mut prover = Pipeline::<GoldilocksField>::default()
+        .with_tmp_output()
+        .from_file(resolve_test_file(f))
+        .with_backend(BackendType::Plonky3, Some("log_blowup=2".to_string()));
+    let proof = prover.compute_proof().cloned().unwrap();
+    let publics = prover
+        .publics()
+        .unwrap()
+        .iter()
+        .map(|(_, v)| v.unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(prover.optimized_pil().unwrap().commitment_count(), 2);
+
+    let vkey_file = prover.output_dir().clone().unwrap().join("vkey.bin");
+    buffered_write_file(&vkey_file, |writer| {
+        prover.export_verification_key(writer).unwrap()
+    })
+    .unwrap();
+    let mut verifier = Pipeline::<GoldilocksField>::default()
+        .from_file(resolve_test_file(f))
+        .with_backend(BackendType::Plonky3, None)
+        .with_vkey_file(Some(vkey_file));
+    assert_eq!(
+        verifier.compute_optimized_pil().unwrap().commitment_count(),
+        2
+    );
+    verifier.verify(&proof, &[publics]).unwrap();
+}
+
+#[test]
+fn optimizer_pass_options() {
+    let f = "pil/fibonacci.pil";
+    let mut pipeline = Pipeline::<GoldilocksField>::default()
+        .with_tmp_output()
+        .from_file(resolve_test_file(f))
+        .with_enabled_optimizer_passes(powdr_pilopt::OPT_IN_PASSES.iter().map(|p| p.to_string()))
+        .with_disabled_optimizer_passes(["extract_common_subexpressions".to_string()])
+        .with_dump_after_optimizer_passes(["simplify_identities".to_string()])
+        .with_optimization_report();
+    pipeline.compute_witness().unwrap();
+    let report = pipeline.optimization_report().unwrap();
+    assert_eq!(report.passes.len(), powdr_pilopt::PASSES.len());
+    assert!(report
+        .passes
+        .iter()
+        .all(|pass| pass.enabled == (pass.name != "extract_common_subexpressions")));
+
+    let output_dir = pipeline.output_dir().clone().unwrap();
+    let name = pipeline.name();
+    assert!(output_dir.join(format!("{name}_opt_report.json")).exists());
+    for (index, _) in powdr_pilopt::PASSES
+        .iter()
+        .enumerate()
+        .filter(|(_, pass)| **pass == "simplify_identities")
+    {
+        assert!(output_dir
+            .join(format!("{name}_opt_{index}_simplify_identities.pil"))
+            .exists());
+    }
+    test_pilcom(pipeline);
+
+    let result = Pipeline::<GoldilocksField>::default()
+        .from_file(resolve_test_file(f))
+        .with_disabled_optimizer_passes(["no_such_pass".to_string()])
+        .compute_optimized_pil();
+    assert_eq!(
+        result.err(),
+        Some(vec!["Unknown optimizer pass: no_such_pass".to_string()])
+    );
+}
+
 #[test]
 fn fibonacci_with_public() {
     // Public references are not supported by the backends yet, but we can test witness generation.
@@ -379,6 +477,15 @@ mod reparse {
     include!(concat!(env!("OUT_DIR"), "/pil_reparse_tests.rs"));
 }
 
+mod translation_validation {
+    use powdr_pipeline::test_util::run_translation_validation_test;
+    use test_log::test;
+    include!(concat!(
+        env!("OUT_DIR"),
+        "/pil_translation_validation_tests.rs"
+    ));
+}
+
 mod book {
     use super::*;
     use test_log::test;
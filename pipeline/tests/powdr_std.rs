@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use powdr_number::{BabyBearField, BigInt, Bn254Field, GoldilocksField};
+use powdr_number::{BabyBearField, BigInt, Bn254Field, GoldilocksField, Mersenne31Field};
 
 use powdr_pil_analyzer::evaluator::Value;
 use powdr_pipeline::{
@@ -51,6 +51,39 @@ fn keccakf16_test() {
     test_plonky3::<BabyBearField>(f, vec![]);
 }
 
+#[test]
+#[ignore = "Too slow"]
+fn sha256_test() {
+    let f = "std/sha256_test.asm";
+    let pipeline = make_simple_prepared_pipeline(f);
+    test_pilcom(pipeline.clone());
+    gen_estark_proof(pipeline);
+}
+
+#[test]
+#[ignore = "Too slow"]
+fn sha256_16_test() {
+    let f = "std/sha256_16_test.asm";
+    test_plonky3::<BabyBearField>(f, vec![]);
+}
+
+#[test]
+fn sha256_witgen() {
+    // Witness generation fails if the digests don't match the test vectors.
+    // Proving is covered by the slow tests above.
+    make_simple_prepared_pipeline::<GoldilocksField>("std/sha256_test.asm");
+    make_simple_prepared_pipeline::<BabyBearField>("std/sha256_16_test.asm");
+}
+
+#[test]
+#[ignore = "Too slow"]
+fn arith384_test() {
+    let f = "std/arith384_test.asm";
+    let pipeline = make_simple_prepared_pipeline(f);
+    test_pilcom(pipeline.clone());
+    gen_estark_proof(pipeline);
+}
+
 #[test]
 #[ignore = "Too slow"]
 fn poseidon_bb_test() {
@@ -88,6 +121,14 @@ fn split_bb_test() {
     test_plonky3::<BabyBearField>(f, vec![]);
 }
 
+#[cfg(feature = "plonky3")]
+#[test]
+#[ignore = "Too slow"]
+fn split_m31_test() {
+    let f = "std/split_m31_test.asm";
+    test_plonky3::<Mersenne31Field>(f, vec![]);
+}
+
 #[test]
 #[ignore = "Too slow"]
 fn add_sub_small_test() {
@@ -12,11 +12,12 @@ use alloc::{
     vec,
     vec::Vec,
 };
+use core::marker::PhantomData;
 use itertools::Itertools;
 use p3_field::AbstractField;
 
 use crate::{
-    params::{Commitment, FieldElementMap, Plonky3Field, ProverData},
+    params::{Commitment, CommitmentHash, FieldElementMap, Plonky3Field, ProverData},
     AirStage,
 };
 use p3_air::{Air, AirBuilder, BaseAir, PairBuilder};
@@ -136,26 +137,28 @@ impl<T: FieldElement> From<&Analyzed<T>> for ConstraintSystem<T> {
     }
 }
 
-pub struct PowdrCircuit<'a, T: FieldElementMap>
+pub struct PowdrCircuit<'a, T: FieldElementMap<H>, H: CommitmentHash>
 where
-    ProverData<T>: Send,
-    Commitment<T>: Send,
+    ProverData<T, H>: Send,
+    Commitment<T, H>: Send,
 {
     /// The split program
     pub split: &'a BTreeMap<String, (Analyzed<T>, ConstraintSystem<T>)>,
     /// Callback to augment the witness in the later stages
     witgen_callback: Option<WitgenCallback<T>>,
+    _phantom: PhantomData<H>,
 }
 
-impl<'a, T: FieldElementMap> PowdrCircuit<'a, T>
+impl<'a, T: FieldElementMap<H>, H: CommitmentHash> PowdrCircuit<'a, T, H>
 where
-    ProverData<T>: Send,
-    Commitment<T>: Send,
+    ProverData<T, H>: Send,
+    Commitment<T, H>: Send,
 {
     pub fn new(split: &'a BTreeMap<String, (Analyzed<T>, ConstraintSystem<T>)>) -> Self {
         Self {
             split,
             witgen_callback: None,
+            _phantom: PhantomData,
         }
     }
 
@@ -198,22 +201,23 @@ where
     }
 }
 
-pub(crate) struct PowdrTable<'a, T: FieldElementMap>
+pub(crate) struct PowdrTable<'a, T: FieldElementMap<H>, H: CommitmentHash>
 where
-    ProverData<T>: Send,
-    Commitment<T>: Send,
+    ProverData<T, H>: Send,
+    Commitment<T, H>: Send,
 {
     /// The constraint system description
     constraint_system: &'a ConstraintSystem<T>,
+    _phantom: PhantomData<H>,
 }
 
 /// Convert a witness for a stage
-pub fn generate_matrix<'a, T: FieldElementMap>(
+pub fn generate_matrix<'a, T: FieldElementMap<H>, H: CommitmentHash>(
     witness: impl Iterator<Item = (&'a String, &'a [T])> + Clone,
-) -> RowMajorMatrix<Plonky3Field<T>>
+) -> RowMajorMatrix<Plonky3Field<T, H>>
 where
-    ProverData<T>: Send,
-    Commitment<T>: Send,
+    ProverData<T, H>: Send,
+    Commitment<T, H>: Send,
 {
     let width = witness.clone().count();
 
@@ -233,17 +237,20 @@ where
     RowMajorMatrix::new(values, width)
 }
 
-impl<'a, T: FieldElementMap> PowdrTable<'a, T>
+impl<'a, T: FieldElementMap<H>, H: CommitmentHash> PowdrTable<'a, T, H>
 where
-    ProverData<T>: Send,
-    Commitment<T>: Send,
+    ProverData<T, H>: Send,
+    Commitment<T, H>: Send,
 {
     pub(crate) fn new(constraint_system: &'a ConstraintSystem<T>) -> Self {
-        Self { constraint_system }
+        Self {
+            constraint_system,
+            _phantom: PhantomData,
+        }
     }
 
     /// Conversion to plonky3 expression
-    fn to_plonky3_expr<AB: AirBuilder<F = Plonky3Field<T>> + MultistageAirBuilder>(
+    fn to_plonky3_expr<AB: AirBuilder<F = Plonky3Field<T, H>> + MultistageAirBuilder>(
         &self,
         e: &AlgebraicExpression<T>,
         traces_by_stage: &[AB::M],
@@ -364,25 +371,30 @@ where
 
 /// An extension of [Air] allowing access to the number of fixed columns
 
-impl<'a, T: FieldElementMap> BaseAir<Plonky3Field<T>> for PowdrTable<'a, T>
+impl<'a, T: FieldElementMap<H>, H: CommitmentHash> BaseAir<Plonky3Field<T, H>>
+    for PowdrTable<'a, T, H>
 where
-    ProverData<T>: Send,
-    Commitment<T>: Send,
+    ProverData<T, H>: Send,
+    Commitment<T, H>: Send,
 {
     fn width(&self) -> usize {
         unimplemented!("use MultiStageAir method instead")
     }
 
-    fn preprocessed_trace(&self) -> Option<RowMajorMatrix<Plonky3Field<T>>> {
+    fn preprocessed_trace(&self) -> Option<RowMajorMatrix<Plonky3Field<T, H>>> {
         unimplemented!()
     }
 }
 
-impl<'a, T: FieldElementMap, AB: PairBuilder + MultistageAirBuilder<F = Plonky3Field<T>>> Air<AB>
-    for PowdrTable<'a, T>
+impl<
+        'a,
+        T: FieldElementMap<H>,
+        H: CommitmentHash,
+        AB: PairBuilder + MultistageAirBuilder<F = Plonky3Field<T, H>>,
+    > Air<AB> for PowdrTable<'a, T, H>
 where
-    ProverData<T>: Send,
-    Commitment<T>: Send,
+    ProverData<T, H>: Send,
+    Commitment<T, H>: Send,
 {
     fn eval(&self, builder: &mut AB) {
         let stage_count = <Self as MultiStageAir<AB>>::stage_count(self);
@@ -471,11 +483,15 @@ where
     }
 }
 
-impl<'a, T: FieldElementMap, AB: PairBuilder + MultistageAirBuilder<F = Plonky3Field<T>>>
-    MultiStageAir<AB> for PowdrTable<'a, T>
+impl<
+        'a,
+        T: FieldElementMap<H>,
+        H: CommitmentHash,
+        AB: PairBuilder + MultistageAirBuilder<F = Plonky3Field<T, H>>,
+    > MultiStageAir<AB> for PowdrTable<'a, T, H>
 where
-    ProverData<T>: Send,
-    Commitment<T>: Send,
+    ProverData<T, H>: Send,
+    Commitment<T, H>: Send,
 {
     fn stage_public_count(&self, stage: u8) -> usize {
         self.constraint_system.publics_by_stage[stage as usize].len()
@@ -504,18 +520,18 @@ where
     }
 }
 
-impl<'a, T: FieldElementMap> PowdrCircuit<'a, T>
+impl<'a, T: FieldElementMap<H>, H: CommitmentHash> PowdrCircuit<'a, T, H>
 where
-    ProverData<T>: Send,
-    Commitment<T>: Send,
+    ProverData<T, H>: Send,
+    Commitment<T, H>: Send,
 {
     /// Computes the stage data for stage number `trace_stage` based on `new_challenge_values` drawn at the end of stage `trace_stage - 1`.
     pub fn compute_stage(
         &self,
         trace_stage: u8,
-        new_challenge_values: &[Plonky3Field<T>],
+        new_challenge_values: &[Plonky3Field<T, H>],
         witness: &mut Vec<(String, Vec<T>)>,
-    ) -> CallbackResult<Plonky3Field<T>> {
+    ) -> CallbackResult<Plonky3Field<T, H>> {
         let previous_stage_challenges: BTreeSet<&u64> = self
             .split
             .values()
@@ -559,7 +575,7 @@ where
                 (
                     table_name.to_string(),
                     AirStage {
-                        trace: generate_matrix(columns.into_iter()),
+                        trace: generate_matrix::<T, H>(columns.into_iter()),
                         public_values: public_values[table_name][trace_stage as usize]
                             .iter()
                             .map(|v| v.expect("public value for stage {trace_stage} should be available at this point").into_p3_field())
@@ -4,6 +4,7 @@ extern crate alloc;
 
 mod circuit_builder;
 mod folder;
+mod options;
 mod params;
 mod proof;
 mod prover;
@@ -13,6 +14,7 @@ mod verifier;
 
 pub use circuit_builder::*;
 pub use folder::*;
+pub use options::*;
 pub use params::*;
 pub use proof::*;
 pub use prover::*;
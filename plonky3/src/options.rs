@@ -0,0 +1,242 @@
+//! Options of the prover, parsed from the backend options string.
+//!
+//! The options string is a comma-separated list of `key=value` pairs, for
+//! example `hash=keccak,log_blowup=2,queries=50,pow_bits=20`. Omitted keys
+//! keep their default value, so the empty string selects the defaults.
+
+use core::{fmt, str::FromStr};
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use serde::{Deserialize, Serialize};
+
+/// The hash function used for the Merkle tree commitments and the Fiat-Shamir challenger.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HashFunction {
+    #[default]
+    Poseidon2,
+    Keccak,
+    Blake3,
+}
+
+impl fmt::Display for HashFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Poseidon2 => write!(f, "poseidon2"),
+            Self::Keccak => write!(f, "keccak"),
+            Self::Blake3 => write!(f, "blake3"),
+        }
+    }
+}
+
+impl FromStr for HashFunction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "poseidon2" => Ok(Self::Poseidon2),
+            "keccak" => Ok(Self::Keccak),
+            "blake3" => Ok(Self::Blake3),
+            _ => Err(format!(
+                "Unsupported hash function: {s}, expected one of poseidon2, keccak, blake3"
+            )),
+        }
+    }
+}
+
+/// The parameters of the FRI commitment scheme.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FriOptions {
+    /// The base 2 logarithm of the blowup factor of the low-degree extension.
+    pub log_blowup: usize,
+    /// The number of FRI queries.
+    pub num_queries: usize,
+    /// The number of bits of proof of work required before sampling the queries.
+    pub proof_of_work_bits: usize,
+}
+
+impl Default for FriOptions {
+    fn default() -> Self {
+        Self {
+            log_blowup: 1,
+            num_queries: 100,
+            proof_of_work_bits: 16,
+        }
+    }
+}
+
+impl FriOptions {
+    /// The security of the FRI parameters, following the ethSTARK conjecture:
+    /// every query contributes `log_blowup` bits, and grinding adds `proof_of_work_bits`.
+    pub fn conjectured_security_bits(&self) -> usize {
+        self.log_blowup * self.num_queries + self.proof_of_work_bits
+    }
+
+    /// The largest constraint degree that can be proven: the quotient of a
+    /// constraint of degree `d` has degree `d - 1` relative to the trace and must
+    /// fit into the low-degree extension.
+    pub fn max_constraint_degree(&self) -> usize {
+        (1 << self.log_blowup) + 1
+    }
+}
+
+/// The options of the prover.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Plonky3Options {
+    pub fri: FriOptions,
+    pub hash: HashFunction,
+}
+
+impl Plonky3Options {
+    /// The conjectured security of a proof in bits. It is bounded by the
+    /// FRI parameters and by the size of the field challenges are drawn from.
+    pub fn conjectured_security_bits(&self, challenge_field_bits: usize) -> usize {
+        self.fri
+            .conjectured_security_bits()
+            .min(challenge_field_bits)
+    }
+}
+
+impl fmt::Display for Plonky3Options {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "hash={},log_blowup={},queries={},pow_bits={}",
+            self.hash, self.fri.log_blowup, self.fri.num_queries, self.fri.proof_of_work_bits
+        )
+    }
+}
+
+impl FromStr for Plonky3Options {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<ExplicitPlonky3Options>()?
+            .apply_to(Self::default())
+    }
+}
+
+/// The options that were explicitly set in an options string. Unlike
+/// [`Plonky3Options`], this distinguishes an omitted option from one that was
+/// explicitly set to its default value.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ExplicitPlonky3Options {
+    pub hash: Option<HashFunction>,
+    pub log_blowup: Option<usize>,
+    pub num_queries: Option<usize>,
+    pub proof_of_work_bits: Option<usize>,
+}
+
+impl ExplicitPlonky3Options {
+    /// Overrides the options in `base` with the explicitly set ones.
+    pub fn apply_to(&self, mut base: Plonky3Options) -> Result<Plonky3Options, String> {
+        if let Some(hash) = self.hash {
+            base.hash = hash;
+        }
+        if let Some(log_blowup) = self.log_blowup {
+            base.fri.log_blowup = log_blowup;
+        }
+        if let Some(num_queries) = self.num_queries {
+            base.fri.num_queries = num_queries;
+        }
+        if let Some(proof_of_work_bits) = self.proof_of_work_bits {
+            base.fri.proof_of_work_bits = proof_of_work_bits;
+        }
+
+        if base.fri.log_blowup == 0 {
+            return Err("log_blowup must be at least 1".to_string());
+        }
+        if base.fri.num_queries == 0 {
+            return Err("queries must be at least 1".to_string());
+        }
+
+        Ok(base)
+    }
+
+    /// Returns true if every explicitly set option has the same value in `options`.
+    pub fn is_compatible_with(&self, options: &Plonky3Options) -> bool {
+        !(self.hash.is_some_and(|hash| hash != options.hash)
+            || self
+                .log_blowup
+                .is_some_and(|log_blowup| log_blowup != options.fri.log_blowup)
+            || self
+                .num_queries
+                .is_some_and(|num_queries| num_queries != options.fri.num_queries)
+            || self.proof_of_work_bits.is_some_and(|proof_of_work_bits| {
+                proof_of_work_bits != options.fri.proof_of_work_bits
+            }))
+    }
+}
+
+impl fmt::Display for ExplicitPlonky3Options {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let options = [
+            self.hash.map(|hash| format!("hash={hash}")),
+            self.log_blowup.map(|v| format!("log_blowup={v}")),
+            self.num_queries.map(|v| format!("queries={v}")),
+            self.proof_of_work_bits.map(|v| format!("pow_bits={v}")),
+        ];
+        let options = options.into_iter().flatten().collect::<Vec<_>>();
+        write!(f, "{}", options.join(","))
+    }
+}
+
+impl FromStr for ExplicitPlonky3Options {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut options = Self::default();
+        for option in s.split(',').map(str::trim).filter(|o| !o.is_empty()) {
+            let (key, value) = option
+                .split_once('=')
+                .ok_or_else(|| format!("Expected `key=value` in plonky3 options, got: {option}"))?;
+            let parse_number = |value: &str| {
+                value
+                    .parse::<usize>()
+                    .map_err(|e| format!("Invalid value for {key}: {value} ({e})"))
+            };
+            match key {
+                "hash" => options.hash = Some(value.parse()?),
+                "log_blowup" => options.log_blowup = Some(parse_number(value)?),
+                "queries" => options.num_queries = Some(parse_number(value)?),
+                "pow_bits" => options.proof_of_work_bits = Some(parse_number(value)?),
+                _ => return Err(format!("Unknown plonky3 option: {key}")),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn explicit_defaults() {
+        let vk_options: Plonky3Options = "hash=keccak,queries=50".parse().unwrap();
+
+        // omitted options do not conflict with the verification key
+        let explicit: ExplicitPlonky3Options = "".parse().unwrap();
+        assert!(explicit.is_compatible_with(&vk_options));
+        assert_eq!(explicit.apply_to(vk_options), Ok(vk_options));
+
+        // an option explicitly set to its default value does
+        let explicit: ExplicitPlonky3Options = "hash=poseidon2".parse().unwrap();
+        assert!(!explicit.is_compatible_with(&vk_options));
+
+        let explicit: ExplicitPlonky3Options = "queries=50,pow_bits=16".parse().unwrap();
+        assert!(explicit.is_compatible_with(&vk_options));
+    }
+
+    #[test]
+    fn invalid_options() {
+        assert!("log_blowup=0".parse::<Plonky3Options>().is_err());
+        assert!("queries=0".parse::<Plonky3Options>().is_err());
+        assert!("hash=sha3".parse::<Plonky3Options>().is_err());
+        assert!("blowup=2".parse::<Plonky3Options>().is_err());
+    }
+}
@@ -0,0 +1,92 @@
+//! Commitments using a hash function over bytes instead of Poseidon2.
+//!
+//! Field elements are serialized before being hashed, following
+//! [this example](https://github.com/Plonky3/Plonky3/blob/2192432ddf28e7359dd2c577447886463e6124f0/keccak-air/examples/prove_baby_bear_keccak.rs).
+//! These hashes are cheaper to verify outside of a circuit, e.g. on Ethereum.
+
+use alloc::vec;
+
+use p3_blake3::Blake3;
+use p3_challenger::{HashChallenger, SerializingChallenger32, SerializingChallenger64};
+use p3_field::{PrimeField32, PrimeField64};
+use p3_keccak::Keccak256Hash;
+use p3_merkle_tree::MerkleTreeMmcs;
+use p3_symmetric::{
+    CompressionFunctionFromHasher, CryptographicHasher, SerializingHasher32, SerializingHasher64,
+};
+
+use crate::{params::CommitmentHash, HashFunction};
+
+const DIGEST_BYTES: usize = 32;
+
+pub trait ByteHash: CommitmentHash {
+    type Hasher: CryptographicHasher<u8, [u8; DIGEST_BYTES]> + Clone + Send + Sync;
+
+    fn hasher() -> Self::Hasher;
+}
+
+pub struct KeccakHash;
+
+impl CommitmentHash for KeccakHash {
+    const FUNCTION: HashFunction = HashFunction::Keccak;
+}
+
+impl ByteHash for KeccakHash {
+    type Hasher = Keccak256Hash;
+
+    fn hasher() -> Self::Hasher {
+        Keccak256Hash {}
+    }
+}
+
+pub struct Blake3Hash;
+
+impl CommitmentHash for Blake3Hash {
+    const FUNCTION: HashFunction = HashFunction::Blake3;
+}
+
+impl ByteHash for Blake3Hash {
+    type Hasher = Blake3;
+
+    fn hasher() -> Self::Hasher {
+        Blake3 {}
+    }
+}
+
+type Compress<H> = CompressionFunctionFromHasher<u8, <H as ByteHash>::Hasher, 2, DIGEST_BYTES>;
+
+/// The Merkle tree commitment scheme for fields of at most 32 bits.
+pub type ValMmcs32<F, H> =
+    MerkleTreeMmcs<F, u8, SerializingHasher32<<H as ByteHash>::Hasher>, Compress<H>, DIGEST_BYTES>;
+
+/// The Merkle tree commitment scheme for fields of at most 64 bits.
+pub type ValMmcs64<F, H> =
+    MerkleTreeMmcs<F, u8, SerializingHasher64<<H as ByteHash>::Hasher>, Compress<H>, DIGEST_BYTES>;
+
+pub type Challenger32<F, H> =
+    SerializingChallenger32<F, HashChallenger<u8, <H as ByteHash>::Hasher, DIGEST_BYTES>>;
+
+pub type Challenger64<F, H> =
+    SerializingChallenger64<F, HashChallenger<u8, <H as ByteHash>::Hasher, DIGEST_BYTES>>;
+
+pub fn val_mmcs_32<F, H: ByteHash>() -> ValMmcs32<F, H> {
+    ValMmcs32::new(
+        SerializingHasher32::new(H::hasher()),
+        Compress::<H>::new(H::hasher()),
+    )
+}
+
+pub fn val_mmcs_64<F, H: ByteHash>() -> ValMmcs64<F, H> {
+    ValMmcs64::new(
+        SerializingHasher64::new(H::hasher()),
+        Compress::<H>::new(H::hasher()),
+    )
+}
+
+pub fn challenger_32<F: PrimeField32, H: ByteHash>() -> Challenger32<F, H> {
+    Challenger32::from_hasher(vec![], H::hasher())
+}
+
+pub fn challenger_64<F: PrimeField64, H: ByteHash>() -> Challenger64<F, H> {
+    Challenger64::from_hasher(vec![], H::hasher())
+}
@@ -6,7 +6,11 @@
 use lazy_static::lazy_static;
 use p3_poseidon2::{poseidon2_round_numbers_128, Poseidon2, Poseidon2ExternalMatrixGeneral};
 
-use crate::params::{poseidon2, Challenger, FieldElementMap, Plonky3Field};
+use crate::params::{
+    byte_hash::{challenger_64, val_mmcs_64, ByteHash, Challenger64, ValMmcs64},
+    poseidon2, Challenger, FieldElementMap, Plonky3Field,
+};
+use crate::FriOptions;
 use p3_challenger::DuplexChallenger;
 use p3_commit::ExtensionMmcs;
 use p3_dft::Radix2DitParallel;
@@ -49,10 +53,6 @@ type ChallengeMmcs = ExtensionMmcs<Goldilocks, FriChallenge, ValMmcs>;
 type Dft = Radix2DitParallel<Goldilocks>;
 type MyPcs = TwoAdicFriPcs<Goldilocks, Dft, ValMmcs, ChallengeMmcs>;
 
-const FRI_LOG_BLOWUP: usize = 1;
-const FRI_NUM_QUERIES: usize = 100;
-const FRI_PROOF_OF_WORK_BITS: usize = 16;
-
 lazy_static! {
     static ref ROUNDS: (usize, usize) = poseidon2_round_numbers_128::<Goldilocks>(WIDTH, D);
     static ref ROUNDS_F: usize = ROUNDS.0;
@@ -82,7 +82,7 @@ impl FieldElementMap for GoldilocksField {
         FriChallenger::new(PERM_GL.clone())
     }
 
-    fn get_config() -> Self::Config {
+    fn get_config(fri: &FriOptions) -> Self::Config {
         let hash = Hash::new(PERM_GL.clone());
 
         let compress = Compress::new(PERM_GL.clone());
@@ -94,9 +94,9 @@ impl FieldElementMap for GoldilocksField {
         let dft = Dft::default();
 
         let fri_config = FriConfig {
-            log_blowup: FRI_LOG_BLOWUP,
-            num_queries: FRI_NUM_QUERIES,
-            proof_of_work_bits: FRI_PROOF_OF_WORK_BITS,
+            log_blowup: fri.log_blowup,
+            num_queries: fri.num_queries,
+            proof_of_work_bits: fri.proof_of_work_bits,
             mmcs: challenge_mmcs,
         };
 
@@ -105,3 +105,40 @@ impl FieldElementMap for GoldilocksField {
         Self::Config::new(pcs)
     }
 }
+
+type ByteHashValMmcs<H> = ValMmcs64<Goldilocks, H>;
+type ByteHashChallengeMmcs<H> = ExtensionMmcs<Goldilocks, FriChallenge, ByteHashValMmcs<H>>;
+type ByteHashPcs<H> = TwoAdicFriPcs<Goldilocks, Dft, ByteHashValMmcs<H>, ByteHashChallengeMmcs<H>>;
+
+impl<H: ByteHash> FieldElementMap<H> for GoldilocksField {
+    type Config = StarkConfig<ByteHashPcs<H>, FriChallenge, Challenger64<Goldilocks, H>>;
+
+    fn into_p3_field(self) -> Plonky3Field<Self, H> {
+        Goldilocks::from_canonical_u64(self.to_integer().try_into_u64().unwrap())
+    }
+
+    fn from_p3_field(e: Plonky3Field<Self, H>) -> Self {
+        Self::from(e.as_canonical_u64())
+    }
+
+    fn get_challenger() -> Challenger<Self, H> {
+        challenger_64::<Goldilocks, H>()
+    }
+
+    fn get_config(fri: &FriOptions) -> Self::Config {
+        let val_mmcs = val_mmcs_64::<Goldilocks, H>();
+
+        let challenge_mmcs = ByteHashChallengeMmcs::<H>::new(val_mmcs.clone());
+
+        let fri_config = FriConfig {
+            log_blowup: fri.log_blowup,
+            num_queries: fri.num_queries,
+            proof_of_work_bits: fri.proof_of_work_bits,
+            mmcs: challenge_mmcs,
+        };
+
+        let pcs = ByteHashPcs::<H>::new(Dft::default(), val_mmcs, fri_config);
+
+        Self::Config::new(pcs)
+    }
+}
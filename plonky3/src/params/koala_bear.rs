@@ -4,7 +4,11 @@
 
 use lazy_static::lazy_static;
 
-use crate::params::{Challenger, FieldElementMap, Plonky3Field};
+use crate::params::{
+    byte_hash::{challenger_32, val_mmcs_32, ByteHash, Challenger32, ValMmcs32},
+    Challenger, FieldElementMap, Plonky3Field,
+};
+use crate::FriOptions;
 use p3_challenger::DuplexChallenger;
 use p3_commit::ExtensionMmcs;
 use p3_dft::Radix2DitParallel;
@@ -49,10 +53,6 @@ type ChallengeMmcs = ExtensionMmcs<KoalaBear, FriChallenge, ValMmcs>;
 type Dft = Radix2DitParallel<KoalaBear>;
 type MyPcs = TwoAdicFriPcs<KoalaBear, Dft, ValMmcs, ChallengeMmcs>;
 
-const FRI_LOG_BLOWUP: usize = 1;
-const FRI_NUM_QUERIES: usize = 100;
-const FRI_PROOF_OF_WORK_BITS: usize = 16;
-
 lazy_static! {
     static ref ROUNDS: (usize, usize) = poseidon2_round_numbers_128::<KoalaBear>(WIDTH, D);
     static ref ROUNDS_F: usize = ROUNDS.0;
@@ -81,7 +81,7 @@ impl FieldElementMap for KoalaBearField {
         FriChallenger::new(PERM_BB.clone())
     }
 
-    fn get_config() -> Self::Config {
+    fn get_config(fri: &FriOptions) -> Self::Config {
         let hash = Hash::new(PERM_BB.clone());
 
         let compress = Compress::new(PERM_BB.clone());
@@ -93,9 +93,9 @@ impl FieldElementMap for KoalaBearField {
         let dft = Dft::default();
 
         let fri_config = FriConfig {
-            log_blowup: FRI_LOG_BLOWUP,
-            num_queries: FRI_NUM_QUERIES,
-            proof_of_work_bits: FRI_PROOF_OF_WORK_BITS,
+            log_blowup: fri.log_blowup,
+            num_queries: fri.num_queries,
+            proof_of_work_bits: fri.proof_of_work_bits,
             mmcs: challenge_mmcs,
         };
 
@@ -104,3 +104,40 @@ impl FieldElementMap for KoalaBearField {
         Self::Config::new(pcs)
     }
 }
+
+type ByteHashValMmcs<H> = ValMmcs32<KoalaBear, H>;
+type ByteHashChallengeMmcs<H> = ExtensionMmcs<KoalaBear, FriChallenge, ByteHashValMmcs<H>>;
+type ByteHashPcs<H> = TwoAdicFriPcs<KoalaBear, Dft, ByteHashValMmcs<H>, ByteHashChallengeMmcs<H>>;
+
+impl<H: ByteHash> FieldElementMap<H> for KoalaBearField {
+    type Config = StarkConfig<ByteHashPcs<H>, FriChallenge, Challenger32<KoalaBear, H>>;
+
+    fn into_p3_field(self) -> Plonky3Field<Self, H> {
+        self.into_inner()
+    }
+
+    fn from_p3_field(e: Plonky3Field<Self, H>) -> Self {
+        KoalaBearField::from_inner(e)
+    }
+
+    fn get_challenger() -> Challenger<Self, H> {
+        challenger_32::<KoalaBear, H>()
+    }
+
+    fn get_config(fri: &FriOptions) -> Self::Config {
+        let val_mmcs = val_mmcs_32::<KoalaBear, H>();
+
+        let challenge_mmcs = ByteHashChallengeMmcs::<H>::new(val_mmcs.clone());
+
+        let fri_config = FriConfig {
+            log_blowup: fri.log_blowup,
+            num_queries: fri.num_queries,
+            proof_of_work_bits: fri.proof_of_work_bits,
+            mmcs: challenge_mmcs,
+        };
+
+        let pcs = ByteHashPcs::<H>::new(Dft::default(), val_mmcs, fri_config);
+
+        Self::Config::new(pcs)
+    }
+}
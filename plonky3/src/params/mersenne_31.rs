@@ -6,7 +6,11 @@ use core::marker::PhantomData;
 
 use lazy_static::lazy_static;
 
-use crate::params::{poseidon2, Challenger, FieldElementMap, Plonky3Field};
+use crate::params::{
+    byte_hash::{challenger_32, val_mmcs_32, ByteHash, Challenger32, ValMmcs32},
+    poseidon2, Challenger, FieldElementMap, Plonky3Field,
+};
+use crate::FriOptions;
 use p3_challenger::DuplexChallenger;
 use p3_circle::CirclePcs;
 use p3_commit::ExtensionMmcs;
@@ -48,10 +52,6 @@ type ValMmcs = MerkleTreeMmcs<
 type ChallengeMmcs = ExtensionMmcs<Mersenne31, FriChallenge, ValMmcs>;
 type Pcs = CirclePcs<Mersenne31, ValMmcs, ChallengeMmcs>;
 
-const FRI_LOG_BLOWUP: usize = 1;
-const FRI_NUM_QUERIES: usize = 100;
-const FRI_PROOF_OF_WORK_BITS: usize = 16;
-
 lazy_static! {
     static ref ROUNDS: (usize, usize) = poseidon2_round_numbers_128::<Mersenne31>(WIDTH, D);
     static ref ROUNDS_F: usize = ROUNDS.0;
@@ -80,7 +80,7 @@ impl FieldElementMap for Mersenne31Field {
         FriChallenger::new(PERM_M31.clone())
     }
 
-    fn get_config() -> Self::Config {
+    fn get_config(fri: &FriOptions) -> Self::Config {
         let hash = Hash::new(PERM_M31.clone());
 
         let compress = Compress::new(PERM_M31.clone());
@@ -90,9 +90,9 @@ impl FieldElementMap for Mersenne31Field {
         let challenge_mmcs = ChallengeMmcs::new(val_mmcs.clone());
 
         let fri_config = FriConfig {
-            log_blowup: FRI_LOG_BLOWUP,
-            num_queries: FRI_NUM_QUERIES,
-            proof_of_work_bits: FRI_PROOF_OF_WORK_BITS,
+            log_blowup: fri.log_blowup,
+            num_queries: fri.num_queries,
+            proof_of_work_bits: fri.proof_of_work_bits,
             mmcs: challenge_mmcs,
         };
 
@@ -105,3 +105,44 @@ impl FieldElementMap for Mersenne31Field {
         Self::Config::new(pcs)
     }
 }
+
+type ByteHashValMmcs<H> = ValMmcs32<Mersenne31, H>;
+type ByteHashChallengeMmcs<H> = ExtensionMmcs<Mersenne31, FriChallenge, ByteHashValMmcs<H>>;
+type ByteHashPcs<H> = CirclePcs<Mersenne31, ByteHashValMmcs<H>, ByteHashChallengeMmcs<H>>;
+
+impl<H: ByteHash> FieldElementMap<H> for Mersenne31Field {
+    type Config = StarkConfig<ByteHashPcs<H>, FriChallenge, Challenger32<Mersenne31, H>>;
+
+    fn into_p3_field(self) -> Plonky3Field<Self, H> {
+        self.into_inner()
+    }
+
+    fn from_p3_field(e: Plonky3Field<Self, H>) -> Self {
+        Self::from_inner(e)
+    }
+
+    fn get_challenger() -> Challenger<Self, H> {
+        challenger_32::<Mersenne31, H>()
+    }
+
+    fn get_config(fri: &FriOptions) -> Self::Config {
+        let val_mmcs = val_mmcs_32::<Mersenne31, H>();
+
+        let challenge_mmcs = ByteHashChallengeMmcs::<H>::new(val_mmcs.clone());
+
+        let fri_config = FriConfig {
+            log_blowup: fri.log_blowup,
+            num_queries: fri.num_queries,
+            proof_of_work_bits: fri.proof_of_work_bits,
+            mmcs: challenge_mmcs,
+        };
+
+        let pcs = ByteHashPcs::<H> {
+            mmcs: val_mmcs,
+            fri_config,
+            _phantom: PhantomData,
+        };
+
+        Self::Config::new(pcs)
+    }
+}
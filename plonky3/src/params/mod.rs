@@ -1,4 +1,5 @@
 pub mod baby_bear;
+pub mod byte_hash;
 pub mod goldilocks;
 pub mod koala_bear;
 pub mod mersenne_31;
@@ -9,27 +10,49 @@ use powdr_number::FieldElement;
 
 use p3_commit::PolynomialSpace;
 
-pub type Plonky3Field<T> =
-    <<Pcs<T> as p3_commit::Pcs<Challenge<T>, Challenger<T>>>::Domain as PolynomialSpace>::Val;
-pub type Pcs<T> = <<T as FieldElementMap>::Config as StarkGenericConfig>::Pcs;
-pub type Challenge<T> = <<T as FieldElementMap>::Config as StarkGenericConfig>::Challenge;
-pub type Challenger<T> = <<T as FieldElementMap>::Config as StarkGenericConfig>::Challenger;
+use crate::{FriOptions, HashFunction};
 
-pub type ProverData<F> = <Pcs<F> as p3_commit::Pcs<Challenge<F>, Challenger<F>>>::ProverData;
-pub type Commitment<F> = <Pcs<F> as p3_commit::Pcs<Challenge<F>, Challenger<F>>>::Commitment;
+pub use byte_hash::{Blake3Hash, KeccakHash};
 
-pub trait FieldElementMap: FieldElement
+pub type Plonky3Field<T, H = Poseidon2Hash> = <<Pcs<T, H> as p3_commit::Pcs<
+    Challenge<T, H>,
+    Challenger<T, H>,
+>>::Domain as PolynomialSpace>::Val;
+pub type Pcs<T, H = Poseidon2Hash> = <<T as FieldElementMap<H>>::Config as StarkGenericConfig>::Pcs;
+pub type Challenge<T, H = Poseidon2Hash> =
+    <<T as FieldElementMap<H>>::Config as StarkGenericConfig>::Challenge;
+pub type Challenger<T, H = Poseidon2Hash> =
+    <<T as FieldElementMap<H>>::Config as StarkGenericConfig>::Challenger;
+
+pub type ProverData<F, H = Poseidon2Hash> =
+    <Pcs<F, H> as p3_commit::Pcs<Challenge<F, H>, Challenger<F, H>>>::ProverData;
+pub type Commitment<F, H = Poseidon2Hash> =
+    <Pcs<F, H> as p3_commit::Pcs<Challenge<F, H>, Challenger<F, H>>>::Commitment;
+
+/// A hash function used for the Merkle tree commitments and the challenger.
+pub trait CommitmentHash: Send + Sync + 'static {
+    const FUNCTION: HashFunction;
+}
+
+/// Poseidon2 over the native field, the default.
+pub struct Poseidon2Hash;
+
+impl CommitmentHash for Poseidon2Hash {
+    const FUNCTION: HashFunction = HashFunction::Poseidon2;
+}
+
+pub trait FieldElementMap<H: CommitmentHash = Poseidon2Hash>: FieldElement
 where
-    ProverData<Self>: Send,
-    Commitment<Self>: Send,
+    ProverData<Self, H>: Send,
+    Commitment<Self, H>: Send,
 {
     type Config: StarkGenericConfig;
 
-    fn into_p3_field(self) -> Plonky3Field<Self>;
+    fn into_p3_field(self) -> Plonky3Field<Self, H>;
 
-    fn from_p3_field(_: Plonky3Field<Self>) -> Self;
+    fn from_p3_field(_: Plonky3Field<Self, H>) -> Self;
 
-    fn get_challenger() -> Challenger<Self>;
+    fn get_challenger() -> Challenger<Self, H>;
 
-    fn get_config() -> Self::Config;
+    fn get_config(fri: &FriOptions) -> Self::Config;
 }
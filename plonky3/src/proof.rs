@@ -7,6 +7,8 @@ use serde::{Deserialize, Serialize};
 
 use p3_uni_stark::{StarkGenericConfig, Val};
 
+use crate::Plonky3Options;
+
 pub type Com<SC> = <<SC as StarkGenericConfig>::Pcs as Pcs<
     <SC as StarkGenericConfig>::Challenge,
     <SC as StarkGenericConfig>::Challenger,
@@ -73,6 +75,9 @@ pub struct TableProvingKey<SC: StarkGenericConfig> {
 #[derive(Serialize, Deserialize)]
 #[serde(bound = "")]
 pub struct StarkVerifyingKey<SC: StarkGenericConfig> {
+    // the options the key was generated with. This comes first, so that it can be
+    // deserialized before knowing which hash function the rest of the key uses.
+    pub options: Plonky3Options,
     // for each table, for each possible size, the commitment
     pub preprocessed: BTreeMap<String, TableVerifyingKeyCollection<SC>>,
 }
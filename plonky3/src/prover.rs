@@ -17,7 +17,7 @@ use p3_util::log2_strict_usize;
 use tracing::{info_span, instrument};
 
 use crate::circuit_builder::{generate_matrix, PowdrCircuit, PowdrTable};
-use crate::params::{Challenge, Challenger, Pcs};
+use crate::params::{Challenge, Challenger, CommitmentHash, Pcs};
 use crate::proof::{OpenedValues, StageOpenedValues};
 use crate::symbolic_builder::{get_log_quotient_degree, SymbolicAirBuilder};
 use crate::traits::MultiStageAir;
@@ -28,18 +28,18 @@ use crate::{
 };
 use p3_uni_stark::{Domain, PackedChallenge, PackedVal, StarkGenericConfig, Val};
 
-pub(crate) struct MultiTable<'a, T: FieldElementMap>
+pub(crate) struct MultiTable<'a, T: FieldElementMap<H>, H: CommitmentHash>
 where
-    ProverData<T>: Send,
-    Commitment<T>: Send,
+    ProverData<T, H>: Send,
+    Commitment<T, H>: Send,
 {
-    pub(crate) tables: BTreeMap<String, Table<'a, T>>,
+    pub(crate) tables: BTreeMap<String, Table<'a, T, H>>,
 }
 
-impl<'a, T: FieldElementMap> MultiTable<'a, T>
+impl<'a, T: FieldElementMap<H>, H: CommitmentHash> MultiTable<'a, T, H>
 where
-    ProverData<T>: Send,
-    Commitment<T>: Send,
+    ProverData<T, H>: Send,
+    Commitment<T, H>: Send,
 {
     fn table_count(&self) -> usize {
         self.tables.len()
@@ -64,7 +64,7 @@ where
     }
 
     /// Observe the instance for each table.
-    fn observe_instances(&self, challenger: &mut Challenger<T>) {
+    fn observe_instances(&self, challenger: &mut Challenger<T, H>) {
         for input in self.tables.values() {
             input.observe_instance(challenger);
         }
@@ -86,10 +86,10 @@ where
     /// The commitment and the prover data.
     fn compute_and_commit_to_quotient(
         &self,
-        state: &mut ProverState<'a, T>,
+        state: &mut ProverState<'a, T, H>,
         proving_key: Option<&StarkProvingKey<T::Config>>,
     ) -> (Com<T::Config>, PcsProverData<T::Config>) {
-        let alpha: Challenge<T> = state.challenger.sample_ext_element();
+        let alpha: Challenge<T, H> = state.challenger.sample_ext_element();
 
         // get the quotient domains and chunks for each table
         let quotient_domains_and_chunks: Vec<_> = self
@@ -125,11 +125,11 @@ where
     /// Opens the commitments to the preprocessed trace, the traces, and the quotient polynomial.
     fn open(
         &self,
-        state: &mut ProverState<T>,
+        state: &mut ProverState<T, H>,
         proving_key: Option<&StarkProvingKey<T::Config>>,
         quotient_data: PcsProverData<T::Config>,
-    ) -> (OpenedValues<Challenge<T>>, PcsProof<T::Config>) {
-        let zeta: Challenge<T> = state.challenger.sample();
+    ) -> (OpenedValues<Challenge<T, H>>, PcsProof<T::Config>) {
+        let zeta: Challenge<T, H> = state.challenger.sample();
 
         let preprocessed_data_and_opening_points = proving_key
             .as_ref()
@@ -219,7 +219,7 @@ where
 
         // get values for the quotient
         let mut value = opened_values.next().unwrap().into_iter();
-        let quotient_chunks: Vec<Vec<Vec<Challenge<T>>>> = self
+        let quotient_chunks: Vec<Vec<Vec<Challenge<T, H>>>> = self
             .tables
             .values()
             .map(|i| {
@@ -280,25 +280,25 @@ where
 }
 
 /// A sub-table to be proven, in the form of an air and a degree
-pub(crate) struct Table<'a, T: FieldElementMap>
+pub(crate) struct Table<'a, T: FieldElementMap<H>, H: CommitmentHash>
 where
-    ProverData<T>: Send,
-    Commitment<T>: Send,
+    ProverData<T, H>: Send,
+    Commitment<T, H>: Send,
 {
-    air: PowdrTable<'a, T>,
+    air: PowdrTable<'a, T, H>,
     degree: usize,
 }
 
-impl<'a, T: FieldElementMap> Table<'a, T>
+impl<'a, T: FieldElementMap<H>, H: CommitmentHash> Table<'a, T, H>
 where
-    ProverData<T>: Send,
-    Commitment<T>: Send,
+    ProverData<T, H>: Send,
+    Commitment<T, H>: Send,
 {
     fn log_degree(&self) -> usize {
         log2_strict_usize(self.degree)
     }
 
-    fn trace_domain(&self, pcs: &Pcs<T>) -> Domain<T::Config> {
+    fn trace_domain(&self, pcs: &Pcs<T, H>) -> Domain<T::Config> {
         pcs.natural_domain_for_degree(self.degree)
     }
 
@@ -314,7 +314,7 @@ where
         get_log_quotient_degree(&self.air, &self.public_input_count_per_stage())
     }
 
-    fn observe_instance(&self, challenger: &mut Challenger<T>) {
+    fn observe_instance(&self, challenger: &mut Challenger<T, H>) {
         challenger.observe(Val::<T::Config>::from_canonical_usize(self.log_degree()));
         // TODO: Might be best practice to include other instance data here; see verifier comment.
     }
@@ -328,9 +328,9 @@ where
     fn quotient_domains_and_chunks(
         &self,
         table_index: usize,
-        state: &ProverState<T>,
+        state: &ProverState<T, H>,
         table_preprocessed_data: Option<&TableProvingKeyCollection<T::Config>>,
-        alpha: Challenge<T>,
+        alpha: Challenge<T, H>,
     ) -> impl Iterator<Item = (Domain<T::Config>, DenseMatrix<Val<T::Config>>)> {
         let quotient_domain = self
             .trace_domain(state.pcs)
@@ -390,15 +390,16 @@ where
 
 #[instrument(skip_all)]
 #[allow(clippy::multiple_bound_locations)] // cfg not supported in where clauses?
-pub fn prove<T: FieldElementMap>(
+pub fn prove<T: FieldElementMap<H>, H: CommitmentHash>(
+    config: &T::Config,
     proving_key: Option<&StarkProvingKey<T::Config>>,
-    program: &PowdrCircuit<T>,
+    program: &PowdrCircuit<T, H>,
     witness: &mut Vec<(String, Vec<T>)>,
-    challenger: &mut Challenger<T>,
+    challenger: &mut Challenger<T, H>,
 ) -> Proof<T::Config>
 where
-    ProverData<T>: Send,
-    Commitment<T>: Send,
+    ProverData<T, H>: Send,
+    Commitment<T, H>: Send,
 {
     let (tables, stage_0): (BTreeMap<_, _>, BTreeMap<_, _>) = program
         .split
@@ -418,7 +419,7 @@ where
                 (
                     name.clone(),
                     AirStage {
-                        trace: generate_matrix(
+                        trace: generate_matrix::<T, H>(
                             columns.iter().map(|(name, values)| (name, values.as_ref())),
                         ),
                         public_values: constraint_system.publics_by_stage[0]
@@ -441,9 +442,7 @@ where
         panic!("No tables to prove");
     }
 
-    let multi_table = MultiTable { tables };
-
-    let config = T::get_config();
+    let multi_table: MultiTable<T, H> = MultiTable { tables };
 
     assert_eq!(stage_0.keys().collect_vec(), multi_table.table_names());
 
@@ -629,24 +628,24 @@ where
         .collect()
 }
 
-struct ProverState<'a, T: FieldElementMap>
+struct ProverState<'a, T: FieldElementMap<H>, H: CommitmentHash>
 where
-    ProverData<T>: Send,
-    Commitment<T>: Send,
+    ProverData<T, H>: Send,
+    Commitment<T, H>: Send,
 {
-    pub(crate) program: &'a MultiTable<'a, T>,
+    pub(crate) program: &'a MultiTable<'a, T, H>,
     pub(crate) processed_stages: Vec<ProcessedStage<T::Config>>,
-    pub(crate) challenger: &'a mut Challenger<T>,
-    pub(crate) pcs: &'a Pcs<T>,
+    pub(crate) challenger: &'a mut Challenger<T, H>,
+    pub(crate) pcs: &'a Pcs<T, H>,
 }
 
-impl<'a, T: FieldElementMap> ProverState<'a, T>
+impl<'a, T: FieldElementMap<H>, H: CommitmentHash> ProverState<'a, T, H>
 where
-    ProverData<T>: Send,
-    Commitment<T>: Send,
+    ProverData<T, H>: Send,
+    Commitment<T, H>: Send,
 {
     pub(crate) fn new(
-        program: &'a MultiTable<'a, T>,
+        program: &'a MultiTable<'a, T, H>,
         pcs: &'a <T::Config as StarkGenericConfig>::Pcs,
         challenger: &'a mut <T::Config as StarkGenericConfig>::Challenger,
     ) -> Self {
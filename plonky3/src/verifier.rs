@@ -14,7 +14,7 @@ use p3_matrix::stack::VerticalPair;
 use tracing::instrument;
 
 use crate::circuit_builder::PowdrTable;
-use crate::params::{Challenge, Challenger, Commitment, Pcs, ProverData};
+use crate::params::{Challenge, Challenger, Commitment, CommitmentHash, Pcs, ProverData};
 use crate::symbolic_builder::{get_log_quotient_degree, SymbolicAirBuilder};
 use crate::{
     ConstraintSystem, FieldElementMap, MultiStageAir, Proof, StageOpenedValues, StarkVerifyingKey,
@@ -23,21 +23,21 @@ use crate::{
 use p3_uni_stark::{Domain, PcsError, StarkGenericConfig, Val};
 
 /// A sub-table to be proven, in the form of an air and values for the public inputs
-struct Table<'a, T: FieldElementMap>
+struct Table<'a, T: FieldElementMap<H>, H: CommitmentHash>
 where
-    ProverData<T>: Send,
-    Commitment<T>: Send,
+    ProverData<T, H>: Send,
+    Commitment<T, H>: Send,
 {
-    air: PowdrTable<'a, T>,
+    air: PowdrTable<'a, T, H>,
     preprocessed: Option<&'a TableVerifyingKeyCollection<T::Config>>,
-    opened_values: &'a TableOpenedValues<Challenge<T>>,
+    opened_values: &'a TableOpenedValues<Challenge<T, H>>,
     public_values_by_stage: &'a [Vec<Val<T::Config>>],
 }
 
-impl<'a, T: FieldElementMap> Table<'a, T>
+impl<'a, T: FieldElementMap<H>, H: CommitmentHash> Table<'a, T, H>
 where
-    ProverData<T>: Send,
-    Commitment<T>: Send,
+    ProverData<T, H>: Send,
+    Commitment<T, H>: Send,
 {
     fn get_log_quotient_degree(&self) -> usize {
         get_log_quotient_degree(
@@ -50,18 +50,18 @@ where
         )
     }
 
-    fn natural_domain(&self, pcs: &Pcs<T>) -> Domain<T::Config> {
+    fn natural_domain(&self, pcs: &Pcs<T, H>) -> Domain<T::Config> {
         let degree = 1 << self.opened_values.log_degree;
         pcs.natural_domain_for_degree(degree)
     }
 
-    fn preprocessed_commit(&self) -> Option<&Commitment<T>> {
+    fn preprocessed_commit(&self) -> Option<&Commitment<T, H>> {
         self.preprocessed
             .as_ref()
             .map(|preprocessed| &preprocessed[&(1 << self.opened_values.log_degree)])
     }
 
-    fn quotient_domains(&self, pcs: &Pcs<T>) -> Vec<Domain<T::Config>> {
+    fn quotient_domains(&self, pcs: &Pcs<T, H>) -> Vec<Domain<T::Config>> {
         let log_quotient_degree = self.get_log_quotient_degree();
         self.natural_domain(pcs)
             .create_disjoint_domain(1 << (self.opened_values.log_degree + log_quotient_degree))
@@ -70,16 +70,17 @@ where
 }
 
 #[instrument(skip_all)]
-pub fn verify<T: FieldElementMap>(
+pub fn verify<T: FieldElementMap<H>, H: CommitmentHash>(
+    config: &T::Config,
     verifying_key: Option<&StarkVerifyingKey<T::Config>>,
     split: &BTreeMap<&String, &ConstraintSystem<T>>,
-    challenger: &mut Challenger<T>,
+    challenger: &mut Challenger<T, H>,
     proof: &Proof<T::Config>,
     public_inputs: BTreeMap<String, Vec<Vec<T>>>,
 ) -> Result<(), VerificationError<PcsError<T::Config>>>
 where
-    ProverData<T>: Send,
-    Commitment<T>: Send,
+    ProverData<T, H>: Send,
+    Commitment<T, H>: Send,
 {
     let public_inputs = public_inputs
         .into_iter()
@@ -108,7 +109,7 @@ where
         return Err(VerificationError::InvalidProofShape);
     }
 
-    let tables: BTreeMap<&String, Table<_>> = split
+    let tables: BTreeMap<&String, Table<T, H>> = split
         .values()
         .zip_eq(public_inputs.iter())
         .zip_eq(opened_values.values())
@@ -129,8 +130,6 @@ where
         )
         .collect();
 
-    let config = T::get_config();
-
     let pcs = config.pcs();
 
     // TODO: Instead of hashing each commit separately, we could hash a summary of all the commitments,
@@ -195,10 +194,10 @@ where
         })
         .collect_vec();
 
-    let alpha: Challenge<T> = challenger.sample_ext_element();
+    let alpha: Challenge<T, H> = challenger.sample_ext_element();
     challenger.observe(commitments.quotient_chunks.clone());
 
-    let zeta: Challenge<T> = challenger.sample();
+    let zeta: Challenge<T, H> = challenger.sample();
 
     // for preprocessed commitments, we have one optional commitment per table, opened on the trace domain at `zeta` and `zeta_next`
     let preprocessed_domains_points_and_opens: Vec<(_, Vec<(_, _)>)> =
@@ -300,7 +299,7 @@ where
                         other_domain.zp_at_point(zeta)
                             * other_domain.zp_at_point(domain.first_point()).inverse()
                     })
-                    .product::<Challenge<T>>()
+                    .product::<Challenge<T, H>>()
             })
             .collect_vec();
 
@@ -369,12 +368,12 @@ where
     Ok(())
 }
 
-fn verify_opening_shape<T: FieldElementMap>(
+fn verify_opening_shape<T: FieldElementMap<H>, H: CommitmentHash>(
     table: &Table<'_, T>,
 ) -> Result<(), VerificationError<PcsError<T::Config>>>
 where
-    ProverData<T>: Send,
-    Commitment<T>: Send,
+    ProverData<T, H>: Send,
+    Commitment<T, H>: Send,
 {
     let log_quotient_degree = get_log_quotient_degree::<Val<T::Config>, _>(
         &table.air,
@@ -399,30 +398,29 @@ where
         .collect::<Vec<usize>>();
     let air_fixed_width =
         <_ as MultiStageAir<SymbolicAirBuilder<Val<T::Config>>>>::preprocessed_width(&table.air);
-    let res = table
-        .opened_values
-        .preprocessed
-        .as_ref()
-        .map(|StageOpenedValues { local, next }| {
-            local.len() == air_fixed_width && next.len() == air_fixed_width
-        })
-        .unwrap_or(true)
-        && table
+    let res =
+        table
             .opened_values
-            .traces_by_stage
-            .iter()
-            .zip_eq(&air_widths)
-            .all(|(StageOpenedValues { local, next }, air_width)| {
-                local.len() == *air_width && next.len() == *air_width
+            .preprocessed
+            .as_ref()
+            .map(|StageOpenedValues { local, next }| {
+                local.len() == air_fixed_width && next.len() == air_fixed_width
             })
-        && table.opened_values.quotient_chunks.len() == quotient_degree
-        && table
-            .opened_values
-            .quotient_chunks
-            .iter()
-            .all(|qc| qc.len() == <Challenge<T> as AbstractExtensionField<Val<T::Config>>>::D)
-        && table.public_values_by_stage.len() as u8 == stage_count
-        && challenge_counts.len() as u8 == stage_count;
+            .unwrap_or(true)
+            && table
+                .opened_values
+                .traces_by_stage
+                .iter()
+                .zip_eq(&air_widths)
+                .all(|(StageOpenedValues { local, next }, air_width)| {
+                    local.len() == *air_width && next.len() == *air_width
+                })
+            && table.opened_values.quotient_chunks.len() == quotient_degree
+            && table.opened_values.quotient_chunks.iter().all(|qc| {
+                qc.len() == <Challenge<T, H> as AbstractExtensionField<Val<T::Config>>>::D
+            })
+            && table.public_values_by_stage.len() as u8 == stage_count
+            && challenge_counts.len() as u8 == stage_count;
 
     res.then_some(())
         .ok_or(VerificationError::InvalidProofShape)
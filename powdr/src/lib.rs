@@ -15,23 +15,61 @@ pub use powdr_number::Bn254Field;
 pub use powdr_number::FieldElement;
 pub use powdr_number::GoldilocksField;
 
+use powdr_backend::ProofContainer;
+use powdr_number::KnownField;
+use riscv::committed_inputs::{input_digest, verify_inputs, InputDigest};
+use riscv::entropy::{verify_entropy_seed, EntropySeed, ENTROPY_SEED_FD};
+use riscv::exit::{verify_exit_code, ExitStatus, GuestError};
+use riscv::journal::Journal;
 use riscv::CompilerOptions;
+use serde::de::DeserializeOwned;
 
+use std::cell::RefCell;
 use std::fs::{self, File};
+use std::marker::PhantomData;
 use std::path::Path;
 use std::path::PathBuf;
 use std::time::Instant;
 
-#[derive(Default)]
-pub struct SessionBuilder {
+pub mod proof;
+
+pub use proof::Proof;
+
+/// Builds a [`Session`]. Use [`Session::builder`] for the Goldilocks field, and
+/// `SessionBuilder::<F>::default()` for other fields.
+pub struct SessionBuilder<F: FieldElement = GoldilocksField> {
     guest_path: String,
     out_path: String,
     chunk_size_log2: Option<u8>,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldElement> Default for SessionBuilder<F> {
+    fn default() -> Self {
+        Self {
+            guest_path: Default::default(),
+            out_path: Default::default(),
+            chunk_size_log2: None,
+            _marker: PhantomData,
+        }
+    }
 }
 
-pub struct Session {
-    pipeline: Pipeline<GoldilocksField>,
+/// Compiles, runs and proves a guest program, and verifies its proofs.
+/// Supports the BabyBear, KoalaBear and Goldilocks fields. Only Goldilocks
+/// supports continuations and committing data to the journal.
+pub struct Session<F: FieldElement = GoldilocksField> {
+    pipeline: Pipeline<F>,
     out_path: String,
+    journal: Option<Journal>,
+    exit_status: Option<ExitStatus>,
+    /// The digests of the data written to each input channel.
+    inputs: Vec<(u32, InputDigest)>,
+    /// Whether the guest must commit every input written to the session.
+    expect_committed_inputs: bool,
+    /// Whether proofs of guests that exited with a non-zero exit code are accepted.
+    allow_nonzero_exit_code: bool,
+    entropy_seed: Option<EntropySeed>,
 }
 
 const DEFAULT_PKEY: &str = "pkey.bin";
@@ -43,9 +81,9 @@ const DEFAULT_MAX_DEGREE_LOG: u8 = 20;
 // Minimum acceptable max degree.
 const DEFAULT_MIN_MAX_DEGREE_LOG: u8 = 18;
 
-impl SessionBuilder {
+impl<F: FieldElement> SessionBuilder<F> {
     /// Builds a session with the given parameters.
-    pub fn build(self) -> Session {
+    pub fn build(self) -> Session<F> {
         Session {
             pipeline: pipeline_from_guest(
                 &self.guest_path,
@@ -54,6 +92,12 @@ impl SessionBuilder {
                 self.chunk_size_log2.unwrap_or(DEFAULT_MAX_DEGREE_LOG),
             ),
             out_path: self.out_path,
+            journal: None,
+            exit_status: None,
+            inputs: vec![],
+            expect_committed_inputs: false,
+            allow_nonzero_exit_code: false,
+            entropy_seed: None,
         }
         .with_backend(powdr_backend::BackendType::Plonky3)
     }
@@ -74,7 +118,8 @@ impl SessionBuilder {
     /// Example: for a chunk size of 2^20, set chunk_size_log2 to 20.
     /// If the execution trace is longer than the 2^chunk_size_log2,
     /// the execution will be split into multiple chunks of length `2^chunk_size_log2`.
-    /// Each chunk will be proven separately.
+    /// Such executions cannot be proven by [`Session::prove`] yet, since the
+    /// links between the chunks cannot be verified.
     pub fn chunk_size_log2(mut self, chunk_size_log2: u8) -> Self {
         assert!(chunk_size_log2 >= DEFAULT_MIN_MAX_DEGREE_LOG);
         self.chunk_size_log2 = Some(chunk_size_log2);
@@ -86,12 +131,14 @@ impl Session {
     pub fn builder() -> SessionBuilder {
         SessionBuilder::default()
     }
+}
 
-    pub fn into_pipeline(self) -> Pipeline<GoldilocksField> {
+impl<F: FieldElement> Session<F> {
+    pub fn into_pipeline(self) -> Pipeline<F> {
         self.pipeline
     }
 
-    pub fn pipeline(&self) -> &Pipeline<GoldilocksField> {
+    pub fn pipeline(&self) -> &Pipeline<F> {
         &self.pipeline
     }
 
@@ -102,23 +149,225 @@ impl Session {
         }
     }
 
-    pub fn write<S: serde::Serialize>(self, channel: u32, data: &S) -> Self {
+    pub fn write<S: serde::Serialize>(mut self, channel: u32, data: &S) -> Self {
+        self.inputs.push((channel, input_digest(data)));
         Session {
             pipeline: self.pipeline.add_data(channel, data),
             ..self
         }
     }
 
-    pub fn run(&mut self) {
-        run(&mut self.pipeline);
+    /// Requires the guest to commit every input written to this session with
+    /// [`Session::write`], which is checked when proving and verifying. Use this
+    /// for guests built with the `commit_inputs` feature of
+    /// `powdr-riscv-runtime`; proofs of guests that did not read and commit all
+    /// of these inputs are rejected.
+    pub fn expect_committed_inputs(self) -> Self {
+        Session {
+            expect_committed_inputs: true,
+            ..self
+        }
+    }
+
+    /// Accepts proofs of guests that panicked or exited with a non-zero exit
+    /// code in [`Session::verify`]. By default, only proofs of guests that
+    /// terminated successfully are accepted.
+    pub fn allow_nonzero_exit_code(self) -> Self {
+        Session {
+            allow_nonzero_exit_code: true,
+            ..self
+        }
+    }
+
+    /// Sets the seed of the entropy source of a guest built with the
+    /// `seeded_rand` feature of `powdr-riscv-runtime`. The guest commits the
+    /// seed it used, and proving and verifying check that the guest used the
+    /// entropy source with exactly this seed.
+    pub fn entropy_seed(mut self, seed: EntropySeed) -> Self {
+        self.entropy_seed = Some(seed);
+        Session {
+            pipeline: self.pipeline.add_data(ENTROPY_SEED_FD, &seed),
+            ..self
+        }
+    }
+
+    /// Runs the guest in the fast executor. Fails if the guest panicked or
+    /// exited with a non-zero exit code.
+    pub fn run(&mut self) -> Result<(), GuestError> {
+        self.journal = None;
+        self.exit_status = None;
+        let (journal, exit_status) = run(&mut self.pipeline)?;
+        self.journal = Some(journal);
+        self.exit_status = exit_status.clone();
+        exit_status
+            .ok_or(GuestError::MissingExitCode)?
+            .into_result()
+    }
+
+    /// The data committed by the guest, available after `run` or `prove`.
+    /// After `prove`, the journal has been checked against the public values of the proof.
+    pub fn journal(&self) -> Option<&Journal> {
+        self.journal.as_ref()
+    }
+
+    /// How the guest terminated, available after `run` or `prove`.
+    pub fn exit_status(&self) -> Option<&ExitStatus> {
+        self.exit_status.as_ref()
+    }
+
+    /// Reads and deserializes the data written by the guest to the given
+    /// output channel, available after `run` or `prove`.
+    /// Unlike the journal, this data is not committed.
+    pub fn output<T: DeserializeOwned>(&self, fd: u32) -> Result<T, String> {
+        self.pipeline.host_context().read(fd)
+    }
+
+    /// Proves the execution of the guest. The proof can be serialized with
+    /// [`Proof::to_bytes`] and verified by another session built for the same
+    /// guest with [`Session::verify`].
+    /// A guest that exited with a non-zero exit code, or that panicked when
+    /// built with the `provable_panic` feature of `powdr-riscv-runtime`, can be
+    /// proven as well, the proof then attests to its exit code.
+    ///
+    /// Fails if the execution does not fit into a single chunk, see
+    /// [`SessionBuilder::chunk_size_log2`], since the links between the
+    /// chunks of a proof cannot be verified yet.
+    pub fn prove(&mut self) -> Result<Proof<F>, String> {
+        self.setup()?;
+
+        let (journal, exit_status, chunks) = prove(&mut self.pipeline)?;
+
+        // If the guest committed its inputs and entropy seed, they must be the
+        // ones we wrote.
+        if uses_continuations::<F>() {
+            let publics = self.pipeline.publics().map_err(|e| e.join("\n"))?;
+            if self.expect_committed_inputs {
+                verify_inputs(&publics, &self.inputs)?;
+            }
+            if let Some(seed) = &self.entropy_seed {
+                verify_entropy_seed(&publics, seed)?;
+            }
+        }
+
+        let proof = Proof::new(chunks, &journal, exit_status.code());
+        self.journal = Some(journal);
+        self.exit_status = Some(exit_status);
+        Ok(proof)
+    }
+
+    /// Verifies a proof created by [`Session::prove`] for the same guest.
+    /// Proofs of a non-zero exit code are rejected, unless they are allowed
+    /// with [`Session::allow_nonzero_exit_code`].
+    /// Also checks that the journal and the exit code of the proof are the
+    /// ones committed by the guest, and, if committed inputs are expected
+    /// (see [`Session::expect_committed_inputs`]) or an entropy seed was
+    /// written to this session, that the inputs and the seed committed by the
+    /// guest are the ones written.
+    ///
+    /// Only Goldilocks proofs can carry a journal, committed inputs or a seed;
+    /// on other fields, the guest can only be proven with exit code 0, and
+    /// proofs claiming anything else are rejected.
+    /// The links between the chunks of a proof using continuations are not
+    /// checked yet, so proofs with more than one chunk are rejected.
+    pub fn verify(&mut self, proof: &Proof<F>) -> Result<(), String> {
+        self.setup()?;
+
+        if proof.exit_code() != 0 && !self.allow_nonzero_exit_code {
+            return Err(format!(
+                "Proof attests that the guest exited with code {}",
+                proof.exit_code()
+            ));
+        }
+
+        let chunk = match proof.chunks() {
+            [] => return Err("Proof does not contain any chunk".to_string()),
+            [chunk] => chunk,
+            chunks => {
+                return Err(format!(
+                    "Proof contains {} chunks, but the links between chunks cannot be verified yet",
+                    chunks.len()
+                ))
+            }
+        };
+        let chunk_publics = chunk
+            .publics::<F>()
+            .map_err(|e| format!("Invalid public values in the proof: {e:?}"))?;
+        // The container records the backend, the PIL and the verification key,
+        // which the pipeline checks against its own.
+        self.pipeline
+            .verify(&chunk.to_bytes(), &[chunk_publics.clone()])
+            .map_err(|e| e.join("\n"))?;
+
+        if !uses_continuations::<F>() {
+            return self.verify_without_public_outputs(proof);
+        }
+
+        let publics = self
+            .pipeline
+            .compute_optimized_pil()
+            .map_err(|e| e.join("\n"))?
+            .public_declarations_in_source_order()
+            .map(|(name, _)| name.clone())
+            .zip(chunk_publics.into_iter().map(Some))
+            .collect::<Vec<_>>();
+        proof.journal().verify(&publics)?;
+        verify_exit_code(&publics, proof.exit_code())?;
+        if self.expect_committed_inputs {
+            verify_inputs(&publics, &self.inputs)?;
+        }
+        if let Some(seed) = &self.entropy_seed {
+            verify_entropy_seed(&publics, seed)?;
+        }
+
+        Ok(())
+    }
+
+    /// On fields without the Poseidon coprocessor, programs committing a
+    /// journal, their inputs or a seed cannot be compiled, and the machine
+    /// fails on a non-zero exit code, so only proofs with an empty journal and
+    /// exit code 0 are valid.
+    fn verify_without_public_outputs(&self, proof: &Proof<F>) -> Result<(), String> {
+        let field = F::known_field().unwrap();
+        if !proof.journal().bytes().is_empty() {
+            return Err(format!(
+                "Proof contains a journal, but a journal cannot be committed on field {field}"
+            ));
+        }
+        if proof.exit_code() != 0 {
+            return Err(format!(
+                "Proof claims exit code {}, but only exit code 0 can be proven on field {field}",
+                proof.exit_code()
+            ));
+        }
+        if self.expect_committed_inputs {
+            return Err(format!("Inputs cannot be committed on field {field}"));
+        }
+        if self.entropy_seed.is_some() {
+            return Err(format!(
+                "The entropy seed cannot be committed on field {field}"
+            ));
+        }
+        Ok(())
     }
 
-    pub fn prove(&mut self) {
-        let asm_name = self.pipeline.asm_string().unwrap().0.clone().unwrap();
+    /// Computes the fixed columns and sets up the backend, re-using the
+    /// artifacts in the output directory if the program did not change.
+    fn setup(&mut self) -> Result<(), String> {
+        let asm_name = self
+            .pipeline
+            .asm_string()
+            .map_err(|e| e.join("\n"))?
+            .0
+            .clone()
+            .unwrap();
         let pil_file = pil_file_path(&asm_name);
 
         let generate_artifacts = if let Ok(existing_pil) = fs::read_to_string(&pil_file) {
-            let computed_pil = self.pipeline.compute_optimized_pil().unwrap().to_string();
+            let computed_pil = self
+                .pipeline
+                .compute_optimized_pil()
+                .map_err(|e| e.join("\n"))?
+                .to_string();
             if existing_pil != computed_pil {
                 log::info!("Compiled PIL changed, invalidating artifacts...");
                 true
@@ -136,8 +385,10 @@ impl Session {
 
         if generate_artifacts {
             println!("Creating program ZK setup. This has to be done only once per program.");
-            self.pipeline.compute_fixed_cols().unwrap();
-            self.pipeline.setup_backend().unwrap();
+            self.pipeline
+                .compute_fixed_cols()
+                .map_err(|e| e.join("\n"))?;
+            self.pipeline.setup_backend().map_err(|e| e.join("\n"))?;
             self.export_setup();
             self.pipeline.set_pkey_file(pkey.clone());
             self.pipeline.set_vkey_file(vkey.clone());
@@ -150,22 +401,23 @@ impl Session {
             {
                 log::info!("Read constants from file...");
             } else {
-                self.pipeline.compute_fixed_cols().unwrap();
+                self.pipeline
+                    .compute_fixed_cols()
+                    .map_err(|e| e.join("\n"))?;
             }
 
             if pkey.exists() && vkey.exists() {
                 log::info!("Re-using proving and verification keys...");
                 self.pipeline.set_pkey_file(pkey.clone());
                 self.pipeline.set_vkey_file(vkey.clone());
-                self.pipeline.setup_backend().unwrap();
+                self.pipeline.setup_backend().map_err(|e| e.join("\n"))?;
             } else {
                 self.export_setup();
                 self.pipeline.set_pkey_file(pkey.clone());
                 self.pipeline.set_vkey_file(vkey.clone());
             }
         }
-
-        prove(&mut self.pipeline);
+        Ok(())
     }
 
     pub fn export_setup(&mut self) {
@@ -183,85 +435,164 @@ impl Session {
     }
 }
 
+/// Only Goldilocks supports continuations, which also provides the Poseidon
+/// coprocessor needed to commit the journal.
+fn uses_continuations<F: FieldElement>() -> bool {
+    F::known_field() == Some(KnownField::GoldilocksField)
+}
+
 fn pil_file_path(asm_name: &Path) -> PathBuf {
     let file_stem = asm_name.file_stem().unwrap().to_str().unwrap();
     let opt_file_stem = format!("{file_stem}_opt");
     asm_name.with_file_name(opt_file_stem).with_extension("pil")
 }
 
-pub fn build_guest(
+pub fn build_guest<F: FieldElement>(
     guest_path: &str,
     out_path: &Path,
     min_degree_log: u8,
     max_degree_log: u8,
 ) -> (PathBuf, String) {
-    riscv::compile_rust(
-        guest_path,
+    let field = F::known_field().unwrap();
+    let options = if uses_continuations::<F>() {
         CompilerOptions::new_gl()
             .with_poseidon()
             .with_continuations()
+    } else {
+        CompilerOptions::new(field, Default::default(), false)
+    };
+    riscv::compile_rust(
+        guest_path,
+        options
             .with_min_degree_log(min_degree_log)
             .with_max_degree_log(max_degree_log),
         out_path,
         true,
         None,
     )
-    .ok_or_else(|| vec!["could not compile rust".to_string()])
     .unwrap()
 }
 
-pub fn pipeline_from_guest(
+pub fn pipeline_from_guest<F: FieldElement>(
     guest_path: &str,
     out_path: &Path,
     min_degree_log: u8,
     max_degree_log: u8,
-) -> Pipeline<GoldilocksField> {
+) -> Pipeline<F> {
     println!("Compiling guest program...");
 
     let (asm_file_path, asm_contents) =
-        build_guest(guest_path, out_path, min_degree_log, max_degree_log);
+        build_guest::<F>(guest_path, out_path, min_degree_log, max_degree_log);
 
     // Create a pipeline from the asm program
-    Pipeline::<GoldilocksField>::default()
+    Pipeline::<F>::default()
         .from_asm_string(asm_contents.clone(), Some(asm_file_path.clone()))
         .with_output(out_path.into(), true)
 }
 
-pub fn run(pipeline: &mut Pipeline<GoldilocksField>) {
+/// Runs the guest program in the fast executor and returns the committed
+/// journal and the exit status, if the guest reported one. Fails with
+/// [`GuestError::Panic`] if the execution failed, e.g. because the guest
+/// panicked without the `provable_panic` feature of `powdr-riscv-runtime`.
+pub fn run<F: FieldElement>(
+    pipeline: &mut Pipeline<F>,
+) -> Result<(Journal, Option<ExitStatus>), GuestError> {
     println!("Running powdr-riscv executor in fast mode...");
     let start = Instant::now();
 
+    // Discard the journal and exit status of previous executions.
+    Journal::take_from_pipeline(pipeline);
+    ExitStatus::take_from_pipeline(pipeline);
+
     let program = pipeline.compute_analyzed_asm().unwrap().clone();
     let initial_memory = riscv::continuations::load_initial_memory(&program);
+    let bootloader_inputs = if uses_continuations::<F>() {
+        riscv::continuations::bootloader::default_input(&[])
+    } else {
+        vec![]
+    };
     let (trace, _mem, _reg_mem) = riscv_executor::execute_ast(
         &program,
         initial_memory,
         pipeline.data_callback().unwrap(),
-        &riscv::continuations::bootloader::default_input(&[]),
+        &bootloader_inputs,
         usize::MAX,
         riscv_executor::ExecMode::Fast,
+        // `build_guest` does not enable custom coprocessors.
+        &Default::default(),
         None,
-    );
+    )
+    .map_err(|e| GuestError::from_execution_error(pipeline, &e))?;
 
     let duration = start.elapsed();
     println!("Fast executor took: {duration:?}");
     println!("Trace length: {}", trace.len);
+
+    Ok((
+        Journal::take_from_pipeline(pipeline),
+        ExitStatus::take_from_pipeline(pipeline),
+    ))
 }
 
-pub fn prove(pipeline: &mut Pipeline<GoldilocksField>) {
+/// Proves the guest program and returns the committed journal and the exit
+/// status together with the proofs of all chunks. With continuations, the
+/// journal and the exit code are checked against the public values of the proof.
+///
+/// Fails if the execution needs more than one chunk, since the links between
+/// chunks cannot be verified yet.
+pub fn prove<F: FieldElement>(
+    pipeline: &mut Pipeline<F>,
+) -> Result<(Journal, ExitStatus, Vec<ProofContainer>), String> {
+    if !uses_continuations::<F>() {
+        // Discard the journal and exit status of previous executions.
+        Journal::take_from_pipeline(pipeline);
+        ExitStatus::take_from_pipeline(pipeline);
+
+        println!("Generating proof...");
+        let start = Instant::now();
+        let chunk = prove_chunk(pipeline).map_err(|e| e.join("\n"))?;
+        let duration = start.elapsed();
+        println!("Proof generation took: {duration:?}");
+
+        return Ok((
+            Journal::take_from_pipeline(pipeline),
+            take_exit_status(pipeline)?,
+            vec![chunk],
+        ));
+    }
+
     log::info!("Running powdr-riscv executor in trace mode for continuations...");
     let start = Instant::now();
 
-    let bootloader_inputs =
-        riscv::continuations::rust_continuations_dry_run(&mut pipeline.clone(), None);
+    // Discard the journal and exit status of previous executions.
+    Journal::take_from_pipeline(pipeline);
+    ExitStatus::take_from_pipeline(pipeline);
+    let bootloader_inputs = riscv::continuations::rust_continuations_dry_run(
+        &mut pipeline.clone(),
+        &Default::default(),
+        None,
+    )
+    .map_err(|e| GuestError::from_execution_error(pipeline, &e).to_string())?;
+    // Witness generation executes the program again, so we take the journal
+    // and exit status written by the dry run.
+    let journal = Journal::take_from_pipeline(pipeline);
+    let exit_status = take_exit_status(pipeline)?;
 
     let duration = start.elapsed();
     log::info!("Trace executor took: {:?}", duration);
 
+    let chunk_count = bootloader_inputs.bootloader_inputs.len();
+    if chunk_count > 1 {
+        return Err(format!(
+            "The execution needs {chunk_count} chunks, but the links between chunks cannot be verified yet. Increase the chunk size to prove it in a single chunk."
+        ));
+    }
+
     // TODO how do we skip PIL compilation and fixed column generation if not needed?
     // We can check whether they exist and not generate it, but what if the asm changed?
     // Maybe one solution is to at least compile asm to PIL and see if that changed.
-    let generate_proof = |pipeline: &mut Pipeline<GoldilocksField>| -> Result<(), Vec<String>> {
+    let chunks = RefCell::new(vec![]);
+    let generate_proof = |pipeline: &mut Pipeline<F>| -> Result<(), Vec<String>> {
         let start = Instant::now();
         log::info!("Generating witness...");
         pipeline.compute_witness()?;
@@ -271,7 +602,7 @@ pub fn prove(pipeline: &mut Pipeline<GoldilocksField>) {
         println!("Generating proof...");
         let start = Instant::now();
 
-        pipeline.compute_proof().unwrap();
+        chunks.borrow_mut().push(prove_chunk(pipeline)?);
 
         let duration = start.elapsed();
         println!("Proof generation took: {duration:?}");
@@ -286,7 +617,25 @@ pub fn prove(pipeline: &mut Pipeline<GoldilocksField>) {
         bootloader_inputs.bootloader_inputs.len()
     );
     let start = Instant::now();
-    riscv::continuations::rust_continuations(pipeline, generate_proof, bootloader_inputs).unwrap();
+    riscv::continuations::rust_continuations(pipeline, generate_proof, bootloader_inputs)
+        .map_err(|e| e.join("\n"))?;
     let duration = start.elapsed();
     log::info!("Proof generation for all chunks took: {:?}", duration);
+
+    // The journal digest and the exit code are committed by the last chunk.
+    let publics = pipeline.publics().map_err(|e| e.join("\n"))?;
+    journal.verify(&publics)?;
+    exit_status.verify(&publics)?;
+
+    Ok((journal, exit_status, chunks.into_inner()))
+}
+
+fn take_exit_status<F: FieldElement>(pipeline: &Pipeline<F>) -> Result<ExitStatus, String> {
+    ExitStatus::take_from_pipeline(pipeline)
+        .ok_or_else(|| "The guest did not report an exit code".to_string())
+}
+
+fn prove_chunk<F: FieldElement>(pipeline: &mut Pipeline<F>) -> Result<ProofContainer, Vec<String>> {
+    let proof = pipeline.compute_proof()?.clone();
+    pipeline.proof_container(proof)
 }
@@ -0,0 +1,109 @@
+//! Proofs of the execution of guest programs, as created by [`crate::Session::prove`].
+
+use std::marker::PhantomData;
+
+use powdr_backend::ProofContainer;
+use powdr_number::FieldElement;
+use powdr_riscv::journal::Journal;
+use serde::{Deserialize, Serialize};
+
+/// A proof of the execution of a guest program.
+/// Besides the proofs of each chunk of the execution, which record the field,
+/// backend, verification key and public values they were created with, it
+/// contains the journal and the exit code committed by the guest.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Proof<F> {
+    chunks: Vec<ProofContainer>,
+    journal: Vec<u8>,
+    exit_code: u32,
+    #[serde(skip)]
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldElement> Proof<F> {
+    pub(crate) fn new(chunks: Vec<ProofContainer>, journal: &Journal, exit_code: u32) -> Self {
+        Self {
+            chunks,
+            journal: journal.bytes().to_vec(),
+            exit_code,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn chunks(&self) -> &[ProofContainer] {
+        &self.chunks
+    }
+
+    /// The data committed by the guest. Verifying the proof checks it against
+    /// the public values of the proof.
+    pub fn journal(&self) -> Journal {
+        Journal::new(self.journal.clone())
+    }
+
+    /// The exit code of the guest. Verifying the proof checks it against the
+    /// public values of the proof, so a proof of a guest that panicked or
+    /// failed can be told apart from a successful one.
+    pub fn exit_code(&self) -> u32 {
+        self.exit_code
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bincode::serialize(self).unwrap()
+    }
+
+    /// Deserializes a proof, failing if it was not created for the field F.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let proof: Self =
+            bincode::deserialize(bytes).map_err(|e| format!("Error deserializing proof: {e}"))?;
+        let field = F::known_field().unwrap();
+        if let Some(chunk) = proof.chunks.iter().find(|chunk| chunk.field != field) {
+            return Err(format!(
+                "Proof was created for field {}, expected {field}",
+                chunk.field
+            ));
+        }
+        Ok(proof)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use powdr_number::{BabyBearField, GoldilocksField, KnownField};
+
+    use super::*;
+
+    fn proof() -> Proof<GoldilocksField> {
+        Proof::new(
+            vec![ProofContainer {
+                backend: "plonky3".to_string(),
+                backend_options: String::new(),
+                field: KnownField::GoldilocksField,
+                pil_hash: "00".to_string(),
+                verification_key_hash: Some("07".to_string()),
+                publics: vec!["42".to_string()],
+                proof: vec![1, 2, 3],
+            }],
+            &Journal::new(vec![4, 5]),
+            3,
+        )
+    }
+
+    #[test]
+    fn roundtrip() {
+        let proof = proof();
+        let deserialized = Proof::<GoldilocksField>::from_bytes(&proof.to_bytes()).unwrap();
+        assert_eq!(deserialized, proof);
+        assert_eq!(deserialized.journal().bytes(), &[4, 5]);
+        assert_eq!(deserialized.exit_code(), 3);
+    }
+
+    #[test]
+    fn wrong_field() {
+        let bytes = proof().to_bytes();
+        let err = Proof::<BabyBearField>::from_bytes(&bytes).unwrap_err();
+        assert_eq!(
+            err,
+            "Proof was created for field Goldilocks, expected BabyBear"
+        );
+    }
+}
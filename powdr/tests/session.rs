@@ -0,0 +1,51 @@
+use mktemp::Temp;
+use powdr::{Proof, Session};
+
+/// The guest reads and commits its inputs on channels 0, 1 and 2, and commits
+/// `sum(values) * factor + offset` to the journal.
+fn new_session(out_path: &Temp, factor: u32) -> Session {
+    Session::builder()
+        .guest_path("../riscv/tests/riscv_data/commit_inputs")
+        .out_path(out_path.to_str().unwrap())
+        .chunk_size_log2(18)
+        .build()
+        .write(1, &vec![1u32, 2, 3])
+        .write(2, &factor)
+        .write(0, &5u32)
+        .expect_committed_inputs()
+}
+
+#[test]
+#[ignore = "Too slow"]
+fn verify_rejects_tampered_proofs() {
+    let out_path = Temp::new_dir().unwrap();
+    let mut session = new_session(&out_path, 10);
+    let proof = session.prove().unwrap();
+    assert_eq!(proof.journal().decode::<u32>().unwrap(), 65);
+    session.verify(&proof).unwrap();
+
+    // The journal is serialized right before the 4-byte exit code, at the end
+    // of the proof.
+    let mut bytes = proof.to_bytes();
+    let journal_end = bytes.len() - 4;
+    bytes[journal_end - 1] ^= 1;
+    let tampered = Proof::from_bytes(&bytes).unwrap();
+    assert_ne!(tampered.journal(), proof.journal());
+    assert!(session.verify(&tampered).is_err());
+
+    // A different exit code is rejected as well.
+    let mut bytes = proof.to_bytes();
+    let last = bytes.len() - 4;
+    bytes[last] = 1;
+    let tampered = Proof::from_bytes(&bytes).unwrap();
+    assert_eq!(tampered.exit_code(), 1);
+    assert!(session.verify(&tampered).is_err());
+    // Even when non-zero exit codes are allowed, it must match the proof.
+    let mut allowing_session = new_session(&out_path, 10).allow_nonzero_exit_code();
+    allowing_session.verify(&proof).unwrap();
+    assert!(allowing_session.verify(&tampered).is_err());
+
+    // The proof does not verify for different inputs.
+    let mut other_session = new_session(&out_path, 11);
+    assert!(other_session.verify(&proof).is_err());
+}
@@ -5,7 +5,7 @@ use k256::{
     },
     ProjectivePoint,
 };
-use powdr_number::{BigUint, FieldElement};
+use powdr_number::{BigInt, BigUint, FieldElement};
 
 use k256::EncodedPoint;
 
@@ -156,3 +156,85 @@ pub fn mod_256<F: FieldElement>(a: &[F], b: &[F], c: &[F]) -> [F; 8] {
 
     remainder
 }
+
+/// Calculates the inverse of a modulo m for 256 bit values.
+/// Panics if a is not invertible modulo m.
+pub fn mod_inv_256<F: FieldElement>(a: &[F], m: &[F]) -> [F; 8] {
+    assert_eq!(a.len(), 8);
+    assert_eq!(m.len(), 8);
+
+    let a: BigUint = a
+        .iter()
+        .enumerate()
+        .map(|(i, fe)| fe.to_arbitrary_integer() << (i * 32))
+        .reduce(|acc, b| acc + b)
+        .unwrap();
+    let m: BigUint = m
+        .iter()
+        .enumerate()
+        .map(|(i, fe)| fe.to_arbitrary_integer() << (i * 32))
+        .reduce(|acc, b| acc + b)
+        .unwrap();
+
+    let (gcd, inv, _) = a.extended_gcd(&m);
+    assert!(
+        gcd == BigUint::from(1u32),
+        "{a} is not invertible modulo {m}"
+    );
+    let m = BigInt::from(m);
+    let res = BigUint::try_from(((inv % &m) + &m) % &m).unwrap();
+    let mut inverse: [F; 8] = Default::default();
+    for (i, r) in inverse.iter_mut().enumerate() {
+        *r = F::from((res.clone() >> (i * 32)) & 0xffffffffu64);
+    }
+
+    inverse
+}
+
+/// Converts 12 little-endian 32-bit words to a 384 bit value.
+fn words_to_biguint(x: &[u32; 12]) -> BigUint {
+    x.iter()
+        .enumerate()
+        .map(|(i, &w)| BigUint::from(w) << (i * 32))
+        .reduce(|acc, b| acc + b)
+        .unwrap()
+}
+
+/// Converts a value of at most 384 bits to 12 little-endian 32-bit words.
+fn biguint_to_words(x: BigUint) -> [u32; 12] {
+    let mut words = [0u32; 12];
+    for (i, w) in words.iter_mut().enumerate() {
+        *w = u32::try_from((x.clone() >> (i * 32)) & 0xffffffffu32).unwrap();
+    }
+    words
+}
+
+/// Calculates (a * b + c * d) % m for 384 bit values.
+pub fn mul_add_384(
+    a: &[u32; 12],
+    b: &[u32; 12],
+    c: &[u32; 12],
+    d: &[u32; 12],
+    m: &[u32; 12],
+) -> [u32; 12] {
+    let m = words_to_biguint(m);
+    let res =
+        (words_to_biguint(a) * words_to_biguint(b) + words_to_biguint(c) * words_to_biguint(d)) % m;
+    biguint_to_words(res)
+}
+
+/// Calculates (a * b - c * d) % m for 384 bit values.
+/// The result is the non-negative remainder.
+pub fn mul_sub_384(
+    a: &[u32; 12],
+    b: &[u32; 12],
+    c: &[u32; 12],
+    d: &[u32; 12],
+    m: &[u32; 12],
+) -> [u32; 12] {
+    let m = BigInt::from(words_to_biguint(m));
+    let res = BigInt::from(words_to_biguint(a) * words_to_biguint(b))
+        - BigInt::from(words_to_biguint(c) * words_to_biguint(d));
+    let res = BigUint::try_from(((res % &m) + &m) % &m).unwrap();
+    biguint_to_words(res)
+}
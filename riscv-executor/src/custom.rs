@@ -0,0 +1,29 @@
+//! User-defined instructions, provided by custom coprocessors.
+//!
+//! The executor only knows the built-in instructions of the RISC-V machine.
+//! Instructions declared by user-defined submachines are executed by the
+//! functions passed to the executor along with the program, by instruction
+//! name. `powdr_riscv` collects them from the custom coprocessors a program
+//! was compiled with, see `RuntimeLibs::custom_instructions`.
+
+use std::collections::BTreeMap;
+
+/// The state of the machine, as seen by a user-defined instruction.
+pub trait MachineState {
+    /// Returns the value of the register at the given address, which must fit in 32 bits.
+    fn get_reg(&mut self, addr: u32) -> u32;
+    /// Sets the register at the given address. Writes to register 0 are ignored.
+    fn set_reg(&mut self, addr: u32, value: u32);
+    /// Returns the memory word at the given address.
+    fn get_mem(&mut self, addr: u32) -> u32;
+    /// Sets the memory word at the given address.
+    fn set_mem(&mut self, addr: u32, value: u32);
+}
+
+/// Executes a user-defined instruction, given the values of its arguments.
+/// Arguments referring to registers are passed as register addresses.
+pub type CustomInstruction = fn(&mut dyn MachineState, &[u32]);
+
+/// The executor implementations of the user-defined instructions of a program,
+/// by instruction name. Built-in instructions take precedence over these.
+pub type CustomInstructions = BTreeMap<String, CustomInstruction>;
@@ -17,6 +17,7 @@ use std::{
 
 use builder::TraceBuilder;
 
+use custom::CustomInstructions;
 use itertools::Itertools;
 use powdr_ast::{
     asm_analysis::{AnalysisASMFile, CallableSymbol, FunctionStatement, LabelStatement, Machine},
@@ -29,11 +30,35 @@ use powdr_number::{FieldElement, LargeInt};
 pub use profiler::ProfilerOptions;
 
 pub mod arith;
+pub mod custom;
 pub mod poseidon_gl;
 mod profiler;
+pub mod sha256;
 
 use crate::profiler::Profiler;
 
+/// An error that stopped the execution of a program.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExecutionError {
+    /// The program reached a `fail` instruction, e.g. because the guest
+    /// panicked. Contains the source location of the last executed debug
+    /// directive, if there was one.
+    Fail { location: Option<String> },
+}
+
+impl Display for ExecutionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Fail {
+                location: Some(location),
+            } => write!(f, "reached a fail instruction at {location}"),
+            Self::Fail { location: None } => write!(f, "reached a fail instruction"),
+        }
+    }
+}
+
+impl std::error::Error for ExecutionError {}
+
 /// Initial value of the PC.
 ///
 /// To match the ZK proof witness, the PC must start after some offset used for
@@ -640,11 +665,42 @@ fn preprocess_main_function<F: FieldElement>(machine: &Machine) -> PreprocessedM
 
 type Callback<'a, F> = dyn powdr_executor::witgen::QueryCallback<F> + 'a;
 
+/// Interprets an element as a 32-bit word, for user-defined instructions.
+fn elem_as_u32<F: FieldElement>(value: &Elem<F>) -> u32 {
+    match value {
+        // Negative values are represented in two's complement.
+        Elem::Binary(b) => *b as u32,
+        Elem::Field(f) => f
+            .to_integer()
+            .try_into_u32()
+            .unwrap_or_else(|| panic!("value {f} does not fit in 32 bits")),
+    }
+}
+
+impl<F: FieldElement> custom::MachineState for TraceBuilder<'_, F> {
+    fn get_reg(&mut self, addr: u32) -> u32 {
+        elem_as_u32(&self.get_reg_mem(addr))
+    }
+
+    fn set_reg(&mut self, addr: u32, value: u32) {
+        self.set_reg_mem(addr, value.into());
+    }
+
+    fn get_mem(&mut self, addr: u32) -> u32 {
+        TraceBuilder::get_mem(self, addr)
+    }
+
+    fn set_mem(&mut self, addr: u32, value: u32) {
+        TraceBuilder::set_mem(self, addr, value);
+    }
+}
+
 struct Executor<'a, 'b, F: FieldElement> {
     proc: TraceBuilder<'b, F>,
     label_map: HashMap<&'a str, Elem<F>>,
     inputs: &'b Callback<'b, F>,
     bootloader_inputs: Vec<Elem<F>>,
+    custom_instructions: &'b CustomInstructions,
     _stdout: io::Stdout,
 }
 
@@ -918,10 +974,7 @@ impl<'a, 'b, F: FieldElement> Executor<'a, 'b, F> {
 
                 Vec::new()
             }
-            "fail" => {
-                // TODO: handle it better
-                panic!("reached a fail instruction")
-            }
+            "fail" => unreachable!("fail instructions are handled by execute_ast"),
             "divremu" => {
                 let val1 = self.proc.get_reg_mem(args[0].u());
                 let val2 = self.proc.get_reg_mem(args[1].u());
@@ -978,7 +1031,69 @@ impl<'a, 'b, F: FieldElement> Executor<'a, 'b, F> {
 
                 Vec::new()
             }
-            "shl" | "shr" => {
+            "andn" | "orn" | "xnor" => {
+                let val1 = self.proc.get_reg_mem(args[0].u());
+                let val2 = self.proc.get_reg_mem(args[1].u());
+                let write_reg = args[2].u();
+
+                let r = match name {
+                    "andn" => val1.u() & !val2.u(),
+                    "orn" => val1.u() | !val2.u(),
+                    "xnor" => val1.u() ^ !val2.u(),
+                    _ => unreachable!(),
+                };
+
+                self.proc.set_reg_mem(write_reg, r.into());
+
+                Vec::new()
+            }
+            "cpop" | "brev" | "rev8" | "orc_b" => {
+                let val = self.proc.get_reg_mem(args[0].u());
+                let write_reg = args[1].u();
+
+                let r = match name {
+                    "cpop" => val.u().count_ones(),
+                    "brev" => val.u().reverse_bits(),
+                    "rev8" => val.u().swap_bytes(),
+                    "orc_b" => {
+                        u32::from_le_bytes(
+                            val.u().to_le_bytes().map(|b| if b == 0 { 0 } else { 0xff }),
+                        )
+                    }
+                    _ => unreachable!(),
+                };
+
+                self.proc.set_reg_mem(write_reg, r.into());
+
+                Vec::new()
+            }
+            "minu" | "maxu" => {
+                let val1 = self.proc.get_reg_mem(args[0].u());
+                let val2 = self.proc.get_reg_mem(args[1].u());
+                let write_reg = args[2].u();
+
+                let r = match name {
+                    "minu" => val1.u().min(val2.u()),
+                    "maxu" => val1.u().max(val2.u()),
+                    _ => unreachable!(),
+                };
+
+                self.proc.set_reg_mem(write_reg, r.into());
+
+                Vec::new()
+            }
+            "add_shifted" => {
+                let val1 = self.proc.get_reg_mem(args[0].u());
+                let val2 = self.proc.get_reg_mem(args[1].u());
+                let factor = args[2].u();
+                let write_reg = args[3].u();
+
+                let r = val1.u().wrapping_mul(factor).wrapping_add(val2.u());
+                self.proc.set_reg_mem(write_reg, r.into());
+
+                Vec::new()
+            }
+            "shl" | "shr" | "rotl" | "rotr" => {
                 let val1 = self.proc.get_reg_mem(args[0].u());
                 let val2 = self.proc.get_reg_mem(args[1].u());
                 let offset = args[2].bin();
@@ -988,6 +1103,8 @@ impl<'a, 'b, F: FieldElement> Executor<'a, 'b, F> {
                 let r = match name {
                     "shl" => val1.u() << val2.u(),
                     "shr" => val1.u() >> val2.u(),
+                    "rotl" => val1.u().rotate_left(val2.u()),
+                    "rotr" => val1.u().rotate_right(val2.u()),
                     _ => unreachable!(),
                 };
 
@@ -1043,6 +1160,53 @@ impl<'a, 'b, F: FieldElement> Executor<'a, 'b, F> {
 
                 vec![]
             }
+            "sha256" => {
+                let state_ptr = self.proc.get_reg_mem(args[0].u()).u();
+                assert_eq!(state_ptr % 4, 0);
+                let block_ptr = self.proc.get_reg_mem(args[1].u()).u();
+                assert_eq!(block_ptr % 4, 0);
+
+                let mut state: [u32; 8] =
+                    std::array::from_fn(|i| self.proc.get_mem(state_ptr + i as u32 * 4));
+                let block: [u32; 16] =
+                    std::array::from_fn(|i| self.proc.get_mem(block_ptr + i as u32 * 4));
+
+                sha256::sha256_compress(&mut state, &block);
+
+                state.iter().enumerate().for_each(|(i, &v)| {
+                    self.proc.set_mem(state_ptr + i as u32 * 4, v);
+                });
+
+                vec![]
+            }
+            "mul_add_384" | "mul_sub_384" => {
+                let input_ptr = self.proc.get_reg_mem(args[0].u()).u();
+                assert_eq!(input_ptr % 4, 0);
+                let output_ptr = self.proc.get_reg_mem(args[1].u()).u();
+                assert_eq!(output_ptr % 4, 0);
+
+                // The input consists of the five 12 word numbers a, b, c, d and m.
+                let [a, b, c, d, m]: [[u32; 12]; 5] = std::array::from_fn(|i| {
+                    std::array::from_fn(|j| self.proc.get_mem(input_ptr + (i * 12 + j) as u32 * 4))
+                });
+
+                let result = if name == "mul_add_384" {
+                    arith::mul_add_384(&a, &b, &c, &d, &m)
+                } else {
+                    arith::mul_sub_384(&a, &b, &c, &d, &m)
+                };
+
+                result.iter().enumerate().for_each(|(i, &v)| {
+                    self.proc.set_mem(output_ptr + i as u32 * 4, v);
+                });
+
+                vec![]
+            }
+            "commit_public" => {
+                // The public outputs are only written to a submachine,
+                // there is no state to update here.
+                vec![]
+            }
             "affine_256" => {
                 assert!(args.is_empty());
                 // take input from registers
@@ -1088,6 +1252,28 @@ impl<'a, 'b, F: FieldElement> Executor<'a, 'b, F> {
                 });
                 vec![]
             }
+            "mod_inv_256_hint" => {
+                let limb = args[0].u() as usize;
+                // take input from registers
+                let a = (0..8)
+                    .map(|i| self.proc.get_reg(&register_by_idx(i)).into_fe())
+                    .collect::<Vec<_>>();
+                let m = (0..8)
+                    .map(|i| self.proc.get_reg(&register_by_idx(i + 24)).into_fe())
+                    .collect::<Vec<_>>();
+                let result = arith::mod_inv_256(&a, &m);
+
+                vec![Elem::Field(result[limb])]
+            }
+            "assert_one_256" => {
+                assert!(args.is_empty());
+                (0..8).for_each(|i| {
+                    let expected = if i == 0 { F::ONE } else { F::ZERO };
+                    assert_eq!(self.proc.get_reg(&register_by_idx(i)).into_fe(), expected);
+                });
+
+                vec![]
+            }
             "ec_add" => {
                 assert!(args.is_empty());
                 // take input from registers
@@ -1139,7 +1325,13 @@ impl<'a, 'b, F: FieldElement> Executor<'a, 'b, F> {
                 vec![]
             }
             instr => {
-                panic!("unknown instruction: {instr}");
+                let Some(&implementation) = self.custom_instructions.get(instr) else {
+                    panic!("unknown instruction: {instr}");
+                };
+                let args = args.iter().map(elem_as_u32).collect::<Vec<_>>();
+                implementation(&mut self.proc, &args);
+
+                vec![]
             }
         }
     }
@@ -1308,6 +1500,10 @@ impl<'a, 'b, F: FieldElement> Executor<'a, 'b, F> {
     }
 }
 
+/// Executes the main machine of the program, running user-defined
+/// instructions with `custom_instructions`. Fails if the program reaches a
+/// `fail` instruction.
+#[allow(clippy::too_many_arguments)]
 pub fn execute_ast<F: FieldElement>(
     program: &AnalysisASMFile,
     initial_memory: MemoryState,
@@ -1315,8 +1511,9 @@ pub fn execute_ast<F: FieldElement>(
     bootloader_inputs: &[F],
     max_steps_to_execute: usize,
     mode: ExecMode,
+    custom_instructions: &CustomInstructions,
     profiling: Option<ProfilerOptions>,
-) -> (ExecutionTrace<F>, MemoryState, RegisterMemoryState<F>) {
+) -> Result<(ExecutionTrace<F>, MemoryState, RegisterMemoryState<F>), ExecutionError> {
     let main_machine = get_main_machine(program);
     let PreprocessedMain {
         statements,
@@ -1335,7 +1532,7 @@ pub fn execute_ast<F: FieldElement>(
         mode,
     ) {
         Ok(proc) => proc,
-        Err(ret) => return *ret,
+        Err(ret) => return Ok(*ret),
     };
 
     let bootloader_inputs = bootloader_inputs
@@ -1348,12 +1545,17 @@ pub fn execute_ast<F: FieldElement>(
         label_map,
         inputs,
         bootloader_inputs,
+        custom_instructions,
         _stdout: io::stdout(),
     };
 
     let mut profiler =
         profiling.map(|opt| Profiler::new(opt, &debug_files[..], function_starts, location_starts));
 
+    // The source location of the last executed debug directive, used to report
+    // where the program failed.
+    let mut last_location = None;
+
     let mut curr_pc = 0u32;
     loop {
         let stm = statements[curr_pc as usize];
@@ -1378,6 +1580,12 @@ pub fn execute_ast<F: FieldElement>(
                     p.add_instruction_cost(e.proc.get_pc().u() as usize);
                 }
 
+                if i.instruction == "fail" {
+                    let location = last_location
+                        .map(|(dir, file, line, column)| format!("{dir}/{file}:{line}:{column}"));
+                    return Err(ExecutionError::Fail { location });
+                }
+
                 if ["jump", "jump_dyn"].contains(&i.instruction.as_str()) {
                     let pc_before = e.proc.get_pc().u();
 
@@ -1413,6 +1621,7 @@ pub fn execute_ast<F: FieldElement>(
                     DebugDirective::Loc(file, line, column) => {
                         let (dir, file) = debug_files[file - 1];
                         log::trace!("Executed {dir}/{file}:{line}:{column}");
+                        last_location = Some((dir, file, line, column));
                     }
                     DebugDirective::OriginalInstruction(insn) => {
                         log::trace!("  {insn}");
@@ -1434,7 +1643,7 @@ pub fn execute_ast<F: FieldElement>(
     if let Some(mut p) = profiler {
         p.finish();
     }
-    e.proc.finish()
+    Ok(e.proc.finish())
 }
 
 pub enum ExecMode {
@@ -1452,8 +1661,9 @@ pub fn execute<F: FieldElement>(
     inputs: &Callback<F>,
     bootloader_inputs: &[F],
     mode: ExecMode,
+    custom_instructions: &CustomInstructions,
     profiling: Option<ProfilerOptions>,
-) -> (ExecutionTrace<F>, MemoryState, RegisterMemoryState<F>) {
+) -> Result<(ExecutionTrace<F>, MemoryState, RegisterMemoryState<F>), ExecutionError> {
     log::info!("Parsing...");
     let parsed = powdr_parser::parse_asm(None, asm_source).unwrap();
     log::info!("Resolving imports...");
@@ -1469,6 +1679,7 @@ pub fn execute<F: FieldElement>(
         bootloader_inputs,
         usize::MAX,
         mode,
+        custom_instructions,
         profiling,
     )
 }
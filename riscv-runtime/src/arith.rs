@@ -158,3 +158,147 @@ pub fn modmul_256_u32_le(
 
     a
 }
+
+/// Calculate `a^-1 % m = r` for 256 bit values (as u8 big-endian arrays),
+/// i.e. `(a*r) % m = 1`. `a` must be invertible modulo `m`.
+/// Returns `r`.
+pub fn modinv_256_u8_be(mut a: [u8; 32], m: [u8; 32]) -> [u8; 32] {
+    let mut a1: [u32; 8] = Default::default();
+    let mut m1: [u32; 8] = Default::default();
+
+    be_to_u32(&a, &mut a1);
+    be_to_u32(&m, &mut m1);
+
+    unsafe {
+        // The result is stored in place in a.
+        asm!("ecall",
+             in("a0") &mut a1 as *mut [u32; 8],
+             in("a1") &m1 as *const [u32; 8],
+             in("t0") u32::from(Syscall::ModInv256));
+    }
+
+    u32_to_be(&a1, &mut a);
+    a
+}
+
+/// Calculate `a^-1 % m = r` for 256 bit values (as u8 little-endian arrays),
+/// i.e. `(a*r) % m = 1`. `a` must be invertible modulo `m`.
+/// Returns `r`.
+pub fn modinv_256_u8_le(mut a: [u8; 32], m: [u8; 32]) -> [u8; 32] {
+    unsafe {
+        // The result is stored in place in a.
+        asm!("ecall",
+             in("a0") a.as_mut_ptr() as *mut [u32; 8],
+             in("a1") m.as_ptr() as *const [u32; 8],
+             in("t0") u32::from(Syscall::ModInv256));
+    }
+
+    a
+}
+
+/// Calculate `a^-1 % m = r` for 256 bit values (as u32 little-endian arrays),
+/// i.e. `(a*r) % m = 1`. `a` must be invertible modulo `m`.
+/// Returns `r`.
+pub fn modinv_256_u32_le(mut a: [u32; 8], m: [u32; 8]) -> [u32; 8] {
+    unsafe {
+        // The result is stored in place in a.
+        asm!("ecall",
+             in("a0") &mut a as *mut [u32; 8],
+             in("a1") &m as *const [u32; 8],
+             in("t0") u32::from(Syscall::ModInv256));
+    }
+
+    a
+}
+
+/// Returns whether `a < b` for 256 bit values (as u32 little-endian arrays).
+pub(crate) fn lt_256(a: &[u32; 8], b: &[u32; 8]) -> bool {
+    a.iter().rev().lt(b.iter().rev())
+}
+
+/// Returns bit `i` of a 256 bit value (as u32 little-endian array).
+pub(crate) fn bit_256(a: &[u32; 8], i: usize) -> bool {
+    (a[i / 32] >> (i % 32)) & 1 == 1
+}
+
+/// Calculate `a + b` for 256 bit values (as u32 little-endian arrays),
+/// wrapping around at 2**256.
+pub(crate) fn add_256(a: &[u32; 8], b: &[u32; 8]) -> [u32; 8] {
+    let mut result = [0u32; 8];
+    let mut carry = false;
+    for (r, (a, b)) in result.iter_mut().zip(a.iter().zip(b)) {
+        let (sum, carry1) = a.overflowing_add(*b);
+        let (sum, carry2) = sum.overflowing_add(carry as u32);
+        *r = sum;
+        carry = carry1 || carry2;
+    }
+    result
+}
+
+/// Calculate `a - b` for 256 bit values (as u32 little-endian arrays),
+/// wrapping around at 2**256.
+pub(crate) fn sub_256(a: &[u32; 8], b: &[u32; 8]) -> [u32; 8] {
+    let mut result = [0u32; 8];
+    let mut borrow = false;
+    for (r, (a, b)) in result.iter_mut().zip(a.iter().zip(b)) {
+        let (diff, borrow1) = a.overflowing_sub(*b);
+        let (diff, borrow2) = diff.overflowing_sub(borrow as u32);
+        *r = diff;
+        borrow = borrow1 || borrow2;
+    }
+    result
+}
+
+/// Reduces a 256 bit value `a < 2*m` (as u32 little-endian arrays) modulo `m`.
+///
+/// The results of the arith machine are only guaranteed to be congruent
+/// modulo `m`, this makes them canonical so that they can be compared.
+pub(crate) fn reduce_256(a: [u32; 8], m: &[u32; 8]) -> [u32; 8] {
+    if lt_256(&a, m) {
+        a
+    } else {
+        sub_256(&a, m)
+    }
+}
+
+/// Calculate `(a*b + c*d) % m` for 384 bit values (as u32 little-endian arrays).
+/// `a`, `b`, `c` and `d` must be smaller than `m`. The result is smaller than `m`.
+pub fn mul_add_384_u32_le(
+    a: [u32; 12],
+    b: [u32; 12],
+    c: [u32; 12],
+    d: [u32; 12],
+    m: [u32; 12],
+) -> [u32; 12] {
+    let input = [a, b, c, d, m];
+    let mut r = [0u32; 12];
+    unsafe {
+        asm!("ecall",
+             in("a0") &input as *const [[u32; 12]; 5],
+             in("a1") &mut r as *mut [u32; 12],
+             in("t0") u32::from(Syscall::MulAdd384));
+    }
+
+    r
+}
+
+/// Calculate `(a*b - c*d) % m` for 384 bit values (as u32 little-endian arrays).
+/// `a`, `b`, `c` and `d` must be smaller than `m`. The result is smaller than `m`.
+pub fn mul_sub_384_u32_le(
+    a: [u32; 12],
+    b: [u32; 12],
+    c: [u32; 12],
+    d: [u32; 12],
+    m: [u32; 12],
+) -> [u32; 12] {
+    let input = [a, b, c, d, m];
+    let mut r = [0u32; 12];
+    unsafe {
+        asm!("ecall",
+             in("a0") &input as *const [[u32; 12]; 5],
+             in("a1") &mut r as *mut [u32; 12],
+             in("t0") u32::from(Syscall::MulSub384));
+    }
+
+    r
+}
@@ -0,0 +1,239 @@
+//! Committed public outputs ("journal").
+//!
+//! Data passed to [`commit`] is written to the [`JOURNAL_FD`] output, so that
+//! the host can read it, and hashed in-circuit using the Poseidon coprocessor.
+//! When the program terminates, the runtime writes the digest of the journal
+//! to the public outputs of the main machine, where a verifier can check it
+//! against the journal it received from the host. The seed of the entropy
+//! source is committed as well, and the exit code of the program is committed
+//! by the `halt` syscall, see [`crate::exit`]. Programs that neither commit
+//! data nor commit inputs nor use the entropy source do not write any of these
+//! public outputs, which are then read as zero.
+//!
+//! Committing data requires the Poseidon coprocessor, which is only available
+//! for the Goldilocks field.
+//!
+//! The digest is computed as follows: the journal is split into little-endian
+//! 32-bit words, which are grouped into blocks of 8 words, padding both with
+//! zeros. Starting from a zero state, each block is hashed together with the
+//! current state using the Poseidon permutation, where the state is the
+//! 4-element "cap". Finally, a block containing only the length of the journal
+//! in bytes is hashed in the same way. Each of the 4 resulting field elements
+//! is split into two 32-bit words. The digest of an empty journal is zero.
+//!
+//! With the `commit_inputs` feature, every input channel read through any of
+//! the functions of [`crate::io`] is committed as well: on its first access,
+//! the whole data of the channel is read from the host and kept in memory,
+//! its 32-bit words are hashed in the same way as the journal (as 4
+//! little-endian bytes each), and the channel and its digest are committed as
+//! public outputs. Later reads are served from the data in memory, so the
+//! guest can never use data that is not committed. This way, a verifier can
+//! check which inputs a proof was produced for. At most
+//! [`powdr_riscv_syscalls::MAX_COMMITTED_INPUTS`] (4) different channels can be read in this mode,
+//! reading a fifth channel panics.
+
+extern crate alloc;
+
+use core::arch::asm;
+use core::ptr::addr_of_mut;
+
+#[cfg(feature = "commit_inputs")]
+use alloc::vec::Vec;
+#[cfg(feature = "commit_inputs")]
+use powdr_riscv_syscalls::{committed_input_cell, MAX_COMMITTED_INPUTS};
+use powdr_riscv_syscalls::{
+    Syscall, COMMITTED_INPUTS_COUNT_CELL, ENTROPY_SEED_CELL, ENTROPY_SEED_USED_CELL,
+    JOURNAL_DIGEST_WORDS, JOURNAL_FD,
+};
+use serde::Serialize;
+
+use crate::hash::poseidon_gl_unsafe;
+use crate::io::write_slice;
+
+/// Number of bytes in a block of 8 32-bit words.
+const BLOCK_BYTES: u64 = 32;
+
+/// Incrementally computes the digest of a sequence of bytes.
+struct Hasher {
+    /// The Poseidon state after hashing all complete blocks.
+    state: [u64; 4],
+    /// The words of the current incomplete block.
+    block: [u64; 8],
+    /// The number of bytes hashed so far.
+    len: u64,
+}
+
+static mut JOURNAL: Hasher = Hasher::new();
+
+/// The committed input channels and their digests, in the order they were first read.
+#[cfg(feature = "commit_inputs")]
+static mut INPUTS: [(u32, [u32; JOURNAL_DIGEST_WORDS]); MAX_COMMITTED_INPUTS] =
+    [(0, [0; JOURNAL_DIGEST_WORDS]); MAX_COMMITTED_INPUTS];
+/// The data of the committed input channels, in the same order as [`INPUTS`].
+#[cfg(feature = "commit_inputs")]
+static mut INPUT_DATA: [Vec<u32>; MAX_COMMITTED_INPUTS] = [NO_DATA; MAX_COMMITTED_INPUTS];
+#[cfg(feature = "commit_inputs")]
+const NO_DATA: Vec<u32> = Vec::new();
+/// The number of committed input channels. Always zero without the `commit_inputs` feature.
+static mut INPUT_COUNT: usize = 0;
+/// Writes the public outputs, set once the guest commits anything. It is only
+/// referenced through this pointer, so that programs which never commit
+/// anything do not contain the `commit_public` syscall at all.
+static mut WRITE_PUBLIC_OUTPUTS: Option<fn()> = None;
+
+impl Hasher {
+    const fn new() -> Self {
+        Self {
+            state: [0; 4],
+            block: [0; 8],
+            len: 0,
+        }
+    }
+
+    fn append(&mut self, data: &[u8]) {
+        for &byte in data {
+            let pos = (self.len % BLOCK_BYTES) as usize;
+            self.block[pos / 4] |= (byte as u64) << (8 * (pos % 4));
+            self.len += 1;
+            if self.len % BLOCK_BYTES == 0 {
+                self.hash_block();
+            }
+        }
+    }
+
+    fn hash_block(&mut self) {
+        let mut data = [0u64; 12];
+        data[..8].copy_from_slice(&self.block);
+        data[8..].copy_from_slice(&self.state);
+        // Safe because all inputs are either 32-bit words or a previous output.
+        self.state = *poseidon_gl_unsafe(&mut data);
+        self.block = [0; 8];
+    }
+
+    fn digest(&mut self) -> [u32; JOURNAL_DIGEST_WORDS] {
+        if self.len == 0 {
+            return [0; JOURNAL_DIGEST_WORDS];
+        }
+        if self.len % BLOCK_BYTES != 0 {
+            self.hash_block();
+        }
+        self.block[0] = self.len;
+        self.hash_block();
+
+        let mut digest = [0; JOURNAL_DIGEST_WORDS];
+        for (i, element) in self.state.iter().enumerate() {
+            digest[2 * i] = *element as u32;
+            digest[2 * i + 1] = (*element >> 32) as u32;
+        }
+        digest
+    }
+}
+
+/// Serializes a value of type T and commits it to the journal.
+pub fn commit<T: Serialize>(data: T) {
+    let data = serde_cbor::to_vec(&data).unwrap();
+    commit_slice(&data);
+}
+
+/// Commits the given bytes to the journal.
+pub fn commit_slice(data: &[u8]) {
+    mark_committed();
+    write_slice(JOURNAL_FD, data);
+    unsafe { (*addr_of_mut!(JOURNAL)).append(data) };
+}
+
+/// Returns the data of an input channel. On the first access to the channel,
+/// its whole data is read from the host and its digest is committed. Later
+/// accesses return the same data without querying the host again.
+///
+/// Panics if more than [`MAX_COMMITTED_INPUTS`] different channels are read.
+#[cfg(feature = "commit_inputs")]
+pub(crate) fn committed_input(fd: u32) -> &'static [u32] {
+    let inputs = unsafe { &mut *addr_of_mut!(INPUTS) };
+    let data = unsafe { &mut *addr_of_mut!(INPUT_DATA) };
+    let count = unsafe { &mut *addr_of_mut!(INPUT_COUNT) };
+    let slot = match inputs[..*count]
+        .iter()
+        .position(|(channel, _)| *channel == fd)
+    {
+        Some(slot) => slot,
+        None => {
+            assert!(
+                *count < MAX_COMMITTED_INPUTS,
+                "Too many committed input channels, at most {MAX_COMMITTED_INPUTS} are supported"
+            );
+            mark_committed();
+            let words = crate::io::read_channel_uncommitted(fd);
+            let mut hasher = Hasher::new();
+            for word in &words {
+                hasher.append(&word.to_le_bytes());
+            }
+            inputs[*count] = (fd, hasher.digest());
+            data[*count] = words;
+            *count += 1;
+            *count - 1
+        }
+    };
+    &data[slot]
+}
+
+/// Records that the public outputs have to be written when the program
+/// terminates.
+pub(crate) fn mark_committed() {
+    unsafe { *addr_of_mut!(WRITE_PUBLIC_OUTPUTS) = Some(write_public_outputs) };
+}
+
+/// Writes the digest of the journal, the committed inputs and the seed of the
+/// entropy source to the public outputs, if the guest committed any of them.
+/// Called by the runtime when the program terminates. Returns whether the
+/// public outputs were written, which the `halt` syscall commits.
+pub(crate) fn finalize() -> bool {
+    match unsafe { *addr_of_mut!(WRITE_PUBLIC_OUTPUTS) } {
+        Some(write_public_outputs) => {
+            write_public_outputs();
+            true
+        }
+        None => false,
+    }
+}
+
+fn write_public_outputs() {
+    let digest = unsafe { (*addr_of_mut!(JOURNAL)).digest() };
+    for (i, word) in digest.into_iter().enumerate() {
+        commit_public(i, word);
+    }
+
+    let count = unsafe { *addr_of_mut!(INPUT_COUNT) };
+    commit_public(COMMITTED_INPUTS_COUNT_CELL, count as u32);
+    #[cfg(feature = "commit_inputs")]
+    for (slot, (channel, digest)) in unsafe { &*addr_of_mut!(INPUTS) }[..count]
+        .iter()
+        .enumerate()
+    {
+        let cell = committed_input_cell(slot);
+        commit_public(cell, *channel);
+        for (i, word) in digest.iter().enumerate() {
+            commit_public(cell + 1 + i, *word);
+        }
+    }
+    let seed = crate::entropy_source::seed();
+    commit_public(ENTROPY_SEED_USED_CELL, seed.is_some() as u32);
+    for (i, word) in seed.unwrap_or_default().into_iter().enumerate() {
+        commit_public(ENTROPY_SEED_CELL + i, word);
+    }
+}
+
+fn commit_public(cell: usize, value: u32) {
+    __powdr_commit_public(cell as u32, value);
+}
+
+/// Issues the `commit_public` syscall. It is never inlined, so that its symbol
+/// ([`powdr_riscv_syscalls::COMMIT_PUBLIC_SYMBOL`]) tells whether a program
+/// commits public outputs.
+#[no_mangle]
+#[inline(never)]
+extern "C" fn __powdr_commit_public(cell: u32, value: u32) {
+    unsafe {
+        asm!("ecall", in("a0") cell, in("a1") value, in("t0") u32::from(Syscall::CommitPublic));
+    }
+}
@@ -0,0 +1,26 @@
+//! Invoking the syscalls of custom coprocessors.
+//!
+//! Custom coprocessors are enabled on the host, each with its own syscall
+//! number. Their syscall implementation receives the arguments in registers
+//! x10 to x13, and may return a value in x10. See `powdr_riscv::coprocessors`
+//! for how to define and enable them.
+
+use core::arch::asm;
+
+/// Calls the custom syscall with the given number, passing the arguments in
+/// registers x10 to x13. Returns the value of x10 after the syscall.
+///
+/// This is unsafe because the syscall may access memory through the arguments.
+pub unsafe fn syscall(number: u32, args: [u32; 4]) -> u32 {
+    let mut result: u32;
+    asm!(
+        "ecall",
+        inout("a0") args[0] => result,
+        // The syscall implementation may overwrite its argument registers.
+        inout("a1") args[1] => _,
+        inout("a2") args[2] => _,
+        inout("a3") args[3] => _,
+        in("t0") number,
+    );
+    result
+}
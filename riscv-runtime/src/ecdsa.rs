@@ -0,0 +1,43 @@
+//! ECDSA signature verification over k256, using the arith machine.
+
+use crate::arith::{be_to_u32, lt_256, modinv_256_u32_le, modmul_256_u32_le, reduce_256};
+use crate::ec::{double_mul_u32_le, is_on_curve_u32_le, GENERATOR, N, P};
+
+/// Verify the ECDSA signature `(r, s)` of the message hash `z` with the public
+/// key `(x, y)`. All values are big-endian u8 arrays.
+pub fn verify_u8_be(z: [u8; 32], r: [u8; 32], s: [u8; 32], x: [u8; 32], y: [u8; 32]) -> bool {
+    let mut z1: [u32; 8] = Default::default();
+    let mut r1: [u32; 8] = Default::default();
+    let mut s1: [u32; 8] = Default::default();
+    let mut x1: [u32; 8] = Default::default();
+    let mut y1: [u32; 8] = Default::default();
+
+    be_to_u32(&z, &mut z1);
+    be_to_u32(&r, &mut r1);
+    be_to_u32(&s, &mut s1);
+    be_to_u32(&x, &mut x1);
+    be_to_u32(&y, &mut y1);
+
+    verify_u32_le(z1, r1, s1, x1, y1)
+}
+
+/// Verify the ECDSA signature `(r, s)` of the message hash `z` with the public
+/// key `(x, y)`. All values are little-endian u32 arrays.
+pub fn verify_u32_le(z: [u32; 8], r: [u32; 8], s: [u32; 8], x: [u32; 8], y: [u32; 8]) -> bool {
+    let zero = [0u32; 8];
+    if r == zero || s == zero || !lt_256(&r, &N) || !lt_256(&s, &N) {
+        return false;
+    }
+    if !lt_256(&x, &P) || !lt_256(&y, &P) || !is_on_curve_u32_le(x, y) {
+        return false;
+    }
+
+    let w = modinv_256_u32_le(s, N);
+    let u1 = modmul_256_u32_le(z, w, N);
+    let u2 = modmul_256_u32_le(r, w, N);
+    match double_mul_u32_le(u1, GENERATOR, u2, (x, y)) {
+        // The x coordinate is smaller than P, which is smaller than 2 * N.
+        Some((rx, _)) => reduce_256(rx, &N) == r,
+        None => false,
+    }
+}
@@ -1,23 +1,135 @@
+//! The entropy source used by `getrandom` and by `std`, e.g. for the hash
+//! function of `HashMap`.
+//!
+//! There is no real entropy source in a ZK program. With the `seeded_rand`
+//! feature, random bytes are taken from a ChaCha20 key stream, keyed with a
+//! seed the host writes to the [`ENTROPY_SEED_FD`] input channel. The seed is
+//! committed as public outputs when the program terminates, so that the
+//! execution is reproducible and a verifier can check which seed was used.
+//!
+//! With the `allow_fake_rand` feature, a fixed value is returned instead.
+//! Otherwise, any attempt to use the entropy source panics.
+
+#[cfg(feature = "seeded_rand")]
+use core::ptr::addr_of_mut;
+
+#[cfg(feature = "seeded_rand")]
+use powdr_riscv_syscalls::ENTROPY_SEED_FD;
+use powdr_riscv_syscalls::ENTROPY_SEED_WORDS;
+
+/// A ChaCha20 key stream with a zero nonce, as specified in RFC 8439.
+#[cfg(feature = "seeded_rand")]
+struct ChaCha20 {
+    key: [u32; ENTROPY_SEED_WORDS],
+    /// The counter of the next block.
+    counter: u32,
+    /// The current block of the key stream.
+    block: [u8; 64],
+    /// The number of bytes of the current block already used.
+    pos: usize,
+}
+
+#[cfg(feature = "seeded_rand")]
+impl ChaCha20 {
+    const fn new(key: [u32; ENTROPY_SEED_WORDS]) -> Self {
+        Self {
+            key,
+            counter: 0,
+            block: [0; 64],
+            pos: 64,
+        }
+    }
+
+    fn quarter_round(state: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+        state[a] = state[a].wrapping_add(state[b]);
+        state[d] = (state[d] ^ state[a]).rotate_left(16);
+        state[c] = state[c].wrapping_add(state[d]);
+        state[b] = (state[b] ^ state[c]).rotate_left(12);
+        state[a] = state[a].wrapping_add(state[b]);
+        state[d] = (state[d] ^ state[a]).rotate_left(8);
+        state[c] = state[c].wrapping_add(state[d]);
+        state[b] = (state[b] ^ state[c]).rotate_left(7);
+    }
+
+    fn next_block(&mut self) {
+        let mut input = [0u32; 16];
+        input[..4].copy_from_slice(&[0x61707865, 0x3320646e, 0x79622d32, 0x6b206574]);
+        input[4..12].copy_from_slice(&self.key);
+        input[12] = self.counter;
+        self.counter = self
+            .counter
+            .checked_add(1)
+            .expect("Entropy source exhausted");
+
+        let mut state = input;
+        for _ in 0..10 {
+            Self::quarter_round(&mut state, 0, 4, 8, 12);
+            Self::quarter_round(&mut state, 1, 5, 9, 13);
+            Self::quarter_round(&mut state, 2, 6, 10, 14);
+            Self::quarter_round(&mut state, 3, 7, 11, 15);
+            Self::quarter_round(&mut state, 0, 5, 10, 15);
+            Self::quarter_round(&mut state, 1, 6, 11, 12);
+            Self::quarter_round(&mut state, 2, 7, 8, 13);
+            Self::quarter_round(&mut state, 3, 4, 9, 14);
+        }
+
+        for (i, (word, input)) in state.iter().zip(input).enumerate() {
+            self.block[4 * i..4 * i + 4].copy_from_slice(&word.wrapping_add(input).to_le_bytes());
+        }
+        self.pos = 0;
+    }
+
+    fn fill(&mut self, mut s: &mut [u8]) {
+        while !s.is_empty() {
+            if self.pos == self.block.len() {
+                self.next_block();
+            }
+            let len = s.len().min(self.block.len() - self.pos);
+            s[..len].copy_from_slice(&self.block[self.pos..self.pos + len]);
+            self.pos += len;
+            s = &mut s[len..];
+        }
+    }
+}
+
+/// The key stream, created from the seed when the entropy source is first used.
+#[cfg(feature = "seeded_rand")]
+static mut RNG: Option<ChaCha20> = None;
+
+#[cfg(feature = "seeded_rand")]
+pub(crate) fn getrandom(s: &mut [u8]) {
+    let rng = unsafe { &mut *addr_of_mut!(RNG) };
+    rng.get_or_insert_with(|| {
+        crate::commit::mark_committed();
+        ChaCha20::new(crate::io::read_uncommitted(ENTROPY_SEED_FD))
+    })
+    .fill(s);
+}
+
 /// This is a placeholder to pretend to provide a random number generator, for
 /// places like the hash function of HashMap who needs something.
-///
-/// This could be improved by using the rand_chacha crate, but I think it is
-/// worse, as it will just mask the fact that we are not providing a real
-/// entropy source.
-///
-/// TODO: figure how to be truly random
-#[cfg(feature = "allow_fake_rand")]
+#[cfg(all(feature = "allow_fake_rand", not(feature = "seeded_rand")))]
 pub(crate) fn getrandom(s: &mut [u8]) {
     const VALUE: u8 = 3;
     s.iter_mut().for_each(|v| *v = VALUE);
 }
 
-#[cfg(not(feature = "allow_fake_rand"))]
+#[cfg(not(any(feature = "allow_fake_rand", feature = "seeded_rand")))]
 pub(crate) fn getrandom(_: &mut [u8]) {
     panic!(
         r#"There is no real entropy source in Powdr.
-You may enable, at your own risk, the "allow_fake_rand" feature of
-"powdr-riscv-runtime" crate to get a deterministic value instead
-of this panic."#
+You may enable the "seeded_rand" feature of the "powdr-riscv-runtime"
+crate to get deterministic values from a seed provided by the host,
+or, at your own risk, the "allow_fake_rand" feature to get a constant
+value instead of this panic."#
     );
 }
+
+/// The seed the entropy source was used with, or `None` if it was not used.
+pub(crate) fn seed() -> Option<[u32; ENTROPY_SEED_WORDS]> {
+    #[cfg(feature = "seeded_rand")]
+    if let Some(rng) = unsafe { &*addr_of_mut!(RNG) } {
+        return Some(rng.key);
+    }
+    None
+}
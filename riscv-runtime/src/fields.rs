@@ -0,0 +1,153 @@
+//! Arithmetic in the base fields of the BN254 and BLS12-381 curves and in
+//! their quadratic extensions, using the arith384 machine.
+//!
+//! Base field elements are 384 bit values as u32 little-endian arrays, which
+//! must be smaller than the modulus `P`. Elements of the quadratic extension
+//! `Fp2 = Fp[u] / (u^2 + 1)` are pairs `(c0, c1)` representing `c0 + c1 * u`.
+//! All results are in canonical form.
+
+use crate::arith::{mul_add_384_u32_le, mul_sub_384_u32_le};
+
+/// A base field element, as u32 little-endian array.
+pub type Fp = [u32; 12];
+/// An element `c0 + c1 * u` of the quadratic extension, as `(c0, c1)`.
+pub type Fp2 = (Fp, Fp);
+
+pub const ZERO: Fp = [0; 12];
+pub const ONE: Fp = [1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+
+fn add(a: Fp, b: Fp, p: Fp) -> Fp {
+    mul_add_384_u32_le(a, ONE, b, ONE, p)
+}
+
+fn sub(a: Fp, b: Fp, p: Fp) -> Fp {
+    mul_sub_384_u32_le(a, ONE, b, ONE, p)
+}
+
+fn neg(a: Fp, p: Fp) -> Fp {
+    mul_sub_384_u32_le(ZERO, ZERO, a, ONE, p)
+}
+
+fn mul(a: Fp, b: Fp, p: Fp) -> Fp {
+    mul_add_384_u32_le(a, b, ZERO, ZERO, p)
+}
+
+/// Computes `a^(p - 2)`, which is the inverse of `a` for non-zero `a`.
+fn inv(a: Fp, p: Fp) -> Fp {
+    assert!(a != ZERO, "zero is not invertible");
+    let mut e = p;
+    // p is odd, so subtracting 2 doesn't borrow from the next word.
+    e[0] -= 2;
+    let bit = |i: usize| (e[i / 32] >> (i % 32)) & 1 == 1;
+    let mut result = ONE;
+    for i in (0..384).rev().skip_while(|&i| !bit(i)) {
+        result = mul(result, result, p);
+        if bit(i) {
+            result = mul(result, a, p);
+        }
+    }
+    result
+}
+
+fn fp2_add(a: Fp2, b: Fp2, p: Fp) -> Fp2 {
+    (add(a.0, b.0, p), add(a.1, b.1, p))
+}
+
+fn fp2_sub(a: Fp2, b: Fp2, p: Fp) -> Fp2 {
+    (sub(a.0, b.0, p), sub(a.1, b.1, p))
+}
+
+fn fp2_neg(a: Fp2, p: Fp) -> Fp2 {
+    (neg(a.0, p), neg(a.1, p))
+}
+
+fn fp2_mul(a: Fp2, b: Fp2, p: Fp) -> Fp2 {
+    (
+        mul_sub_384_u32_le(a.0, b.0, a.1, b.1, p),
+        mul_add_384_u32_le(a.0, b.1, a.1, b.0, p),
+    )
+}
+
+fn fp2_square(a: Fp2, p: Fp) -> Fp2 {
+    fp2_mul(a, a, p)
+}
+
+/// The inverse of `c0 + c1 * u` is `(c0 - c1 * u) / (c0^2 + c1^2)`.
+fn fp2_inv(a: Fp2, p: Fp) -> Fp2 {
+    let norm_inv = inv(mul_add_384_u32_le(a.0, a.0, a.1, a.1, p), p);
+    (
+        mul(a.0, norm_inv, p),
+        mul_sub_384_u32_le(ZERO, ZERO, a.1, norm_inv, p),
+    )
+}
+
+macro_rules! field_impl {
+    ($p:expr) => {
+        use super::{Fp, Fp2};
+
+        /// The modulus of the base field.
+        pub const P: Fp = $p;
+
+        pub fn add(a: Fp, b: Fp) -> Fp {
+            super::add(a, b, P)
+        }
+
+        pub fn sub(a: Fp, b: Fp) -> Fp {
+            super::sub(a, b, P)
+        }
+
+        pub fn neg(a: Fp) -> Fp {
+            super::neg(a, P)
+        }
+
+        pub fn mul(a: Fp, b: Fp) -> Fp {
+            super::mul(a, b, P)
+        }
+
+        /// Panics if `a` is zero.
+        pub fn inv(a: Fp) -> Fp {
+            super::inv(a, P)
+        }
+
+        pub fn fp2_add(a: Fp2, b: Fp2) -> Fp2 {
+            super::fp2_add(a, b, P)
+        }
+
+        pub fn fp2_sub(a: Fp2, b: Fp2) -> Fp2 {
+            super::fp2_sub(a, b, P)
+        }
+
+        pub fn fp2_neg(a: Fp2) -> Fp2 {
+            super::fp2_neg(a, P)
+        }
+
+        pub fn fp2_mul(a: Fp2, b: Fp2) -> Fp2 {
+            super::fp2_mul(a, b, P)
+        }
+
+        pub fn fp2_square(a: Fp2) -> Fp2 {
+            super::fp2_square(a, P)
+        }
+
+        /// Panics if `a` is zero.
+        pub fn fp2_inv(a: Fp2) -> Fp2 {
+            super::fp2_inv(a, P)
+        }
+    };
+}
+
+/// The base field of BN254 and its quadratic extension.
+pub mod bn254 {
+    field_impl!([
+        0xd87cfd47, 0x3c208c16, 0x6871ca8d, 0x97816a91, 0x8181585d, 0xb85045b6, 0xe131a029,
+        0x30644e72, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    ]);
+}
+
+/// The base field of BLS12-381 and its quadratic extension.
+pub mod bls12_381 {
+    field_impl!([
+        0xffffaaab, 0xb9feffff, 0xb153ffff, 0x1eabfffe, 0xf6b0f624, 0x6730d2a0, 0xf38512bf,
+        0x64774b84, 0x434bacd7, 0x4b1ba7b6, 0x397fe69a, 0x1a0111ea,
+    ]);
+}
@@ -11,32 +11,43 @@ macro_rules! print {
 }
 
 pub fn print_args(args: fmt::Arguments) {
-    fmt::write(&mut ProverWriter {}, args).unwrap();
+    write_args(1, args);
 }
 
-struct ProverWriter {}
+/// Writes formatted output to the file descriptor fd.
+pub fn write_args(fd: u32, args: fmt::Arguments) {
+    fmt::write(&mut ProverWriter { fd }, args).unwrap();
+}
+
+struct ProverWriter {
+    fd: u32,
+}
 
 impl fmt::Write for ProverWriter {
     fn write_str(&mut self, s: &str) -> fmt::Result {
-        print_str(s);
+        write_str(self.fd, s);
         Ok(())
     }
 }
 
 pub fn print_str(s: &str) {
+    write_str(1, s);
+}
+
+fn write_str(fd: u32, s: &str) {
     // DEAR DEV, please don't allow this function to panic.
     //
     // This is called from the panic handler.
     for b in s.bytes() {
-        print_prover_char(b)
+        print_prover_char(fd, b)
     }
 }
 
 #[inline]
-fn print_prover_char(c: u8) {
+fn print_prover_char(fd: u32, c: u8) {
     let mut value = c as u32;
     #[allow(unused_assignments)]
     unsafe {
-        asm!("ecall", lateout("a0") value, in("a0") 1, in("a1") value, in("t0") u32::from(Syscall::Output));
+        asm!("ecall", lateout("a0") value, in("a0") fd, in("a1") value, in("t0") u32::from(Syscall::Output));
     }
 }
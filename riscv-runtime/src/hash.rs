@@ -78,3 +78,52 @@ pub fn keccak(data: &[u8], delim: u8) -> [u8; W] {
     output.copy_from_slice(&b_output[..W]);
     output
 }
+
+/// Calls the sha256 machine.
+/// Compresses the message block, given as 16 big-endian words, into the state.
+pub fn sha256_compress(state: &mut [u32; 8], block: &[u32; 16]) {
+    unsafe {
+        // Syscall inputs: memory pointer to the state and memory pointer to the block.
+        asm!("ecall", in("a0") state, in("a1") block, in("t0") u32::from(Syscall::Sha256));
+    }
+}
+
+const SHA256_INITIAL_STATE: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// SHA-256 hash function that calls the sha256 machine.
+/// Input is a byte array of arbitrary length.
+pub fn sha256(data: &[u8]) -> [u8; 32] {
+    let mut state = SHA256_INITIAL_STATE;
+    let mut compress = |bytes: &[u8]| {
+        let mut block = [0u32; 16];
+        for (word, bytes) in block.iter_mut().zip(bytes.chunks_exact(4)) {
+            *word = u32::from_be_bytes(bytes.try_into().unwrap());
+        }
+        sha256_compress(&mut state, &block);
+    };
+
+    let mut blocks = data.chunks_exact(64);
+    for block in &mut blocks {
+        compress(block);
+    }
+
+    // Pad the remaining bytes with a single 1 bit and zeros, followed by the
+    // message length in bits, to one or two blocks.
+    let remainder = blocks.remainder();
+    let mut last = [0u8; 128];
+    last[..remainder.len()].copy_from_slice(remainder);
+    last[remainder.len()] = 0x80;
+    let len = if remainder.len() < 56 { 64 } else { 128 };
+    last[len - 8..len].copy_from_slice(&(data.len() as u64 * 8).to_be_bytes());
+    for block in last[..len].chunks_exact(64) {
+        compress(block);
+    }
+
+    let mut output = [0u8; 32];
+    for (bytes, word) in output.chunks_exact_mut(4).zip(state) {
+        bytes.copy_from_slice(&word.to_be_bytes());
+    }
+    output
+}
@@ -1,3 +1,12 @@
+//! Input and output channels shared with the host.
+//!
+//! With the `commit_inputs` feature, every read function goes through
+//! [`crate::commit`]: the first access to an input channel reads its whole
+//! data from the host and commits its digest, and all later reads of the
+//! channel are served from that data. At most
+//! [`powdr_riscv_syscalls::MAX_COMMITTED_INPUTS`] different channels can be
+//! read in this mode.
+
 use core::arch::asm;
 
 extern crate alloc;
@@ -9,29 +18,62 @@ use alloc::vec::Vec;
 
 /// A single u32 from input channel 0.
 pub fn read_u32(idx: u32) -> u32 {
-    let mut value: u32;
-    unsafe {
-        asm!("ecall", lateout("a0") value, in("a0") 0, in("a1") idx + 1, in("t0") u32::from(Syscall::Input));
+    #[cfg(feature = "commit_inputs")]
+    {
+        crate::commit::committed_input(0)[idx as usize]
+    }
+    #[cfg(not(feature = "commit_inputs"))]
+    {
+        query_input(0, idx + 1)
     }
-    value
 }
 
 /// Reads data.len() u32s from the file descriptor fd into the data slice.
 pub fn read_slice(fd: u32, data: &mut [u32]) {
-    for (i, d) in data.iter_mut().enumerate() {
-        unsafe {
-            asm!("ecall", lateout("a0") *d, in("a0") fd, in("a1") (i+1) as u32, in("t0") u32::from(Syscall::Input))
-        };
-    }
+    #[cfg(feature = "commit_inputs")]
+    data.copy_from_slice(&crate::commit::committed_input(fd)[..data.len()]);
+    #[cfg(not(feature = "commit_inputs"))]
+    query_slice(fd, data);
 }
 
 /// Reads the length of the data first at index 0, then the data itself.
 pub fn read_data_len(fd: u32) -> usize {
-    let mut out: u32;
+    #[cfg(feature = "commit_inputs")]
+    {
+        crate::commit::committed_input(fd).len()
+    }
+    #[cfg(not(feature = "commit_inputs"))]
+    {
+        query_input(fd, 0) as usize
+    }
+}
+
+/// Reads the whole data of the file descriptor fd from the host, bypassing
+/// the committed inputs.
+#[cfg_attr(
+    not(any(feature = "commit_inputs", feature = "seeded_rand")),
+    allow(dead_code)
+)]
+pub(crate) fn read_channel_uncommitted(fd: u32) -> Vec<u32> {
+    let mut data = vec![0; query_input(fd, 0) as usize];
+    query_slice(fd, &mut data);
+    data
+}
+
+fn query_slice(fd: u32, data: &mut [u32]) {
+    for (i, d) in data.iter_mut().enumerate() {
+        *d = query_input(fd, (i + 1) as u32);
+    }
+}
+
+/// Queries the word at the given index of the file descriptor fd from the
+/// host, index 0 being the length of the data.
+fn query_input(fd: u32, idx: u32) -> u32 {
+    let mut value: u32;
     unsafe {
-        asm!("ecall", lateout("a0") out, in("a0") fd, in("a1") 0, in("t0") u32::from(Syscall::Input))
-    };
-    out as usize
+        asm!("ecall", lateout("a0") value, in("a0") fd, in("a1") idx, in("t0") u32::from(Syscall::Input));
+    }
+    value
 }
 
 /// Writes a single u8 to the file descriptor fd.
@@ -56,10 +98,18 @@ pub fn read<T: DeserializeOwned>(fd: u32) -> T {
     let l = read_data_len(fd);
     let mut data = vec![0; l];
     read_slice(fd, &mut data);
+    deserialize(data)
+}
 
+/// Like [`read`], but never commits the data, for inputs the runtime commits otherwise.
+#[cfg_attr(not(feature = "seeded_rand"), allow(dead_code))]
+pub(crate) fn read_uncommitted<T: DeserializeOwned>(fd: u32) -> T {
+    deserialize(read_channel_uncommitted(fd))
+}
+
+fn deserialize<T: DeserializeOwned>(data: Vec<u32>) -> T {
     // TODO this extra conversion can be removed if we change everything to be u8
     let data: Vec<u8> = data.into_iter().map(|x| x as u8).collect();
-
     serde_cbor::from_slice(data.as_slice()).unwrap()
 }
 
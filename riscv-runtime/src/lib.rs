@@ -7,14 +7,19 @@
 )]
 
 use core::arch::{asm, global_asm};
-use powdr_riscv_syscalls::Syscall;
+use powdr_riscv_syscalls::{Syscall, EXIT_FD};
 
 mod allocator;
 pub mod arith;
+pub mod commit;
+pub mod custom;
 pub mod ec;
+pub mod ecdsa;
+pub mod fields;
 pub mod fmt;
 pub mod hash;
 pub mod io;
+pub mod schnorr;
 
 mod entropy_source;
 #[cfg(feature = "getrandom")]
@@ -24,22 +29,37 @@ mod no_std_support;
 #[cfg(feature = "std")]
 mod std_support;
 
-pub fn halt() -> ! {
+/// Terminates the program with the given exit code, committing the journal.
+/// The exit code is reported to the host and committed as a public output by
+/// the `halt` syscall, so that a proof attests to it.
+pub fn exit(code: u32) -> ! {
+    io::write_slice(EXIT_FD, &code.to_le_bytes());
+    let committed = commit::finalize();
     unsafe {
-        asm!("ecall", in("t0") u32::from(Syscall::Halt));
+        asm!("ecall", in("a0") code, in("a1") u32::from(committed), in("t0") u32::from(Syscall::Halt));
     }
     #[allow(clippy::empty_loop)]
     loop {}
 }
 
+/// Terminates the program successfully, committing the journal.
+pub fn halt() -> ! {
+    exit(0)
+}
+
+/// Called by `__runtime_start` when `main` returns.
+#[no_mangle]
+extern "C" fn __runtime_exit() -> ! {
+    halt()
+}
+
 // Entry point function __runtime_start:
 // 1. Sets the global pointer register (the symbol __global_pointer$ is standard
 //    in RISC-V, and it is set by the linker).
 // 2. Sets the stack pointer to the extern symbol __powdr_stack_start (this must
 //    also be set by the linker, but the name is powdr specific).
-// 3. Tail call the main function (in powdr, the return address register is already
-//    set, so that returning from the entry point function will cause the execution
-//    to succeed).
+// 3. Calls the main function.
+// 4. Tail calls `__runtime_exit`, which commits the journal and halts.
 // TODO: support Position Independent Executables (PIE) by using lla.
 global_asm!(
     r"
@@ -55,6 +75,7 @@ __runtime_start:
     #lla sp, __powdr_stack_start
     lui sp, %hi(__powdr_stack_start)
     addi sp, sp, %lo(__powdr_stack_start)
-    tail main
+    call main
+    tail __runtime_exit
 "
 );
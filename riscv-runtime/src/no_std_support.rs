@@ -10,6 +10,11 @@ unsafe fn panic(panic: &PanicInfo<'_>) -> ! {
         IS_PANICKING = true;
 
         print!("Panic: {panic}\n");
+        // Report the message and location to the host. With `provable_panic`,
+        // then exit with a distinct exit code, so that the panic can be proven.
+        crate::fmt::write_args(powdr_riscv_syscalls::PANIC_FD, format_args!("{panic}"));
+        #[cfg(feature = "provable_panic")]
+        crate::exit(powdr_riscv_syscalls::PANIC_EXIT_CODE);
     } else {
         print_str("Panic handler has panicked! Things are very dire indeed...\n");
     }
@@ -0,0 +1,75 @@
+//! BIP-340 Schnorr signature verification over k256, using the arith machine
+//! and the sha256 machine.
+
+use crate::arith::{be_to_u32, bit_256, lt_256, modmul_256_u32_le, reduce_256, sub_256};
+use crate::ec::{curve_rhs, double_mul_u32_le, GENERATOR, N, P};
+use crate::hash::sha256;
+
+/// `(P + 1) / 4`, square roots modulo `P` are powers with this exponent.
+const SQRT_EXPONENT: [u32; 8] = [
+    0xbfffff0c, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff, 0x3fffffff,
+];
+
+/// Verify the BIP-340 Schnorr signature of a 32 byte message with the x-only
+/// public key. All values are big-endian u8 arrays.
+pub fn verify(public_key: [u8; 32], message: [u8; 32], signature: [u8; 64]) -> bool {
+    let Some((px, py)) = lift_x(public_key) else {
+        return false;
+    };
+
+    let (r_bytes, s_bytes) = signature.split_at(32);
+    let mut r: [u32; 8] = Default::default();
+    let mut s: [u32; 8] = Default::default();
+    be_to_u32(r_bytes.try_into().unwrap(), &mut r);
+    be_to_u32(s_bytes.try_into().unwrap(), &mut s);
+    if !lt_256(&r, &P) || !lt_256(&s, &N) {
+        return false;
+    }
+
+    // e = sha256(tag || tag || r || public_key || message), where the tag is
+    // sha256("BIP0340/challenge"). There is no need to reduce it modulo N.
+    let tag = sha256(b"BIP0340/challenge");
+    let mut challenge = [0u8; 160];
+    challenge[..32].copy_from_slice(&tag);
+    challenge[32..64].copy_from_slice(&tag);
+    challenge[64..96].copy_from_slice(r_bytes);
+    challenge[96..128].copy_from_slice(&public_key);
+    challenge[128..].copy_from_slice(&message);
+    let mut e: [u32; 8] = Default::default();
+    be_to_u32(&sha256(&challenge), &mut e);
+
+    // R = s * G - e * P
+    match double_mul_u32_le(s, GENERATOR, e, (px, sub_256(&P, &py))) {
+        Some((rx, ry)) => ry[0] & 1 == 0 && rx == r,
+        None => false,
+    }
+}
+
+/// Returns the point with the given x coordinate and an even y coordinate,
+/// if there is one.
+fn lift_x(x: [u8; 32]) -> Option<([u32; 8], [u32; 8])> {
+    let mut x1: [u32; 8] = Default::default();
+    be_to_u32(&x, &mut x1);
+    if !lt_256(&x1, &P) {
+        return None;
+    }
+
+    let c = curve_rhs(x1);
+    let y = reduce_256(pow_mod(c, &SQRT_EXPONENT), &P);
+    if reduce_256(modmul_256_u32_le(y, y, P), &P) != c {
+        return None;
+    }
+    Some((x1, if y[0] & 1 == 0 { y } else { sub_256(&P, &y) }))
+}
+
+/// Compute `base^exponent % P`.
+fn pow_mod(base: [u32; 8], exponent: &[u32; 8]) -> [u32; 8] {
+    let mut result = [1, 0, 0, 0, 0, 0, 0, 0];
+    for i in (0..256).rev() {
+        result = modmul_256_u32_le(result, result, P);
+        if bit_256(exponent, i) {
+            result = modmul_256_u32_le(result, base, P);
+        }
+    }
+    result
+}
@@ -7,7 +7,7 @@
 // allocator, because we already define a global allocator in the `allocator`
 // module. It is used in both `std` and `no_std` modes.
 
-use core::{alloc::Layout, arch::asm, slice};
+use core::{alloc::Layout, slice};
 
 use powdr_riscv_syscalls::Syscall;
 
@@ -26,16 +26,31 @@ extern "C" fn sys_rand(buf: *mut u32, words: usize) {
 #[no_mangle]
 extern "C" fn sys_panic(msg_ptr: *const u8, len: usize) -> ! {
     let out = u32::from(Syscall::Output);
-    unsafe {
-        write_slice(out, "Panic: ".as_bytes());
-        write_slice(out, slice::from_raw_parts(msg_ptr, len));
-        write_slice(out, b"\n");
+    let msg = unsafe { slice::from_raw_parts(msg_ptr, len) };
+    write_slice(out, "Panic: ".as_bytes());
+    write_slice(out, msg);
+    write_slice(out, b"\n");
+    // Report the panic message to the host.
+    write_slice(powdr_riscv_syscalls::PANIC_FD, msg);
+
+    abort()
+}
 
-        asm!("unimp");
+/// Exits with a distinct exit code, so that the panic can be proven.
+#[cfg(feature = "provable_panic")]
+fn abort() -> ! {
+    crate::exit(powdr_riscv_syscalls::PANIC_EXIT_CODE)
+}
 
-        #[allow(clippy::empty_loop)]
-        loop {}
+/// Fails the execution, so that the panic cannot be proven.
+#[cfg(not(feature = "provable_panic"))]
+fn abort() -> ! {
+    unsafe {
+        core::arch::asm!("unimp");
     }
+
+    #[allow(clippy::empty_loop)]
+    loop {}
 }
 
 #[no_mangle]
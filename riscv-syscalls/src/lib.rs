@@ -55,4 +55,77 @@ syscalls!(
     (7, KeccakF, "keccakf"),
     (8, Mod256, "mod_256"),
     (9, Halt, "halt"),
+    (10, CommitPublic, "commit_public"),
+    (11, Sha256, "sha256"),
+    (12, ModInv256, "mod_inv_256"),
+    (13, MulAdd384, "mul_add_384"),
+    (14, MulSub384, "mul_sub_384"),
 );
+
+/// The output file descriptor the committed public outputs ("journal") are
+/// written to, so that the host can read them.
+pub const JOURNAL_FD: u32 = 1000;
+
+/// The number of 32-bit words of the journal digest that are committed as
+/// public values.
+pub const JOURNAL_DIGEST_WORDS: usize = 8;
+
+/// The maximum number of input channels whose digest can be committed as
+/// public values, when the runtime commits its inputs.
+pub const MAX_COMMITTED_INPUTS: usize = 4;
+
+/// The public output cell holding the number of committed input channels.
+/// It follows the journal digest.
+pub const COMMITTED_INPUTS_COUNT_CELL: usize = JOURNAL_DIGEST_WORDS;
+
+/// The first public output cell of the given committed input slot. Each slot
+/// holds the channel, followed by the `JOURNAL_DIGEST_WORDS` words of the
+/// digest of the data read from it.
+pub const fn committed_input_cell(slot: usize) -> usize {
+    COMMITTED_INPUTS_COUNT_CELL + 1 + slot * (1 + JOURNAL_DIGEST_WORDS)
+}
+
+/// The public output cell holding the exit code of the guest. It follows the
+/// committed input slots. It is written by the `halt` syscall.
+pub const EXIT_CODE_CELL: usize = committed_input_cell(MAX_COMMITTED_INPUTS);
+
+/// The number of 32-bit words of the seed of the deterministic entropy source.
+pub const ENTROPY_SEED_WORDS: usize = 8;
+
+/// The public output cell holding 1 if the guest used the deterministic
+/// entropy source, and 0 otherwise. It follows the exit code.
+pub const ENTROPY_SEED_USED_CELL: usize = EXIT_CODE_CELL + 1;
+
+/// The first public output cell holding the seed of the deterministic entropy
+/// source. It follows the flag of [`ENTROPY_SEED_USED_CELL`]. The seed is zero
+/// if the guest did not use the entropy source.
+pub const ENTROPY_SEED_CELL: usize = ENTROPY_SEED_USED_CELL + 1;
+
+/// The public output cell holding 1 if the runtime committed the journal
+/// digest, the committed inputs and the entropy seed, and 0 if the guest used
+/// none of them. In the latter case, these cells are not written and must be
+/// read as zero. It follows the seed and is written by the `halt` syscall.
+pub const OUTPUTS_COMMITTED_CELL: usize = ENTROPY_SEED_CELL + ENTROPY_SEED_WORDS;
+
+/// The total number of public output cells.
+pub const PUBLIC_OUTPUT_CELLS: usize = OUTPUTS_COMMITTED_CELL + 1;
+
+/// The symbol of the runtime function issuing the `commit_public` syscall. It
+/// is only linked into programs that commit public outputs, which allows
+/// rejecting them when translating for fields without public outputs.
+pub const COMMIT_PUBLIC_SYMBOL: &str = "__powdr_commit_public";
+
+/// The output file descriptor the exit code of the guest is written to, as a
+/// little-endian 32-bit integer.
+pub const EXIT_FD: u32 = 1001;
+
+/// The output file descriptor the message of a guest panic is written to,
+/// including the source location of the panic.
+pub const PANIC_FD: u32 = 1002;
+
+/// The exit code used when the guest panics.
+pub const PANIC_EXIT_CODE: u32 = 101;
+
+/// The input channel the seed of the deterministic entropy source is read
+/// from, as an array of `ENTROPY_SEED_WORDS` 32-bit words.
+pub const ENTROPY_SEED_FD: u32 = 1003;
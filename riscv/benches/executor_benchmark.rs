@@ -4,6 +4,7 @@ use powdr_number::GoldilocksField;
 use powdr_riscv::{
     compile_rust_crate_to_riscv, continuations::bootloader::default_input, elf, CompilerOptions,
 };
+use powdr_riscv_executor::{ExecMode, MemoryState};
 
 use criterion::{criterion_group, criterion_main, Criterion};
 use mktemp::Temp;
@@ -19,7 +20,7 @@ fn executor_benchmark(c: &mut Criterion) {
     let executable =
         compile_rust_crate_to_riscv("./tests/riscv_data/keccak/Cargo.toml", &tmp_dir, None);
     let options = CompilerOptions::new_gl();
-    let contents = elf::translate(&executable, options);
+    let contents = elf::translate(&executable, options.clone()).unwrap();
     let mut pipeline = Pipeline::<T>::default().from_asm_string(contents, None);
     pipeline.compute_optimized_pil().unwrap();
     pipeline.compute_fixed_cols().unwrap();
@@ -32,7 +33,7 @@ fn executor_benchmark(c: &mut Criterion) {
     let executable =
         compile_rust_crate_to_riscv("./tests/riscv_data/many_chunks/Cargo.toml", &tmp_dir, None);
     let options = options.with_continuations().with_poseidon();
-    let contents = elf::translate(&executable, options);
+    let contents = elf::translate(&executable, options).unwrap();
     let mut pipeline = Pipeline::<T>::default().from_asm_string(contents, None);
     pipeline.compute_optimized_pil().unwrap();
     pipeline.compute_fixed_cols().unwrap();
@@ -44,6 +45,45 @@ fn executor_benchmark(c: &mut Criterion) {
     group.bench_function("many_chunks_chunk_0", |b| {
         b.iter(|| pipeline.clone().compute_witness().unwrap())
     });
+
+    // The elliptic curve operations of the arith machine, and the ECDSA scalar
+    // multiplications, with a double-and-add loop in the guest and with the
+    // batched implementation of the runtime
+    let options = CompilerOptions::new_gl().with_arith();
+    let benchmarks: [(&str, &str, Vec<T>); 4] = [
+        ("ec_add", "ec_add", vec![]),
+        ("ec_double", "ec_double", vec![]),
+        ("ec_mul_loop", "ec_mul", vec![0.into()]),
+        ("ec_mul_batched", "ec_mul", vec![1.into()]),
+    ];
+    for (name, case, inputs) in benchmarks {
+        let executable = compile_rust_crate_to_riscv(
+            &format!("./tests/riscv_data/{case}/Cargo.toml"),
+            &tmp_dir,
+            None,
+        );
+        let contents = elf::translate(&executable, options.clone()).unwrap();
+        let mut pipeline = Pipeline::<T>::default()
+            .from_asm_string(contents, None)
+            .with_prover_inputs(inputs);
+        let asm = pipeline.compute_asm_string().unwrap().1.clone();
+        let execute = || {
+            powdr_riscv_executor::execute::<T>(
+                &asm,
+                MemoryState::new(),
+                pipeline.data_callback().unwrap(),
+                &[],
+                ExecMode::Fast,
+                &options.libs.custom_instructions(),
+                None,
+            )
+            .unwrap()
+            .0
+            .len
+        };
+        log::info!("{name}: {} cycles", execute());
+        group.bench_function(name, |b| b.iter(execute));
+    }
     group.finish();
 }
 
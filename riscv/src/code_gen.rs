@@ -107,10 +107,14 @@ pub trait RiscVProgram {
 /// Translates a RISC-V program to POWDR ASM.
 ///
 /// Will call each of the methods in the `RiscVProgram` just once.
-pub fn translate_program(program: impl RiscVProgram, options: CompilerOptions) -> String {
+/// Fails if a selected coprocessor is not supported on the selected field.
+pub fn translate_program(
+    program: impl RiscVProgram,
+    options: CompilerOptions,
+) -> Result<String, String> {
     match options.field.field_size() {
         FieldSize::Small => small_field::code_gen::translate_program(program, options),
-        FieldSize::Large => large_field::code_gen::translate_program(program, options),
+        FieldSize::Large => Ok(large_field::code_gen::translate_program(program, options)),
     }
 }
 
@@ -0,0 +1,160 @@
+//! Host side of the committed inputs of a RISC-V program.
+//!
+//! When the guest runtime is built with the `commit_inputs` feature, it hashes
+//! the data of every input channel it reads and commits the channel and its
+//! digest as public values of the main machine. The digest is computed over
+//! the 32-bit words of the channel, each hashed as 4 little-endian bytes. At
+//! most [`MAX_COMMITTED_INPUTS`] channels can be committed. This module
+//! computes the same digests from the data passed to the guest and checks them
+//! against the public values of a proof.
+
+use itertools::Itertools;
+use powdr_number::FieldElement;
+use serde::Serialize;
+
+use crate::journal::{digest, public_output};
+
+pub use powdr_riscv_syscalls::{
+    committed_input_cell, COMMITTED_INPUTS_COUNT_CELL, JOURNAL_DIGEST_WORDS, MAX_COMMITTED_INPUTS,
+};
+
+/// The digest of the data read from an input channel.
+pub type InputDigest = [u32; JOURNAL_DIGEST_WORDS];
+
+/// Computes the digest of a value, as committed by the guest when reading it
+/// from a channel the value was added to with [`powdr_pipeline::Pipeline::add_data`].
+pub fn input_digest<S: Serialize>(data: &S) -> InputDigest {
+    words_digest(
+        &serde_cbor::to_vec(data)
+            .unwrap()
+            .into_iter()
+            .map(u32::from)
+            .collect_vec(),
+    )
+}
+
+/// Computes the digest of the raw words of an input channel, as committed by
+/// the guest when reading them, e.g. through `io::read_u32`.
+pub fn words_digest(words: &[u32]) -> InputDigest {
+    digest(&words.iter().flat_map(|w| w.to_le_bytes()).collect_vec())
+}
+
+/// Returns the input channels committed by the guest together with their
+/// digests, in the order they were first read, from the public values of a
+/// proof. For programs using continuations, these are the public values of the
+/// last chunk.
+pub fn committed_inputs<F: FieldElement>(
+    publics: &[(String, Option<F>)],
+) -> Result<Vec<(u32, InputDigest)>, String> {
+    let count = public_output(publics, COMMITTED_INPUTS_COUNT_CELL)? as usize;
+    if count > MAX_COMMITTED_INPUTS {
+        return Err(format!(
+            "Invalid number of committed inputs: {count}, at most {MAX_COMMITTED_INPUTS} are supported"
+        ));
+    }
+    (0..count)
+        .map(|slot| {
+            let cell = committed_input_cell(slot);
+            let channel = public_output(publics, cell)?;
+            let mut digest = [0; JOURNAL_DIGEST_WORDS];
+            for (i, word) in digest.iter_mut().enumerate() {
+                *word = public_output(publics, cell + 1 + i)?;
+            }
+            Ok((channel, digest))
+        })
+        .collect()
+}
+
+/// Checks the committed inputs against the digests of the data provided for
+/// each channel. Every channel committed by the guest must have been provided
+/// with the same data, and every provided channel must have been committed:
+/// a guest that did not commit its inputs does not pass.
+pub fn verify_inputs<F: FieldElement>(
+    publics: &[(String, Option<F>)],
+    inputs: &[(u32, InputDigest)],
+) -> Result<(), String> {
+    let committed_inputs = committed_inputs(publics)?;
+    if let Some((channel, _)) = inputs
+        .iter()
+        .find(|(channel, _)| committed_inputs.iter().all(|(c, _)| c != channel))
+    {
+        return Err(format!(
+            "Input channel {channel} was provided but not committed"
+        ));
+    }
+    for (channel, committed) in committed_inputs {
+        let (_, expected) = inputs
+            .iter()
+            .find(|(c, _)| *c == channel)
+            .ok_or_else(|| format!("Input channel {channel} was committed but not provided"))?;
+        if *expected != committed {
+            return Err(format!(
+                "Input channel {channel} does not match the committed digest"
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use itertools::Itertools;
+    use powdr_number::GoldilocksField;
+
+    use powdr_riscv_syscalls::OUTPUTS_COMMITTED_CELL;
+
+    use super::*;
+    use crate::journal::public_output_name;
+
+    fn publics(committed: &[(u32, InputDigest)]) -> Vec<(String, Option<GoldilocksField>)> {
+        let mut values = vec![0; committed_input_cell(committed.len())];
+        values[COMMITTED_INPUTS_COUNT_CELL] = committed.len() as u32;
+        for (slot, (channel, digest)) in committed.iter().enumerate() {
+            let cell = committed_input_cell(slot);
+            values[cell] = *channel;
+            values[cell + 1..cell + 1 + JOURNAL_DIGEST_WORDS].copy_from_slice(digest);
+        }
+        values
+            .into_iter()
+            .enumerate()
+            .chain([(OUTPUTS_COMMITTED_CELL, 1)])
+            .map(|(cell, v)| (public_output_name(cell), Some(GoldilocksField::from(v))))
+            .collect_vec()
+    }
+
+    #[test]
+    fn verify() {
+        let a = input_digest(&vec![1u32, 2, 3]);
+        let b = input_digest(&"hello");
+        assert_ne!(a, b);
+
+        let publics = publics(&[(1, a), (3, b)]);
+        assert_eq!(committed_inputs(&publics).unwrap(), vec![(1, a), (3, b)]);
+
+        verify_inputs(&publics, &[(3, b), (1, a)]).unwrap();
+        assert!(verify_inputs(&publics, &[(1, a), (3, a)]).is_err());
+        assert!(verify_inputs(&publics, &[(1, a)]).is_err());
+        // Channel 7 was provided but not read by the guest.
+        assert!(verify_inputs(&publics, &[(3, b), (1, a), (7, a)]).is_err());
+    }
+
+    #[test]
+    fn no_committed_inputs() {
+        let publics = publics(&[]);
+        assert!(committed_inputs(&publics).unwrap().is_empty());
+        verify_inputs(&publics, &[]).unwrap();
+        assert!(verify_inputs(&publics, &[(1, input_digest(&1u32))]).is_err());
+    }
+
+    #[test]
+    fn digest_of_words() {
+        let data = 5u32;
+        let words = serde_cbor::to_vec(&data)
+            .unwrap()
+            .into_iter()
+            .map(u32::from)
+            .collect_vec();
+        assert_eq!(input_digest(&data), words_digest(&words));
+        assert_ne!(words_digest(&[1]), words_digest(&[1 << 8]));
+    }
+}
@@ -9,7 +9,10 @@ use powdr_ast::{
 };
 use powdr_number::{FieldElement, KnownField, LargeInt};
 use powdr_pipeline::Pipeline;
-use powdr_riscv_executor::{get_main_machine, ExecutionTrace, MemoryState, ProfilerOptions};
+use powdr_riscv_executor::{
+    custom::CustomInstructions, get_main_machine, ExecutionError, ExecutionTrace, MemoryState,
+    ProfilerOptions,
+};
 
 pub mod bootloader;
 mod memory_merkle_tree;
@@ -224,10 +227,16 @@ pub struct DryRunResult<F: FieldElement> {
 /// Runs the entire execution using the RISC-V executor. For each chunk, it collects:
 /// - The inputs to the bootloader, needed to restore the correct state.
 /// - The number of rows after which the prover should jump to the shutdown routine.
+///
+/// User-defined instructions are run with `custom_instructions`, see
+/// `RuntimeLibs::custom_instructions`.
+///
+/// Fails if the program reaches a `fail` instruction.
 pub fn rust_continuations_dry_run<F: FieldElement>(
     pipeline: &mut Pipeline<F>,
+    custom_instructions: &CustomInstructions,
     profiler_opt: Option<ProfilerOptions>,
-) -> DryRunResult<F> {
+) -> Result<DryRunResult<F>, ExecutionError> {
     let field = F::known_field().unwrap();
 
     // All inputs for all chunks.
@@ -266,8 +275,9 @@ pub fn rust_continuations_dry_run<F: FieldElement>(
             &default_input(&[]),
             usize::MAX,
             powdr_riscv_executor::ExecMode::Trace,
+            custom_instructions,
             profiler_opt,
-        )
+        )?
         .0;
         (transposed_trace::<F>(&trace), trace.mem_ops)
     };
@@ -368,9 +378,10 @@ pub fn rust_continuations_dry_run<F: FieldElement>(
                     &bootloader_inputs,
                     num_rows,
                     powdr_riscv_executor::ExecMode::Trace,
+                    custom_instructions,
                     // profiling was done when full trace was generated
                     None,
-                );
+                )?;
             (
                 transposed_trace(&trace),
                 memory_snapshot_update,
@@ -532,8 +543,8 @@ pub fn rust_continuations_dry_run<F: FieldElement>(
 
         chunk_index += 1;
     }
-    DryRunResult {
+    Ok(DryRunResult {
         bootloader_inputs: bootloader_inputs_and_num_rows,
         trace_len: full_trace_length,
-    }
+    })
 }
@@ -0,0 +1,156 @@
+//! User-defined coprocessors ("precompiles").
+//!
+//! A custom coprocessor bundles everything the built-in coprocessors are wired
+//! with by hand: a syscall number, a submachine providing instructions to the
+//! main machine, the asm implementing the syscall using these instructions, and
+//! the executor implementation of the instructions. It is enabled by passing it
+//! to [`crate::RuntimeLibs::with_custom`] or [`crate::CompilerOptions::with_custom`].
+//!
+//! The guest invokes it with `powdr_riscv_runtime::custom::syscall`, passing
+//! the syscall number and up to four arguments in registers x10 to x13.
+
+use powdr_riscv_executor::custom::CustomInstruction;
+use powdr_riscv_syscalls::Syscall;
+
+/// The names of the built-in coprocessors, which can't be used by custom ones.
+const BUILTIN_COPROCESSORS: [&str; 5] = ["arith", "arith384", "keccakf", "poseidon_gl", "sha256"];
+
+/// A user-defined coprocessor, see the module documentation.
+#[derive(Clone)]
+pub struct CustomCoprocessor {
+    /// The name of the coprocessor. Must be a valid identifier.
+    pub name: String,
+    /// The syscall number, which must not be used by a built-in syscall.
+    pub syscall: u32,
+    /// Asm definitions added to the generated program, e.g. the module
+    /// defining the submachine if it is not part of the standard library.
+    pub definitions: String,
+    /// Full path to the submachine (e.g, `path::to::Machine`).
+    pub submachine_path: String,
+    /// Instance declaration name of the submachine.
+    pub instance_name: String,
+    /// Arguments of the submachine instance (e.g. `memory`).
+    pub arguments: Vec<String>,
+    /// Instruction declarations linking to the submachine.
+    pub instructions: Vec<String>,
+    /// Number of extra registers needed by the instruction declarations.
+    pub extra_registers: u8,
+    /// Statements calling the submachine during initialization, if it needs to be
+    /// called at least once.
+    pub init_call: Vec<String>,
+    /// Statements implementing the syscall. See `SyscallImpl` for the registers
+    /// they may use.
+    pub syscall_impl: Vec<String>,
+    /// The executor implementation of each declared instruction, by name.
+    pub executors: Vec<(String, CustomInstruction)>,
+}
+
+/// Checks that a custom coprocessor can be enabled together with the given ones.
+pub(crate) fn check_coprocessor(
+    enabled: &[CustomCoprocessor],
+    coprocessor: &CustomCoprocessor,
+) -> Result<(), String> {
+    let name = &coprocessor.name;
+    if BUILTIN_COPROCESSORS.contains(&name.as_str()) {
+        return Err(format!("Coprocessor {name} is a built-in coprocessor"));
+    }
+    if enabled.iter().any(|c| c.name == *name) {
+        return Err(format!("Coprocessor {name} is already enabled"));
+    }
+    if let Ok(syscall) = Syscall::try_from(coprocessor.syscall) {
+        return Err(format!(
+            "Syscall number {} of coprocessor {name} is used by the built-in syscall {syscall}",
+            coprocessor.syscall
+        ));
+    }
+    if let Some(other) = enabled.iter().find(|c| c.syscall == coprocessor.syscall) {
+        return Err(format!(
+            "Syscall number {} of coprocessor {name} is used by coprocessor {}",
+            coprocessor.syscall, other.name
+        ));
+    }
+    if let Some((instruction, other)) = coprocessor.executors.iter().find_map(|(instruction, _)| {
+        enabled
+            .iter()
+            .find(|c| c.executors.iter().any(|(i, _)| i == instruction))
+            .map(|other| (instruction, other))
+    }) {
+        return Err(format!(
+            "Instruction {instruction} of coprocessor {name} is also declared by coprocessor {}",
+            other.name
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use powdr_riscv_executor::custom::MachineState;
+
+    use crate::RuntimeLibs;
+
+    use super::*;
+
+    fn double(state: &mut dyn MachineState, args: &[u32]) {
+        let value = state.get_reg(args[0]);
+        state.set_reg(args[1], value * 2);
+    }
+
+    fn coprocessor(name: &str, syscall: u32) -> CustomCoprocessor {
+        CustomCoprocessor {
+            name: name.to_string(),
+            syscall,
+            definitions: String::new(),
+            submachine_path: "double::Double".to_string(),
+            instance_name: name.to_string(),
+            arguments: vec![],
+            instructions: vec![],
+            extra_registers: 0,
+            init_call: vec![],
+            syscall_impl: vec![],
+            executors: vec![(format!("{name}_double"), double)],
+        }
+    }
+
+    #[test]
+    fn enable() {
+        let libs = RuntimeLibs::new()
+            .with_custom(coprocessor("test_double", 100))
+            .unwrap();
+        assert_eq!(libs.custom_coprocessors()[0].name, "test_double");
+        assert!(libs
+            .custom_instructions()
+            .contains_key("test_double_double"));
+
+        assert_eq!(
+            libs.clone()
+                .with_custom(coprocessor("test_double", 101))
+                .unwrap_err(),
+            "Coprocessor test_double is already enabled"
+        );
+        assert_eq!(
+            libs.clone()
+                .with_custom(coprocessor("test_other", 100))
+                .unwrap_err(),
+            "Syscall number 100 of coprocessor test_other is used by coprocessor test_double"
+        );
+        assert_eq!(
+            libs.clone()
+                .with_custom(coprocessor("test_halt", 9))
+                .unwrap_err(),
+            "Syscall number 9 of coprocessor test_halt is used by the built-in syscall halt"
+        );
+        assert_eq!(
+            libs.clone()
+                .with_custom(coprocessor("arith", 102))
+                .unwrap_err(),
+            "Coprocessor arith is a built-in coprocessor"
+        );
+        let mut other = coprocessor("test_other", 103);
+        other.executors[0].0 = "test_double_double".to_string();
+        assert_eq!(
+            libs.with_custom(other).unwrap_err(),
+            "Instruction test_double_double of coprocessor test_other is also declared by coprocessor test_double"
+        );
+    }
+}
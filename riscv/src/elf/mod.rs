@@ -31,7 +31,8 @@ use self::debug_info::DebugInfo;
 mod debug_info;
 
 /// Generates a Powdr Assembly program from a RISC-V 32 executable ELF file.
-pub fn translate(file_name: &Path, options: CompilerOptions) -> String {
+/// Fails if a selected coprocessor is not supported on the selected field.
+pub fn translate(file_name: &Path, options: CompilerOptions) -> Result<String, String> {
     let elf_program = load_elf(file_name);
     code_gen::translate_program(elf_program, options)
 }
@@ -618,13 +619,14 @@ enum UnimpOrInstruction {
     Unimp16,
     Unimp32,
     Instruction(Ins),
+    BitManip(BitManipInsn),
 }
 
 impl UnimpOrInstruction {
     fn len(&self) -> u32 {
         match self {
             UnimpOrInstruction::Unimp16 => 2,
-            UnimpOrInstruction::Unimp32 => 4,
+            UnimpOrInstruction::Unimp32 | UnimpOrInstruction::BitManip(_) => 4,
             UnimpOrInstruction::Instruction(ins) => match ins.extension {
                 Extensions::C => 2,
                 _ => 4,
@@ -917,12 +919,30 @@ impl TwoOrOneMapper<MaybeInstruction, HighLevelInsn> for InstructionLifter<'_> {
             address: insn.address,
             size: insn.insn.len(),
         };
-        let UnimpOrInstruction::Instruction(insn) = insn.insn else {
-            return HighLevelInsn {
-                op: "unimp",
-                args: Default::default(),
-                loc,
-            };
+        let insn = match insn.insn {
+            UnimpOrInstruction::Instruction(insn) => insn,
+            UnimpOrInstruction::BitManip(insn) => {
+                return HighLevelInsn {
+                    op: insn.op,
+                    args: HighLevelArgs {
+                        rd: Some(insn.rd),
+                        rs1: Some(insn.rs1),
+                        rs2: insn.rs2,
+                        imm: match insn.shamt {
+                            Some(shamt) => HighLevelImmediate::Value(shamt as i32),
+                            None => HighLevelImmediate::None,
+                        },
+                    },
+                    loc,
+                };
+            }
+            UnimpOrInstruction::Unimp16 | UnimpOrInstruction::Unimp32 => {
+                return HighLevelInsn {
+                    op: "unimp",
+                    args: Default::default(),
+                    loc,
+                };
+            }
         };
 
         let mut imm = match insn.opc {
@@ -1062,32 +1082,36 @@ impl Iterator for RiscVInstructionIterator<'_> {
         if self.remaining_data[0] & 0b11 == 0b11 {
             // 32 bits
             advance = 4;
-            let insn = u32::from_le_bytes(
+            let bin_instruction = u32::from_le_bytes(
                 self.remaining_data[0..4]
                     .try_into()
                     .expect("Not enough bytes to complete a 32-bit instruction"),
-            )
-            .decode(Isa::Rv32)
-            .unwrap_or_else(|_| {
-                // TODO: maybe instead of failing we should just emit `unimp`.
-                // This way we would support the default GNU binutils
-                // linker script, that places the ELF header in the text
-                // section.
-                panic!(
-                    "Failed to decode 32-bit instruction at {:08x}",
-                    self.curr_address
-                )
-            });
+            );
 
-            // When C extension is disabled, both LLVM and GNU binutils uses the
-            // privileged instruction CSRRW to represent the `unimp` mnemonic.
-            // https://groups.google.com/a/groups.riscv.org/g/sw-dev/c/Xu6UmcIAKIk/m/piJEHdBlAAAJ
-            //
-            // We must handle this case here.
-            let insn = if matches!(insn.opc, Op::CSRRW) {
-                UnimpOrInstruction::Unimp32
+            let insn = if let Some(insn) = decode_bit_manip(bin_instruction) {
+                UnimpOrInstruction::BitManip(insn)
             } else {
-                UnimpOrInstruction::Instruction(insn)
+                let insn = bin_instruction.decode(Isa::Rv32).unwrap_or_else(|_| {
+                    // TODO: maybe instead of failing we should just emit `unimp`.
+                    // This way we would support the default GNU binutils
+                    // linker script, that places the ELF header in the text
+                    // section.
+                    panic!(
+                        "Failed to decode 32-bit instruction at {:08x}",
+                        self.curr_address
+                    )
+                });
+
+                // When C extension is disabled, both LLVM and GNU binutils uses the
+                // privileged instruction CSRRW to represent the `unimp` mnemonic.
+                // https://groups.google.com/a/groups.riscv.org/g/sw-dev/c/Xu6UmcIAKIk/m/piJEHdBlAAAJ
+                //
+                // We must handle this case here.
+                if matches!(insn.opc, Op::CSRRW) {
+                    UnimpOrInstruction::Unimp32
+                } else {
+                    UnimpOrInstruction::Instruction(insn)
+                }
             };
 
             maybe_insn = MaybeInstruction {
@@ -1137,6 +1161,69 @@ impl Iterator for RiscVInstructionIterator<'_> {
     }
 }
 
+/// An instruction of the "Zba" or "Zbb" bit-manipulation extensions, which
+/// are not supported by the decoder.
+struct BitManipInsn {
+    op: &'static str,
+    rd: u32,
+    rs1: u32,
+    rs2: Option<u32>,
+    shamt: Option<u32>,
+}
+
+/// Decodes a 32-bit instruction of the "Zba" or "Zbb" extensions.
+fn decode_bit_manip(bin_instruction: u32) -> Option<BitManipInsn> {
+    const OP: u32 = 0b0110011;
+    const OP_IMM: u32 = 0b0010011;
+
+    let opcode = bin_instruction & 0x7f;
+    let rd = (bin_instruction >> 7) & 0x1f;
+    let funct3 = (bin_instruction >> 12) & 0x7;
+    let rs1 = (bin_instruction >> 15) & 0x1f;
+    let rs2 = (bin_instruction >> 20) & 0x1f;
+    let funct7 = bin_instruction >> 25;
+    let imm = bin_instruction >> 20;
+
+    let (op, rs2, shamt) = match (opcode, funct7, funct3) {
+        (OP, 0b0100000, 0b111) => ("andn", Some(rs2), None),
+        (OP, 0b0100000, 0b110) => ("orn", Some(rs2), None),
+        (OP, 0b0100000, 0b100) => ("xnor", Some(rs2), None),
+        (OP, 0b0000101, 0b100) => ("min", Some(rs2), None),
+        (OP, 0b0000101, 0b101) => ("minu", Some(rs2), None),
+        (OP, 0b0000101, 0b110) => ("max", Some(rs2), None),
+        (OP, 0b0000101, 0b111) => ("maxu", Some(rs2), None),
+        (OP, 0b0110000, 0b001) => ("rol", Some(rs2), None),
+        (OP, 0b0110000, 0b101) => ("ror", Some(rs2), None),
+        (OP, 0b0010000, 0b010) => ("sh1add", Some(rs2), None),
+        (OP, 0b0010000, 0b100) => ("sh2add", Some(rs2), None),
+        (OP, 0b0010000, 0b110) => ("sh3add", Some(rs2), None),
+        (OP, 0b0000100, 0b100) if rs2 == 0 => ("zext.h", None, None),
+        (OP_IMM, 0b0110000, 0b101) => ("rori", None, Some(rs2)),
+        (OP_IMM, _, 0b001) => match imm {
+            0x600 => ("clz", None, None),
+            0x601 => ("ctz", None, None),
+            0x602 => ("cpop", None, None),
+            0x604 => ("sext.b", None, None),
+            0x605 => ("sext.h", None, None),
+            _ => return None,
+        },
+        (OP_IMM, _, 0b101) => match imm {
+            0x287 => ("orc.b", None, None),
+            0x698 => ("rev8", None, None),
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    Some(BitManipInsn {
+        op,
+        rd,
+        rs1,
+        rs2,
+        shamt,
+    })
+}
+
 /// Translates an extension "C" instruction to the equivalent 32-bit instruction.
 fn to_32bit_equivalent(mut insn: Ins) -> Ins {
     let new_opc = match insn.opc {
@@ -1298,3 +1385,72 @@ fn try_map_two_by_two<E, R>(
 
     result
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn decode(bin_instruction: u32) -> Option<(&'static str, u32, u32, Option<u32>, Option<u32>)> {
+        decode_bit_manip(bin_instruction)
+            .map(|insn| (insn.op, insn.rd, insn.rs1, insn.rs2, insn.shamt))
+    }
+
+    #[test]
+    fn decode_bit_manip_register_instructions() {
+        // All with rd = a0 (x10), rs1 = a1 (x11) and rs2 = a2 (x12).
+        for (bin_instruction, op) in [
+            (0x40c5f533, "andn"),
+            (0x40c5e533, "orn"),
+            (0x40c5c533, "xnor"),
+            (0x0ac5c533, "min"),
+            (0x0ac5f533, "maxu"),
+            (0x60c59533, "rol"),
+            (0x60c5d533, "ror"),
+            (0x20c5a533, "sh1add"),
+            (0x20c5e533, "sh3add"),
+        ] {
+            assert_eq!(
+                decode(bin_instruction),
+                Some((op, 10, 11, Some(12), None)),
+                "{op}"
+            );
+        }
+    }
+
+    #[test]
+    fn decode_bit_manip_unary_instructions() {
+        // All with rd = a0 (x10) and rs1 = a1 (x11).
+        for (bin_instruction, op) in [
+            (0x0805c533, "zext.h"),
+            (0x60059513, "clz"),
+            (0x60159513, "ctz"),
+            (0x60259513, "cpop"),
+            (0x60459513, "sext.b"),
+            (0x60559513, "sext.h"),
+            (0x2875d513, "orc.b"),
+            (0x6985d513, "rev8"),
+        ] {
+            assert_eq!(
+                decode(bin_instruction),
+                Some((op, 10, 11, None, None)),
+                "{op}"
+            );
+        }
+
+        // rori a0, a1, 7
+        assert_eq!(decode(0x6075d513), Some(("rori", 10, 11, None, Some(7))));
+    }
+
+    #[test]
+    fn decode_bit_manip_ignores_base_instructions() {
+        for bin_instruction in [
+            0x00c58533, // add a0, a1, a2
+            0x40c58533, // sub a0, a1, a2
+            0x02c58533, // mul a0, a1, a2
+            0x00359513, // slli a0, a1, 3
+            0x4035d513, // srai a0, a1, 3
+        ] {
+            assert_eq!(decode(bin_instruction), None, "{bin_instruction:08x}");
+        }
+    }
+}
@@ -0,0 +1,104 @@
+//! Host side of the deterministic entropy source of a RISC-V program.
+//!
+//! With the `seeded_rand` feature of `powdr_riscv_runtime`, the guest reads the
+//! seed of its entropy source from the [`ENTROPY_SEED_FD`] input channel and
+//! commits it as the public output cells starting at [`ENTROPY_SEED_CELL`].
+//! Whether the guest used the entropy source at all is committed separately in
+//! [`ENTROPY_SEED_USED_CELL`], so that an unused entropy source cannot be
+//! confused with the zero seed.
+
+use powdr_number::FieldElement;
+
+use crate::journal::public_output;
+
+pub use powdr_riscv_syscalls::{
+    ENTROPY_SEED_CELL, ENTROPY_SEED_FD, ENTROPY_SEED_USED_CELL, ENTROPY_SEED_WORDS,
+};
+
+/// The seed of the entropy source. It is written to the guest as an array of
+/// words, e.g. with `pipeline.add_data(ENTROPY_SEED_FD, &seed)`.
+pub type EntropySeed = [u32; ENTROPY_SEED_WORDS];
+
+/// Returns the seed committed by the guest in the given public values, as
+/// returned by [`powdr_pipeline::Pipeline::publics`], or `None` if the guest
+/// did not use the entropy source. For programs using continuations, these are
+/// the public values of the last chunk.
+pub fn entropy_seed<F: FieldElement>(
+    publics: &[(String, Option<F>)],
+) -> Result<Option<EntropySeed>, String> {
+    match public_output(publics, ENTROPY_SEED_USED_CELL)? {
+        0 => return Ok(None),
+        1 => {}
+        flag => return Err(format!("Invalid entropy seed flag {flag}")),
+    }
+    let mut seed = [0; ENTROPY_SEED_WORDS];
+    for (i, word) in seed.iter_mut().enumerate() {
+        *word = public_output(publics, ENTROPY_SEED_CELL + i)?;
+    }
+    Ok(Some(seed))
+}
+
+/// Checks that the guest used the entropy source with exactly the expected seed.
+pub fn verify_entropy_seed<F: FieldElement>(
+    publics: &[(String, Option<F>)],
+    expected: &EntropySeed,
+) -> Result<(), String> {
+    match entropy_seed(publics)? {
+        Some(seed) if seed == *expected => Ok(()),
+        Some(seed) => Err(format!(
+            "Guest used entropy seed {seed:?}, expected {expected:?}"
+        )),
+        None => Err(format!(
+            "Guest did not use the entropy source, expected seed {expected:?}"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use powdr_number::GoldilocksField;
+
+    use powdr_riscv_syscalls::OUTPUTS_COMMITTED_CELL;
+
+    use crate::journal::public_output_name;
+
+    use super::*;
+
+    fn publics(seed: Option<&EntropySeed>) -> Vec<(String, Option<GoldilocksField>)> {
+        let used = (
+            public_output_name(ENTROPY_SEED_USED_CELL),
+            Some(GoldilocksField::from(seed.is_some() as u32)),
+        );
+        let committed = (
+            public_output_name(OUTPUTS_COMMITTED_CELL),
+            Some(GoldilocksField::from(1)),
+        );
+        let seed = seed.copied().unwrap_or_default();
+        [committed, used]
+            .into_iter()
+            .chain(seed.iter().enumerate().map(|(i, w)| {
+                (
+                    public_output_name(ENTROPY_SEED_CELL + i),
+                    Some(GoldilocksField::from(*w)),
+                )
+            }))
+            .collect()
+    }
+
+    #[test]
+    fn verify() {
+        let seed = [1, 2, 3, 4, 5, 6, 7, 8];
+        assert_eq!(entropy_seed(&publics(Some(&seed))), Ok(Some(seed)));
+        verify_entropy_seed(&publics(Some(&seed)), &seed).unwrap();
+        assert!(verify_entropy_seed(&publics(Some(&seed)), &[0; 8]).is_err());
+        assert!(verify_entropy_seed(&publics(Some(&[9; 8])), &seed).is_err());
+
+        // The zero seed is a valid seed.
+        verify_entropy_seed(&publics(Some(&[0; 8])), &[0; 8]).unwrap();
+
+        // A guest that did not use the entropy source does not match any seed.
+        assert_eq!(entropy_seed(&publics(None)), Ok(None));
+        assert!(verify_entropy_seed(&publics(None), &seed).is_err());
+        assert!(verify_entropy_seed(&publics(None), &[0; 8]).is_err());
+    }
+}
@@ -0,0 +1,208 @@
+//! Host side of the exit status of a RISC-V program.
+//!
+//! The guest terminates through `powdr_riscv_runtime::exit`, which writes the
+//! exit code to the [`EXIT_FD`] output. The `halt` syscall then commits it as
+//! the public output cell [`EXIT_CODE_CELL`], so that a proof attests to it. Returning from
+//! `main` exits with code 0. On a panic, the runtime writes the panic message,
+//! including its source location, to the [`PANIC_FD`] output. By default, it
+//! then makes the execution fail, so that the panic cannot be proven. With the
+//! `provable_panic` feature of `powdr-riscv-runtime`, it instead exits with
+//! [`PANIC_EXIT_CODE`].
+
+use powdr_number::FieldElement;
+use powdr_pipeline::Pipeline;
+use powdr_riscv_executor::ExecutionError;
+use thiserror::Error;
+
+use crate::journal::public_output;
+
+pub use powdr_riscv_syscalls::{EXIT_CODE_CELL, EXIT_FD, PANIC_EXIT_CODE, PANIC_FD};
+
+/// A guest program that did not terminate successfully.
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub enum GuestError {
+    #[error("guest panicked: {message}")]
+    Panic { message: String },
+    #[error("guest exited with code {0}")]
+    Exit(u32),
+    #[error("guest terminated without reporting an exit code")]
+    MissingExitCode,
+}
+
+impl GuestError {
+    /// Converts the error of an execution that failed, e.g. because the guest
+    /// panicked, into a panic. The message is the one the runtime wrote to
+    /// [`PANIC_FD`] or, if there is none, the location of the failure.
+    pub fn from_execution_error<F: FieldElement>(
+        pipeline: &Pipeline<F>,
+        error: &ExecutionError,
+    ) -> Self {
+        let message = pipeline
+            .host_context()
+            .file_data
+            .lock()
+            .unwrap()
+            .remove(&PANIC_FD)
+            .map(|data| String::from_utf8_lossy(&data).into_owned())
+            .unwrap_or_else(|| error.to_string());
+        Self::Panic { message }
+    }
+}
+
+/// How a guest program terminated.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExitStatus {
+    code: u32,
+    panic_message: Option<String>,
+}
+
+impl ExitStatus {
+    pub fn new(code: u32, panic_message: Option<String>) -> Self {
+        Self {
+            code,
+            panic_message,
+        }
+    }
+
+    /// Takes the exit status written by the guest so far from the host context of the pipeline.
+    /// Returns `None` if the guest did not report a valid exit code, e.g. because
+    /// it did not terminate through the runtime.
+    pub fn take_from_pipeline<F: FieldElement>(pipeline: &Pipeline<F>) -> Option<Self> {
+        let mut file_data = pipeline.host_context().file_data.lock().unwrap();
+        let code = file_data.remove(&EXIT_FD);
+        let panic_message = file_data
+            .remove(&PANIC_FD)
+            .map(|data| String::from_utf8_lossy(&data).into_owned());
+        let code = u32::from_le_bytes(code?.try_into().ok()?);
+        Some(Self::new(code, panic_message))
+    }
+
+    /// The exit code of the guest. It is 0 if the program terminated successfully.
+    pub fn code(&self) -> u32 {
+        self.code
+    }
+
+    /// The panic message and source location, if the guest panicked.
+    pub fn panic_message(&self) -> Option<&str> {
+        self.panic_message.as_deref()
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.code == 0 && self.panic_message.is_none()
+    }
+
+    /// Converts the status into an error if the guest did not terminate successfully.
+    pub fn into_result(self) -> Result<(), GuestError> {
+        match self.panic_message {
+            Some(message) => Err(GuestError::Panic { message }),
+            None if self.code != 0 => Err(GuestError::Exit(self.code)),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks the exit code against the public values of a proof, as returned
+    /// by [`Pipeline::publics`]. For programs using continuations, these are
+    /// the public values of the last chunk.
+    pub fn verify<F: FieldElement>(&self, publics: &[(String, Option<F>)]) -> Result<(), String> {
+        verify_exit_code(publics, self.code)
+    }
+}
+
+/// Returns the exit code committed by the guest in the given public values.
+pub fn exit_code<F: FieldElement>(publics: &[(String, Option<F>)]) -> Result<u32, String> {
+    public_output(publics, EXIT_CODE_CELL)
+}
+
+/// Checks that the guest committed the expected exit code in the given public values.
+pub fn verify_exit_code<F: FieldElement>(
+    publics: &[(String, Option<F>)],
+    expected: u32,
+) -> Result<(), String> {
+    let code = exit_code(publics)?;
+    if code != expected {
+        return Err(format!(
+            "Guest exited with code {code}, expected {expected}"
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use powdr_number::GoldilocksField;
+
+    use crate::journal::public_output_name;
+
+    use super::*;
+
+    #[test]
+    fn into_result() {
+        assert_eq!(ExitStatus::default().into_result(), Ok(()));
+        assert_eq!(
+            ExitStatus::new(3, None).into_result(),
+            Err(GuestError::Exit(3))
+        );
+        let message = "panicked at src/main.rs:4:5:\noops".to_string();
+        assert_eq!(
+            ExitStatus::new(PANIC_EXIT_CODE, Some(message.clone())).into_result(),
+            Err(GuestError::Panic { message })
+        );
+    }
+
+    #[test]
+    fn take_from_pipeline() {
+        let pipeline = Pipeline::<GoldilocksField>::default();
+        assert_eq!(ExitStatus::take_from_pipeline(&pipeline), None);
+
+        pipeline
+            .host_context()
+            .file_data
+            .lock()
+            .unwrap()
+            .insert(EXIT_FD, 3u32.to_le_bytes().to_vec());
+        assert_eq!(
+            ExitStatus::take_from_pipeline(&pipeline),
+            Some(ExitStatus::new(3, None))
+        );
+        assert_eq!(ExitStatus::take_from_pipeline(&pipeline), None);
+    }
+
+    #[test]
+    fn from_execution_error() {
+        let pipeline = Pipeline::<GoldilocksField>::default();
+        let error = ExecutionError::Fail {
+            location: Some("std/src/lib.rs:1:1".to_string()),
+        };
+        assert_eq!(
+            GuestError::from_execution_error(&pipeline, &error),
+            GuestError::Panic {
+                message: "reached a fail instruction at std/src/lib.rs:1:1".to_string()
+            }
+        );
+
+        let message = "panicked at src/main.rs:4:5:\noops".to_string();
+        pipeline
+            .host_context()
+            .file_data
+            .lock()
+            .unwrap()
+            .insert(PANIC_FD, message.clone().into_bytes());
+        assert_eq!(
+            GuestError::from_execution_error(&pipeline, &error),
+            GuestError::Panic { message }
+        );
+    }
+
+    #[test]
+    fn verify() {
+        let publics = vec![(
+            public_output_name(EXIT_CODE_CELL),
+            Some(GoldilocksField::from(PANIC_EXIT_CODE)),
+        )];
+        assert_eq!(exit_code(&publics), Ok(PANIC_EXIT_CODE));
+        ExitStatus::new(PANIC_EXIT_CODE, Some("oops".to_string()))
+            .verify(&publics)
+            .unwrap();
+        assert!(ExitStatus::default().verify(&publics).is_err());
+    }
+}
@@ -0,0 +1,227 @@
+//! Host side of the committed public outputs ("journal") of a RISC-V program.
+//!
+//! The guest commits data through `powdr_riscv_runtime::commit`, which writes
+//! it to the [`JOURNAL_FD`] output and exposes its digest as public values of
+//! the main machine. See the documentation of that module for how the digest
+//! is computed.
+
+use itertools::Itertools;
+use powdr_number::{FieldElement, GoldilocksField, LargeInt};
+use powdr_pipeline::Pipeline;
+use powdr_riscv_executor::poseidon_gl::poseidon_gl;
+use serde::de::DeserializeOwned;
+
+use powdr_riscv_syscalls::{EXIT_CODE_CELL, OUTPUTS_COMMITTED_CELL};
+pub use powdr_riscv_syscalls::{JOURNAL_DIGEST_WORDS, JOURNAL_FD};
+
+/// The name of the public value holding the given public output cell.
+pub fn public_output_name(cell: usize) -> String {
+    format!("main_publics::output_{cell}")
+}
+
+/// The name of the public value holding the given word of the journal digest.
+pub fn digest_public_name(word: usize) -> String {
+    public_output_name(word)
+}
+
+/// Returns the value of the given public output cell, as a 32-bit word. The
+/// cells written by the runtime are unconstrained and read as zero if the
+/// runtime did not write them, see [`OUTPUTS_COMMITTED_CELL`].
+pub(crate) fn public_output<F: FieldElement>(
+    publics: &[(String, Option<F>)],
+    cell: usize,
+) -> Result<u32, String> {
+    if ![EXIT_CODE_CELL, OUTPUTS_COMMITTED_CELL].contains(&cell) {
+        match raw_public_output(publics, OUTPUTS_COMMITTED_CELL)? {
+            0 => return Ok(0),
+            1 => {}
+            value => {
+                return Err(format!(
+                    "Public value {} is not a flag: {value}",
+                    public_output_name(OUTPUTS_COMMITTED_CELL)
+                ))
+            }
+        }
+    }
+    raw_public_output(publics, cell)
+}
+
+fn raw_public_output<F: FieldElement>(
+    publics: &[(String, Option<F>)],
+    cell: usize,
+) -> Result<u32, String> {
+    let name = public_output_name(cell);
+    let value = publics
+        .iter()
+        .find(|(n, _)| *n == name)
+        .ok_or_else(|| format!("Public value {name} not found"))?
+        .1
+        .ok_or_else(|| format!("Public value {name} is not known"))?;
+    value
+        .to_integer()
+        .try_into_u32()
+        .ok_or_else(|| format!("Public value {name} is not a 32-bit word: {value}"))
+}
+
+/// Computes the digest of the given bytes, as computed in-circuit by the guest
+/// for the journal and the committed inputs.
+pub fn digest(bytes: &[u8]) -> [u32; JOURNAL_DIGEST_WORDS] {
+    if bytes.is_empty() {
+        return [0; JOURNAL_DIGEST_WORDS];
+    }
+
+    let hash = |block: &[u64], state: [GoldilocksField; 4]| {
+        let inputs = block
+            .iter()
+            .copied()
+            .chain(std::iter::repeat(0))
+            .take(8)
+            .map(GoldilocksField::from)
+            .chain(state)
+            .collect_vec();
+        poseidon_gl(&inputs)
+    };
+
+    let words = bytes
+        .chunks(4)
+        .map(|chunk| {
+            let mut word = [0; 4];
+            word[..chunk.len()].copy_from_slice(chunk);
+            u32::from_le_bytes(word) as u64
+        })
+        .collect_vec();
+    let state = words
+        .chunks(8)
+        .fold([GoldilocksField::from(0); 4], |state, block| {
+            hash(block, state)
+        });
+    let state = hash(&[bytes.len() as u64], state);
+
+    let mut digest = [0; JOURNAL_DIGEST_WORDS];
+    for (i, element) in state.iter().enumerate() {
+        let element = element.to_integer().try_into_u64().unwrap();
+        digest[2 * i] = element as u32;
+        digest[2 * i + 1] = (element >> 32) as u32;
+    }
+    digest
+}
+
+/// The data committed by a guest program.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Journal {
+    bytes: Vec<u8>,
+}
+
+impl Journal {
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self { bytes }
+    }
+
+    /// Takes the journal written by the guest so far from the host context of the pipeline.
+    pub fn take_from_pipeline<F: FieldElement>(pipeline: &Pipeline<F>) -> Self {
+        let mut file_data = pipeline.host_context().file_data.lock().unwrap();
+        Self::new(file_data.remove(&JOURNAL_FD).unwrap_or_default())
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Deserializes the journal, assuming the guest committed a single value of type T.
+    pub fn decode<T: DeserializeOwned>(&self) -> Result<T, String> {
+        serde_cbor::from_slice(&self.bytes).map_err(|e| format!("Error decoding journal: {e}"))
+    }
+
+    /// Deserializes the journal, assuming the guest committed values of type T only.
+    pub fn decode_all<T: DeserializeOwned>(&self) -> Result<Vec<T>, String> {
+        serde_cbor::Deserializer::from_slice(&self.bytes)
+            .into_iter()
+            .collect::<Result<_, _>>()
+            .map_err(|e| format!("Error decoding journal: {e}"))
+    }
+
+    /// Computes the digest of the journal, as computed in-circuit by the guest.
+    pub fn digest(&self) -> [u32; JOURNAL_DIGEST_WORDS] {
+        digest(&self.bytes)
+    }
+
+    /// Checks the journal against the public values of a proof, as returned by
+    /// [`Pipeline::publics`]. For programs using continuations, these are the
+    /// public values of the last chunk.
+    pub fn verify<F: FieldElement>(&self, publics: &[(String, Option<F>)]) -> Result<(), String> {
+        for (i, expected) in self.digest().into_iter().enumerate() {
+            let value = public_output(publics, i)?;
+            if value != expected {
+                return Err(format!(
+                    "Journal does not match the committed digest: expected {expected} for {}, got {value}",
+                    digest_public_name(i)
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn empty_digest() {
+        assert_eq!(Journal::default().digest(), [0; JOURNAL_DIGEST_WORDS]);
+    }
+
+    #[test]
+    fn digest_depends_on_length() {
+        // The same words, but a different number of bytes.
+        let a = Journal::new(vec![1, 2, 3]);
+        let b = Journal::new(vec![1, 2, 3, 0]);
+        assert_ne!(a.digest(), b.digest());
+        assert_ne!(a.digest(), [0; JOURNAL_DIGEST_WORDS]);
+    }
+
+    #[test]
+    fn verify() {
+        let journal = Journal::new(serde_cbor::to_vec(&(1u32, "hello")).unwrap());
+        assert_eq!(
+            journal.decode::<(u32, String)>().unwrap(),
+            (1, "hello".into())
+        );
+
+        let mut publics = journal
+            .digest()
+            .into_iter()
+            .enumerate()
+            .map(|(i, w)| (digest_public_name(i), Some(GoldilocksField::from(w))))
+            .chain([(
+                public_output_name(OUTPUTS_COMMITTED_CELL),
+                Some(GoldilocksField::from(1)),
+            )])
+            .collect_vec();
+        journal.verify(&publics).unwrap();
+
+        publics[3].1 = Some(GoldilocksField::from(0));
+        assert!(journal.verify(&publics).is_err());
+    }
+
+    #[test]
+    fn unwritten_outputs_read_as_zero() {
+        let journal = Journal::new(vec![1, 2, 3]);
+        let mut publics = journal
+            .digest()
+            .into_iter()
+            .enumerate()
+            .map(|(i, w)| (digest_public_name(i), Some(GoldilocksField::from(w))))
+            .chain([(
+                public_output_name(OUTPUTS_COMMITTED_CELL),
+                Some(GoldilocksField::from(0)),
+            )])
+            .collect_vec();
+        // The digest cells are ignored, whatever values the prover chose.
+        assert!(journal.verify(&publics).is_err());
+        Journal::default().verify(&publics).unwrap();
+
+        publics[JOURNAL_DIGEST_WORDS].1 = Some(GoldilocksField::from(2));
+        assert!(Journal::default().verify(&publics).is_err());
+    }
+}
@@ -4,6 +4,7 @@ use itertools::Itertools;
 use powdr_isa_utils::SingleDataValue;
 use powdr_isa_utils::{escape_label, quote};
 use powdr_number::KnownField;
+use powdr_riscv_syscalls::PUBLIC_OUTPUT_CELLS;
 
 use crate::continuations::bootloader::{bootloader_and_shutdown_routine, bootloader_preamble};
 
@@ -19,13 +20,13 @@ use crate::large_field::runtime::Runtime;
 ///
 /// Will call each of the methods in the `RiscVProgram` just once.
 pub fn translate_program(program: impl RiscVProgram, options: CompilerOptions) -> String {
-    let runtime = Runtime::new(options.libs, options.continuations);
+    let runtime = Runtime::new(&options.libs, options.continuations);
     // Do this in a separate function to avoid most of the code being generic on F.
     let (initial_mem, instructions) =
         translate_program_impl(program, options.field, &runtime, options.continuations);
 
     riscv_machine(
-        options,
+        &options,
         &runtime,
         &preamble(options.field, &runtime, options.continuations),
         initial_mem,
@@ -185,7 +186,7 @@ fn translate_program_impl(
 }
 
 fn riscv_machine(
-    options: CompilerOptions,
+    options: &CompilerOptions,
     runtime: &Runtime,
     preamble: &str,
     initial_memory: Vec<String>,
@@ -214,6 +215,9 @@ let initial_memory: (fe, fe)[] = [
 {}
     }}
 }}    
+
+{}
+{}
 "#,
         runtime.submachines_import(),
         options.min_degree_log,
@@ -230,6 +234,51 @@ let initial_memory: (fe, fe)[] = [
         program
             .into_iter()
             .format_with("\n", |line, f| f(&format_args!("\t\t{line}"))),
+        public_outputs_machine(),
+        custom_definitions(options),
+    )
+}
+
+/// The definitions added to the program by the custom coprocessors.
+fn custom_definitions(options: &CompilerOptions) -> String {
+    options
+        .libs
+        .custom_coprocessors()
+        .iter()
+        .map(|c| c.definitions.as_str())
+        .join("\n")
+}
+
+/// The module holding the machine that exposes the public output cells written
+/// by the `commit_public` syscall as public values. It is generated because each
+/// cell needs its own `public` declaration.
+fn public_outputs_machine() -> String {
+    let publics = (0..PUBLIC_OUTPUT_CELLS)
+        .map(|i| format!("        public output_{i} = value({i});"))
+        .join("\n");
+    format!(
+        r#"
+mod public_outputs {{
+    // Write-once memory whose cells are exposed as public values.
+    // Cells that are never written are unconstrained. The halt syscall
+    // always writes the exit code and whether the runtime wrote all other
+    // cells; if it did not, the host reads them as zero.
+    pub machine PublicOutputs with
+        degree: {},
+        latch: LATCH
+    {{
+        operation access ADDR, value ->;
+
+        let LATCH = 1;
+
+        let ADDR: col = |i| i;
+        let value;
+
+{publics}
+    }}
+}}
+"#,
+        PUBLIC_OUTPUT_CELLS.next_power_of_two()
     )
 }
 
@@ -243,6 +292,7 @@ fn preamble(field: KnownField, runtime: &Runtime, with_bootloader: bool) -> Stri
     for machine in [
         "binary",
         "shift",
+        "rotate",
         "bit2",
         "bit6",
         "bit7",
@@ -250,6 +300,7 @@ fn preamble(field: KnownField, runtime: &Runtime, with_bootloader: bool) -> Stri
         "byte2",
         "byte_binary",
         "byte_shift",
+        "byte_rotate",
         "byte_compare",
     ] {
         assert!(
@@ -433,6 +484,28 @@ fn preamble(field: KnownField, runtime: &Runtime, with_bootloader: bool) -> Stri
         tmp3_col = 1 - XXIsZero
     }
 
+    // Stores the minimum of val(X) and val(Y), as unsigned numbers, in register W.
+    instr minu X, Y, W
+        link ~> tmp1_col = regs.mload(X, STEP)
+        link ~> tmp2_col = regs.mload(Y, STEP + 1)
+        link ~> regs.mstore(W, STEP + 2, tmp3_col)
+    {
+        // wrap_bit is 1 if val(Y) > val(X).
+        (tmp2_col - tmp1_col) + 2**32 - 1 = X_b1 + X_b2 * 0x100 + X_b3 * 0x10000 + X_b4 * 0x1000000 + wrap_bit * 2**32,
+        tmp3_col = tmp2_col + wrap_bit * (tmp1_col - tmp2_col)
+    }
+
+    // Stores the maximum of val(X) and val(Y), as unsigned numbers, in register W.
+    instr maxu X, Y, W
+        link ~> tmp1_col = regs.mload(X, STEP)
+        link ~> tmp2_col = regs.mload(Y, STEP + 1)
+        link ~> regs.mstore(W, STEP + 2, tmp3_col)
+    {
+        // wrap_bit is 1 if val(Y) > val(X).
+        (tmp2_col - tmp1_col) + 2**32 - 1 = X_b1 + X_b2 * 0x100 + X_b3 * 0x10000 + X_b4 * 0x1000000 + wrap_bit * 2**32,
+        tmp3_col = tmp1_col + wrap_bit * (tmp2_col - tmp1_col)
+    }
+
     // ================= submachine instructions =================
 "# + &runtime
         .submachines_instructions()
@@ -490,6 +563,14 @@ fn preamble(field: KnownField, runtime: &Runtime, with_bootloader: bool) -> Stri
         tmp3_col = tmp1_col - wrap_bit * 0x100000000
     }
 
+    // Reverses the order of the bytes of the value in register X and stores it in register Y.
+    instr rev8 X, Y
+        link ~> tmp1_col = regs.mload(X, STEP)
+        link ~> regs.mstore(Y, STEP + 3, X_b4 + X_b3 * 0x100 + X_b2 * 0x10000 + X_b1 * 0x1000000)
+    {
+        tmp1_col = X_b1 + X_b2 * 0x100 + X_b3 * 0x10000 + X_b4 * 0x1000000
+    }
+
     // ======================= assertions =========================
 
     instr fail { 1 = 0 }
@@ -506,6 +587,17 @@ fn preamble(field: KnownField, runtime: &Runtime, with_bootloader: bool) -> Stri
         tmp3_col = X_b1 + X_b2 * 0x100 + X_b3 * 0x10000 + X_b4 * 0x1000000
     }
 
+    // Computes V = val(X) * Z + val(Y), wraps it in 32 bits, and stores the result in register W.
+    // Requires 0 <= Z < 2**8.
+    instr add_shifted X, Y, Z, W
+        link ~> tmp1_col = regs.mload(X, STEP)
+        link ~> tmp2_col = regs.mload(Y, STEP + 1)
+        link ~> regs.mstore(W, STEP + 3, tmp3_col)
+    {
+        tmp1_col * Z + tmp2_col = Y_b5 * 2**32 + tmp3_col,
+        tmp3_col = X_b1 + X_b2 * 0x100 + X_b3 * 0x10000 + X_b4 * 0x1000000
+    }
+
     col witness Y_b5;
     col witness Y_b6;
     col witness Y_b7;
@@ -945,6 +1037,72 @@ fn process_instruction<A: InstructionArgs>(instr: &str, args: A) -> Result<Vec<S
                 format!("sub_wrap_with_offset 0, {}, -1, {};", rs.addr(), rd.addr()),
             )
         }
+        "andn" | "orn" | "xnor" => {
+            let (rd, r1, r2) = args.rrr()?;
+            only_if_no_write_to_zero(
+                rd,
+                format!("{instr} {}, {}, {};", r1.addr(), r2.addr(), rd.addr()),
+            )
+        }
+        "cpop" | "rev8" => {
+            let (rd, rs) = args.rr()?;
+            only_if_no_write_to_zero(rd, format!("{instr} {}, {};", rs.addr(), rd.addr()))
+        }
+        "orc.b" => {
+            let (rd, rs) = args.rr()?;
+            only_if_no_write_to_zero(rd, format!("orc_b {}, {};", rs.addr(), rd.addr()))
+        }
+        "ctz" => {
+            // The trailing zeros of x are the set bits of (x - 1) & ~x.
+            let (rd, rs) = args.rr()?;
+            only_if_no_write_to_zero_vec(
+                rd,
+                vec![
+                    format!(
+                        "sub_wrap_with_offset {}, 0, -1, {};",
+                        rs.addr(),
+                        tmp1.addr()
+                    ),
+                    format!("andn {}, {}, {};", tmp1.addr(), rs.addr(), tmp1.addr()),
+                    format!("cpop {}, {};", tmp1.addr(), rd.addr()),
+                ],
+            )
+        }
+        "clz" => {
+            // The leading zeros of x are the trailing zeros of x with its bits reversed.
+            let (rd, rs) = args.rr()?;
+            only_if_no_write_to_zero_vec(
+                rd,
+                vec![
+                    format!("brev {}, {};", rs.addr(), tmp1.addr()),
+                    format!(
+                        "sub_wrap_with_offset {}, 0, -1, {};",
+                        tmp1.addr(),
+                        tmp2.addr()
+                    ),
+                    format!("andn {}, {}, {};", tmp2.addr(), tmp1.addr(), tmp2.addr()),
+                    format!("cpop {}, {};", tmp2.addr(), rd.addr()),
+                ],
+            )
+        }
+        "sext.b" => {
+            let (rd, rs) = args.rr()?;
+            only_if_no_write_to_zero(
+                rd,
+                format!("sign_extend_byte {}, {};", rs.addr(), rd.addr()),
+            )
+        }
+        "sext.h" => {
+            let (rd, rs) = args.rr()?;
+            only_if_no_write_to_zero(
+                rd,
+                format!("sign_extend_16_bits {}, {};", rs.addr(), rd.addr()),
+            )
+        }
+        "zext.h" => {
+            let (rd, rs) = args.rr()?;
+            only_if_no_write_to_zero(rd, format!("and {}, 0, 0xffff, {};", rs.addr(), rd.addr()))
+        }
 
         // shift
         "slli" => {
@@ -1028,6 +1186,43 @@ fn process_instruction<A: InstructionArgs>(instr: &str, args: A) -> Result<Vec<S
             )
         }
 
+        "rol" | "ror" => {
+            let (rd, r1, r2) = args.rrr()?;
+            let op = if instr == "rol" { "rotl" } else { "rotr" };
+            only_if_no_write_to_zero_vec(
+                rd,
+                vec![
+                    format!("and {}, 0, 0x1f, {};", r2.addr(), tmp1.addr()),
+                    format!("{op} {}, {}, 0, {};", r1.addr(), tmp1.addr(), rd.addr()),
+                ],
+            )
+        }
+        "rori" => {
+            let (rd, rs, amount) = args.rri()?;
+            assert!(amount <= 31);
+            only_if_no_write_to_zero(
+                rd,
+                format!("rotr {}, 0, {amount}, {};", rs.addr(), rd.addr()),
+            )
+        }
+        "sh1add" | "sh2add" | "sh3add" => {
+            let (rd, r1, r2) = args.rrr()?;
+            let factor = match instr {
+                "sh1add" => 2,
+                "sh2add" => 4,
+                _ => 8,
+            };
+            only_if_no_write_to_zero(
+                rd,
+                format!(
+                    "add_shifted {}, {}, {factor}, {};",
+                    r1.addr(),
+                    r2.addr(),
+                    rd.addr()
+                ),
+            )
+        }
+
         // comparison
         "seqz" => {
             let (rd, rs) = args.rr()?;
@@ -1102,6 +1297,27 @@ fn process_instruction<A: InstructionArgs>(instr: &str, args: A) -> Result<Vec<S
             )
         }
 
+        "minu" | "maxu" => {
+            let (rd, r1, r2) = args.rrr()?;
+            only_if_no_write_to_zero(
+                rd,
+                format!("{instr} {}, {}, {};", r1.addr(), r2.addr(), rd.addr()),
+            )
+        }
+        "min" | "max" => {
+            // Flipping the sign bit maps the signed order to the unsigned order.
+            let (rd, r1, r2) = args.rrr()?;
+            only_if_no_write_to_zero_vec(
+                rd,
+                vec![
+                    format!("add_wrap {}, 0, 0x80000000, {};", r1.addr(), tmp1.addr()),
+                    format!("add_wrap {}, 0, 0x80000000, {};", r2.addr(), tmp2.addr()),
+                    format!("{instr}u {}, {}, {};", tmp1.addr(), tmp2.addr(), rd.addr()),
+                    format!("add_wrap {}, 0, 0x80000000, {};", rd.addr(), rd.addr()),
+                ],
+            )
+        }
+
         // branching
         "beq" => {
             let (r1, r2, label) = args.rrl()?;
@@ -1,11 +1,12 @@
 use std::collections::BTreeMap;
 
-use powdr_riscv_syscalls::Syscall;
+use powdr_riscv_syscalls::{Syscall, EXIT_CODE_CELL, OUTPUTS_COMMITTED_CELL};
 
 use itertools::Itertools;
 
 use crate::code_gen::Register;
 
+use crate::coprocessors::CustomCoprocessor;
 use crate::runtime::{
     parse_function_statement, parse_instruction_declaration, SubMachine, SyscallImpl,
     EXTRA_REG_PREFIX,
@@ -18,10 +19,12 @@ use crate::RuntimeLibs;
 pub struct Runtime {
     submachines: BTreeMap<String, SubMachine>,
     syscalls: BTreeMap<Syscall, SyscallImpl>,
+    /// Syscalls of custom coprocessors, by number, with the name of their coprocessor.
+    custom_syscalls: BTreeMap<u32, (String, SyscallImpl)>,
 }
 
 impl Runtime {
-    pub fn new(libs: RuntimeLibs, continuations: bool) -> Self {
+    pub fn new(libs: &RuntimeLibs, continuations: bool) -> Self {
         let mut runtime = Runtime::base();
         if libs.poseidon {
             runtime = runtime.with_poseidon(continuations);
@@ -32,6 +35,15 @@ impl Runtime {
         if libs.arith {
             runtime = runtime.with_arith();
         }
+        if libs.sha256 {
+            runtime = runtime.with_sha256();
+        }
+        if libs.arith384 {
+            runtime = runtime.with_arith384();
+        }
+        for coprocessor in libs.custom_coprocessors() {
+            runtime = runtime.with_custom(coprocessor);
+        }
         runtime
     }
 
@@ -39,6 +51,7 @@ impl Runtime {
         let mut r = Runtime {
             submachines: Default::default(),
             syscalls: Default::default(),
+            custom_syscalls: Default::default(),
         };
 
         // Base submachines
@@ -64,6 +77,41 @@ impl Runtime {
                     link ~> tmp2_col = regs.mload(Y, STEP + 1)
                     link ~> tmp3_col = binary.xor(tmp1_col, tmp2_col + Z)
                     link ~> regs.mstore(W, STEP + 3, tmp3_col);"#,
+                r#"instr andn X, Y, W
+                    link ~> tmp1_col = regs.mload(X, STEP)
+                    link ~> tmp2_col = regs.mload(Y, STEP + 1)
+                    link ~> tmp3_col = binary.and(tmp1_col, 0xffffffff - tmp2_col)
+                    link ~> regs.mstore(W, STEP + 3, tmp3_col);"#,
+                r#"instr orn X, Y, W
+                    link ~> tmp1_col = regs.mload(X, STEP)
+                    link ~> tmp2_col = regs.mload(Y, STEP + 1)
+                    link ~> tmp3_col = binary.or(tmp1_col, 0xffffffff - tmp2_col)
+                    link ~> regs.mstore(W, STEP + 3, tmp3_col);"#,
+                r#"instr xnor X, Y, W
+                    link ~> tmp1_col = regs.mload(X, STEP)
+                    link ~> tmp2_col = regs.mload(Y, STEP + 1)
+                    link ~> tmp3_col = binary.xor(tmp1_col, 0xffffffff - tmp2_col)
+                    link ~> regs.mstore(W, STEP + 3, tmp3_col);"#,
+                // Counts the set bits of each byte and adds the counts up.
+                r#"instr cpop X, W
+                    link ~> tmp1_col = regs.mload(X, STEP)
+                    link ~> tmp3_col = binary.bytewise(tmp1_col, 0)
+                    link ~> regs.mstore(W, STEP + 3, X_b1 + X_b2 + X_b3 + X_b4)
+                {
+                    tmp3_col = X_b1 + X_b2 * 0x100 + X_b3 * 0x10000 + X_b4 * 0x1000000
+                }"#,
+                // Reverses the bits of each byte and then the order of the bytes.
+                r#"instr brev X, W
+                    link ~> tmp1_col = regs.mload(X, STEP)
+                    link ~> tmp3_col = binary.bytewise(tmp1_col, 0x01010101)
+                    link ~> regs.mstore(W, STEP + 3, X_b4 + X_b3 * 0x100 + X_b2 * 0x10000 + X_b1 * 0x1000000)
+                {
+                    tmp3_col = X_b1 + X_b2 * 0x100 + X_b3 * 0x10000 + X_b4 * 0x1000000
+                }"#,
+                r#"instr orc_b X, W
+                    link ~> tmp1_col = regs.mload(X, STEP)
+                    link ~> tmp3_col = binary.bytewise(tmp1_col, 0x02020202)
+                    link ~> regs.mstore(W, STEP + 3, tmp3_col);"#,
             ],
             0,
             ["and 0, 0, 0, 0;"],
@@ -90,6 +138,29 @@ impl Runtime {
             ["shl 0, 0, 0, 0;"],
         );
 
+        // The rotations of the "Zbb" extension have their own table, so that
+        // the shift table does not grow for them.
+        r.add_submachine(
+            "std::machines::large_field::rotate::Rotate",
+            None,
+            "rotate",
+            vec!["byte_rotate", "MIN_DEGREE", "LARGE_SUBMACHINES_MAX_DEGREE"],
+            [
+                r#"instr rotl X, Y, Z, W
+                    link ~> tmp1_col = regs.mload(X, STEP)
+                    link ~> tmp2_col = regs.mload(Y, STEP + 1)
+                    link ~> tmp3_col = rotate.rotl(tmp1_col, tmp2_col + Z)
+                    link ~> regs.mstore(W, STEP + 3, tmp3_col);"#,
+                r#"instr rotr X, Y, Z, W
+                    link ~> tmp1_col = regs.mload(X, STEP)
+                    link ~> tmp2_col = regs.mload(Y, STEP + 1)
+                    link ~> tmp3_col = rotate.rotr(tmp1_col, tmp2_col + Z)
+                    link ~> regs.mstore(W, STEP + 3, tmp3_col);"#,
+            ],
+            0,
+            ["rotl 0, 0, 0, 0;"],
+        );
+
         r.add_submachine(
             "std::machines::split::split_gl::SplitGL",
             None,
@@ -174,6 +245,16 @@ impl Runtime {
             [],
         );
 
+        r.add_submachine::<&str, _, _>(
+            "std::machines::large_field::rotate::ByteRotate",
+            None,
+            "byte_rotate",
+            vec![],
+            [],
+            0,
+            [],
+        );
+
         r.add_submachine::<&str, _, _>(
             "std::machines::split::ByteCompare",
             None,
@@ -184,6 +265,22 @@ impl Runtime {
             [],
         );
 
+        // The public outputs of the program, written by the `commit_public` syscall.
+        r.add_submachine(
+            "public_outputs::PublicOutputs",
+            None,
+            "publics",
+            vec![],
+            [r#"instr commit_public X, Y
+                    link ~> tmp1_col = regs.mload(X, STEP)
+                    link ~> tmp2_col = regs.mload(Y, STEP + 1)
+                    link => publics.access(tmp1_col, tmp2_col);"#],
+            0,
+            // The cells are only written when the program terminates,
+            // so it must not be called during initialization.
+            [],
+        );
+
         // Base syscalls
         r.add_syscall(
             // TODO this is a quite inefficient way of getting prover inputs.
@@ -207,7 +304,22 @@ impl Runtime {
             ]
         );
 
-        r.add_syscall(Syscall::Halt, ["return;"]);
+        // The commit_public syscall takes the index of the public output in x10
+        // and the value to commit in x11.
+        r.add_syscall(Syscall::CommitPublic, ["commit_public 10, 11;"]);
+
+        // The halt syscall takes the exit code in x10 and whether the runtime
+        // has written the other public outputs in x11, and commits both.
+        r.add_syscall(
+            Syscall::Halt,
+            [
+                format!("set_reg 12, {OUTPUTS_COMMITTED_CELL};"),
+                "commit_public 12, 11;".to_string(),
+                format!("set_reg 12, {EXIT_CODE_CELL};"),
+                "commit_public 12, 10;".to_string(),
+                "return;".to_string(),
+            ],
+        );
 
         r
     }
@@ -248,6 +360,79 @@ impl Runtime {
         self
     }
 
+    fn with_sha256(mut self) -> Self {
+        self.add_submachine(
+            "std::machines::hash::sha256::Sha256",
+            None,
+            "sha256",
+            vec!["memory", "byte2"],
+            [r#"instr sha256 X, Y
+                    link ~> tmp1_col = regs.mload(X, STEP)
+                    link ~> tmp2_col = regs.mload(Y, STEP + 1)
+                    link ~> sha256.sha256(tmp1_col, tmp2_col, STEP)
+                {
+                    // make sure tmp1_col and tmp2_col are aligned memory addresses
+                    tmp3_col * 4 = tmp1_col,
+                    tmp4_col * 4 = tmp2_col,
+                    // make sure the factors fit in 32 bits
+                    tmp3_col = X_b1 + X_b2 * 0x100 + X_b3 * 0x10000 + X_b4 * 0x1000000,
+                    tmp4_col = Y_b5 + Y_b6 * 0x100 + Y_b7 * 0x10000 + Y_b8 * 0x1000000
+                }
+            "#],
+            0,
+            // The state and the message block must not overlap, so the block is
+            // read from [32, 96). With continuations, this region is initialized
+            // by the init call of the poseidon_gl machine, which comes first.
+            ["set_reg 11, 32;", "sha256 0, 11;"],
+        );
+
+        // The sha256 syscall has two arguments passed on x10 and x11,
+        // the memory address of the 8 word state, which is overwritten with
+        // the result, and the memory address of the 16 word message block.
+        let implementation = std::iter::once("sha256 10, 11;".to_string());
+
+        self.add_syscall(Syscall::Sha256, implementation);
+        self
+    }
+
+    fn with_arith384(mut self) -> Self {
+        let instruction = |name: &str| {
+            format!(
+                r#"instr {name}_384 X, Y
+                    link ~> tmp1_col = regs.mload(X, STEP)
+                    link ~> tmp2_col = regs.mload(Y, STEP + 1)
+                    link ~> arith384.{name}(tmp1_col, tmp2_col, STEP)
+                {{
+                    // make sure tmp1_col and tmp2_col are aligned memory addresses
+                    tmp3_col * 4 = tmp1_col,
+                    tmp4_col * 4 = tmp2_col,
+                    // make sure the factors fit in 32 bits
+                    tmp3_col = X_b1 + X_b2 * 0x100 + X_b3 * 0x10000 + X_b4 * 0x1000000,
+                    tmp4_col = Y_b5 + Y_b6 * 0x100 + Y_b7 * 0x10000 + Y_b8 * 0x1000000
+                }}
+            "#
+            )
+        };
+        self.add_submachine(
+            "std::machines::large_field::arith384::Arith384",
+            None,
+            "arith384",
+            vec!["memory", "byte2"],
+            [instruction("mul_add"), instruction("mul_sub")],
+            0,
+            // The machine requires the modulus to be non-zero, so there is
+            // no input it could be called with during initialization.
+            [],
+        );
+
+        // The mul_add_384 and mul_sub_384 syscalls have two arguments passed on x10 and x11:
+        // the memory address of the five 12 word numbers a, b, c, d and m, and
+        // the memory address of the 12 word result.
+        self.add_syscall(Syscall::MulAdd384, ["mul_add_384 10, 11;"]);
+        self.add_syscall(Syscall::MulSub384, ["mul_sub_384 10, 11;"]);
+        self
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn add_submachine<S: AsRef<str>, I1: IntoIterator<Item = S>, I2: IntoIterator<Item = S>>(
         &mut self,
@@ -299,6 +484,32 @@ impl Runtime {
         }
     }
 
+    fn with_custom(mut self, coprocessor: &CustomCoprocessor) -> Self {
+        self.add_submachine(
+            &coprocessor.submachine_path,
+            None,
+            &coprocessor.instance_name,
+            coprocessor.arguments.iter().map(String::as_str).collect(),
+            &coprocessor.instructions,
+            coprocessor.extra_registers,
+            &coprocessor.init_call,
+        );
+
+        let implementation = SyscallImpl(
+            coprocessor
+                .syscall_impl
+                .iter()
+                .map(|s| parse_function_statement(s))
+                .collect(),
+        );
+        // Syscall numbers are checked to be unique when registering the coprocessor.
+        self.custom_syscalls.insert(
+            coprocessor.syscall,
+            (coprocessor.name.clone(), implementation),
+        );
+        self
+    }
+
     fn with_poseidon(mut self, continuations: bool) -> Self {
         let init_call = if continuations {
             vec![
@@ -389,6 +600,15 @@ impl Runtime {
                     "instr mod_256 link ~> {};",
                     instr_link("arith.mod_256", 24, 8)
                 ),
+                // Non-deterministically provides limb Y of the inverse of the value in
+                // registers 0..8 modulo the value in registers 24..32.
+                "instr mod_inv_256_hint Y -> X { }".to_string(),
+                format!(
+                    "instr assert_one_256 {{ {} }}",
+                    (0..8)
+                        .map(|i| format!("{} = {}", reg(i), (i == 0) as u32))
+                        .join(", ")
+                ),
             ],
             32,
             // calling ec_double for machine initialization.
@@ -460,6 +680,29 @@ impl Runtime {
 
         self.add_syscall(Syscall::Mod256, mod256);
 
+        // The mod_inv_256 syscall takes as input the addresses of a and m.
+        // The inverse is provided as a hint and checked with affine_256 and
+        // mod_256, i.e. we check that a * inv % m == 1.
+        let mod_inv256 =
+            // Load a in 0..8
+            (0..8).flat_map(|i| load_word(10, i as u32 * 4, &reg(i)))
+            // Load m in 24..32
+            .chain((0..8).flat_map(|i| load_word(11, i as u32 * 4, &reg(i + 24))))
+            // Get the inverse in 8..16
+            .chain((0..8).map(|i| format!("{} <=X= mod_inv_256_hint({i});", reg(i + 8))))
+            // Store the inverse in a's memory
+            .chain((0..8).flat_map(|i| store_word(10, i as u32 * 4, &reg(i + 8))))
+            // Set 16..24 to zero
+            .chain((0..8).map(|i| format!("{} <=X= 0;", reg(i + 16))))
+            // Compute a * inv in 0..16
+            .chain(std::iter::once("affine_256;".to_string()))
+            // Move m to 16..24
+            .chain((0..8).map(|i| format!("{} <=X= {};", reg(i + 16), reg(i + 24))))
+            // Reduce a * inv modulo m in 0..8 and check that the result is 1
+            .chain(["mod_256;".to_string(), "assert_one_256;".to_string()]);
+
+        self.add_syscall(Syscall::ModInv256, mod_inv256);
+
         // The ec_add syscall takes as input the four addresses of x1, y1, x2, y2.
         let ec_add =
             // Load x1 in 0..8
@@ -539,6 +782,20 @@ impl Runtime {
             .collect()
     }
 
+    /// All syscalls, as (number, label, implementation).
+    fn all_syscalls(&self) -> impl Iterator<Item = (u32, String, &SyscallImpl)> {
+        self.syscalls
+            .iter()
+            .map(|(syscall, implementation)| (*syscall as u32, syscall.to_string(), implementation))
+            .chain(
+                self.custom_syscalls
+                    .iter()
+                    .map(|(number, (name, implementation))| {
+                        (*number, format!("custom_{name}"), implementation)
+                    }),
+            )
+    }
+
     pub fn ecall_handler(&self) -> Vec<String> {
         let ecall = [
             "// ecall handler".to_string(),
@@ -546,17 +803,14 @@ impl Runtime {
         ]
         .into_iter();
 
-        let jump_table = self.syscalls.keys().map(|s| {
-            format!(
-                "branch_if_diff_equal 5, 0, {}, __ecall_handler_{};",
-                *s as u32, s
-            )
+        let jump_table = self.all_syscalls().map(|(number, label, _)| {
+            format!("branch_if_diff_equal 5, 0, {number}, __ecall_handler_{label};")
         });
 
         let invalid_handler = ["__invalid_syscall:".to_string(), "fail;".to_string()].into_iter();
 
-        let handlers = self.syscalls.iter().flat_map(|(syscall, implementation)| {
-            std::iter::once(format!("__ecall_handler_{syscall}:"))
+        let handlers = self.all_syscalls().flat_map(|(_, label, implementation)| {
+            std::iter::once(format!("__ecall_handler_{label}:"))
                 .chain(implementation.0.iter().map(|i| i.to_string()))
                 .chain([format!("jump_dyn 1, {};", Register::from("tmp1").addr())])
         });
@@ -9,11 +9,17 @@ use std::{
 };
 
 use powdr_number::KnownField;
+use powdr_riscv_executor::custom::CustomInstructions;
 use std::fs;
 
 mod code_gen;
+pub mod committed_inputs;
 pub mod continuations;
+pub mod coprocessors;
 pub mod elf;
+pub mod entropy;
+pub mod exit;
+pub mod journal;
 pub mod large_field;
 pub mod runtime;
 pub mod small_field;
@@ -21,11 +27,15 @@ pub mod small_field;
 static TARGET_STD: &str = "riscv32im-risc0-zkvm-elf";
 static TARGET_NO_STD: &str = "riscv32imac-unknown-none-elf";
 
-#[derive(Copy, Default, Clone)]
+#[derive(Default, Clone)]
 pub struct RuntimeLibs {
     pub arith: bool,
     pub keccak: bool,
     pub poseidon: bool,
+    pub sha256: bool,
+    pub arith384: bool,
+    /// The enabled custom coprocessors.
+    custom: Vec<coprocessors::CustomCoprocessor>,
 }
 
 impl RuntimeLibs {
@@ -34,6 +44,9 @@ impl RuntimeLibs {
             arith: false,
             keccak: false,
             poseidon: false,
+            sha256: false,
+            arith384: false,
+            custom: vec![],
         }
     }
 
@@ -57,14 +70,95 @@ impl RuntimeLibs {
             ..self
         }
     }
+
+    pub fn with_sha256(self) -> Self {
+        Self {
+            sha256: true,
+            ..self
+        }
+    }
+
+    pub fn with_arith384(self) -> Self {
+        Self {
+            arith384: true,
+            ..self
+        }
+    }
+
+    /// Enables a custom coprocessor. Fails if its name, syscall number or
+    /// instructions clash with a built-in or an already enabled coprocessor.
+    pub fn with_custom(self, coprocessor: coprocessors::CustomCoprocessor) -> Result<Self, String> {
+        coprocessors::check_coprocessor(&self.custom, &coprocessor)?;
+        let mut custom = self.custom;
+        custom.push(coprocessor);
+        Ok(Self { custom, ..self })
+    }
+
+    /// The enabled custom coprocessors, in the order they were enabled.
+    pub fn custom_coprocessors(&self) -> &[coprocessors::CustomCoprocessor] {
+        &self.custom
+    }
+
+    /// The executor implementations of the instructions of the enabled custom
+    /// coprocessors, which have to be passed to the executor to run programs
+    /// compiled with these libraries.
+    pub fn custom_instructions(&self) -> CustomInstructions {
+        self.custom
+            .iter()
+            .flat_map(|c| c.executors.iter().cloned())
+            .collect()
+    }
+}
+/// The RISC-V extensions, besides the base integer and "M" extensions, that
+/// guest crates are compiled with. The powdr RISC-V machine implements all of
+/// them natively, so they are enabled by default.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TargetFeatures {
+    /// The "C" extension of compressed instructions.
+    pub compressed: bool,
+    /// The "Zba" and "Zbb" bit-manipulation extensions.
+    pub bit_manip: bool,
 }
-#[derive(Copy, Clone)]
+
+impl Default for TargetFeatures {
+    fn default() -> Self {
+        Self {
+            compressed: true,
+            bit_manip: true,
+        }
+    }
+}
+
+impl TargetFeatures {
+    /// Only the base instructions, e.g. to compare the cycle counts with and
+    /// without the extensions.
+    pub fn base() -> Self {
+        Self {
+            compressed: false,
+            bit_manip: false,
+        }
+    }
+
+    /// The `-C target-feature` flag for rustc.
+    fn rustc_flag(&self) -> String {
+        let sign = |enabled| if enabled { '+' } else { '-' };
+        let bit_manip = sign(self.bit_manip);
+        format!(
+            "-C target-feature={}c,{bit_manip}zba,{bit_manip}zbb",
+            sign(self.compressed)
+        )
+    }
+}
+
+#[derive(Clone)]
 pub struct CompilerOptions {
     pub field: KnownField,
     pub libs: RuntimeLibs,
     pub continuations: bool,
     pub min_degree_log: u8,
     pub max_degree_log: u8,
+    /// The extensions the guest crate is compiled with by `compile_rust`.
+    pub target_features: TargetFeatures,
 }
 
 impl CompilerOptions {
@@ -75,6 +169,7 @@ impl CompilerOptions {
             continuations,
             min_degree_log: 5,
             max_degree_log: 20,
+            target_features: TargetFeatures::default(),
         }
     }
 
@@ -85,6 +180,18 @@ impl CompilerOptions {
             continuations: false,
             min_degree_log: 5,
             max_degree_log: 20,
+            target_features: TargetFeatures::default(),
+        }
+    }
+
+    pub fn new_m31() -> Self {
+        Self {
+            field: KnownField::Mersenne31Field,
+            libs: RuntimeLibs::new(),
+            continuations: false,
+            min_degree_log: 5,
+            max_degree_log: 20,
+            target_features: TargetFeatures::default(),
         }
     }
 
@@ -95,6 +202,7 @@ impl CompilerOptions {
             continuations: false,
             min_degree_log: 5,
             max_degree_log: 20,
+            target_features: TargetFeatures::default(),
         }
     }
 
@@ -112,6 +220,13 @@ impl CompilerOptions {
         }
     }
 
+    pub fn with_target_features(self, target_features: TargetFeatures) -> Self {
+        Self {
+            target_features,
+            ..self
+        }
+    }
+
     pub fn with_continuations(self) -> Self {
         Self {
             continuations: true,
@@ -139,17 +254,39 @@ impl CompilerOptions {
             ..self
         }
     }
+
+    pub fn with_sha256(self) -> Self {
+        Self {
+            libs: self.libs.with_sha256(),
+            ..self
+        }
+    }
+
+    pub fn with_arith384(self) -> Self {
+        Self {
+            libs: self.libs.with_arith384(),
+            ..self
+        }
+    }
+
+    pub fn with_custom(self, coprocessor: coprocessors::CustomCoprocessor) -> Result<Self, String> {
+        Ok(Self {
+            libs: self.libs.with_custom(coprocessor)?,
+            ..self
+        })
+    }
 }
 
 /// Compiles a rust file to Powdr asm.
-#[allow(clippy::print_stderr)]
+/// Fails if the output file exists and `force_overwrite` is not set, or if a
+/// selected coprocessor is not supported on the selected field.
 pub fn compile_rust(
     file_name: &str,
     options: CompilerOptions,
     output_dir: &Path,
     force_overwrite: bool,
     features: Option<Vec<String>>,
-) -> Option<(PathBuf, String)> {
+) -> Result<(PathBuf, String), String> {
     if options.continuations {
         match options.field {
             KnownField::BabyBearField => {
@@ -178,7 +315,12 @@ pub fn compile_rust(
         panic!("input must be a crate directory or `Cargo.toml` file");
     };
 
-    let elf_path = compile_rust_crate_to_riscv(&file_path, output_dir, features);
+    let elf_path = compile_rust_crate_to_riscv_with_target_features(
+        &file_path,
+        output_dir,
+        features,
+        options.target_features,
+    );
 
     compile_riscv_elf(file_name, &elf_path, options, output_dir, force_overwrite)
 }
@@ -189,8 +331,8 @@ fn compile_program<P>(
     options: CompilerOptions,
     output_dir: &Path,
     force_overwrite: bool,
-    translator: impl FnOnce(P, CompilerOptions) -> String,
-) -> Option<(PathBuf, String)> {
+    translator: impl FnOnce(P, CompilerOptions) -> Result<String, String>,
+) -> Result<(PathBuf, String), String> {
     let powdr_asm_file_name = output_dir.join(format!(
         "{}.asm",
         Path::new(original_file_name)
@@ -200,19 +342,18 @@ fn compile_program<P>(
             .unwrap()
     ));
     if powdr_asm_file_name.exists() && !force_overwrite {
-        eprintln!(
+        return Err(format!(
             "Target file {} already exists. Not overwriting.",
             powdr_asm_file_name.to_str().unwrap()
-        );
-        return None;
+        ));
     }
 
-    let powdr_asm = translator(input_program, options);
+    let powdr_asm = translator(input_program, options)?;
 
     fs::write(powdr_asm_file_name.clone(), &powdr_asm).unwrap();
     log::info!("Wrote {}", powdr_asm_file_name.to_str().unwrap());
 
-    Some((powdr_asm_file_name, powdr_asm))
+    Ok((powdr_asm_file_name, powdr_asm))
 }
 
 /// Translates a RISC-V ELF file to powdr asm.
@@ -222,7 +363,7 @@ pub fn compile_riscv_elf(
     options: CompilerOptions,
     output_dir: &Path,
     force_overwrite: bool,
-) -> Option<(PathBuf, String)> {
+) -> Result<(PathBuf, String), String> {
     compile_program::<&Path>(
         original_file_name,
         input_file,
@@ -241,10 +382,27 @@ macro_rules! as_ref [
     };
 ];
 
+/// Compiles a rust crate to a RISC-V executable, using all the extensions of
+/// [`TargetFeatures::default`].
 pub fn compile_rust_crate_to_riscv(
     input_dir: &str,
     output_dir: &Path,
     features: Option<Vec<String>>,
+) -> PathBuf {
+    compile_rust_crate_to_riscv_with_target_features(
+        input_dir,
+        output_dir,
+        features,
+        TargetFeatures::default(),
+    )
+}
+
+/// Compiles a rust crate to a RISC-V executable, using the given extensions.
+pub fn compile_rust_crate_to_riscv_with_target_features(
+    input_dir: &str,
+    output_dir: &Path,
+    features: Option<Vec<String>>,
+    target_features: TargetFeatures,
 ) -> PathBuf {
     const CARGO_TARGET_DIR: &str = "cargo_target";
     let target_dir = output_dir.join(CARGO_TARGET_DIR);
@@ -252,10 +410,15 @@ pub fn compile_rust_crate_to_riscv(
     let metadata = CargoMetadata::from_input_dir(input_dir);
 
     // Run build.
-    let build_status =
-        build_cargo_command(input_dir, &target_dir, metadata.use_std, features.clone())
-            .status()
-            .unwrap();
+    let build_status = build_cargo_command(
+        input_dir,
+        &target_dir,
+        metadata.use_std,
+        features.clone(),
+        target_features,
+    )
+    .status()
+    .unwrap();
     assert!(build_status.success());
 
     let target = if metadata.use_std {
@@ -340,6 +503,7 @@ fn build_cargo_command(
     target_dir: &Path,
     use_std: bool,
     features: Option<Vec<String>>,
+    target_features: TargetFeatures,
 ) -> Command {
     /*
         The explanation for the more exotic options we are using to build the user code:
@@ -361,6 +525,13 @@ fn build_cargo_command(
         non-interrupting implementation. This is only needed for std support, that uses
         risc0 target, but it is probably beneficial to leave this on for no_std as well.
 
+        `-C target-feature=+c,+zba,+zbb`: the Powdr RISC-V machine implements the "C"
+        compressed instructions and the "Zba" and "Zbb" bit-manipulation instructions
+        natively, so we let LLVM use them instead of longer sequences of base instructions
+        for things like rotations, leading zero counts and address computations. The
+        risc0 target used for std support does not enable "C" by itself. The extensions
+        can be disabled through `TargetFeatures`.
+
         `-Zbuild-std=std,panic_abort`: there are no pre-packaged builds of standard
         libraries for risc0 target, so we have to instruct cargo to build the ones we
         will be using.
@@ -377,7 +548,10 @@ fn build_cargo_command(
     let mut cmd = Command::new("cargo");
     cmd.env(
         "RUSTFLAGS",
-        "-g -C link-arg=-Tpowdr.x -C link-arg=--emit-relocs -C passes=lower-atomic -C panic=abort",
+        format!(
+            "-g -C link-arg=-Tpowdr.x -C link-arg=--emit-relocs -C passes=lower-atomic -C panic=abort {}",
+            target_features.rustc_flag()
+        ),
     );
     // keep debug info for the profiler (callgrind/flamegraph)
     cmd.env("CARGO_PROFILE_RELEASE_DEBUG", "true");
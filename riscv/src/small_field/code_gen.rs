@@ -5,6 +5,7 @@ use powdr_isa_utils::SingleDataValue;
 use powdr_isa_utils::{escape_label, quote};
 
 use powdr_number::KnownField;
+use powdr_riscv_syscalls::COMMIT_PUBLIC_SYMBOL;
 
 use crate::continuations::bootloader::{bootloader_and_shutdown_routine, bootloader_preamble};
 
@@ -21,19 +22,24 @@ use crate::small_field::runtime::Runtime;
 /// and the 24-bit requirement is for this machine only.
 ///
 /// Will call each of the methods in the `RiscVProgram` just once.
-pub fn translate_program(program: impl RiscVProgram, options: CompilerOptions) -> String {
-    let runtime = Runtime::new(options.libs, options.continuations);
+/// Fails if a selected coprocessor is not supported on small fields, or if the
+/// program commits public outputs, which small fields do not support yet.
+pub fn translate_program(
+    program: impl RiscVProgram,
+    options: CompilerOptions,
+) -> Result<String, String> {
+    let runtime = Runtime::new(&options.libs, options.continuations)?;
 
     let (initial_mem, instructions) =
-        translate_program_impl(program, options.field, &runtime, options.continuations);
+        translate_program_impl(program, options.field, &runtime, options.continuations)?;
 
-    riscv_machine(
-        options,
+    Ok(riscv_machine(
+        &options,
         &runtime,
         &preamble(options.field, &runtime, options.continuations),
         initial_mem,
         instructions,
-    )
+    ))
 }
 
 fn translate_program_impl(
@@ -41,7 +47,7 @@ fn translate_program_impl(
     field: KnownField,
     runtime: &Runtime,
     continuations: bool,
-) -> (Vec<String>, Vec<String>) {
+) -> Result<(Vec<String>, Vec<String>), String> {
     let mut initial_mem = Vec::new();
     let mut data_code = Vec::new();
     for MemEntry { label, addr, value } in program.take_initial_mem() {
@@ -165,7 +171,17 @@ fn translate_program_impl(
             Statement::DebugLoc { file, line, col } => {
                 statements.push(format!(".debug loc {file} {line} {col};"))
             }
-            Statement::Label(l) => statements.push(format!("{}:", escape_label(l.as_ref()))),
+            Statement::Label(l) => {
+                // The runtime only links this function into programs that
+                // commit a journal, their inputs or an entropy seed.
+                if l.as_ref() == COMMIT_PUBLIC_SYMBOL {
+                    return Err(
+                        "Public outputs (journal, committed inputs and entropy seed) are not supported on small fields yet"
+                            .to_string(),
+                    );
+                }
+                statements.push(format!("{}:", escape_label(l.as_ref())))
+            }
             Statement::Instruction { op, args } => {
                 let processed_instr = match process_instruction(op, args) {
                     Ok(s) => s,
@@ -192,11 +208,11 @@ fn translate_program_impl(
     }
     statements.extend(runtime.ecall_handler());
 
-    (initial_mem, statements)
+    Ok((initial_mem, statements))
 }
 
 fn riscv_machine(
-    options: CompilerOptions,
+    options: &CompilerOptions,
     runtime: &Runtime,
     preamble: &str,
     initial_memory: Vec<String>,
@@ -230,6 +246,8 @@ let initial_memory: (fe, fe)[] = [
 {}
     }}
 }}
+
+{}
 "#,
         runtime.submachines_import(),
         options.min_degree_log,
@@ -246,9 +264,20 @@ let initial_memory: (fe, fe)[] = [
         program
             .into_iter()
             .format_with("\n", |line, f| f(&format_args!("\t\t{line}"))),
+        custom_definitions(options),
     )
 }
 
+/// The definitions added to the program by the custom coprocessors.
+fn custom_definitions(options: &CompilerOptions) -> String {
+    options
+        .libs
+        .custom_coprocessors()
+        .iter()
+        .map(|c| c.definitions.as_str())
+        .join("\n")
+}
+
 fn preamble(field: KnownField, runtime: &Runtime, with_bootloader: bool) -> String {
     let bootloader_preamble_if_included = if with_bootloader {
         bootloader_preamble(field)
@@ -259,6 +288,7 @@ fn preamble(field: KnownField, runtime: &Runtime, with_bootloader: bool) -> Stri
     for machine in [
         "binary",
         "shift",
+        "rotate",
         "bit2",
         "bit6",
         "bit7",
@@ -267,6 +297,7 @@ fn preamble(field: KnownField, runtime: &Runtime, with_bootloader: bool) -> Stri
         "byte2",
         "byte_binary",
         "byte_shift",
+        "byte_rotate",
         "byte_compare",
     ] {
         assert!(
@@ -532,6 +563,14 @@ fn preamble(field: KnownField, runtime: &Runtime, with_bootloader: bool) -> Stri
         link ~> (tmp4_h, tmp4_l) = add_sub.add(tmp2_h, tmp2_l, WH, WL)
         link ~> regs.mstore(0, YL, STEP + 1, tmp4_h, tmp4_l);
 
+    // Computes V = val(XL) * ZL + val(YL) in 32-bit machine arithmetic, and stores the result in register WL.
+    instr add_shifted XL, YL, ZL, WL
+        link ~> (tmp1_h, tmp1_l) = regs.mload(0, XL, STEP)
+        link ~> (tmp2_h, tmp2_l) = regs.mload(0, YL, STEP + 1)
+        link ~> (tmp3_h, tmp3_l, tmp4_h, tmp4_l) = arith_mul.mul(tmp1_h, tmp1_l, tmp2_h, tmp2_l, 0, ZL)
+        // we ignore tmp3 because that's the high 32 bits of the 64 bits result
+        link ~> regs.mstore(0, WL, STEP + 2, tmp4_h, tmp4_l);
+
     // ================= wrapping instructions =================
 
     // Computes V = val(XL) + val(YL) + (ZH, ZL) in 32-bit machine arithmetic, and stores the result in register WL.
@@ -573,6 +612,30 @@ fn preamble(field: KnownField, runtime: &Runtime, with_bootloader: bool) -> Stri
         XX = (tmp3_h + tmp3_l)
     }
 
+    // Stores the minimum of val(XL) and val(YL), as unsigned numbers, in register WL.
+    instr minu XL, YL, WL
+        link ~> (tmp1_h, tmp1_l) = regs.mload(0, XL, STEP)
+        link ~> (tmp2_h, tmp2_l) = regs.mload(0, YL, STEP + 1)
+        link ~> wrap_bit = add_sub.gt(tmp1_h, tmp1_l, tmp2_h, tmp2_l)
+        link ~> regs.mstore(0, WL, STEP + 2, tmp3_h, tmp3_l)
+    {
+        // wrap_bit is 1 if val(YL) > val(XL).
+        tmp3_h = tmp2_h + wrap_bit * (tmp1_h - tmp2_h),
+        tmp3_l = tmp2_l + wrap_bit * (tmp1_l - tmp2_l)
+    }
+
+    // Stores the maximum of val(XL) and val(YL), as unsigned numbers, in register WL.
+    instr maxu XL, YL, WL
+        link ~> (tmp1_h, tmp1_l) = regs.mload(0, XL, STEP)
+        link ~> (tmp2_h, tmp2_l) = regs.mload(0, YL, STEP + 1)
+        link ~> wrap_bit = add_sub.gt(tmp1_h, tmp1_l, tmp2_h, tmp2_l)
+        link ~> regs.mstore(0, WL, STEP + 2, tmp3_h, tmp3_l)
+    {
+        // wrap_bit is 1 if val(YL) > val(XL).
+        tmp3_h = tmp1_h + wrap_bit * (tmp2_h - tmp1_h),
+        tmp3_l = tmp1_l + wrap_bit * (tmp2_l - tmp1_l)
+    }
+
     // ================= submachine instructions =================
 "# + &runtime
         .submachines_instructions()
@@ -619,6 +682,19 @@ fn preamble(field: KnownField, runtime: &Runtime, with_bootloader: bool) -> Stri
         tmp3_l = tmp1_l
     }
 
+    // Reverses the order of the bytes of the value in register XL and stores it in register YL.
+    instr rev8 XL, YL
+        link ~> (tmp1_h, tmp1_l) = regs.mload(0, XL, STEP)
+        link ~> regs.mstore(0, YL, STEP + 3, tmp4_l * 2**8 + tmp4_h, tmp3_l * 2**8 + tmp3_h)
+        link => byte.check(tmp3_h)
+        link => byte.check(tmp3_l)
+        link => byte.check(tmp4_h)
+        link => byte.check(tmp4_l)
+    {
+        tmp1_h = tmp3_h * 2**8 + tmp3_l,
+        tmp1_l = tmp4_h * 2**8 + tmp4_l
+    }
+
     // ======================= assertions =========================
 
     instr fail 
@@ -1111,6 +1187,79 @@ fn process_instruction<A: InstructionArgs>(instr: &str, args: A) -> Result<Vec<S
                 ),
             )
         }
+        "andn" | "orn" | "xnor" => {
+            let (rd, r1, r2) = args.rrr()?;
+            only_if_no_write_to_zero(
+                rd,
+                format!("{instr} {}, {}, {};", r1.addr(), r2.addr(), rd.addr()),
+            )
+        }
+        "cpop" | "rev8" => {
+            let (rd, rs) = args.rr()?;
+            only_if_no_write_to_zero(rd, format!("{instr} {}, {};", rs.addr(), rd.addr()))
+        }
+        "orc.b" => {
+            let (rd, rs) = args.rr()?;
+            only_if_no_write_to_zero(rd, format!("orc_b {}, {};", rs.addr(), rd.addr()))
+        }
+        "ctz" => {
+            // The trailing zeros of x are the set bits of (x - 1) & ~x.
+            let (rd, rs) = args.rr()?;
+            only_if_no_write_to_zero_vec(
+                rd,
+                vec![
+                    format!(
+                        "sub_wrap_with_offset {}, 0, {}, {}, {};",
+                        rs.addr(),
+                        i32_high(-1),
+                        i32_low(-1),
+                        tmp1.addr()
+                    ),
+                    format!("andn {}, {}, {};", tmp1.addr(), rs.addr(), tmp1.addr()),
+                    format!("cpop {}, {};", tmp1.addr(), rd.addr()),
+                ],
+            )
+        }
+        "clz" => {
+            // The leading zeros of x are the trailing zeros of x with its bits reversed.
+            let (rd, rs) = args.rr()?;
+            only_if_no_write_to_zero_vec(
+                rd,
+                vec![
+                    format!("brev {}, {};", rs.addr(), tmp1.addr()),
+                    format!(
+                        "sub_wrap_with_offset {}, 0, {}, {}, {};",
+                        tmp1.addr(),
+                        i32_high(-1),
+                        i32_low(-1),
+                        tmp2.addr()
+                    ),
+                    format!("andn {}, {}, {};", tmp2.addr(), tmp1.addr(), tmp2.addr()),
+                    format!("cpop {}, {};", tmp2.addr(), rd.addr()),
+                ],
+            )
+        }
+        "sext.b" => {
+            let (rd, rs) = args.rr()?;
+            only_if_no_write_to_zero(
+                rd,
+                format!("sign_extend_byte {}, {};", rs.addr(), rd.addr()),
+            )
+        }
+        "sext.h" => {
+            let (rd, rs) = args.rr()?;
+            only_if_no_write_to_zero(
+                rd,
+                format!("sign_extend_16_bits {}, {};", rs.addr(), rd.addr()),
+            )
+        }
+        "zext.h" => {
+            let (rd, rs) = args.rr()?;
+            only_if_no_write_to_zero(
+                rd,
+                format!("and {}, 0, 0, 0xffff, {};", rs.addr(), rd.addr()),
+            )
+        }
 
         // shift
         "slli" => {
@@ -1188,6 +1337,43 @@ fn process_instruction<A: InstructionArgs>(instr: &str, args: A) -> Result<Vec<S
             )
         }
 
+        "rol" | "ror" => {
+            let (rd, r1, r2) = args.rrr()?;
+            let op = if instr == "rol" { "rotl" } else { "rotr" };
+            only_if_no_write_to_zero_vec(
+                rd,
+                vec![
+                    format!("and {}, 0, 0, 0x1f, {};", r2.addr(), tmp1.addr()),
+                    format!("{op} {}, {}, 0, 0, {};", r1.addr(), tmp1.addr(), rd.addr()),
+                ],
+            )
+        }
+        "rori" => {
+            let (rd, rs, amount) = args.rri()?;
+            assert!(amount <= 31);
+            only_if_no_write_to_zero(
+                rd,
+                format!("rotr {}, 0, 0, {amount}, {};", rs.addr(), rd.addr()),
+            )
+        }
+        "sh1add" | "sh2add" | "sh3add" => {
+            let (rd, r1, r2) = args.rrr()?;
+            let factor = match instr {
+                "sh1add" => 2,
+                "sh2add" => 4,
+                _ => 8,
+            };
+            only_if_no_write_to_zero(
+                rd,
+                format!(
+                    "add_shifted {}, {}, {factor}, {};",
+                    r1.addr(),
+                    r2.addr(),
+                    rd.addr()
+                ),
+            )
+        }
+
         // comparison
         "seqz" => {
             let (rd, rs) = args.rr()?;
@@ -1310,6 +1496,27 @@ fn process_instruction<A: InstructionArgs>(instr: &str, args: A) -> Result<Vec<S
             )
         }
 
+        "minu" | "maxu" => {
+            let (rd, r1, r2) = args.rrr()?;
+            only_if_no_write_to_zero(
+                rd,
+                format!("{instr} {}, {}, {};", r1.addr(), r2.addr(), rd.addr()),
+            )
+        }
+        "min" | "max" => {
+            // Flipping the sign bit maps the signed order to the unsigned order.
+            let (rd, r1, r2) = args.rrr()?;
+            only_if_no_write_to_zero_vec(
+                rd,
+                vec![
+                    format!("add_wrap {}, 0, 0x8000, 0, {};", r1.addr(), tmp1.addr()),
+                    format!("add_wrap {}, 0, 0x8000, 0, {};", r2.addr(), tmp2.addr()),
+                    format!("{instr}u {}, {}, {};", tmp1.addr(), tmp2.addr(), rd.addr()),
+                    format!("add_wrap {}, 0, 0x8000, 0, {};", rd.addr(), rd.addr()),
+                ],
+            )
+        }
+
         // branching
         "beq" => {
             let (r1, r2, label) = args.rrl()?;
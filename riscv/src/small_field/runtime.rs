@@ -7,6 +7,7 @@ use itertools::Itertools;
 use crate::code_gen::Register;
 use crate::small_field::code_gen::{u32_high, u32_low};
 
+use crate::coprocessors::CustomCoprocessor;
 use crate::runtime::{
     parse_function_statement, parse_instruction_declaration, SubMachine, SyscallImpl,
     EXTRA_REG_PREFIX,
@@ -19,27 +20,41 @@ use crate::RuntimeLibs;
 pub struct Runtime {
     submachines: BTreeMap<String, SubMachine>,
     syscalls: BTreeMap<Syscall, SyscallImpl>,
+    /// Syscalls of custom coprocessors, by number, with the name of their coprocessor.
+    custom_syscalls: BTreeMap<u32, (String, SyscallImpl)>,
 }
 
 impl Runtime {
-    pub fn new(libs: RuntimeLibs, continuations: bool) -> Self {
+    /// Fails if a built-in coprocessor that is not supported on small fields is
+    /// selected.
+    pub fn new(libs: &RuntimeLibs, continuations: bool) -> Result<Self, String> {
         let mut runtime = Runtime::base();
         if libs.poseidon {
-            runtime = runtime.with_poseidon(continuations);
+            runtime = runtime.with_poseidon(continuations)?;
         }
         if libs.keccak {
-            runtime = runtime.with_keccak();
+            runtime = runtime.with_keccak()?;
         }
         if libs.arith {
-            runtime = runtime.with_arith();
+            runtime = runtime.with_arith()?;
         }
-        runtime
+        if libs.sha256 {
+            runtime = runtime.with_sha256()?;
+        }
+        if libs.arith384 {
+            runtime = runtime.with_arith384()?;
+        }
+        for coprocessor in libs.custom_coprocessors() {
+            runtime = runtime.with_custom(coprocessor);
+        }
+        Ok(runtime)
     }
 
     pub fn base() -> Self {
         let mut r = Runtime {
             submachines: Default::default(),
             syscalls: Default::default(),
+            custom_syscalls: Default::default(),
         };
 
         // Base submachines
@@ -68,6 +83,51 @@ impl Runtime {
                             link ~> (tmp3_h, tmp3_l) = add_sub.add(tmp2_h, tmp2_l, ZH, ZL)
                             link ~> (tmp4_h, tmp4_l) = binary.xor(tmp1_h, tmp1_l, tmp3_h, tmp3_l)
                             link ~> regs.mstore(0, WL, STEP + 3, tmp4_h, tmp4_l);"#,
+                r#"instr andn XL, YL, WL
+                            link ~> (tmp1_h, tmp1_l) = regs.mload(0, XL, STEP)
+                            link ~> (tmp2_h, tmp2_l) = regs.mload(0, YL, STEP + 1)
+                            link ~> (tmp4_h, tmp4_l) = binary.and(tmp1_h, tmp1_l, 0xffff - tmp2_h, 0xffff - tmp2_l)
+                            link ~> regs.mstore(0, WL, STEP + 3, tmp4_h, tmp4_l);"#,
+                r#"instr orn XL, YL, WL
+                            link ~> (tmp1_h, tmp1_l) = regs.mload(0, XL, STEP)
+                            link ~> (tmp2_h, tmp2_l) = regs.mload(0, YL, STEP + 1)
+                            link ~> (tmp4_h, tmp4_l) = binary.or(tmp1_h, tmp1_l, 0xffff - tmp2_h, 0xffff - tmp2_l)
+                            link ~> regs.mstore(0, WL, STEP + 3, tmp4_h, tmp4_l);"#,
+                r#"instr xnor XL, YL, WL
+                            link ~> (tmp1_h, tmp1_l) = regs.mload(0, XL, STEP)
+                            link ~> (tmp2_h, tmp2_l) = regs.mload(0, YL, STEP + 1)
+                            link ~> (tmp4_h, tmp4_l) = binary.xor(tmp1_h, tmp1_l, 0xffff - tmp2_h, 0xffff - tmp2_l)
+                            link ~> regs.mstore(0, WL, STEP + 3, tmp4_h, tmp4_l);"#,
+                // Counts the set bits of each byte and adds the counts up.
+                r#"instr cpop XL, WL
+                            link ~> (tmp1_h, tmp1_l) = regs.mload(0, XL, STEP)
+                            link ~> (tmp2_h, tmp2_l) = binary.bytewise(tmp1_h, tmp1_l, 0, 0)
+                            link ~> regs.mstore(0, WL, STEP + 3, 0, tmp3_h + tmp3_l + tmp4_h + tmp4_l)
+                            link => byte.check(tmp3_h)
+                            link => byte.check(tmp3_l)
+                            link => byte.check(tmp4_h)
+                            link => byte.check(tmp4_l)
+                        {
+                            tmp2_h = tmp3_h * 2**8 + tmp3_l,
+                            tmp2_l = tmp4_h * 2**8 + tmp4_l
+                        }"#,
+                // Reverses the bits of each byte and then the order of the bytes.
+                r#"instr brev XL, WL
+                            link ~> (tmp1_h, tmp1_l) = regs.mload(0, XL, STEP)
+                            link ~> (tmp2_h, tmp2_l) = binary.bytewise(tmp1_h, tmp1_l, 0x0101, 0x0101)
+                            link ~> regs.mstore(0, WL, STEP + 3, tmp4_l * 2**8 + tmp4_h, tmp3_l * 2**8 + tmp3_h)
+                            link => byte.check(tmp3_h)
+                            link => byte.check(tmp3_l)
+                            link => byte.check(tmp4_h)
+                            link => byte.check(tmp4_l)
+                        {
+                            tmp2_h = tmp3_h * 2**8 + tmp3_l,
+                            tmp2_l = tmp4_h * 2**8 + tmp4_l
+                        }"#,
+                r#"instr orc_b XL, WL
+                            link ~> (tmp1_h, tmp1_l) = regs.mload(0, XL, STEP)
+                            link ~> (tmp4_h, tmp4_l) = binary.bytewise(tmp1_h, tmp1_l, 0x0202, 0x0202)
+                            link ~> regs.mstore(0, WL, STEP + 3, tmp4_h, tmp4_l);"#,
             ],
             0,
             ["and 0, 0, 0, 0, 0;"],
@@ -98,6 +158,33 @@ impl Runtime {
             ["shl 0, 0, 0, 0, 0;"],
         );
 
+        // The rotations of the "Zbb" extension have their own table, so that
+        // the shift table does not grow for them.
+        r.add_submachine(
+            "std::machines::small_field::rotate::Rotate",
+            None,
+            "rotate",
+            vec!["byte_rotate", "MIN_DEGREE", "LARGE_SUBMACHINES_MAX_DEGREE"],
+            [
+                r#"instr rotl XL, YL, ZH, ZL, WL
+                    link ~> (tmp1_h, tmp1_l) = regs.mload(0, XL, STEP)
+                    link ~> (tmp2_h, tmp2_l) = regs.mload(0, YL, STEP)
+                    link ~> (tmp3_h, tmp3_l) = add_sub.add(tmp2_h, tmp2_l, ZH, ZL)
+                    link ~> (tmp4_l, tmp4_h) = rotate.rotl(tmp1_l, tmp1_h, tmp3_l)
+                    link ~> regs.mstore(0, WL, STEP + 3, tmp4_h, tmp4_l);
+"#,
+                r#"instr rotr XL, YL, ZH, ZL, WL
+                    link ~> (tmp1_h, tmp1_l) = regs.mload(0, XL, STEP)
+                    link ~> (tmp2_h, tmp2_l) = regs.mload(0, YL, STEP)
+                    link ~> (tmp3_h, tmp3_l) = add_sub.add(tmp2_h, tmp2_l, ZH, ZL)
+                    link ~> (tmp4_l, tmp4_h) = rotate.rotr(tmp1_l, tmp1_h, tmp3_l)
+                    link ~> regs.mstore(0, WL, STEP + 3, tmp4_h, tmp4_l);
+"#,
+            ],
+            0,
+            ["rotl 0, 0, 0, 0, 0;"],
+        );
+
         r.add_submachine::<&str, _, _>(
             "std::machines::range::Bit2",
             None,
@@ -178,6 +265,16 @@ impl Runtime {
             [],
         );
 
+        r.add_submachine::<&str, _, _>(
+            "std::machines::small_field::rotate::ByteRotate",
+            None,
+            "byte_rotate",
+            vec![],
+            [],
+            0,
+            [],
+        );
+
         r.add_submachine::<&str, _, _>(
             "std::machines::split::ByteCompare",
             None,
@@ -212,13 +309,31 @@ impl Runtime {
             ]
         );
 
-        r.add_syscall(Syscall::Halt, ["return;"]);
+        // Public outputs are not supported yet for small fields, programs
+        // committing them are rejected when they are translated. The exit code
+        // in x10 can therefore not be committed either, so the halt syscall
+        // fails unless it is zero, and only successful executions can be proven.
+        r.add_syscall(
+            Syscall::Halt,
+            ["skip_if_equal 10, 0, 0, 0, 1;", "fail;", "return;"],
+        );
 
         r
     }
 
-    fn with_keccak(self) -> Self {
-        todo!()
+    fn with_keccak(self) -> Result<Self, String> {
+        Err(unsupported_coprocessor("keccakf"))
+    }
+
+    fn with_sha256(self) -> Result<Self, String> {
+        // The Sha256_16 machine takes the state and the message block as
+        // operation parameters, there is no instruction loading them from
+        // memory yet.
+        Err(unsupported_coprocessor("sha256"))
+    }
+
+    fn with_arith384(self) -> Result<Self, String> {
+        Err(unsupported_coprocessor("arith384"))
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -272,16 +387,42 @@ impl Runtime {
         }
     }
 
-    fn with_poseidon(self, _continuations: bool) -> Self {
-        todo!()
+    fn with_custom(mut self, coprocessor: &CustomCoprocessor) -> Self {
+        self.add_submachine(
+            &coprocessor.submachine_path,
+            None,
+            &coprocessor.instance_name,
+            coprocessor.arguments.iter().map(String::as_str).collect(),
+            &coprocessor.instructions,
+            coprocessor.extra_registers,
+            &coprocessor.init_call,
+        );
+
+        let implementation = SyscallImpl(
+            coprocessor
+                .syscall_impl
+                .iter()
+                .map(|s| parse_function_statement(s))
+                .collect(),
+        );
+        // Syscall numbers are checked to be unique when registering the coprocessor.
+        self.custom_syscalls.insert(
+            coprocessor.syscall,
+            (coprocessor.name.clone(), implementation),
+        );
+        self
+    }
+
+    fn with_poseidon(self, _continuations: bool) -> Result<Self, String> {
+        Err(unsupported_coprocessor("poseidon_gl"))
     }
 
     pub fn has_submachine(&self, name: &str) -> bool {
         self.submachines.contains_key(name)
     }
 
-    fn with_arith(self) -> Self {
-        todo!()
+    fn with_arith(self) -> Result<Self, String> {
+        Err(unsupported_coprocessor("arith"))
     }
 
     pub fn submachines_init(&self) -> Vec<String> {
@@ -324,6 +465,20 @@ impl Runtime {
             .collect()
     }
 
+    /// All syscalls, as (number, label, implementation).
+    fn all_syscalls(&self) -> impl Iterator<Item = (u32, String, &SyscallImpl)> {
+        self.syscalls
+            .iter()
+            .map(|(syscall, implementation)| (*syscall as u32, syscall.to_string(), implementation))
+            .chain(
+                self.custom_syscalls
+                    .iter()
+                    .map(|(number, (name, implementation))| {
+                        (*number, format!("custom_{name}"), implementation)
+                    }),
+            )
+    }
+
     pub fn ecall_handler(&self) -> Vec<String> {
         let ecall = [
             "// ecall handler".to_string(),
@@ -331,16 +486,16 @@ impl Runtime {
         ]
         .into_iter();
 
-        let jump_table = self.syscalls.keys().map(|s| {
-            let s32_h = u32_high(*s as u32);
-            let s32_l = u32_low(*s as u32);
-            format!("branch_if_diff_equal 5, 0, {s32_h}, {s32_l}, __ecall_handler_{s};",)
+        let jump_table = self.all_syscalls().map(|(number, label, _)| {
+            let s32_h = u32_high(number);
+            let s32_l = u32_low(number);
+            format!("branch_if_diff_equal 5, 0, {s32_h}, {s32_l}, __ecall_handler_{label};",)
         });
 
         let invalid_handler = ["__invalid_syscall:".to_string(), "fail;".to_string()].into_iter();
 
-        let handlers = self.syscalls.iter().flat_map(|(syscall, implementation)| {
-            std::iter::once(format!("__ecall_handler_{syscall}:"))
+        let handlers = self.all_syscalls().flat_map(|(_, label, implementation)| {
+            std::iter::once(format!("__ecall_handler_{label}:"))
                 .chain(implementation.0.iter().map(|i| i.to_string()))
                 .chain([format!("jump_dyn 1, {};", Register::from("tmp1").addr())])
         });
@@ -353,3 +508,27 @@ impl Runtime {
             .collect()
     }
 }
+
+fn unsupported_coprocessor(name: &str) -> String {
+    format!("The {name} coprocessor is not supported on small fields yet")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn unsupported_coprocessors() {
+        let libs = RuntimeLibs::new().with_sha256();
+        assert_eq!(
+            Runtime::new(&libs, false).err().unwrap(),
+            "The sha256 coprocessor is not supported on small fields yet"
+        );
+        let libs = RuntimeLibs::new().with_arith384();
+        assert_eq!(
+            Runtime::new(&libs, false).err().unwrap(),
+            "The arith384 coprocessor is not supported on small fields yet"
+        );
+        assert!(Runtime::new(&RuntimeLibs::new(), false).is_ok());
+    }
+}
@@ -1,10 +1,13 @@
 use mktemp::Temp;
-use powdr_number::{BabyBearField, FieldElement, GoldilocksField, KnownField, KoalaBearField};
+use powdr_number::{
+    BabyBearField, FieldElement, GoldilocksField, KnownField, KoalaBearField, Mersenne31Field,
+};
 use powdr_pipeline::{
     test_util::{run_pilcom_with_backend_variant, test_plonky3_pipeline, BackendVariant},
     Pipeline,
 };
 use powdr_riscv::CompilerOptions;
+use powdr_riscv_executor::custom::CustomInstructions;
 use std::{
     path::{Path, PathBuf},
     process::Command,
@@ -16,6 +19,7 @@ pub fn verify_riscv_asm_string<T: FieldElement, S: serde::Serialize + Send + Syn
     contents: &str,
     inputs: &[T],
     data: Option<&[(u32, S)]>,
+    custom_instructions: &CustomInstructions,
 ) {
     let temp_dir = mktemp::Temp::new_dir().unwrap().release();
 
@@ -39,8 +43,10 @@ pub fn verify_riscv_asm_string<T: FieldElement, S: serde::Serialize + Send + Syn
             &[],
             usize::MAX,
             powdr_riscv_executor::ExecMode::Fast,
+            custom_instructions,
             Default::default(),
-        );
+        )
+        .unwrap_or_else(|e| panic!("{e}"));
         let pipeline_gl: Pipeline<GoldilocksField> =
             unsafe { std::mem::transmute(pipeline.clone()) };
         run_pilcom_with_backend_variant(pipeline_gl, BackendVariant::Composite).unwrap();
@@ -76,7 +82,7 @@ pub fn verify_riscv_asm_file(asm_file: &Path, options: CompilerOptions, use_pie:
     let assembler = find_assembler();
     log::info!("Using assembler: {}", assembler);
     Command::new(assembler)
-        .arg("-march=rv32imac")
+        .arg("-march=rv32imac_zba_zbb")
         .arg("-mabi=ilp32")
         .arg("-o")
         .arg(&obj_file)
@@ -95,15 +101,18 @@ pub fn verify_riscv_asm_file(asm_file: &Path, options: CompilerOptions, use_pie:
 
     let case_name = asm_file.file_stem().unwrap().to_str().unwrap();
 
-    let powdr_asm = powdr_riscv::elf::translate(&executable, options);
+    let field = options.field;
+    let custom_instructions = options.libs.custom_instructions();
+    let powdr_asm = powdr_riscv::elf::translate(&executable, options).unwrap();
 
-    match options.field {
+    match field {
         KnownField::BabyBearField => {
             verify_riscv_asm_string::<BabyBearField, ()>(
                 &format!("{case_name}.asm"),
                 &powdr_asm,
                 &[],
                 None,
+                &custom_instructions,
             );
         }
         KnownField::KoalaBearField => {
@@ -112,15 +121,25 @@ pub fn verify_riscv_asm_file(asm_file: &Path, options: CompilerOptions, use_pie:
                 &powdr_asm,
                 &[],
                 None,
+                &custom_instructions,
+            );
+        }
+        KnownField::Mersenne31Field => {
+            verify_riscv_asm_string::<Mersenne31Field, ()>(
+                &format!("{case_name}.asm"),
+                &powdr_asm,
+                &[],
+                None,
+                &custom_instructions,
             );
         }
-        KnownField::Mersenne31Field => todo!(),
         KnownField::GoldilocksField => {
             verify_riscv_asm_string::<GoldilocksField, ()>(
                 &format!("{case_name}.asm"),
                 &powdr_asm,
                 &[],
                 None,
+                &custom_instructions,
             );
         }
         KnownField::Bn254Field => todo!(),
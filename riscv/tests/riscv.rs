@@ -4,17 +4,23 @@ use common::{verify_riscv_asm_file, verify_riscv_asm_string};
 use mktemp::Temp;
 use powdr_number::{
     read_polys_csv_file, BabyBearField, CsvRenderMode, FieldElement, GoldilocksField, KnownField,
+    Mersenne31Field,
 };
 use powdr_pipeline::{
-    test_util::{run_pilcom_with_backend_variant, BackendVariant},
+    test_util::{run_pilcom_with_backend_variant, test_plonky3_pipeline, BackendVariant},
     Pipeline,
 };
-use powdr_riscv_executor::ProfilerOptions;
+use powdr_riscv_executor::{custom::MachineState, ProfilerOptions};
 use std::path::{Path, PathBuf};
 use test_log::test;
 
 use powdr_riscv::{
+    committed_inputs::{committed_inputs, input_digest, verify_inputs, words_digest},
     continuations::{rust_continuations, rust_continuations_dry_run},
+    coprocessors::CustomCoprocessor,
+    entropy::{entropy_seed, verify_entropy_seed, ENTROPY_SEED_FD},
+    exit::{exit_code, ExitStatus, GuestError, PANIC_EXIT_CODE},
+    journal::Journal,
     CompilerOptions, RuntimeLibs,
 };
 
@@ -35,7 +41,8 @@ pub fn test_continuations(case: &str) {
         CompilerOptions::new_gl()
             .with_poseidon()
             .with_continuations(),
-    );
+    )
+    .unwrap();
     run_continuations_test(case, powdr_asm);
 }
 
@@ -52,7 +59,8 @@ fn run_continuations_test(case: &str, powdr_asm: String) {
 
         Ok(())
     };
-    let bootloader_inputs = rust_continuations_dry_run(&mut pipeline, Default::default());
+    let bootloader_inputs =
+        rust_continuations_dry_run(&mut pipeline, &Default::default(), Default::default()).unwrap();
     rust_continuations(&mut pipeline, pipeline_callback, bootloader_inputs).unwrap();
 }
 
@@ -72,7 +80,7 @@ fn bn254_sanity_check() {
 
     log::info!("Verifying {case} converted from ELF file");
     let options = CompilerOptions::new(KnownField::Bn254Field, RuntimeLibs::new(), false);
-    let from_elf = powdr_riscv::elf::translate(&executable, options);
+    let from_elf = powdr_riscv::elf::translate(&executable, options).unwrap();
 
     let temp_dir = mktemp::Temp::new_dir().unwrap().release();
 
@@ -90,8 +98,10 @@ fn bn254_sanity_check() {
         &[],
         usize::MAX,
         powdr_riscv_executor::ExecMode::Fast,
+        &Default::default(),
         Default::default(),
-    );
+    )
+    .unwrap();
     run_pilcom_with_backend_variant(pipeline, BackendVariant::Composite).unwrap();
 }
 
@@ -102,6 +112,14 @@ fn trivial() {
     verify_riscv_crate(case, Default::default())
 }
 
+#[test]
+#[ignore = "Too slow"]
+fn trivial_m31() {
+    let case = "trivial";
+    let options = CompilerOptions::new_m31();
+    verify_riscv_crate_impl::<Mersenne31Field, ()>(case, options, Default::default(), None)
+}
+
 #[test]
 #[ignore = "Too slow"]
 fn halt() {
@@ -124,6 +142,61 @@ fn runtime_poseidon_gl() {
     verify_riscv_crate_gl_with_options(case, Default::default(), options);
 }
 
+#[test]
+#[ignore = "Too slow"]
+fn runtime_sha256() {
+    let case = "sha256_via_coprocessor";
+    let options = CompilerOptions::new_gl().with_sha256();
+    verify_riscv_crate_gl_with_options(case, Default::default(), options);
+}
+
+fn double(state: &mut dyn MachineState, args: &[u32]) {
+    let value = state.get_reg(args[0]);
+    state.set_reg(args[1], value * 2);
+}
+
+#[test]
+#[ignore = "Too slow"]
+fn custom_coprocessor() {
+    let case = "custom_coprocessor";
+    let coprocessor = CustomCoprocessor {
+        name: "double".to_string(),
+        syscall: 100,
+        definitions: r#"
+mod custom_double {
+    pub machine Double with
+        latch: latch,
+        operation_id: operation_id,
+        call_selectors: sel,
+    {
+        operation double<0> x -> y;
+
+        let operation_id;
+        let latch: col = |i| 1;
+        let x;
+        let y;
+        y = 2 * x;
+    }
+}
+"#
+        .to_string(),
+        submachine_path: "custom_double::Double".to_string(),
+        instance_name: "doubler".to_string(),
+        arguments: vec!["MIN_DEGREE".to_string(), "MAIN_MAX_DEGREE".to_string()],
+        instructions: vec![r#"instr double_reg X, W
+            link ~> tmp1_col = regs.mload(X, STEP)
+            link ~> tmp3_col = doubler.double(tmp1_col)
+            link ~> regs.mstore(W, STEP + 3, tmp3_col);"#
+            .to_string()],
+        extra_registers: 0,
+        init_call: vec![],
+        syscall_impl: vec!["double_reg 10, 10;".to_string()],
+        executors: vec![("double_reg".to_string(), double)],
+    };
+    let options = CompilerOptions::new_gl().with_custom(coprocessor).unwrap();
+    verify_riscv_crate_gl_with_options(case, vec![21.into()], options);
+}
+
 #[test]
 #[ignore = "Too slow"]
 fn sum() {
@@ -174,6 +247,39 @@ fn keccak() {
     verify_riscv_crate(case, Default::default());
 }
 
+#[test]
+#[ignore = "Too slow"]
+fn cross_machine_optimizations() {
+    // Prove the guests with and without the optimizer passes across machine boundaries.
+    let passes = [
+        "inline_trivial_submachines",
+        "merge_identical_fixed_columns",
+        "merge_lookups",
+    ];
+    for case in ["trivial", "keccak", "memfuncs"] {
+        let temp_dir = Temp::new_dir().unwrap();
+        let executable = powdr_riscv::compile_rust_crate_to_riscv(
+            &format!("tests/riscv_data/{case}/Cargo.toml"),
+            &temp_dir,
+            None,
+        );
+        let asm = powdr_riscv::elf::translate(&executable, CompilerOptions::new_gl()).unwrap();
+
+        let mut column_counts = vec![];
+        for enabled_passes in [&[][..], &passes[..]] {
+            let output_dir = Temp::new_dir().unwrap();
+            let mut pipeline = Pipeline::<GoldilocksField>::default()
+                .with_output(output_dir.to_path_buf(), true)
+                .with_enabled_optimizer_passes(enabled_passes.iter().map(|p| p.to_string()))
+                .from_asm_string(asm.clone(), Some(PathBuf::from(format!("{case}.asm"))));
+            let pil = pipeline.compute_optimized_pil().unwrap();
+            column_counts.push(pil.commitment_count() + pil.constant_count());
+            test_plonky3_pipeline(pipeline);
+        }
+        assert!(column_counts[1] <= column_counts[0]);
+    }
+}
+
 #[cfg(feature = "estark-polygon")]
 #[test]
 #[ignore = "Too slow"]
@@ -226,6 +332,201 @@ fn runtime_ec_add() {
     verify_riscv_crate_gl_with_options(case, vec![], options);
 }
 
+#[test]
+#[ignore = "Too slow"]
+fn runtime_ec_mul() {
+    let case = "ec_mul";
+    let options = CompilerOptions::new_gl().with_arith();
+    verify_riscv_crate_gl_with_options(case, vec![1.into()], options);
+}
+
+#[test]
+#[ignore = "Too slow"]
+fn runtime_ecdsa() {
+    let case = "ecdsa";
+    let options = CompilerOptions::new_gl().with_arith().with_sha256();
+    verify_riscv_crate_gl_with_options(case, vec![], options);
+}
+
+/// The inputs a = (a0, a1), b = (b0, b1) of the `fields` guest, followed by the
+/// results it is expected to compute, as 12 little-endian words per element.
+fn fields_test_data<P: ark_ff::Fp2Config>(a: ark_ff::Fp2<P>, b: ark_ff::Fp2<P>) -> Vec<u32> {
+    use ark_ff::{BigInteger, Field, PrimeField};
+    let fp = [
+        a.c0,
+        a.c1,
+        b.c0,
+        b.c1,
+        a.c0 * b.c0,
+        a.c0 + b.c0,
+        a.c0 - b.c0,
+        -a.c0,
+        a.c0.inverse().unwrap(),
+    ];
+    let fp2 = [a * b, a.square(), a.inverse().unwrap(), a + b, a - b];
+    fp.into_iter()
+        .chain(fp2.into_iter().flat_map(|x| [x.c0, x.c1]))
+        .flat_map(|x| {
+            let mut bytes = x.into_bigint().to_bytes_le();
+            bytes.resize(48, 0);
+            bytes
+                .chunks(4)
+                .map(|c| u32::from_le_bytes(c.try_into().unwrap()))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// The inputs of the `bit_manip` guest: a and b, followed by the results it is
+/// expected to compute.
+fn bit_manip_inputs(a: u32, b: u32) -> Vec<u64> {
+    let results = [
+        a & !b,
+        a | !b,
+        !(a ^ b),
+        a.leading_zeros(),
+        a.trailing_zeros(),
+        a.count_ones(),
+        (a as i32).min(b as i32) as u32,
+        (a as i32).max(b as i32) as u32,
+        a.min(b),
+        a.max(b),
+        a.rotate_left(b),
+        a.rotate_right(b),
+        a.rotate_right(7),
+        a.swap_bytes(),
+        a as i8 as u32,
+        a as i16 as u32,
+        a as u16 as u32,
+        a.wrapping_add(b << 1),
+        a.wrapping_add(b << 2),
+        a.wrapping_add(b << 3),
+    ];
+    [a, b].into_iter().chain(results).map(u64::from).collect()
+}
+
+#[test]
+#[ignore = "Too slow"]
+fn bit_manip() {
+    verify_riscv_crate("bit_manip", &bit_manip_inputs(0x13579acf, 0x80000025));
+}
+
+#[test]
+fn bit_manip_execution() {
+    // Checks that the guest is compiled with the "Zba" and "Zbb" instructions,
+    // and runs it in the executor for a few inputs.
+    let case = "bit_manip";
+    let temp_dir = Temp::new_dir().unwrap();
+    let executable = powdr_riscv::compile_rust_crate_to_riscv(
+        &format!("tests/riscv_data/{case}/Cargo.toml"),
+        &temp_dir,
+        None,
+    );
+    let from_elf = powdr_riscv::elf::translate(&executable, CompilerOptions::new_gl()).unwrap();
+    for instr in ["andn", "cpop", "rotl", "rotr", "add_shifted"] {
+        assert!(
+            from_elf
+                .lines()
+                .any(|line| line.trim_start().starts_with(&format!("{instr} "))),
+            "{instr} is not used"
+        );
+    }
+
+    for (a, b) in [(0x13579acf, 0x80000025), (0, 0xffffffff), (0xff00ff00, 0x7)] {
+        let inputs = bit_manip_inputs(a, b)
+            .into_iter()
+            .map(GoldilocksField::from)
+            .collect();
+        let mut pipeline = Pipeline::<GoldilocksField>::default()
+            .from_asm_string(from_elf.clone(), Some(PathBuf::from(case)))
+            .with_prover_inputs(inputs);
+        let analyzed = pipeline.compute_analyzed_asm().unwrap().clone();
+        // A failed check in the guest fails the execution.
+        powdr_riscv_executor::execute_ast(
+            &analyzed,
+            Default::default(),
+            pipeline.data_callback().unwrap(),
+            &[],
+            usize::MAX,
+            powdr_riscv_executor::ExecMode::Fast,
+            &Default::default(),
+            Default::default(),
+        )
+        .unwrap();
+    }
+}
+
+/// The data of the `fields` guest, on channel 1 for bn254 and 2 for bls12-381.
+fn fields_data() -> Vec<(u32, Vec<u32>)> {
+    use ark_ff::PrimeField;
+
+    fn element<F: PrimeField>(seed: u8) -> F {
+        let bytes = (0..48)
+            .map(|i| seed.wrapping_mul(i + 1))
+            .collect::<Vec<_>>();
+        F::from_le_bytes_mod_order(&bytes)
+    }
+    fn fp2<F: PrimeField, E>(seed: u8, new: impl Fn(F, F) -> E) -> E {
+        new(element(seed), element(seed.wrapping_add(1)))
+    }
+
+    let bn254 = fields_test_data(fp2(11, ark_bn254::Fq2::new), fp2(37, ark_bn254::Fq2::new));
+    let bls12_381 = fields_test_data(
+        fp2(11, ark_bls12_381::Fq2::new),
+        fp2(37, ark_bls12_381::Fq2::new),
+    );
+    vec![(1, bn254), (2, bls12_381)]
+}
+
+#[test]
+#[ignore = "Too slow"]
+fn runtime_fields() {
+    let case = "fields";
+    let options = CompilerOptions::new_gl().with_arith384();
+    verify_riscv_crate_impl::<GoldilocksField, _>(case, options, vec![], Some(fields_data()));
+}
+
+#[test]
+fn runtime_fields_execution() {
+    // Only runs the executor, so that the field arithmetic of the runtime is
+    // checked on every CI run.
+    let case = "fields";
+    let temp_dir = Temp::new_dir().unwrap();
+    let executable = powdr_riscv::compile_rust_crate_to_riscv(
+        &format!("tests/riscv_data/{case}/Cargo.toml"),
+        &temp_dir,
+        None,
+    );
+    let options = CompilerOptions::new_gl().with_arith384();
+    let from_elf = powdr_riscv::elf::translate(&executable, options).unwrap();
+
+    let mut pipeline = Pipeline::<GoldilocksField>::default()
+        .from_asm_string(
+            from_elf,
+            Some(PathBuf::from(format!("{case}_from_elf.asm"))),
+        )
+        .add_data_vec(&fields_data());
+    let analyzed = pipeline.compute_analyzed_asm().unwrap().clone();
+    // A failed check in the guest panics the executor.
+    powdr_riscv_executor::execute_ast(
+        &analyzed,
+        Default::default(),
+        pipeline.data_callback().unwrap(),
+        &[],
+        usize::MAX,
+        powdr_riscv_executor::ExecMode::Fast,
+        &Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    assert_eq!(
+        ExitStatus::take_from_pipeline(&pipeline)
+            .unwrap()
+            .into_result(),
+        Ok(())
+    );
+}
+
 #[test]
 #[ignore = "Too slow"]
 fn runtime_affine_256() {
@@ -276,6 +577,7 @@ fn sum_serde() {
 #[ignore = "Too slow"]
 fn read_slice() {
     read_slice_with_options::<BabyBearField>(CompilerOptions::new_bb());
+    read_slice_with_options::<Mersenne31Field>(CompilerOptions::new_m31());
     read_slice_with_options::<GoldilocksField>(CompilerOptions::new_gl());
 }
 
@@ -287,7 +589,7 @@ fn read_slice_with_options<T: FieldElement>(options: CompilerOptions) {
         &temp_dir,
         None,
     );
-    let powdr_asm = powdr_riscv::elf::translate(&executable, options);
+    let powdr_asm = powdr_riscv::elf::translate(&executable, options).unwrap();
 
     let data: Vec<u32> = vec![];
     let answer = data.iter().sum::<u32>();
@@ -336,6 +638,11 @@ const DISPATCH_TABLE_S: &str = "tests/riscv_data/dispatch_table/dispatch_table.s
 #[test]
 fn dispatch_table_pie_relocation() {
     verify_riscv_asm_file(Path::new(DISPATCH_TABLE_S), CompilerOptions::new_bb(), true);
+    verify_riscv_asm_file(
+        Path::new(DISPATCH_TABLE_S),
+        CompilerOptions::new_m31(),
+        true,
+    );
     verify_riscv_asm_file(Path::new(DISPATCH_TABLE_S), CompilerOptions::new_gl(), true);
 }
 
@@ -388,12 +695,13 @@ fn features_with_options<T: FieldElement>(options: CompilerOptions) {
     );
 
     log::info!("Verifying {case} converted from ELF file");
-    let from_elf = powdr_riscv::elf::translate(&executable, options);
+    let from_elf = powdr_riscv::elf::translate(&executable, options.clone()).unwrap();
     verify_riscv_asm_string::<T, usize>(
         &format!("{case}_from_elf.asm"),
         &from_elf,
         &[expected.into()],
         None,
+        &Default::default(),
     );
 
     // "add_two"
@@ -405,12 +713,13 @@ fn features_with_options<T: FieldElement>(options: CompilerOptions) {
     );
 
     log::info!("Verifying {case} converted from ELF file");
-    let from_elf = powdr_riscv::elf::translate(&executable, options);
+    let from_elf = powdr_riscv::elf::translate(&executable, options.clone()).unwrap();
     verify_riscv_asm_string::<T, usize>(
         &format!("{case}_from_elf.asm"),
         &from_elf,
         &[expected.into()],
         None,
+        &Default::default(),
     );
 
     // "add_two" and "add_three"
@@ -422,12 +731,13 @@ fn features_with_options<T: FieldElement>(options: CompilerOptions) {
     );
 
     log::info!("Verifying {case} converted from ELF file");
-    let from_elf = powdr_riscv::elf::translate(&executable, options);
+    let from_elf = powdr_riscv::elf::translate(&executable, options).unwrap();
     verify_riscv_asm_string::<T, usize>(
         &format!("{case}_from_elf.asm"),
         &from_elf,
         &[expected.into()],
         None,
+        &Default::default(),
     );
 }
 
@@ -449,12 +759,18 @@ fn many_chunks_dry() {
         CompilerOptions::new_gl()
             .with_poseidon()
             .with_continuations(),
-    );
+    )
+    .unwrap();
 
     let mut pipeline = Pipeline::default()
         .from_asm_string(powdr_asm, Some(PathBuf::from(case)))
         .with_prover_inputs(Default::default());
-    rust_continuations_dry_run::<GoldilocksField>(&mut pipeline, Default::default());
+    rust_continuations_dry_run::<GoldilocksField>(
+        &mut pipeline,
+        &Default::default(),
+        Default::default(),
+    )
+    .unwrap();
 }
 
 use serde::{Deserialize, Serialize};
@@ -465,6 +781,233 @@ struct Point {
     y: i32,
 }
 
+#[test]
+#[ignore = "Too slow"]
+fn commit() {
+    let case = "commit";
+    let temp_dir = Temp::new_dir().unwrap();
+    let executable = powdr_riscv::compile_rust_crate_to_riscv(
+        &format!("tests/riscv_data/{case}/Cargo.toml"),
+        &temp_dir,
+        None,
+    );
+    let powdr_asm =
+        powdr_riscv::elf::translate(&executable, CompilerOptions::new_gl().with_poseidon())
+            .unwrap();
+
+    let inputs = vec![3u32, 5]
+        .into_iter()
+        .map(GoldilocksField::from)
+        .collect();
+    let mut pipeline = Pipeline::<GoldilocksField>::default()
+        .from_asm_string(powdr_asm, Some(PathBuf::from(case)))
+        .with_prover_inputs(inputs);
+
+    pipeline.compute_witness().unwrap();
+
+    let journal = Journal::take_from_pipeline(&pipeline);
+    assert_eq!(journal.decode_all::<u32>().unwrap(), vec![8, 15]);
+    journal.verify(&pipeline.publics().unwrap()).unwrap();
+
+    // A different journal does not match the committed digest.
+    let forged = Journal::new(serde_cbor::to_vec(&9u32).unwrap());
+    assert!(forged.verify(&pipeline.publics().unwrap()).is_err());
+
+    // Small fields do not support public outputs, so the program is rejected.
+    let error = powdr_riscv::elf::translate(&executable, CompilerOptions::new_bb()).unwrap_err();
+    assert!(error.contains("not supported on small fields"), "{error}");
+}
+
+#[test]
+#[ignore = "Too slow"]
+fn exit_code_and_panic() {
+    let case = "exit_code";
+    let temp_dir = Temp::new_dir().unwrap();
+    let executable = powdr_riscv::compile_rust_crate_to_riscv(
+        &format!("tests/riscv_data/{case}/Cargo.toml"),
+        &temp_dir,
+        None,
+    );
+    let powdr_asm =
+        powdr_riscv::elf::translate(&executable, CompilerOptions::new_gl().with_poseidon())
+            .unwrap();
+
+    let run = |input: u32| {
+        let mut pipeline = Pipeline::<GoldilocksField>::default()
+            .from_asm_string(powdr_asm.clone(), Some(PathBuf::from(case)))
+            .with_prover_inputs(vec![GoldilocksField::from(input)]);
+        pipeline.compute_witness().unwrap();
+
+        let publics = pipeline.publics().unwrap();
+        let journal = Journal::take_from_pipeline(&pipeline);
+        assert_eq!(journal.decode::<u32>().unwrap(), input);
+        journal.verify(&publics).unwrap();
+
+        let status = ExitStatus::take_from_pipeline(&pipeline).unwrap();
+        status.verify(&publics).unwrap();
+        assert_eq!(exit_code(&publics).unwrap(), status.code());
+        status
+    };
+
+    assert_eq!(run(5).into_result(), Ok(()));
+    assert_eq!(run(0).into_result(), Err(GuestError::Exit(3)));
+
+    let status = run(42);
+    assert_eq!(status.code(), PANIC_EXIT_CODE);
+    let message = status.panic_message().unwrap();
+    assert!(message.contains("src/main.rs:16:5"), "{message}");
+    assert!(message.contains("value too large: 42"), "{message}");
+}
+
+#[test]
+#[ignore = "Too slow"]
+fn seeded_rand() {
+    let case = "seeded_rand";
+    let temp_dir = Temp::new_dir().unwrap();
+    let executable = powdr_riscv::compile_rust_crate_to_riscv(
+        &format!("tests/riscv_data/{case}/Cargo.toml"),
+        &temp_dir,
+        None,
+    );
+    let powdr_asm =
+        powdr_riscv::elf::translate(&executable, CompilerOptions::new_gl().with_poseidon())
+            .unwrap();
+
+    let run = |seed: [u32; 8]| {
+        let mut pipeline = Pipeline::<GoldilocksField>::default()
+            .from_asm_string(powdr_asm.clone(), Some(PathBuf::from(case)))
+            .add_data(ENTROPY_SEED_FD, &seed);
+        pipeline.compute_witness().unwrap();
+
+        let publics = pipeline.publics().unwrap();
+        assert_eq!(entropy_seed(&publics).unwrap(), Some(seed));
+        verify_entropy_seed(&publics, &seed).unwrap();
+        assert!(verify_entropy_seed(&publics, &[7; 8]).is_err());
+
+        let journal = Journal::take_from_pipeline(&pipeline);
+        journal.verify(&publics).unwrap();
+        journal.bytes().to_vec()
+    };
+
+    // The values are reproducible, but depend on the seed.
+    let seed = [1, 2, 3, 4, 5, 6, 7, 8];
+    let journal = run(seed);
+    assert_eq!(run(seed), journal);
+    assert_ne!(run([8, 7, 6, 5, 4, 3, 2, 1]), journal);
+
+    // A guest that does not use the entropy source does not commit a seed,
+    // which is rejected when a seed is expected, even the zero seed.
+    let case = "trivial";
+    let executable = powdr_riscv::compile_rust_crate_to_riscv(
+        &format!("tests/riscv_data/{case}/Cargo.toml"),
+        &temp_dir,
+        None,
+    );
+    let powdr_asm =
+        powdr_riscv::elf::translate(&executable, CompilerOptions::new_gl().with_poseidon())
+            .unwrap();
+    let mut pipeline = Pipeline::<GoldilocksField>::default()
+        .from_asm_string(powdr_asm, Some(PathBuf::from(case)));
+    pipeline.compute_witness().unwrap();
+    let publics = pipeline.publics().unwrap();
+    assert_eq!(entropy_seed(&publics).unwrap(), None);
+    assert!(verify_entropy_seed(&publics, &[0; 8]).is_err());
+}
+
+#[test]
+fn seeded_rand_test_vectors() {
+    // The first 120 bytes of the key stream of the entropy source, taken by the
+    // `seeded_rand` guest, must match the ChaCha20 key stream of RFC 8439 for
+    // the zero key and the zero nonce (test vectors #1 and #2 of section A.1).
+    let case = "seeded_rand";
+    let temp_dir = Temp::new_dir().unwrap();
+    let executable = powdr_riscv::compile_rust_crate_to_riscv(
+        &format!("tests/riscv_data/{case}/Cargo.toml"),
+        &temp_dir,
+        None,
+    );
+    let powdr_asm =
+        powdr_riscv::elf::translate(&executable, CompilerOptions::new_gl().with_poseidon())
+            .unwrap();
+
+    let mut pipeline = Pipeline::<GoldilocksField>::default()
+        .from_asm_string(powdr_asm, Some(PathBuf::from(case)))
+        .add_data(ENTROPY_SEED_FD, &[0u32; 8]);
+    let analyzed = pipeline.compute_analyzed_asm().unwrap().clone();
+    powdr_riscv_executor::execute_ast(
+        &analyzed,
+        Default::default(),
+        pipeline.data_callback().unwrap(),
+        &[],
+        usize::MAX,
+        powdr_riscv_executor::ExecMode::Fast,
+        &Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+
+    let key_stream = Journal::take_from_pipeline(&pipeline)
+        .decode_all::<Vec<u8>>()
+        .unwrap()
+        .concat();
+    let expected = [
+        "76b8e0ada0f13d90405d6ae55386bd28bdd219b8a08ded1aa836efcc8b770dc7",
+        "da41597c5157488d7724e03fb8d84a376a43b8f41518a11cc387b669b2ee6586",
+        "9f07e7be5551387a98ba977c732d080dcb0f29a048e3656912c6533e32ee7aed",
+        "29b721769ce64e43d57133b074d839d531ed1f28510afb45ace10a1f4b794d6f",
+    ]
+    .concat();
+    let expected = (0..expected.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&expected[i..i + 2], 16).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(key_stream, expected[..120]);
+}
+
+#[test]
+#[ignore = "Too slow"]
+fn commit_inputs() {
+    let case = "commit_inputs";
+    let temp_dir = Temp::new_dir().unwrap();
+    let executable = powdr_riscv::compile_rust_crate_to_riscv(
+        &format!("tests/riscv_data/{case}/Cargo.toml"),
+        &temp_dir,
+        None,
+    );
+    let powdr_asm =
+        powdr_riscv::elf::translate(&executable, CompilerOptions::new_gl().with_poseidon())
+            .unwrap();
+
+    let values = vec![1u32, 2, 3];
+    let mut pipeline = Pipeline::<GoldilocksField>::default()
+        .from_asm_string(powdr_asm, Some(PathBuf::from(case)))
+        .add_data(1, &values)
+        .add_data(2, &10u32)
+        .with_prover_inputs(vec![5.into()]);
+
+    pipeline.compute_witness().unwrap();
+
+    let journal = Journal::take_from_pipeline(&pipeline);
+    assert_eq!(journal.decode::<u32>().unwrap(), 65);
+    let publics = pipeline.publics().unwrap();
+    journal.verify(&publics).unwrap();
+
+    let inputs = [
+        (1, input_digest(&values)),
+        (2, input_digest(&10u32)),
+        (0, words_digest(&[5])),
+    ];
+    assert_eq!(committed_inputs(&publics).unwrap(), inputs);
+    verify_inputs(&publics, &inputs).unwrap();
+
+    // The proof does not verify for different inputs.
+    let other_inputs = [(1, input_digest(&vec![1u32, 2, 4])), inputs[1], inputs[2]];
+    assert!(verify_inputs(&publics, &other_inputs).is_err());
+    // Nor if an input was expected on a channel the guest did not commit.
+    let extra_inputs = [inputs[0], inputs[1], inputs[2], (3, input_digest(&1u32))];
+    assert!(verify_inputs(&publics, &extra_inputs).is_err());
+}
+
 #[test]
 #[ignore = "Too slow"]
 fn output_syscall() {
@@ -480,7 +1023,7 @@ fn output_syscall_with_options<T: FieldElement>(options: CompilerOptions) {
         &temp_dir,
         None,
     );
-    let powdr_asm = powdr_riscv::elf::translate(&executable, options);
+    let powdr_asm = powdr_riscv::elf::translate(&executable, options).unwrap();
 
     let inputs = vec![1u32, 2, 3].into_iter().map(T::from).collect();
     let mut pipeline = Pipeline::<T>::default()
@@ -569,12 +1112,14 @@ fn verify_riscv_crate_impl<T: FieldElement, S: serde::Serialize + Send + Sync +
     );
 
     log::info!("Verifying {case}");
-    let from_elf = powdr_riscv::elf::translate(&executable, options);
+    let custom_instructions = options.libs.custom_instructions();
+    let from_elf = powdr_riscv::elf::translate(&executable, options).unwrap();
     verify_riscv_asm_string(
         &format!("{case}_from_elf.asm"),
         &from_elf,
         &inputs,
         data.as_deref(),
+        &custom_instructions,
     );
 }
 
@@ -590,7 +1135,7 @@ fn profiler_sanity_check() {
     );
 
     let options = CompilerOptions::new(KnownField::GoldilocksField, RuntimeLibs::new(), false);
-    let asm = powdr_riscv::elf::translate(&executable, options);
+    let asm = powdr_riscv::elf::translate(&executable, options).unwrap();
 
     let temp_dir = mktemp::Temp::new_dir().unwrap().release();
     let file_name = format!("{case}.asm");
@@ -611,8 +1156,10 @@ fn profiler_sanity_check() {
         &[],
         usize::MAX,
         powdr_riscv_executor::ExecMode::Fast,
+        &Default::default(),
         Some(profiler_opt),
-    );
+    )
+    .unwrap();
 
     // check files were created in temp dir, and that they are not empty
     let mut svg_path = temp_dir.to_path_buf();
@@ -641,7 +1188,7 @@ fn exported_csv_as_external_witness() {
 
     // compile
     let options = CompilerOptions::new(KnownField::GoldilocksField, RuntimeLibs::new(), false);
-    let asm = powdr_riscv::elf::translate(&executable, options);
+    let asm = powdr_riscv::elf::translate(&executable, options).unwrap();
 
     // export witness
     let temp_dir = mktemp::Temp::new_dir().unwrap().release();
@@ -0,0 +1,40 @@
+#![no_main]
+#![no_std]
+
+use powdr_riscv_runtime::io::read_u32;
+
+/// Reads a and b, computes operations that LLVM implements with the "Zba" and
+/// "Zbb" instructions, and checks the results against the expected values
+/// computed by the host.
+#[no_mangle]
+pub fn main() {
+    let a = read_u32(0);
+    let b = read_u32(1);
+
+    let results = [
+        a & !b,
+        a | !b,
+        !(a ^ b),
+        a.leading_zeros(),
+        a.trailing_zeros(),
+        a.count_ones(),
+        (a as i32).min(b as i32) as u32,
+        (a as i32).max(b as i32) as u32,
+        a.min(b),
+        a.max(b),
+        a.rotate_left(b),
+        a.rotate_right(b),
+        a.rotate_right(7),
+        a.swap_bytes(),
+        a as i8 as u32,
+        a as i16 as u32,
+        a as u16 as u32,
+        a.wrapping_add(b << 1),
+        a.wrapping_add(b << 2),
+        a.wrapping_add(b << 3),
+    ];
+
+    for (i, result) in results.into_iter().enumerate() {
+        assert_eq!(result, read_u32(2 + i as u32), "operation {i}");
+    }
+}
@@ -0,0 +1,14 @@
+#![no_main]
+#![no_std]
+
+use powdr_riscv_runtime::commit::commit;
+use powdr_riscv_runtime::io::read_u32;
+
+#[no_mangle]
+pub fn main() {
+    let a = read_u32(0);
+    let b = read_u32(1);
+
+    commit(a + b);
+    commit(a * b);
+}
@@ -0,0 +1,21 @@
+#![no_main]
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use powdr_riscv_runtime::commit::commit;
+use powdr_riscv_runtime::io::{read, read_u32};
+
+#[no_mangle]
+pub fn main() {
+    let values: Vec<u32> = read(1);
+    let factor: u32 = read(2);
+    // Reading the same channel again is fine, as long as the data is the same.
+    let again: Vec<u32> = read(1);
+    assert_eq!(values, again);
+    // Raw words are committed as well.
+    let offset = read_u32(0);
+
+    commit(values.iter().sum::<u32>() * factor + offset);
+}
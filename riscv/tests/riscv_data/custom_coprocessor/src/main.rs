@@ -0,0 +1,15 @@
+#![no_main]
+#![no_std]
+
+use powdr_riscv_runtime::custom::syscall;
+use powdr_riscv_runtime::io::read_u32;
+
+/// The syscall number of the coprocessor enabled by the test.
+const DOUBLE: u32 = 100;
+
+#[no_mangle]
+pub fn main() {
+    let x = read_u32(0);
+    let doubled = unsafe { syscall(DOUBLE, [x, 0, 0, 0]) };
+    assert_eq!(doubled, 2 * x);
+}
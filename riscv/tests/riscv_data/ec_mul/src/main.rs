@@ -0,0 +1,55 @@
+#![no_main]
+#![no_std]
+
+use hex_literal::hex;
+
+use powdr_riscv_runtime::ec::{add_u8_be, double_mul_u8_be, double_u8_be};
+use powdr_riscv_runtime::io::read_u32;
+
+/// Scalar multiplication with a double-and-add loop over the ec_add and
+/// ec_double syscalls, as guests had to do before `ec::double_mul_u8_be`.
+fn mul_loop(k: [u8; 32], x: [u8; 32], y: [u8; 32]) -> Option<([u8; 32], [u8; 32])> {
+    let mut result = None;
+    for byte in k {
+        for bit in (0..8).rev() {
+            result = result.map(|(rx, ry)| double_u8_be(rx, ry));
+            if (byte >> bit) & 1 == 1 {
+                result = match result {
+                    None => Some((x, y)),
+                    Some((rx, ry)) => Some(add_u8_be(rx, ry, x, y)),
+                };
+            }
+        }
+    }
+    result
+}
+
+/// Computes `k1 * G + k2 * Q`, as in ECDSA verification.
+/// If the first input is 0, this uses a loop in the guest, otherwise it uses
+/// the batched scalar multiplication of the runtime.
+#[no_mangle]
+pub fn main() {
+    let k1 = hex!("d23f0824128b2f330c5c7fd0a6a3a4506513270e269e0d37f2a74de452e6b438");
+    let k2 = hex!("36f675cc81e74ef5e8e25d940ed904759531985d5d9dc9f81818e811892f902b");
+    let g = (
+        hex!("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"),
+        hex!("483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8"),
+    );
+    let q = (
+        hex!("38eeacfd883362760d2c42968b09de1aa694a556df80d241a23f97b7dccd5325"),
+        hex!("c00bae04d243a977f0da07d69066e65d776217dee98b877c493800938a808742"),
+    );
+    let expected = (
+        hex!("a5fbf82bf63b2af64f36d00e5789b7205228f9a58172256a9c5bca23403c3c9a"),
+        hex!("314605e5f5d215d7459f8f973eefd202e195a23ba5ab0898705f38b5be11f980"),
+    );
+
+    let result = if read_u32(0) == 0 {
+        let (x1, y1) = mul_loop(k1, g.0, g.1).unwrap();
+        let (x2, y2) = mul_loop(k2, q.0, q.1).unwrap();
+        add_u8_be(x1, y1, x2, y2)
+    } else {
+        double_mul_u8_be(k1, g, k2, q).unwrap()
+    };
+    assert_eq!(result, expected);
+}
@@ -0,0 +1,67 @@
+#![no_main]
+#![no_std]
+
+use hex_literal::hex;
+
+use powdr_riscv_runtime::ec::mul_u8_be;
+use powdr_riscv_runtime::{ecdsa, schnorr};
+
+#[no_mangle]
+pub fn main() {
+    // Scalar multiplication: k * (x, y) = (x2, y2)
+    assert_eq!(
+        mul_u8_be(
+            hex!("8d88348a7eed8d14f06d3fef701966a0c381e88f38c0c8fd8712b8bc076f3787"),
+            hex!("d7da18e28d6463ea9b7e93402aec0e122b76a669e04ad12f5f4b913f772751ef"),
+            hex!("30b2258b85b729c8df9dda0cd1a5aa20ead6e416644fc6e75fbc327be89ead17")
+        ),
+        Some((
+            hex!("5b72406f163905c5165ec7016e9c0556e5085ae4eee076a04dfd12c06eb471a5"),
+            hex!("08809499da3e6a6e857573c878eed31204cb31b70e7aec5dadfffe73959a09fc")
+        ))
+    );
+    assert_eq!(
+        mul_u8_be(
+            hex!("f79b17aeefba91fc803468b6b610a9f7f9270f4eb8b333a8e5446dd4552b82f6"),
+            hex!("6e3367a1923f68df69820c8cd4a51946effd816c67e9ed98d162731c8a2f51e6"),
+            hex!("bf0f21ed1cfc9852d964e60dba9c65f28a74bcdce7dc9c8d124d676624a08805")
+        ),
+        Some((
+            hex!("70c9c79060e283bc6d8690447a0448036fd0da2cf93a48e5f7e25cf5b6cd033a"),
+            hex!("f7eb8b290c129790c772e8834dbd90e753bb4e2b4698e2a63a68e2eeabff641a")
+        ))
+    );
+
+    // ECDSA: message hash, r, s, public key x and y
+    let z = hex!("35bf992dc9e9c616612e7696a6cecc1b78e510617311d8a3c2ce6f447ed4d57b");
+    let r = hex!("e051692d947920dcaa29eb6ea5b59ef088fa05eb0c430a2b3513c0f5e4ba17e3");
+    let s = hex!("469db988c9b428ac8412437582de88547149ffaaaab2c2d173df1f85b3263b02");
+    let x = hex!("d7da18e28d6463ea9b7e93402aec0e122b76a669e04ad12f5f4b913f772751ef");
+    let y = hex!("30b2258b85b729c8df9dda0cd1a5aa20ead6e416644fc6e75fbc327be89ead17");
+    assert!(ecdsa::verify_u8_be(z, r, s, x, y));
+    // Wrong message hash
+    let mut z = z;
+    z[0] ^= 1;
+    assert!(!ecdsa::verify_u8_be(z, r, s, x, y));
+    let z = hex!("a46d6753ec148cb48e73ca47ea90a8f0d66b829e6a8ac4ba05805975ed2f89d9");
+    let r = hex!("a70de675414fcf85afec4eee476bf5671320ace963b7236d95a8d6b9dcb1401e");
+    let s = hex!("dc24c0d27e2a514ba39d91bba0b3d631c3abafb695e1ee11d324a0081164740c");
+    let x = hex!("6e3367a1923f68df69820c8cd4a51946effd816c67e9ed98d162731c8a2f51e6");
+    let y = hex!("bf0f21ed1cfc9852d964e60dba9c65f28a74bcdce7dc9c8d124d676624a08805");
+    assert!(ecdsa::verify_u8_be(z, r, s, x, y));
+
+    // BIP-340 Schnorr: public key, message, signature
+    assert!(schnorr::verify(
+        hex!("F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9"),
+        [0; 32],
+        hex!("E907831F80848D1069A5371B402410364BDF1C5F8307B0084C55F1CE2DCA821525F66A4A85EA8B71E482A74F382D2CE5EBEEE8FDB2172F477DF4900D310536C0")
+    ));
+    let public_key = hex!("ec9a0aa69b7721104b0ebd5673bff3ba463370fd1f88c4adcb06e534347e3a22");
+    let message = hex!("64f33bdcf8e85f8c07e56f3ba35a8a67298a868322f9045811e7d4f31834f3d8");
+    let signature = hex!("63e11f83b73f5fa54bb5f858e98a604e0ea5b8447bcceebec1315806fb8ea0ecabc06890b423e27a97685d60bd285c1aaa5664432b2e0e71d9f705867bddd98f");
+    assert!(schnorr::verify(public_key, message, signature));
+    // Wrong message
+    let mut message = message;
+    message[0] ^= 1;
+    assert!(!schnorr::verify(public_key, message, signature));
+}
@@ -0,0 +1,17 @@
+#![no_main]
+#![no_std]
+
+use powdr_riscv_runtime::commit::commit;
+use powdr_riscv_runtime::exit;
+use powdr_riscv_runtime::io::read_u32;
+
+#[no_mangle]
+pub fn main() {
+    let a = read_u32(0);
+    commit(a);
+
+    if a == 0 {
+        exit(3);
+    }
+    assert!(a < 10, "value too large: {a}");
+}
@@ -0,0 +1,82 @@
+#![no_main]
+#![no_std]
+
+extern crate alloc;
+use alloc::vec::Vec;
+
+use powdr_riscv_runtime::fields::{bls12_381, bn254, Fp, Fp2};
+use powdr_riscv_runtime::io::read;
+
+/// The operations of one of the fields, in the order of the expected results.
+struct Ops {
+    mul: fn(Fp, Fp) -> Fp,
+    add: fn(Fp, Fp) -> Fp,
+    sub: fn(Fp, Fp) -> Fp,
+    neg: fn(Fp) -> Fp,
+    inv: fn(Fp) -> Fp,
+    fp2_mul: fn(Fp2, Fp2) -> Fp2,
+    fp2_square: fn(Fp2) -> Fp2,
+    fp2_inv: fn(Fp2) -> Fp2,
+    fp2_add: fn(Fp2, Fp2) -> Fp2,
+    fp2_sub: fn(Fp2, Fp2) -> Fp2,
+}
+
+/// Reads the inputs a = (a0, a1) and b = (b0, b1), followed by the expected
+/// results, computed by the host, and checks them.
+fn check(fd: u32, ops: Ops) {
+    let words: Vec<u32> = read(fd);
+    let elements: Vec<Fp> = words
+        .chunks_exact(12)
+        .map(|c| c.try_into().unwrap())
+        .collect();
+    let [a0, a1, b0, b1, expected @ ..] = &elements[..] else {
+        panic!("unexpected number of field elements");
+    };
+    let (a, b) = ((*a0, *a1), (*b0, *b1));
+    let fp2 = |i: usize| (expected[i], expected[i + 1]);
+
+    assert_eq!((ops.mul)(*a0, *b0), expected[0]);
+    assert_eq!((ops.add)(*a0, *b0), expected[1]);
+    assert_eq!((ops.sub)(*a0, *b0), expected[2]);
+    assert_eq!((ops.neg)(*a0), expected[3]);
+    assert_eq!((ops.inv)(*a0), expected[4]);
+    assert_eq!((ops.fp2_mul)(a, b), fp2(5));
+    assert_eq!((ops.fp2_square)(a), fp2(7));
+    assert_eq!((ops.fp2_inv)(a), fp2(9));
+    assert_eq!((ops.fp2_add)(a, b), fp2(11));
+    assert_eq!((ops.fp2_sub)(a, b), fp2(13));
+}
+
+#[no_mangle]
+pub fn main() {
+    check(
+        1,
+        Ops {
+            mul: bn254::mul,
+            add: bn254::add,
+            sub: bn254::sub,
+            neg: bn254::neg,
+            inv: bn254::inv,
+            fp2_mul: bn254::fp2_mul,
+            fp2_square: bn254::fp2_square,
+            fp2_inv: bn254::fp2_inv,
+            fp2_add: bn254::fp2_add,
+            fp2_sub: bn254::fp2_sub,
+        },
+    );
+    check(
+        2,
+        Ops {
+            mul: bls12_381::mul,
+            add: bls12_381::add,
+            sub: bls12_381::sub,
+            neg: bls12_381::neg,
+            inv: bls12_381::inv,
+            fp2_mul: bls12_381::fp2_mul,
+            fp2_square: bls12_381::fp2_square,
+            fp2_inv: bls12_381::fp2_inv,
+            fp2_add: bls12_381::fp2_add,
+            fp2_sub: bls12_381::fp2_sub,
+        },
+    );
+}
@@ -0,0 +1,16 @@
+#![no_main]
+#![no_std]
+
+use powdr_riscv_runtime::commit::commit;
+
+#[no_mangle]
+pub fn main() {
+    let mut first = [0u8; 20];
+    let mut second = [0u8; 100];
+    getrandom::getrandom(&mut first).unwrap();
+    getrandom::getrandom(&mut second).unwrap();
+    assert_ne!(first[..], second[..20]);
+
+    commit(first);
+    commit(&second[..]);
+}
@@ -0,0 +1,25 @@
+#![no_main]
+#![no_std]
+
+use hex_literal::hex;
+use powdr_riscv_runtime::hash::sha256;
+
+#[no_mangle]
+fn main() {
+    assert_eq!(
+        sha256(b""),
+        hex!("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855")
+    );
+    assert_eq!(
+        sha256(b"abc"),
+        hex!("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+    );
+    assert_eq!(
+        sha256(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+        hex!("248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1")
+    );
+    assert_eq!(
+        sha256(&[b'a'; 1000]),
+        hex!("41edece42d63e8d9bf515a9ba6932e1c20cbc9f5a5d134645adb5db1b9737ea3")
+    );
+}
@@ -1,14 +1,14 @@
 /// A function that takes a field element or integer and converts
 /// it to a (non-negative) integer.
 /// The actual implementation is replaced by a built-in function.
-let int = [];
+pub let int = [];
 
 /// A function that takes a field element or integer and converts
 /// it to a field element.
 /// Panics if the input is negative or larger or equal to the field modulus.
 /// The actual implementation is replaced by a built-in function.
-let fe = [];
+pub let fe = [];
 
 /// A function that takes an integer or a field element and converts it to an expression.
 /// The actual implementation is replaced by a built-in function.
-let expr = [];
\ No newline at end of file
+pub let expr = [];
\ No newline at end of file
@@ -2,6 +2,6 @@
 /// when evaluated.
 /// It returns an empty array so that it can be used at constraint level.
 /// This symbol is not an empty array, the actual semantics are overridden.
-let<T: ToString> print: T -> () = [];
+pub let<T: ToString> print: T -> () = [];
 
-let<T: ToString> println: T -> () = |msg| { let _ = print(msg); print("\n") };
+pub let<T: ToString> println: T -> () = |msg| { let _ = print(msg); print("\n") };
@@ -1,14 +1,14 @@
 /// A function that returns the current field modulus as an integer.
 /// The actual implementation is replaced by a built-in function.
-let modulus: -> int = [];
+pub let modulus: -> int = [];
 
-let BN254_PRIME: int = 0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001;
-let GOLDILOCKS_PRIME: int = 0xffffffff00000001;
-let KOALABEAR_PRIME: int = 2**31 - 2**24 + 1;
-let BABYBEAR_PRIME: int = 0x78000001;
+pub let BN254_PRIME: int = 0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001;
+pub let GOLDILOCKS_PRIME: int = 0xffffffff00000001;
+pub let KOALABEAR_PRIME: int = 2**31 - 2**24 + 1;
+pub let BABYBEAR_PRIME: int = 0x78000001;
 
 /// All known fields
-enum KnownField {
+pub enum KnownField {
     BN254,
     Goldilocks,
     KoalaBear,
@@ -17,7 +17,7 @@ enum KnownField {
 
 /// Checks whether the function is called in a context where it is operating on
 /// any of the known fields.
-let known_field: -> Option<KnownField> = || if modulus() == BABYBEAR_PRIME {
+pub let known_field: -> Option<KnownField> = || if modulus() == BABYBEAR_PRIME {
     Option::Some(KnownField::BabyBear)
 } else {
     if modulus() == KOALABEAR_PRIME {
@@ -35,7 +35,7 @@ let known_field: -> Option<KnownField> = || if modulus() == BABYBEAR_PRIME {
     }
 };
 
-let require_known_field: KnownField, (-> string) -> () = |f, err| match (f, known_field()) {
+pub let require_known_field: KnownField, (-> string) -> () = |f, err| match (f, known_field()) {
     (KnownField::BN254, Option::Some(KnownField::BN254)) => (),
     (KnownField::Goldilocks, Option::Some(KnownField::Goldilocks)) => (),
     (KnownField::KoalaBear, Option::Some(KnownField::KoalaBear)) => (),
@@ -1,8 +1,11 @@
 use std::convert::int;
 use std::utils::cross_product;
 
-// Binary for single bytes using an exhaustive table
-machine ByteBinary with
+// Binary for single bytes using an exhaustive table.
+// Operations 0 to 2 are the bitwise and, or and xor of A and B. Operation 3
+// applies a function to A that is selected by B: 0 counts the set bits,
+// 1 reverses the order of the bits and 2 sets all bits if any bit is set.
+pub machine ByteBinary with
     latch: latch,
     operation_id: operation_id,
     degree: 262144
@@ -12,9 +15,9 @@ machine ByteBinary with
     col fixed latch = [1]*;
     col fixed operation_id = [0]*;
 
-    let bit_counts = [256, 256, 3];
+    let bit_counts = [256, 256, 4];
     let min_degree = std::array::product(bit_counts);
-    std::check::assert(std::prover::min_degree() >= std::array::product(bit_counts), || "The binary machine needs at least 196608 rows to work.");
+    std::check::assert(std::prover::min_degree() >= std::array::product(bit_counts), || "The binary machine needs at least 262144 rows to work.");
     // TODO would be nice with destructuring assignment for arrays.
     let inputs: (int -> int)[] = cross_product(bit_counts);
     let a = inputs[0];
@@ -28,6 +31,12 @@ machine ByteBinary with
             0 => a(i) & b(i),
             1 => a(i) | b(i),
             2 => a(i) ^ b(i),
+            3 => match b(i) {
+                0 => std::utils::sum(8, |j| (a(i) >> j) & 1),
+                1 => std::utils::sum(8, |j| ((a(i) >> j) & 1) << (7 - j)),
+                2 => if a(i) == 0 { 0 } else { 0xff },
+                _ => 0,
+            },
         }
     };
 }
@@ -1,7 +1,7 @@
 use std::machines::memory::Memory;
 
 // TODO: placeholder for Rust wrapper
-machine KeccakF(mem: Memory) with
+pub machine KeccakF(mem: Memory) with
     latch: LATCH,
     call_selectors: sel,
 { 
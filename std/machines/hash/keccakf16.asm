@@ -10,7 +10,7 @@ use std::prelude::Query;
 use std::prover::eval;
 use std::prover::provide_value;
 
-machine Keccakf16 with
+pub machine Keccakf16 with
     latch: final_step,
     operation_id: operation_id,
     call_selectors: sel,
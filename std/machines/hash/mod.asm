@@ -1,6 +1,7 @@
-mod poseidon_bn254;
-mod poseidon_gl;
-mod poseidon_gl_memory;
-mod poseidon_bb;
-mod poseidon2_bb;
-mod keccakf16;
+pub mod poseidon_bn254;
+pub mod poseidon_gl;
+pub mod poseidon_gl_memory;
+pub mod poseidon_bb;
+pub mod poseidon2_bb;
+pub mod keccakf16;
+pub mod sha256;
@@ -17,7 +17,7 @@ use std::machines::split::split_bb::SplitBB;
 //
 // As it stands, it cannot be used in a Sponge construction, because we don't
 // output the entire state.
-machine Poseidon2BB(mem: Memory, split_BB: SplitBB) with
+pub machine Poseidon2BB(mem: Memory, split_BB: SplitBB) with
     latch: latch,
     operation_id: operation_id,
     // Allow this machine to be connected via a permutation
@@ -2,7 +2,7 @@ use std::array;
 use std::utils::unchanged_until;
 
 // Implements the Poseidon permutation for the BN254 curve.
-machine PoseidonBN254 with
+pub machine PoseidonBN254 with
     latch: FIRSTBLOCK,
     operation_id: operation_id,
     // Allow this machine to be connected via a permutation
@@ -2,7 +2,7 @@ use std::array;
 use std::utils::unchanged_until;
 
 // Implements the Poseidon permutation for the Goldilocks field.
-machine PoseidonGL with
+pub machine PoseidonGL with
     latch: FIRSTBLOCK,
     operation_id: operation_id,
     // Allow this machine to be connected via a permutation
@@ -21,7 +21,7 @@ use std::machines::split::split_gl::SplitGL;
 //   - 2 to read the low and high words from the memory
 //   - 2 to write the low and high words to the memory
 //   - 1 to split the current output into low and high words
-machine PoseidonGLMemory(mem: Memory, split_gl: SplitGL) with
+pub machine PoseidonGLMemory(mem: Memory, split_gl: SplitGL) with
     latch: CLK_0,
     operation_id: operation_id,
     // Allow this machine to be connected via a permutation
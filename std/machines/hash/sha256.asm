@@ -0,0 +1,323 @@
+use std::array;
+use std::utils;
+use std::utils::unchanged_until;
+use std::utils::force_bool;
+use std::check::require_field_bits;
+use std::machines::large_field::memory::Memory;
+use std::machines::range::Byte2;
+
+// Implements the SHA-256 compression function, see FIPS 180-4, section 6.2.2.
+// A compression takes one row per round and a final row computing the new state.
+// The working variables a, b, c, e, f and g and the two words of the message
+// schedule that are needed to extend it are decomposed into bits, from which
+// the bitwise functions are computed. Additions modulo 2^32 are done with
+// boolean carry columns.
+// There are two variants:
+// - Sha256 works on 32-bit words and interacts with memory directly to fetch
+//   its inputs and write its outputs. It requires a large field.
+// - Sha256_16 works on 16-bit limbs and receives its inputs and returns its
+//   outputs as operation parameters, so that it can be used in small fields.
+
+// The round constants
+let ROUND_CONSTANTS: int[] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2
+];
+
+let ROUNDS: int = 64;
+let ROWS_PER_BLOCK: int = ROUNDS + 1;
+
+// The round constant used in the given row, 0 in the final row of a block.
+let round_constant: int -> int = |row| if row % ROWS_PER_BLOCK < ROUNDS { ROUND_CONSTANTS[row % ROWS_PER_BLOCK] } else { 0 };
+
+// The bitwise functions operate on 32-bit words given as arrays of their
+// 32 bits, least significant bit first.
+
+let xor: expr, expr -> expr = |a, b| a + b - 2 * a * b;
+let xor3: expr, expr, expr -> expr = |a, b, c| xor(xor(a, b), c);
+
+// XOR of x rotated right by r1, r2 and r3, or shifted right by r3 if shift is true.
+let rotate_xor: expr[], int, int, int, bool -> expr[] = |x, r1, r2, r3, shift| array::new(32, |i|
+    if shift && i + r3 >= 32 {
+        xor(x[(i + r1) % 32], x[(i + r2) % 32])
+    } else {
+        xor3(x[(i + r1) % 32], x[(i + r2) % 32], x[(i + r3) % 32])
+    }
+);
+
+let big_sigma_0: expr[] -> expr[] = |x| rotate_xor(x, 2, 13, 22, false);
+let big_sigma_1: expr[] -> expr[] = |x| rotate_xor(x, 6, 11, 25, false);
+let small_sigma_0: expr[] -> expr[] = |x| rotate_xor(x, 7, 18, 3, true);
+let small_sigma_1: expr[] -> expr[] = |x| rotate_xor(x, 17, 19, 10, true);
+let ch: expr[], expr[], expr[] -> expr[] = |x, y, z| array::new(32, |i| x[i] * y[i] + (1 - x[i]) * z[i]);
+let maj: expr[], expr[], expr[] -> expr[] = |x, y, z| array::new(32, |i| x[i] * y[i] + x[i] * z[i] + y[i] * z[i] - 2 * x[i] * y[i] * z[i]);
+
+// The value of `len` bits starting at bit `start`, least significant bit first.
+let bits_to_value: expr[], int, int -> expr = |bits, start, len| array::fold(array::reverse(array::sub_array(bits, start, len)), 0, |acc, e| acc * 2 + e);
+let word: expr[] -> expr = |bits| bits_to_value(bits, 0, 32);
+let limbs: expr[] -> expr[] = |bits| [bits_to_value(bits, 0, 16), bits_to_value(bits, 16, 16)];
+
+let next: expr[] -> expr[] = |arr| array::map(arr, |x| x');
+
+pub machine Sha256(mem: Memory, byte2: Byte2) with
+    latch: CLK_0,
+    operation_id: operation_id,
+    // Allow this machine to be connected via a permutation
+    call_selectors: sel,
+{
+    require_field_bits(35, || "The field modulus should be at least 2^35 to work in the sha256 machine.");
+
+    // Compresses the message block of 16 32-bit words at block_addr into the
+    // state of 8 32-bit words at state_addr, which is overwritten with the result.
+    // Message words are expected to be already converted from big-endian bytes.
+    // The state and the message block must not overlap.
+    // Reads happen at the provided time step; writes happen at the next time step.
+    operation sha256<0> state_addr, block_addr, time_step ->;
+
+    let operation_id;
+
+    // One-hot encoding of the row number (for the first 16 rows)
+    let CLK: col[16] = array::new(16, |i| |row| if row % ROWS_PER_BLOCK == i { 1 } else { 0 });
+    let CLK_0 = CLK[0];
+    let ROW: col = |row| row % ROWS_PER_BLOCK;
+    let LASTBLOCK: col = |row| if row % ROWS_PER_BLOCK == ROUNDS { 1 } else { 0 };
+    // Like LASTBLOCK, but also 1 in the last row of the table
+    col fixed is_last = [0]* + [1];
+    let LAST = LASTBLOCK + is_last;
+    let K: col = round_constant;
+
+    // ------------- Begin memory read / write ---------------
+
+    // Get an intermediate column that indicates that we're in an
+    // actual block, not a default block. Its value is constant
+    // within the block.
+    let used = array::sum(sel);
+    array::map(sel, |s| unchanged_until(s, LAST));
+    force_bool(used);
+
+    // Repeat the time step and the addresses in the whole block
+    let time_step;
+    let state_addr;
+    let block_addr;
+    unchanged_until(time_step, LAST);
+    unchanged_until(state_addr, LAST);
+    unchanged_until(block_addr, LAST);
+
+    // Repeat the state before and after the compression in the whole block
+    let input: col[8];
+    let output: col[8];
+    array::map(input, |c| unchanged_until(c, LAST));
+    array::map(output, |c| unchanged_until(c, LAST));
+
+    // In row i < 8, read state word i and write output word i.
+    let do_state_access;
+    do_state_access = used * utils::sum(8, |i| CLK[i]);
+    let state_word;
+    link if do_state_access ~> state_word = mem.mload(state_addr + 4 * ROW, time_step);
+    do_state_access * (state_word - utils::sum(8, |i| CLK[i] * input[i])) = 0;
+
+    // The output words are split into 16-bit limbs to make sure they fit 32 bits.
+    let output_low;
+    let output_high;
+    link => byte2.check(output_low);
+    link => byte2.check(output_high);
+    output_low + 2**16 * output_high = utils::sum(8, |i| CLK[i] * output[i]);
+    link if do_state_access ~> mem.mstore(state_addr + 4 * ROW, time_step + 1, output_low + 2**16 * output_high);
+
+    // In row i < 16, read message word i, which is the current word of the message schedule.
+    let do_block_read;
+    do_block_read = used * utils::sum(16, |i| CLK[i]);
+    link if do_block_read ~> w[0] = mem.mload(block_addr + 4 * ROW, time_step);
+
+    // ------------- End memory read / write ---------------
+
+    // The working variables. b, c, f and g are the values a, b, e and f had
+    // in the previous round, but they are kept in separate columns so that
+    // their bits are accessible.
+    let a: col[32];
+    let b: col[32];
+    let c: col[32];
+    let d;
+    let e: col[32];
+    let f: col[32];
+    let g: col[32];
+    let h;
+    array::map(a + b + c + e + f + g, |x| force_bool(x));
+    let state: expr[] = [word(a), word(b), word(c), d, word(e), word(f), word(g), h];
+
+    // A window of the message schedule: In round t, w[i] is the word W_{t + i}.
+    let w: col[16];
+    // The bits of w[1] and w[14], which are needed to compute W_{t + 16}.
+    let w_1: col[32];
+    let w_14: col[32];
+    array::map(w_1 + w_14, |x| force_bool(x));
+    word(w_1) = w[1];
+    word(w_14) = w[14];
+
+    // The results of the bitwise functions, which have degree up to 3.
+    let a_sigma;
+    let a_maj;
+    let e_sigma;
+    let e_ch;
+    let w_1_sigma;
+    let w_14_sigma;
+    a_sigma = word(big_sigma_0(a));
+    a_maj = word(maj(a, b, c));
+    e_sigma = word(big_sigma_1(e));
+    e_ch = word(ch(e, f, g));
+    w_1_sigma = word(small_sigma_0(w_1));
+    w_14_sigma = word(small_sigma_1(w_14));
+
+    // In the first row, the working variables are initialized with the input state.
+    array::zip(state, input, |s, i| CLK_0 * (s - i) = 0);
+
+    // Apply a round to get the working variables of the next row.
+    // The sums can exceed 32 bits, carries are at most 6 for a, 5 for e and 3 for w.
+    let carry_a: col[3];
+    let carry_e: col[3];
+    let carry_w: col[2];
+    array::map(carry_a + carry_e + carry_w, |x| force_bool(x));
+
+    let t1 = h + e_sigma + e_ch + K + w[0];
+    let t2 = a_sigma + a_maj;
+    (1 - LAST) * (word(next(a)) + 2**32 * bits_to_value(carry_a, 0, 3) - t1 - t2) = 0;
+    array::zip(next(b), a, |x, y| (1 - LAST) * (x - y) = 0);
+    array::zip(next(c), b, |x, y| (1 - LAST) * (x - y) = 0);
+    (1 - LAST) * (d' - word(c)) = 0;
+    (1 - LAST) * (word(next(e)) + 2**32 * bits_to_value(carry_e, 0, 3) - d - t1) = 0;
+    array::zip(next(f), e, |x, y| (1 - LAST) * (x - y) = 0);
+    array::zip(next(g), f, |x, y| (1 - LAST) * (x - y) = 0);
+    (1 - LAST) * (h' - word(g)) = 0;
+
+    // Shift the message schedule window and append W_{t + 16}.
+    array::new(15, |i| (1 - LAST) * (w[i]' - w[i + 1]) = 0);
+    (1 - LAST) * (w[15]' + 2**32 * bits_to_value(carry_w, 0, 2) - w_14_sigma - w[9] - w_1_sigma - w[0]) = 0;
+
+    // In the final row, the output is the sum of the input and the working variables.
+    let output_carry: col[8];
+    array::map(output_carry, |x| force_bool(x));
+    array::new(8, |i| LASTBLOCK * (output[i] + 2**32 * output_carry[i] - input[i] - state[i]) = 0);
+}
+
+pub machine Sha256_16(byte2: Byte2) with
+    latch: CLK_0,
+    operation_id: operation_id,
+    call_selectors: sel,
+{
+    require_field_bits(19, || "The field modulus should be at least 2^19 to work in the sha256_16 machine.");
+
+    // Compresses the message block of 16 32-bit words into the state of
+    // 8 32-bit words and returns the new state.
+    // Each word is passed as two 16-bit limbs, least significant limb first.
+    // Message words are expected to be already converted from big-endian bytes.
+    operation sha256_16<0> input[0], input[1], input[2], input[3], input[4], input[5], input[6], input[7], input[8], input[9], input[10], input[11], input[12], input[13], input[14], input[15], w[0], w[1], w[2], w[3], w[4], w[5], w[6], w[7], w[8], w[9], w[10], w[11], w[12], w[13], w[14], w[15], w[16], w[17], w[18], w[19], w[20], w[21], w[22], w[23], w[24], w[25], w[26], w[27], w[28], w[29], w[30], w[31] -> output[0], output[1], output[2], output[3], output[4], output[5], output[6], output[7], output[8], output[9], output[10], output[11], output[12], output[13], output[14], output[15];
+
+    let operation_id;
+
+    // One-hot encoding of the row number (for the first 16 rows)
+    let CLK: col[16] = array::new(16, |i| |row| if row % ROWS_PER_BLOCK == i { 1 } else { 0 });
+    let CLK_0 = CLK[0];
+    let LASTBLOCK: col = |row| if row % ROWS_PER_BLOCK == ROUNDS { 1 } else { 0 };
+    // Like LASTBLOCK, but also 1 in the last row of the table
+    col fixed is_last = [0]* + [1];
+    let LAST = LASTBLOCK + is_last;
+    let K_LOW: col = |row| round_constant(row) & 0xffff;
+    let K_HIGH: col = |row| round_constant(row) >> 16;
+
+    // Repeat the state before and after the compression in the whole block
+    let input: col[16];
+    let output: col[16];
+    array::map(input, |c| unchanged_until(c, LAST));
+    array::map(output, |c| unchanged_until(c, LAST));
+
+    // In row i < 16, range check output limb i.
+    let output_limb;
+    link => byte2.check(output_limb);
+    output_limb = utils::sum(16, |i| CLK[i] * output[i]);
+
+    // The working variables, as in Sha256, but d and h consist of two limbs.
+    let a: col[32];
+    let b: col[32];
+    let c: col[32];
+    let d: col[2];
+    let e: col[32];
+    let f: col[32];
+    let g: col[32];
+    let h: col[2];
+    array::map(a + b + c + e + f + g, |x| force_bool(x));
+    let state: expr[] = limbs(a) + limbs(b) + limbs(c) + d + limbs(e) + limbs(f) + limbs(g) + h;
+
+    // A window of the message schedule: In round t, w[2 * i] and w[2 * i + 1]
+    // are the limbs of the word W_{t + i}.
+    let w: col[32];
+    // The bits of W_{t + 1} and W_{t + 14}, which are needed to compute W_{t + 16}.
+    let w_1: col[32];
+    let w_14: col[32];
+    array::map(w_1 + w_14, |x| force_bool(x));
+    array::zip(limbs(w_1), [w[2], w[3]], |x, y| x = y);
+    array::zip(limbs(w_14), [w[28], w[29]], |x, y| x = y);
+
+    // The results of the bitwise functions, which have degree up to 3.
+    let a_sigma: col[2];
+    let a_maj: col[2];
+    let e_sigma: col[2];
+    let e_ch: col[2];
+    let w_1_sigma: col[2];
+    let w_14_sigma: col[2];
+    array::zip(a_sigma, limbs(big_sigma_0(a)), |x, y| x = y);
+    array::zip(a_maj, limbs(maj(a, b, c)), |x, y| x = y);
+    array::zip(e_sigma, limbs(big_sigma_1(e)), |x, y| x = y);
+    array::zip(e_ch, limbs(ch(e, f, g)), |x, y| x = y);
+    array::zip(w_1_sigma, limbs(small_sigma_0(w_1)), |x, y| x = y);
+    array::zip(w_14_sigma, limbs(small_sigma_1(w_14)), |x, y| x = y);
+
+    // In the first row, the working variables are initialized with the input state.
+    array::zip(state, input, |s, i| CLK_0 * (s - i) = 0);
+
+    // Apply a round to get the working variables of the next row.
+    // The sums are computed limb by limb, the carry of the low limb is added
+    // to the high limb. Carries are at most 6 for a, 5 for e and 3 for w.
+    let carry_a_low: col[3];
+    let carry_a_high: col[3];
+    let carry_e_low: col[3];
+    let carry_e_high: col[3];
+    let carry_w_low: col[2];
+    let carry_w_high: col[2];
+    array::map(carry_a_low + carry_a_high + carry_e_low + carry_e_high + carry_w_low + carry_w_high, |x| force_bool(x));
+    let carry_a = [bits_to_value(carry_a_low, 0, 3), bits_to_value(carry_a_high, 0, 3)];
+    let carry_e = [bits_to_value(carry_e_low, 0, 3), bits_to_value(carry_e_high, 0, 3)];
+    let carry_w = [bits_to_value(carry_w_low, 0, 2), bits_to_value(carry_w_high, 0, 2)];
+
+    let K = [K_LOW, K_HIGH];
+    let t1: expr[] = array::new(2, |i| h[i] + e_sigma[i] + e_ch[i] + K[i] + w[i]);
+    let t2: expr[] = array::new(2, |i| a_sigma[i] + a_maj[i]);
+    let next_a = limbs(next(a));
+    let next_e = limbs(next(e));
+    (1 - LAST) * (next_a[0] + 2**16 * carry_a[0] - t1[0] - t2[0]) = 0;
+    (1 - LAST) * (next_a[1] + 2**16 * carry_a[1] - t1[1] - t2[1] - carry_a[0]) = 0;
+    array::zip(next(b), a, |x, y| (1 - LAST) * (x - y) = 0);
+    array::zip(next(c), b, |x, y| (1 - LAST) * (x - y) = 0);
+    array::zip(next(d), limbs(c), |x, y| (1 - LAST) * (x - y) = 0);
+    (1 - LAST) * (next_e[0] + 2**16 * carry_e[0] - d[0] - t1[0]) = 0;
+    (1 - LAST) * (next_e[1] + 2**16 * carry_e[1] - d[1] - t1[1] - carry_e[0]) = 0;
+    array::zip(next(f), e, |x, y| (1 - LAST) * (x - y) = 0);
+    array::zip(next(g), f, |x, y| (1 - LAST) * (x - y) = 0);
+    array::zip(next(h), limbs(g), |x, y| (1 - LAST) * (x - y) = 0);
+
+    // Shift the message schedule window and append W_{t + 16}.
+    array::new(30, |i| (1 - LAST) * (w[i]' - w[i + 2]) = 0);
+    (1 - LAST) * (w[30]' + 2**16 * carry_w[0] - w_14_sigma[0] - w[18] - w_1_sigma[0] - w[0]) = 0;
+    (1 - LAST) * (w[31]' + 2**16 * carry_w[1] - w_14_sigma[1] - w[19] - w_1_sigma[1] - w[1] - carry_w[0]) = 0;
+
+    // In the final row, the output is the sum of the input and the working variables.
+    let output_carry: col[16];
+    array::map(output_carry, |x| force_bool(x));
+    array::new(8, |i| LASTBLOCK * (output[2 * i] + 2**16 * output_carry[2 * i] - input[2 * i] - state[2 * i]) = 0);
+    array::new(8, |i| LASTBLOCK * (output[2 * i + 1] + 2**16 * output_carry[2 * i + 1] - input[2 * i + 1] - state[2 * i + 1] - output_carry[2 * i]) = 0);
+}
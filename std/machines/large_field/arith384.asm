@@ -0,0 +1,234 @@
+use std::array;
+use std::utils::unchanged_until;
+use std::utils::force_bool;
+use std::utils::sum;
+use std::check::require_field_bits;
+use std::convert::int;
+use std::convert::fe;
+use std::convert::expr;
+use std::prover::eval;
+use std::machines::large_field::memory::Memory;
+use std::machines::range::Byte2;
+
+// Implements 384-bit modular multiply-add and multiply-sub for arbitrary moduli,
+// which is enough to build arithmetic in the base fields of BN254 and BLS12-381
+// and their quadratic extensions.
+//
+// Numbers are stored in memory as 12 32-bit words in little-endian order.
+// The input at input_addr consists of the five numbers a, b, c, d and m
+// (60 words), the result r (12 words) is written to output_addr.
+// a, b, c and d are expected to be smaller than m; otherwise the prover might
+// not find a witness. The result is always in canonical form, i.e. r < m.
+//
+// The operations are verified by checking a * b +/- c * d (+ m * m) = q * m + r
+// over the integers, using 16-bit limbs, one limb position per row and a carry
+// that is propagated to the next row, similar to the Arith machine.
+// Requires the field to contain at least 49 bits.
+pub machine Arith384(mem: Memory, byte2: Byte2) with
+    latch: CLK_0,
+    operation_id: operation_id,
+    // Allow this machine to be connected via a permutation
+    call_selectors: sel,
+{
+    require_field_bits(49, || "Arith384 requires a field that fits any 49-Bit value.");
+
+    // Computes r = (a * b + c * d) % m.
+    // Reads happen at the provided time step; writes happen at the next time step.
+    operation mul_add<0> input_addr, output_addr, time_step ->;
+
+    // Computes r = (a * b - c * d) % m.
+    // Reads happen at the provided time step; writes happen at the next time step.
+    operation mul_sub<1> input_addr, output_addr, time_step ->;
+
+    // 0 for mul_add, 1 for mul_sub.
+    col witness operation_id;
+
+    // Number of 16-bit limbs of a 384-bit number
+    let LIMBS: int = 24;
+    // Number of 32-bit words of a 384-bit number
+    let WORDS: int = 12;
+    // One row for each limb position of the product
+    let ROWS_PER_BLOCK: int = 2 * LIMBS;
+
+    // One-hot encoding of the row number
+    let CLK: col[ROWS_PER_BLOCK] = array::new(ROWS_PER_BLOCK, |i| |row| if row % ROWS_PER_BLOCK == i { 1 } else { 0 });
+    let CLK_0 = CLK[0];
+    let ROW: col = |row| row % ROWS_PER_BLOCK;
+    let LASTBLOCK = CLK[ROWS_PER_BLOCK - 1];
+    // Like LASTBLOCK, but also 1 in the last row of the table
+    col fixed is_last = [0]* + [1];
+    let LAST = LASTBLOCK + is_last;
+
+    pol commit a[LIMBS], b[LIMBS], c[LIMBS], d[LIMBS], m[LIMBS], r[LIMBS];
+    // The quotient has an extra limb, because a * b + c * d + m * m can be up to 3 * m * m.
+    pol commit q[LIMBS + 1];
+
+    /****
+    *
+    * Prover hints
+    *
+    *****/
+
+    let limbs_to_int: expr[] -> int = query |limbs| array::sum(array::map_enumerated(limbs, |i, limb| int(eval(limb)) << (i * 16)));
+
+    let provide_values = query |column_arr, row, value| {
+        let _ = array::map_enumerated(column_arr, |j, column| std::prover::provide_value(column, row, fe((value >> (j * 16)) & 0xffff)));
+    };
+
+    query |i| match (std::prover::try_eval(d[LIMBS - 1]), std::prover::try_eval(m[LIMBS - 1]), std::prover::try_eval(r[0])) {
+        (Option::Some(_), Option::Some(_), Option::None) => {
+            let m_val = limbs_to_int(m);
+            let product = limbs_to_int(a) * limbs_to_int(b);
+            let total = if int(eval(operation_id)) == 0 {
+                product + limbs_to_int(c) * limbs_to_int(d)
+            } else {
+                product - limbs_to_int(c) * limbs_to_int(d) + m_val * m_val
+            };
+            provide_values(q, i, total / m_val);
+            provide_values(r, i, total % m_val);
+        },
+        _ => {}
+    };
+
+    /****
+    *
+    * Block structure
+    *
+    *****/
+
+    // Get an intermediate column that indicates that we're in an
+    // actual block, not a default block. Its value is constant
+    // within the block.
+    let used = array::sum(sel);
+    array::map(sel, |s| unchanged_until(s, LAST));
+    force_bool(used);
+
+    force_bool(operation_id);
+    unchanged_until(operation_id, LAST);
+
+    // Repeat the time step, the addresses and the operands in the whole block
+    let time_step;
+    let input_addr;
+    let output_addr;
+    unchanged_until(time_step, LAST);
+    unchanged_until(input_addr, LAST);
+    unchanged_until(output_addr, LAST);
+
+    let fixed_inside_block = |e| unchanged_until(e, LAST);
+    array::map(a, fixed_inside_block);
+    array::map(b, fixed_inside_block);
+    array::map(c, fixed_inside_block);
+    array::map(d, fixed_inside_block);
+    array::map(m, fixed_inside_block);
+    array::map(r, fixed_inside_block);
+    array::map(q, fixed_inside_block);
+
+    /****
+    *
+    * Memory read / write
+    *
+    *****/
+
+    // The 32-bit word i of a number given by its limbs
+    let word = |x, i| x[2 * i] + 2**16 * x[2 * i + 1];
+
+    // In row i, read word i of a, b, c and d (in that order).
+    let in_word;
+    link if used ~> in_word = mem.mload(input_addr + 4 * ROW, time_step);
+    in_word = sum(WORDS, |i| CLK[i] * word(a, i) + CLK[WORDS + i] * word(b, i) + CLK[2 * WORDS + i] * word(c, i) + CLK[3 * WORDS + i] * word(d, i));
+
+    // In row i < 12, also read word i of m and write word i of r.
+    let do_m_access;
+    do_m_access = used * sum(WORDS, |i| CLK[i]);
+    let m_word;
+    link if do_m_access ~> m_word = mem.mload(input_addr + expr(16 * WORDS) + 4 * ROW, time_step);
+    m_word = sum(WORDS, |i| CLK[i] * word(m, i));
+    let r_word;
+    r_word = sum(WORDS, |i| CLK[i] * word(r, i));
+    link if do_m_access ~> mem.mstore(output_addr + 4 * ROW, time_step + 1, r_word);
+
+    /****
+    *
+    * Range checks
+    *
+    *****/
+
+    link => byte2.check(sum(LIMBS, |i| a[i] * CLK[i]) + sum(LIMBS, |i| b[i] * CLK[LIMBS + i]));
+    link => byte2.check(sum(LIMBS, |i| c[i] * CLK[i]) + sum(LIMBS, |i| d[i] * CLK[LIMBS + i]));
+    link => byte2.check(sum(LIMBS, |i| m[i] * CLK[i]) + sum(LIMBS, |i| r[i] * CLK[LIMBS + i]));
+    link => byte2.check(sum(LIMBS + 1, |i| q[i] * CLK[i]));
+
+    /****
+    *
+    * r < m, checked by computing m - r - 1 limb by limb in the first 24 rows
+    *
+    *****/
+
+    let diff;
+    let borrow;
+    link => byte2.check(diff);
+    force_bool(borrow);
+    borrow * CLK[0] = 0;
+    borrow * CLK[LIMBS] = 0;
+    let m_limb = sum(LIMBS, |i| CLK[i] * m[i]);
+    let r_limb = sum(LIMBS, |i| CLK[i] * r[i]);
+    let do_diff;
+    do_diff = used * sum(LIMBS, |i| CLK[i]);
+    do_diff * (m_limb - r_limb - CLK[0] - borrow + 2**16 * borrow' - diff) = 0;
+
+    /****
+    *
+    * EQ: a * b + s * c * d + operation_id * m * m - q * m - r = 0,
+    * where s = 1 for mul_add and s = -1 for mul_sub.
+    *
+    *****/
+
+    /// returns a(0) * b(0) + ... + a(n - 1) * b(n - 1)
+    let dot_prod = |n, a, b| sum(n, |i| a(i) * b(i));
+    /// returns |n| a(0) * b(n) + ... + a(n) * b(0)
+    let product = |a, b| |n| dot_prod(n + 1, a, |i| b(n - i));
+    /// Converts array to function, extended by zeros.
+    let array_as_fun: expr[] -> (int -> expr) = |arr| |i| if 0 <= i && i < array::len(arr) {
+        arr[i]
+    } else {
+        0
+    };
+
+    let af = array_as_fun(a);
+    let bf = array_as_fun(b);
+    let cf = array_as_fun(c);
+    let df = array_as_fun(d);
+    let mf = array_as_fun(m);
+    let rf = array_as_fun(r);
+    let qf = array_as_fun(q);
+
+    // Defined for arguments from 0 to 47 (inclusive)
+    let eq = |nr| product(af, bf)(nr) - product(qf, mf)(nr) - rf(nr);
+
+    // The terms depending on the operation are summed up in witness columns,
+    // so that multiplying them with the operation ID keeps the degree at 3.
+    col witness cd_sum, mm_sum;
+    cd_sum = sum(ROWS_PER_BLOCK, |i| product(cf, df)(i) * CLK[i]);
+    mm_sum = sum(ROWS_PER_BLOCK, |i| product(mf, mf)(i) * CLK[i]);
+
+    /*******
+    *
+    * Carry
+    *
+    *******/
+
+    // Each limb position sums up at most 4 * 25 products of 16-bit limbs, so the
+    // carries fit in 24 bits. We allow them to be any integer in the range
+    // [-2**31, 2**31), which still can't overflow a 49-bit field.
+    pol witness carry_low, carry_high;
+    link => byte2.check(carry_low);
+    link => byte2.check(carry_high);
+
+    let carry = carry_high * 2**16 + carry_low - 2 ** 31;
+
+    carry * CLK[0] = 0;
+
+    col eq_sum = sum(ROWS_PER_BLOCK, |i| eq(i) * CLK[i]);
+
+    eq_sum + (1 - 2 * operation_id) * cd_sum + operation_id * mm_sum + carry = carry' * 2**16;
+}
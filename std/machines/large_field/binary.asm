@@ -3,7 +3,7 @@ use std::field::modulus;
 use std::check::require_field_bits;
 use std::machines::binary::ByteBinary;
 
-machine Binary(byte_binary: ByteBinary) with
+pub machine Binary(byte_binary: ByteBinary) with
     latch: latch,
     operation_id: operation_id,
     // Allow this machine to be connected via a permutation
@@ -17,6 +17,10 @@ machine Binary(byte_binary: ByteBinary) with
 
     operation xor<2> A, B -> C;
 
+    // Applies the function selected by each byte of B to the corresponding
+    // byte of A, see ByteBinary.
+    operation bytewise<3> A, B -> C;
+
     let operation_id;
     unchanged_until(operation_id, latch);
 
@@ -5,7 +5,7 @@ use std::check::require_field_bits;
 
 // A read/write memory, similar to that of Polygon:
 // https://github.com/0xPolygonHermez/zkevm-proverjs/blob/main/pil/mem.pil
-machine Memory(byte2: Byte2) with
+pub machine Memory(byte2: Byte2) with
     latch: LATCH,
     operation_id: m_is_write,
     call_selectors: selectors,
@@ -7,7 +7,7 @@ use std::check::require_field_bits;
 /// where in addition to mstore, there is an mstore_bootloader operation. It behaves
 /// just like mstore, except that the first access to each memory cell must come
 /// from the mstore_bootloader operation.
-machine MemoryWithBootloaderWrite(byte2: Byte2) with
+pub machine MemoryWithBootloaderWrite(byte2: Byte2) with
     latch: LATCH,
     operation_id: operation_id,
     call_selectors: selectors,
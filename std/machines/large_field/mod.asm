@@ -1,9 +1,10 @@
 // Machines for fields:
 // Goldilocks, Bn254
 
-mod arith;
-mod binary;
-mod memory;
-mod memory_with_bootloader_write;
-mod rotate;
-mod shift;
+pub mod arith;
+pub mod arith384;
+pub mod binary;
+pub mod memory;
+pub mod memory_with_bootloader_write;
+pub mod rotate;
+pub mod shift;
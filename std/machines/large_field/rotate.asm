@@ -5,7 +5,7 @@ use std::check::require_field_bits;
 
 /// Rotate on single byte input using an exhaustive table, returning 32-bit value as result.
 /// We can rotate by at most 31 bits
-machine ByteRotate with
+pub machine ByteRotate with
     latch: latch,
     operation_id: operation_id,
     degree: 65536
@@ -37,7 +37,7 @@ machine ByteRotate with
     };
 }
 
-machine Rotate(byte_rotate: ByteRotate) with
+pub machine Rotate(byte_rotate: ByteRotate) with
     latch: latch,
     operation_id: operation_id,
     // Allow this machine to be connected via a permutation
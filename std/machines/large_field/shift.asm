@@ -7,7 +7,7 @@ use std::check::require_field_bits;
 
 // Shift for single bytes using an exhaustive table
 // TODO this way, we cannot prove anything that shifts by more than 31 bits.
-machine ByteShift with
+pub machine ByteShift with
     latch: latch,
     operation_id: operation_id,
     degree: 65536
@@ -37,7 +37,7 @@ machine ByteShift with
     };
 }
 
-machine Shift(byte_shift: ByteShift) with
+pub machine Shift(byte_shift: ByteShift) with
     latch: latch,
     operation_id: operation_id,
     // Allow this machine to be connected via a permutation
@@ -1,8 +1,8 @@
-mod small_field;
-mod large_field;
+pub mod small_field;
+pub mod large_field;
 
-mod binary;
-mod range;
-mod hash;
-mod split;
-mod write_once_memory;
\ No newline at end of file
+pub mod binary;
+pub mod range;
+pub mod hash;
+pub mod split;
+pub mod write_once_memory;
\ No newline at end of file
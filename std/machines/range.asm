@@ -1,4 +1,4 @@
-machine Byte with
+pub machine Byte with
     latch: latch,
     operation_id: operation_id,
     degree: 256
@@ -10,7 +10,7 @@ machine Byte with
     col fixed operation_id = [0]*;
 }
 
-machine Byte2 with
+pub machine Byte2 with
     latch: latch,
     operation_id: operation_id,
     degree: 65536
@@ -22,7 +22,7 @@ machine Byte2 with
     col fixed operation_id = [0]*;
 }
 
-machine Bit2 with
+pub machine Bit2 with
     latch: latch,
     operation_id: operation_id,
     degree: 4
@@ -34,7 +34,7 @@ machine Bit2 with
     col fixed operation_id = [0]*;
 }
 
-machine Bit6 with
+pub machine Bit6 with
     latch: latch,
     operation_id: operation_id,
     degree: 64
@@ -46,7 +46,7 @@ machine Bit6 with
     col fixed operation_id = [0]*;
 }
 
-machine Bit7 with
+pub machine Bit7 with
     latch: latch,
     operation_id: operation_id,
     degree: 128
@@ -58,7 +58,7 @@ machine Bit7 with
     col fixed operation_id = [0]*;
 }
 
-machine Bit12 with
+pub machine Bit12 with
     latch: latch,
     operation_id: operation_id,
     degree: 4096
@@ -4,7 +4,7 @@ use std::check::require_field_bits;
 
 // Implements 32-bit addition and subtraction using 16-bit limbs.
 // Requires the field to contain at least 17 bits.
-machine AddSub(byte2: Byte2) with
+pub machine AddSub(byte2: Byte2) with
     latch: latch,
     operation_id: operation_id,
     // Allow this machine to be connected via a permutation
@@ -17,7 +17,7 @@ use std::machines::range::Byte2;
 // Implements 32-Bit multiplication and division.
 // Inspired by Polygon's 256-Bit Arith machine: https://github.com/0xPolygonHermez/zkevm-proverjs/blob/main/pil/arith.pil
 // Requires the field to contain at least 18 bits.
-machine Arith(byte: Byte, byte2: Byte2) with
+pub machine Arith(byte: Byte, byte2: Byte2) with
     latch: CLK8_7,
     operation_id: is_division,
     // Allow this machine to be connected via a permutation
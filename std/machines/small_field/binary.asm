@@ -5,7 +5,7 @@ use std::check::require_field_bits;
 
 // Computes bitwise operations on two 32-bit numbers
 // decomposed into 4 bytes each.
-machine Binary8(byte_binary: ByteBinary) with
+pub machine Binary8(byte_binary: ByteBinary) with
     latch: latch,
     operation_id: operation_id,
     // Allow this machine to be connected via a permutation
@@ -19,6 +19,10 @@ machine Binary8(byte_binary: ByteBinary) with
 
     operation xor<2> A1, A2, A3, A4, B1, B2, B3, B4 -> C1, C2, C3, C4;
 
+    // Applies the function selected by each byte of B to the corresponding
+    // byte of A, see ByteBinary.
+    operation bytewise<3> A1, A2, A3, A4, B1, B2, B3, B4 -> C1, C2, C3, C4;
+
     let operation_id;
 
     let latch: col = std::well_known::one;
@@ -44,7 +48,7 @@ machine Binary8(byte_binary: ByteBinary) with
 
 // Computes bitwise operations on two 32-bit numbers
 // decomposed into two 16-bit limbs each.
-machine Binary(byte_binary: ByteBinary) with
+pub machine Binary(byte_binary: ByteBinary) with
     latch: latch,
     operation_id: operation_id,
     // Allow this machine to be connected via a permutation
@@ -55,6 +59,9 @@ machine Binary(byte_binary: ByteBinary) with
     operation and<0> I1, I2, I3, I4 -> O1, O2;
     operation or<1> I1, I2, I3, I4 -> O1, O2;
     operation xor<2> I1, I2, I3, I4 -> O1, O2;
+    // Applies the function selected by each byte of B to the corresponding
+    // byte of A, see ByteBinary.
+    operation bytewise<3> I1, I2, I3, I4 -> O1, O2;
 
     let operation_id;
 
@@ -6,7 +6,7 @@ use std::machines::range::Byte2;
 
 // A read/write memory, similar to that of Polygon:
 // https://github.com/0xPolygonHermez/zkevm-proverjs/blob/main/pil/mem.pil
-machine Memory(bit12: Bit12, byte2: Byte2) with
+pub machine Memory(bit12: Bit12, byte2: Byte2) with
     latch: LATCH,
     operation_id: m_is_write,
     call_selectors: selectors,
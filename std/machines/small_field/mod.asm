@@ -1,10 +1,10 @@
 // Machines for fields:
 // Baby Bear, M31
 
-mod add_sub;
-mod arith;
-mod binary;
-mod memory;
-mod pointer_arith;
-mod rotate;
-mod shift;
+pub mod add_sub;
+pub mod arith;
+pub mod binary;
+pub mod memory;
+pub mod pointer_arith;
+pub mod rotate;
+pub mod shift;
@@ -16,7 +16,7 @@
 /// caller will be able to latch-disable them in rows they aren't needed.
 ///
 /// This constr function introduces 2 new witness column and some helper constraints.
-let increment_ptr: expr, expr, expr, expr, expr -> Constr[] = constr |
+pub let increment_ptr: expr, expr, expr, expr, expr -> Constr[] = constr |
     amount,
     pre_high,
     pre_low,
@@ -5,7 +5,7 @@ use std::check::require_field_bits;
 
 /// Rotate on single byte input using an exhaustive table, returning two 16-bit values as result.
 /// We can rotate by at most 31 bits
-machine ByteRotate with
+pub machine ByteRotate with
     latch: latch,
     operation_id: operation_id,
     degree: 65536
@@ -39,7 +39,7 @@ machine ByteRotate with
     col fixed P_C1(i) { (P_C(i) >> 16) & 0xffff };
 }
 
-machine Rotate(byte_rotate: ByteRotate) with
+pub machine Rotate(byte_rotate: ByteRotate) with
     latch: latch,
     operation_id: operation_id,
     // Allow this machine to be connected via a permutation
@@ -7,7 +7,7 @@ use std::check::require_field_bits;
 
 // Shift for single bytes using an exhaustive table, returning two 16-bit values as result.
 // TODO this way, we cannot prove anything that shifts by more than 31 bits.
-machine ByteShift with
+pub machine ByteShift with
     latch: latch,
     operation_id: operation_id,
     degree: 65536
@@ -39,7 +39,7 @@ machine ByteShift with
     col fixed P_CHi(i) { (c(i) >> 16) & 0xffff };
 }
 
-machine Shift(byte_shift: ByteShift) with
+pub machine Shift(byte_shift: ByteShift) with
     latch: latch,
     operation_id: operation_id,
     // Allow this machine to be connected via a permutation
@@ -1,11 +1,12 @@
-mod split_bn254;
-mod split_gl;
-mod split_bb;
+pub mod split_bn254;
+pub mod split_gl;
+pub mod split_bb;
+pub mod split_m31;
 
 use std::utils::cross_product;
 
 // Byte comparison block machine
-machine ByteCompare with
+pub machine ByteCompare with
         latch: latch,
         operation_id: operation_id,
         degree: 65536
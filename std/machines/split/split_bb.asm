@@ -2,7 +2,7 @@ use std::prelude::Query;
 use super::ByteCompare;
 
 // Splits an arbitrary field element into two u16s, on the BabyBear field.
-machine SplitBB(byte_compare: ByteCompare) with
+pub machine SplitBB(byte_compare: ByteCompare) with
     latch: RESET,
     // Allow this machine to be connected via a permutation
     call_selectors: sel,
@@ -2,7 +2,7 @@ use std::prelude::Query;
 use super::ByteCompare;
 
 // Splits an arbitrary field element into 8 u32s (in little endian order), on the BN254 field.
-machine SplitBN254(byte_compare: ByteCompare) with
+pub machine SplitBN254(byte_compare: ByteCompare) with
     latch: RESET,
     // Allow this machine to be connected via a permutation
     call_selectors: sel,
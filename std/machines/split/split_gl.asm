@@ -2,7 +2,7 @@ use std::prelude::Query;
 use super::ByteCompare;
 
 // Splits an arbitrary field element into two u32s, on the Goldilocks field.
-machine SplitGL(byte_compare: ByteCompare) with
+pub machine SplitGL(byte_compare: ByteCompare) with
     latch: RESET,
     // Allow this machine to be connected via a permutation
     call_selectors: sel,
@@ -5,7 +5,7 @@
 // A typical use-case would be to pass the `value` column as an "external"
 // witness. This way the prover can provide some input vector and the program
 // can read the same input multiple times.
-machine WriteOnceMemory with
+pub machine WriteOnceMemory with
 	latch: LATCH
 {
 
@@ -3,7 +3,7 @@ use std::convert::int;
 
 /// Inverts `x` in the finite field with modulus `modulus`.
 /// Assumes that `modulus` is prime, but does not check it.
-let inverse = |x, modulus|
+pub let inverse = |x, modulus|
     if x <= 0 || x >= modulus {
         if x == 0 {
             std::check::panic("Tried to compute the inverse of zero.")
@@ -16,30 +16,30 @@ let inverse = |x, modulus|
     };
 
 /// Field inversion (defined on fe instead of int)
-let inv_field: fe -> fe = |x| fe(inverse(int(x), std::field::modulus()));
+pub let inv_field: fe -> fe = |x| fe(inverse(int(x), std::field::modulus()));
 
 /// Computes `x + y` modulo the modulus.
-let add = |x, y, modulus| reduce(x + y, modulus);
+pub let add = |x, y, modulus| reduce(x + y, modulus);
 
 /// Computes `x - y` modulo the modulus.
-let sub = |x, y, modulus| reduce(x - y, modulus);
+pub let sub = |x, y, modulus| reduce(x - y, modulus);
 
 /// Computes `x * y` modulo the modulus.
-let mul = |x, y, modulus| reduce(x * y, modulus);
+pub let mul = |x, y, modulus| reduce(x * y, modulus);
 
 /// Computes `x / y` modulo the modulus.
-let div = |x, y, modulus| mul(x, inverse(y, modulus), modulus);
+pub let div = |x, y, modulus| mul(x, inverse(y, modulus), modulus);
 
 /// Reduces `x` modulo `modulus`, so that it is in the range
 /// between `0` and `modulus`. Works on negative `x`.
-let reduce = |x, modulus|
+pub let reduce = |x, modulus|
     if x < 0 {
         (modulus - ((-x) % modulus)) % modulus
     } else {
         x % modulus
     };
 
-let extended_gcd = |a, b|
+pub let extended_gcd = |a, b|
     if b == 0 {
         if a == 1 {
             (1, 0)
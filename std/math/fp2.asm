@@ -31,15 +31,15 @@ use std::prover::eval;
 /// where Fp2(a0, a1) is interpreted as a0 + a1 * X.
 /// T is assumed to either be fe, expr or any other object whose algebraic operations
 /// are compatible with fe.
-enum Fp2<T> {
+pub enum Fp2<T> {
     Fp2(T, T)
 }
 
 /// Converts a base field element to an extension field element
-let<T: FromLiteral> from_base: T -> Fp2<T> = |x| Fp2::Fp2(x, 0);
+pub let<T: FromLiteral> from_base: T -> Fp2<T> = |x| Fp2::Fp2(x, 0);
 
 /// Extension field addition
-let<T: Add> add_ext: Fp2<T>, Fp2<T> -> Fp2<T> = |a, b| match (a, b) {
+pub let<T: Add> add_ext: Fp2<T>, Fp2<T> -> Fp2<T> = |a, b| match (a, b) {
     (Fp2::Fp2(a0, a1), Fp2::Fp2(b0, b1)) => Fp2::Fp2(
         a0 + b0,
         a1 + b1
@@ -47,7 +47,7 @@ let<T: Add> add_ext: Fp2<T>, Fp2<T> -> Fp2<T> = |a, b| match (a, b) {
 };
 
 /// Extension field subtraction
-let<T: Sub> sub_ext: Fp2<T>, Fp2<T> -> Fp2<T> = |a, b| match (a, b) {
+pub let<T: Sub> sub_ext: Fp2<T>, Fp2<T> -> Fp2<T> = |a, b| match (a, b) {
     (Fp2::Fp2(a0, a1), Fp2::Fp2(b0, b1)) => Fp2::Fp2(
         a0 - b0,
         a1 - b1
@@ -55,7 +55,7 @@ let<T: Sub> sub_ext: Fp2<T>, Fp2<T> -> Fp2<T> = |a, b| match (a, b) {
 };
 
 /// Extension field multiplication
-let<T: Add + FromLiteral + Mul> mul_ext: Fp2<T>, Fp2<T> -> Fp2<T> = |a, b| match (a, b) {
+pub let<T: Add + FromLiteral + Mul> mul_ext: Fp2<T>, Fp2<T> -> Fp2<T> = |a, b| match (a, b) {
     (Fp2::Fp2(a0, a1), Fp2::Fp2(b0, b1)) => Fp2::Fp2(
         // Multiplication modulo the polynomial x^2 - 11. We'll use the fact
         // that x^2 == 11 (mod x^2 - 11), so:
@@ -68,7 +68,7 @@ let<T: Add + FromLiteral + Mul> mul_ext: Fp2<T>, Fp2<T> -> Fp2<T> = |a, b| match
 /// Extension field squaring
 /// This implementation yields a shorter expression than `mul_ext(a, a)` and should be preferred
 /// when squaring expressions many times.
-let<T: Add + FromLiteral + Mul> square_ext: Fp2<T> -> Fp2<T> = |a| match (a) {
+pub let<T: Add + FromLiteral + Mul> square_ext: Fp2<T> -> Fp2<T> = |a| match (a) {
     Fp2::Fp2(a0, a1) => Fp2::Fp2(
         a0 * a0 + 11 * a1 * a1,
         2 * a1 * a0
@@ -76,7 +76,7 @@ let<T: Add + FromLiteral + Mul> square_ext: Fp2<T> -> Fp2<T> = |a| match (a) {
 };
 
 /// Computes the power operation on an extension field element.
-let<T: Add + Mul + FromLiteral> pow_ext: Fp2<T>, int -> Fp2<T> = |x, i| match i {
+pub let<T: Add + Mul + FromLiteral> pow_ext: Fp2<T>, int -> Fp2<T> = |x, i| match i {
     0 => from_base(1),
     1 => x,
     _ => {
@@ -91,27 +91,27 @@ let<T: Add + Mul + FromLiteral> pow_ext: Fp2<T>, int -> Fp2<T> = |x, i| match i
 
 
 /// Converts an Fp2<expr> into an Fp2<fe>
-let eval_ext: Fp2<expr> -> Fp2<fe> = query |a| match a {
+pub let eval_ext: Fp2<expr> -> Fp2<fe> = query |a| match a {
     Fp2::Fp2(a0, a1) => Fp2::Fp2(eval(a0), eval(a1))
 };
 
 /// Converts an Fp2<fe> into an Fp2<expr>
-let expr_ext: Fp2<fe> -> Fp2<expr> = |a| match a {
+pub let expr_ext: Fp2<fe> -> Fp2<expr> = |a| match a {
     Fp2::Fp2(a0, a1) => Fp2::Fp2(expr(a0), expr(a1))
 };
 
 /// Extension field equality
-let eq_ext: Fp2<fe>, Fp2<fe> -> bool = |a, b| match (a, b) {
+pub let eq_ext: Fp2<fe>, Fp2<fe> -> bool = |a, b| match (a, b) {
     (Fp2::Fp2(a0, a1), Fp2::Fp2(b0, b1)) => (a0 == b0) && (a1 == b1)
 };
 
 /// Returns constraints that two extension field elements are equal
-let constrain_eq_ext: Fp2<expr>, Fp2<expr> -> Constr[] = |a, b| match (a, b) {
+pub let constrain_eq_ext: Fp2<expr>, Fp2<expr> -> Constr[] = |a, b| match (a, b) {
     (Fp2::Fp2(a0, a1), Fp2::Fp2(b0, b1)) => [a0 = b0, a1 = b1]
 };
 
 /// Extension field inversion
-let inv_ext: Fp2<fe> -> Fp2<fe> = |a| match a {
+pub let inv_ext: Fp2<fe> -> Fp2<fe> = |a| match a {
     // The inverse of (a0, a1) is a point (b0, b1) such that:
     // (a0 + a1 * x) (b0 + b1 * x) = 1 (mod x^2 - 11)
     // Multiplying out and plugging in x^2 = 11 yields the following system of linear equations:
@@ -125,39 +125,39 @@ let inv_ext: Fp2<fe> -> Fp2<fe> = |a| match a {
 };
 
 /// Applies the next operator to both components of the extension field element
-let next_ext: Fp2<expr> -> Fp2<expr> = |a| match a {
+pub let next_ext: Fp2<expr> -> Fp2<expr> = |a| match a {
     Fp2::Fp2(a0, a1) => Fp2::Fp2(a0', a1')
 };
 
 /// Returns the two components of the extension field element as a tuple
-let<T> unpack_ext: Fp2<T> -> (T, T) = |a| match a {
+pub let<T> unpack_ext: Fp2<T> -> (T, T) = |a| match a {
     Fp2::Fp2(a0, a1) => (a0, a1)
 };
 
 /// Returns the two components of the extension field element as an array
-let<T> unpack_ext_array: Fp2<T> -> T[] = |a| match a {
+pub let<T> unpack_ext_array: Fp2<T> -> T[] = |a| match a {
     Fp2::Fp2(a0, a1) => [a0, a1]
 };
 
 /// Whether we need to operate on the F_{p^2} extension field (because the current field is too small).
-let needs_extension: -> bool = || required_extension_size() > 1;
+pub let needs_extension: -> bool = || required_extension_size() > 1;
 
 /// How many field elements / field extensions are recommended for the current base field.
-let required_extension_size: -> int = || match known_field() {
+pub let required_extension_size: -> int = || match known_field() {
     Option::Some(KnownField::Goldilocks) => 2,
     Option::Some(KnownField::BN254) => 1,
     None => panic("The permutation/lookup argument is not implemented for the current field!")
 };
 
 /// Matches whether the length of a given array is correct to operate on the extension field
-let is_extension = |arr| match len(arr) {
+pub let is_extension = |arr| match len(arr) {
         1 => false,
         2 => true,
         _ => panic("Expected 1 or 2 accumulator columns!")
 };
 
 /// Constructs an extension field element `a0 + a1 * X` from either `[a0, a1]` or `[a0]` (setting `a1`to zero in that case)
-let fp2_from_array = |arr| {
+pub let fp2_from_array = |arr| {
     if is_extension(arr) {
         Fp2::Fp2(arr[0], arr[1])
     } else {
@@ -166,7 +166,7 @@ let fp2_from_array = |arr| {
     }
 };
 
-mod test {
+pub mod test {
     use super::Fp2;
     use super::from_base;
     use super::add_ext;
@@ -179,7 +179,7 @@ mod test {
     use std::check::assert;
     use std::array::map;
 
-    let test_add = || {
+    pub let test_add = || {
         let test_add = |a, b, c| assert(eq_ext(add_ext(a, b), c), || "Wrong addition result");
 
         // Test adding 0
@@ -192,7 +192,7 @@ mod test {
         test_add(Fp2::Fp2(-1, -1), Fp2::Fp2(3, 4), Fp2::Fp2(2, 3))
     };
 
-    let test_sub = || {
+    pub let test_sub = || {
         let test_sub = |a, b, c| assert(eq_ext(sub_ext(a, b), c), || "Wrong subtraction result");
 
         // Test subtracting 0
@@ -204,7 +204,7 @@ mod test {
         test_sub(Fp2::Fp2(-1, -1), Fp2::Fp2(0x78000000, 1), Fp2::Fp2(-0x78000000 - 1, -2))
     };
 
-    let test_mul = || {
+    pub let test_mul = || {
         let test_mul = |a, b, c| assert(eq_ext(mul_ext(a, b), c), || "Wrong multiplication result");
 
         // Test multiplication by 1
@@ -223,7 +223,7 @@ mod test {
         test_mul(Fp2::Fp2(-1, -2), Fp2::Fp2(-3, 4), Fp2::Fp2(3 - 11 * 8, 6 - 4))
     };
 
-    let test_square = || {
+    pub let test_square = || {
         // Tests consistency with mul_ext
         let test_square = |a| assert(eq_ext(mul_ext(a, a), square_ext(a)), || "Wrong squaring result");
 
@@ -235,7 +235,7 @@ mod test {
         test_square(Fp2::Fp2(-1, -2));
     };
 
-    let test_inverse = || {
+    pub let test_inverse = || {
         let test_elements = [
             from_base(1),
             Fp2::Fp2(123, 1234),
@@ -249,7 +249,7 @@ mod test {
         })
     };
 
-    let test_pow = || {
+    pub let test_pow = || {
         let test_pow = |a, i, b| assert(eq_ext(pow_ext(a, i), b), || "Wrong power result");
 
         test_pow(from_base(0), 0, from_base(1));
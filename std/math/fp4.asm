@@ -11,15 +11,15 @@ use std::prover::eval;
 /// where Fp4(a0, a1, a2, a3) is interpreted as a0 + a1 * X + a2 * X^2 + a3 * X^3
 /// T is assumed to either be fe, expr or any other object whose algebraic operations
 /// are compatible with fe.
-enum Fp4<T> {
+pub enum Fp4<T> {
     Fp4(T, T, T, T)
 }
 
 /// Converts a base field element to the extension field
-let<T: FromLiteral> from_base: T -> Fp4<T> = |x| Fp4::Fp4(x, 0, 0, 0);
+pub let<T: FromLiteral> from_base: T -> Fp4<T> = |x| Fp4::Fp4(x, 0, 0, 0);
 
 /// Addition for extension field
-let<T: Add> add_ext: Fp4<T>, Fp4<T> -> Fp4<T> = |a, b| match (a, b) {
+pub let<T: Add> add_ext: Fp4<T>, Fp4<T> -> Fp4<T> = |a, b| match (a, b) {
     (Fp4::Fp4(a0, a1, a2, a3), Fp4::Fp4(b0, b1, b2, b3)) => Fp4::Fp4(
         a0 + b0,
         a1 + b1,
@@ -29,7 +29,7 @@ let<T: Add> add_ext: Fp4<T>, Fp4<T> -> Fp4<T> = |a, b| match (a, b) {
 };
 
 /// Subtraction for extension field
-let<T: Sub> sub_ext: Fp4<T>, Fp4<T> -> Fp4<T> = |a, b| match (a, b) {
+pub let<T: Sub> sub_ext: Fp4<T>, Fp4<T> -> Fp4<T> = |a, b| match (a, b) {
     (Fp4::Fp4(a0, a1, a2, a3), Fp4::Fp4(b0, b1, b2, b3)) => Fp4::Fp4(
         a0 - b0,
         a1 - b1,
@@ -50,7 +50,7 @@ let<T: Sub> sub_ext: Fp4<T>, Fp4<T> -> Fp4<T> = |a, b| match (a, b) {
 /// + (a0 * b1 + a1 * b0 + NBETA * (a2 * b3 + a3 * b2)) * X
 /// + (a0 * b2 + a1 * b1 + a2 * b0 + NBETA * (a3 * b3)) * X^2
 /// + (a0 * b3 + a1 * b2 + a2 * b1 + a3 * b0) * X^3
-let<T: Add + FromLiteral + Mul> mul_ext: Fp4<T>, Fp4<T> -> Fp4<T> = |a, b| match (a, b) {
+pub let<T: Add + FromLiteral + Mul> mul_ext: Fp4<T>, Fp4<T> -> Fp4<T> = |a, b| match (a, b) {
     (Fp4::Fp4(a0, a1, a2, a3), Fp4::Fp4(b0, b1, b2, b3)) => Fp4::Fp4(
         a0 * b0 + 11 * (a1 * b3 + a2 * b2 + a3 * b1),
         a0 * b1 + a1 * b0 + 11 * (a2 * b3 + a3 * b2),
@@ -83,7 +83,7 @@ let<T: Add + FromLiteral + Mul> mul_ext: Fp4<T>, Fp4<T> -> Fp4<T> = |a, b| match
 ///   + (-1 * a1 * b0 + 11 * a3 * b2) * x
 ///   + (-1 * a0 * b2 + a2 * b0) * x^2
 ///   + (a1 * b2 - a3 * b0) * x^3
-let inv_ext: Fp4<fe> -> Fp4<fe> = |a| match a {
+pub let inv_ext: Fp4<fe> -> Fp4<fe> = |a| match a {
     Fp4::Fp4(a0, a1, a2, a3) => {
         let b0 = a0 * a0 - 11 * (a1 * (a3 + a3) - a2 * a2);
         let b2 = a0 * (a2 + a2) - a1 * a1 - 11 * (a3 * a3);
@@ -101,41 +101,41 @@ let inv_ext: Fp4<fe> -> Fp4<fe> = |a| match a {
 };
 
 /// Converts an Fp4<expr> into an Fp4<fe>
-let eval_ext: Fp4<expr> -> Fp4<fe> = query |a| match a {
+pub let eval_ext: Fp4<expr> -> Fp4<fe> = query |a| match a {
     Fp4::Fp4(a0, a1, a2, a3) => Fp4::Fp4(eval(a0), eval(a1), eval(a2), eval(a3))
 };
 
 /// Converts an Fp4<fe> into an Fp4<expr>
-let expr_ext: Fp4<fe> -> Fp4<expr> = |a| match a {
+pub let expr_ext: Fp4<fe> -> Fp4<expr> = |a| match a {
     Fp4::Fp4(a0, a1, a2, a3) => Fp4::Fp4(expr(a0), expr(a1), expr(a2), expr(a3))
 };
 
 /// Checks the equality of two Fp4 elements
-let eq_ext: Fp4<fe>, Fp4<fe> -> bool = |a, b| match (a, b) {
+pub let eq_ext: Fp4<fe>, Fp4<fe> -> bool = |a, b| match (a, b) {
     (Fp4::Fp4(a0, a1, a2, a3), Fp4::Fp4(b0, b1, b2, b3)) => (a0 == b0) && (a1 == b1) && (a2 == b2) && (a3 == b3)
 };
 
 /// Returns constraints that two Fp4 elements are equal
-let constrain_eq_ext: Fp4<expr>, Fp4<expr> -> Constr[] = |a, b| match (a, b) {
+pub let constrain_eq_ext: Fp4<expr>, Fp4<expr> -> Constr[] = |a, b| match (a, b) {
     (Fp4::Fp4(a0, a1, a2, a3), Fp4::Fp4(b0, b1, b2, b3)) => [a0 = b0, a1 = b1, a2 = b2, a3 = b3]
 };
 
 /// Applies the next operator to both components of the extension field element
-let next_ext: Fp4<expr> -> Fp4<expr> = |a| match a {
+pub let next_ext: Fp4<expr> -> Fp4<expr> = |a| match a {
     Fp4::Fp4(a0, a1, a2, a3) => Fp4::Fp4(a0', a1', a2', a3')
 };
 
 /// Returns the two components of the extension field element as a tuple
-let<T> unpack_ext: Fp4<T> -> (T, T, T, T) = |a| match a {
+pub let<T> unpack_ext: Fp4<T> -> (T, T, T, T) = |a| match a {
     Fp4::Fp4(a0, a1, a2, a3) => (a0, a1, a2, a3)
 };
 
 /// Returns the two components of the extension field element as an array
-let<T> unpack_ext_array: Fp4<T> -> T[] = |a| match a {
+pub let<T> unpack_ext_array: Fp4<T> -> T[] = |a| match a {
     Fp4::Fp4(a0, a1, a2, a3) => [a0, a1, a2, a3]
 };
 
-mod test {
+pub mod test {
     use super::Fp4;
     use super::from_base;
     use super::add_ext;
@@ -146,7 +146,7 @@ mod test {
     use std::check::assert;
     use std::array::map;
 
-    let test_add = || {
+    pub let test_add = || {
         let test_add = |a, b, c| assert(eq_ext(add_ext(a, b), c), || "Wrong addition result");
 
         // Test adding 0
@@ -165,7 +165,7 @@ mod test {
         test_add(Fp4::Fp4(-1, 0, 0, 0), Fp4::Fp4(1, 0, 0, 0), from_base(0));
     };
 
-    let test_sub = || {
+    pub let test_sub = || {
         let test_sub = |a, b, c| assert(eq_ext(sub_ext(a, b), c), || "Wrong subtraction result");
 
         // Test subtracting 0
@@ -177,7 +177,7 @@ mod test {
         test_sub(Fp4::Fp4(-1, -1, 0, 0), Fp4::Fp4(0x78000000, 1, 0, 0), Fp4::Fp4(-0x78000000 - 1, -2, 0, 0))
     };
 
-    let test_mul = || {
+    pub let test_mul = || {
         let test_mul = |a, b, c| assert(eq_ext(mul_ext(a, b), c), || "Wrong multiplication result");
 
         // Test multiplication by 1
@@ -197,7 +197,7 @@ mod test {
         test_mul(Fp4::Fp4(-1, -2, -3, -4), Fp4::Fp4(-3, 4, 4, 5), Fp4::Fp4(-415, -339, -223, -13));
     };
 
-    let test_inverse = || {
+    pub let test_inverse = || {
         let test_elements = [
             from_base(1),
             Fp4::Fp4(123, 1234, 1, 2),
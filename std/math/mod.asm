@@ -1,3 +1,3 @@
-mod ff;
-mod fp2;
-mod fp4;
\ No newline at end of file
+pub mod ff;
+pub mod fp2;
+pub mod fp4;
\ No newline at end of file
@@ -1,15 +1,15 @@
-mod array;
-mod btree;
-mod check;
-mod constraints;
-mod convert;
-mod debug;
-mod field;
-mod machines;
-mod math;
-mod prelude;
-mod protocols;
-mod prover;
-mod test;
-mod utils;
-mod well_known;
+pub mod array;
+pub mod btree;
+pub mod check;
+pub mod constraints;
+pub mod convert;
+pub mod debug;
+pub mod field;
+pub mod machines;
+pub mod math;
+pub mod prelude;
+pub mod protocols;
+pub mod prover;
+pub mod test;
+pub mod utils;
+pub mod well_known;
@@ -1,21 +1,21 @@
 /// Like in Rust, all symbols from this file are available in all modules,
 /// meaning that a failed symbol resolution always re-tries relative to this module.
 
-use std::convert::expr as to_expr;
-use std::convert::fe as to_fe;
-use std::convert::int as to_int;
+pub use std::convert::expr as to_expr;
+pub use std::convert::fe as to_fe;
+pub use std::convert::int as to_int;
 
-let true: bool = "" == "";
-let false: bool = !true;
+pub let true: bool = "" == "";
+pub let false: bool = !true;
 
-enum Option<T> {
+pub enum Option<T> {
     None,
     Some(T)
 }
 
 /// The "constraint" type, i.e. the result of the operators
 /// "=", "in", "is" and "connect".
-enum Constr {
+pub enum Constr {
     /// A polynomial identity, result of the "=" operator.
     Identity(expr, expr),
 
@@ -46,14 +46,14 @@ enum Constr {
 
 /// This is the result of the "$" operator. It can be used as the left and
 /// right hand side of a lookup or permutation constraint.
-enum SelectedExprs {
+pub enum SelectedExprs {
     SelectedExprs(expr, expr[]),
     JustExprs(expr[]),
 }
 
 
 /// The return type of a prover query function.
-enum Query {
+pub enum Query {
     /// Generate a hint to fill a witness column with.
     Hint(fe),
     /// Query a prover input (field element) by channel id and index.
@@ -66,9 +66,9 @@ enum Query {
 }
 
 /// Adds a hint / query function to an existing witness column.
-let set_hint: expr, (int -> std::prelude::Query) -> () = [];
+pub let set_hint: expr, (int -> std::prelude::Query) -> () = [];
 
 /// Constructs a challenge object.
 /// The arguments are the proof stage and the id of the challenge, in this order.
 /// This is a built-in function.
-let challenge: int, int -> expr = [];
+pub let challenge: int, int -> expr = [];
@@ -35,7 +35,7 @@ use std::prover::eval;
 /// # Returns:
 ///
 /// - Constraints to be added to enforce the bus
-let bus_interaction: expr, expr[], expr, expr[], Fp2<expr>, Fp2<expr> -> () = constr |id, tuple, multiplicity, acc, alpha, beta| {
+pub let bus_interaction: expr, expr[], expr, expr[], Fp2<expr>, Fp2<expr> -> () = constr |id, tuple, multiplicity, acc, alpha, beta| {
 
     // Implemented as: folded = (beta - fingerprint(id, tuple...));
     let folded = sub_ext(beta, fingerprint_with_id(id, tuple, alpha));
@@ -65,7 +65,7 @@ let bus_interaction: expr, expr[], expr, expr[], Fp2<expr>, Fp2<expr> -> () = co
 /// using extension field arithmetic.
 /// This is intended to be used as a hint in the extension field case; for the base case
 /// automatic witgen is smart enough to figure out the value of the accumulator.
-let compute_next_z_send: expr, expr, expr[], expr, Fp2<expr>, Fp2<expr>, Fp2<expr> -> fe[] = query |is_first, id, tuple, multiplicity, acc, alpha, beta| {
+pub let compute_next_z_send: expr, expr, expr[], expr, Fp2<expr>, Fp2<expr>, Fp2<expr> -> fe[] = query |is_first, id, tuple, multiplicity, acc, alpha, beta| {
     // Implemented as: folded = (beta - fingerprint(id, tuple...));
     // `multiplicity / (beta - fingerprint(id, tuple...))` to `acc`
     let folded = sub_ext(beta, fingerprint_with_id(id, tuple, alpha));
@@ -90,15 +90,15 @@ let compute_next_z_send: expr, expr, expr[], expr, Fp2<expr>, Fp2<expr>, Fp2<exp
 /// using extension field arithmetic.
 /// This is intended to be used as a hint in the extension field case; for the base case
 /// automatic witgen is smart enough to figure out the value of the accumulator.
-let compute_next_z_receive: expr, expr, expr[], expr, Fp2<expr>, Fp2<expr>, Fp2<expr> -> fe[] = query |is_first, id, tuple, multiplicity, acc, alpha, beta| 
+pub let compute_next_z_receive: expr, expr, expr[], expr, Fp2<expr>, Fp2<expr>, Fp2<expr> -> fe[] = query |is_first, id, tuple, multiplicity, acc, alpha, beta| 
     compute_next_z_send(is_first, id, tuple, -multiplicity, acc, alpha, beta);
 
 /// Convenience function for bus interaction to send columns
-let bus_send: expr, expr[], expr, expr[], Fp2<expr>, Fp2<expr> -> () = constr |id, tuple, multiplicity, acc, alpha, beta| {
+pub let bus_send: expr, expr[], expr, expr[], Fp2<expr>, Fp2<expr> -> () = constr |id, tuple, multiplicity, acc, alpha, beta| {
     bus_interaction(id, tuple, multiplicity, acc, alpha, beta);
 };
 
 /// Convenience function for bus interaction to receive columns
-let bus_receive: expr, expr[], expr, expr[], Fp2<expr>, Fp2<expr> -> () = constr |id, tuple, multiplicity, acc, alpha, beta| {
+pub let bus_receive: expr, expr[], expr, expr[], Fp2<expr>, Fp2<expr> -> () = constr |id, tuple, multiplicity, acc, alpha, beta| {
     bus_interaction(id, tuple, -1 * multiplicity, acc, alpha, beta);
 };
\ No newline at end of file
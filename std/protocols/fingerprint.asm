@@ -7,7 +7,7 @@ use std::math::fp2::pow_ext;
 use std::math::fp2::from_base;
 
 /// Maps [x_1, x_2, ..., x_n] to its Read-Solomon fingerprint, using a challenge alpha: $\sum_{i=1}^n alpha**{(n - i)} * x_i$
-let<T: Add + Mul + FromLiteral> fingerprint: T[], Fp2<T> -> Fp2<T> = |expr_array, alpha| {
+pub let<T: Add + Mul + FromLiteral> fingerprint: T[], Fp2<T> -> Fp2<T> = |expr_array, alpha| {
     let n = len(expr_array);
     fold(
         n,
@@ -18,9 +18,9 @@ let<T: Add + Mul + FromLiteral> fingerprint: T[], Fp2<T> -> Fp2<T> = |expr_array
 };
 
 /// Maps [id, x_1, x_2, ..., x_n] to its Read-Solomon fingerprint, using a challenge alpha: $\sum_{i=1}^n alpha**{(n - i)} * x_i$
-let<T: Add + Mul + FromLiteral> fingerprint_with_id: T, T[], Fp2<T> -> Fp2<T> = |id, expr_array, alpha| fingerprint([id] + expr_array, alpha);
+pub let<T: Add + Mul + FromLiteral> fingerprint_with_id: T, T[], Fp2<T> -> Fp2<T> = |id, expr_array, alpha| fingerprint([id] + expr_array, alpha);
 
-mod test {
+pub mod test {
     use super::fingerprint;
     use std::check::assert;
     use std::math::fp2::Fp2;
@@ -28,7 +28,7 @@ mod test {
 
     /// Helper function to assert that the fingerprint of a tuple is equal to the expected value.
     /// We are working on integers here, wrapping them as Fp2 elements.
-    let assert_fingerprint_equal: int[], int, int -> () = |tuple, challenge, expected| {
+    pub let assert_fingerprint_equal: int[], int, int -> () = |tuple, challenge, expected| {
         let result = fingerprint(tuple, from_base(challenge));
         match result {
             Fp2::Fp2(actual, should_be_zero) => {
@@ -38,7 +38,7 @@ mod test {
         }
     };
 
-    let test_fingerprint = || {
+    pub let test_fingerprint = || {
         // A tuple t of size n with challenge x should be mapped to:
         // t[0] * x**(n-1) + t[1] * x**(n-2) + ... + t[n-2] * x + t[n-1]
 
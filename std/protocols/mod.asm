@@ -1,6 +1,6 @@
-mod bus;
-mod fingerprint;
-mod lookup;
-mod lookup_via_bus;
-mod permutation;
-mod permutation_via_bus;
\ No newline at end of file
+pub mod bus;
+pub mod fingerprint;
+pub mod lookup;
+pub mod lookup_via_bus;
+pub mod permutation;
+pub mod permutation_via_bus;
\ No newline at end of file
@@ -4,7 +4,7 @@ use std::btree::get;
 use std::btree::CmpResult;
 use std::btree::BTree;
 
-let cmp: int, int -> CmpResult = |a, b|
+pub let cmp: int, int -> CmpResult = |a, b|
     if a < b {
         CmpResult::Less
     } else {
@@ -15,12 +15,12 @@ let cmp: int, int -> CmpResult = |a, b|
         }
     };
 
-let one: int = 1;
-let false: bool = one == 0;
+pub let one: int = 1;
+pub let false: bool = one == 0;
 
 use std::debug::print;
 use std::debug::println;
-let spaces: int -> string = |n|
+pub let spaces: int -> string = |n|
     if n == 0 {
         ""
     } else {
@@ -30,7 +30,7 @@ let spaces: int -> string = |n|
             1 => s + s +  " ",
         }
     };
-let print_item: (int, string), int -> () = |(k, v), indent| {
+pub let print_item: (int, string), int -> () = |(k, v), indent| {
     let _ = print(spaces(indent * 4));
     let _ = print(k);
     let _ = print(": ");
@@ -38,7 +38,7 @@ let print_item: (int, string), int -> () = |(k, v), indent| {
     ()
 };
 
-let print_btree: BTree<int, string>, int -> () = |n, indent| match n {
+pub let print_btree: BTree<int, string>, int -> () = |n, indent| match n {
     BTree::Inner(items, children) => {
         let _ = std::array::map_enumerated(children, |i, child| {
             let _ = print_btree(child, indent + 1);
@@ -56,18 +56,18 @@ let print_btree: BTree<int, string>, int -> () = |n, indent| match n {
     }
 };
 
-let expect: BTree<int, string>, int, string -> () = |b_tree, k, v|
+pub let expect: BTree<int, string>, int, string -> () = |b_tree, k, v|
     match get(b_tree, k, cmp) {
         Option::Some(x) => std::check::assert(x == v, || ""),
         _ => std::check::assert(false, || ""),
     };
 
-let do_print: BTree<int, string> -> () = |b_tree| {
+pub let do_print: BTree<int, string> -> () = |b_tree| {
     let _ = print_btree(b_tree, 0);
     ()
 };
 
-let test_btree = || {
+pub let test_btree = || {
     let b1 = new::<int, string>();
     let b2 = insert(b1, (1, "a"), cmp);
     let b3 = insert(b2, (8, "b"), cmp);
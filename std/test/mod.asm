@@ -1 +1,31 @@
-mod btree;
+pub mod btree;
+
+// The functions in this module are query functions, since they run witness
+// generation or inspect its result, so they can only be used in `query` tests.
+// They are only available when running `powdr test` on a file. Witness generation
+// always runs the entry point of the file (the `Main` machine) and only provides
+// prover inputs on channel 0.
+
+/// Runs witness generation for the program under test, using the given values
+/// as prover inputs (channel zero).
+/// The generated witness is used by `witness_value`, `public_value` and
+/// `expect_constraint_failure` until the next call to this function.
+/// This function is only available when running `powdr test`.
+pub let run_witgen: fe[] -> () = [];
+
+/// Returns the value of the witness column with the given (fully qualified) name
+/// at the given row of the witness generated by the last call to `run_witgen`.
+pub let witness_value: string, int -> fe = [];
+
+/// Returns the value of the public with the given (fully qualified) name
+/// in the witness generated by the last call to `run_witgen`.
+pub let public_value: string -> fe = [];
+
+/// Runs witness generation for the program under test using the given prover inputs
+/// and fails if witness generation succeeds.
+pub let expect_witgen_failure: fe[] -> () = [];
+
+/// Takes the witness generated by the last call to `run_witgen`, replaces the values
+/// of the given cells (column name, row, new value) and fails if the modified
+/// witness still satisfies all constraints.
+pub let expect_constraint_failure: (string, int, fe)[] -> () = [];
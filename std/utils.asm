@@ -3,7 +3,7 @@
 /// using the function `folder`, starting with the value `initial`.
 ///
 /// See `sum` for an example use.
-let<T1, T2>
+pub let<T1, T2>
     fold: int, (int -> T1), T2, (T2, T1 -> T2) -> T2 = |length, f, initial, folder|
         if length <= 0 {
             initial
@@ -12,13 +12,13 @@ let<T1, T2>
         };
 
 /// Evaluates to f(0) + f(1) + ... + f(length - 1).
-let<T: Add + FromLiteral> sum: int, (int -> T) -> T = |length, f| fold(length, f, 0, |acc, e| (acc + e));
+pub let<T: Add + FromLiteral> sum: int, (int -> T) -> T = |length, f| fold(length, f, 0, |acc, e| (acc + e));
 
-let<T: Ord> max: T, T -> T = |a, b| if a < b { b } else { a };
-let<T: Ord> min: T, T -> T = |a, b| if a < b { a } else { b };
+pub let<T: Ord> max: T, T -> T = |a, b| if a < b { b } else { a };
+pub let<T: Ord> min: T, T -> T = |a, b| if a < b { a } else { b };
 
 /// Returns x for Some(x) and f() otherwise.
-let<T> unwrap_or_else: Option<T>, (-> T) -> T = |o, f| match o {
+pub let<T> unwrap_or_else: Option<T>, (-> T) -> T = |o, f| match o {
     Option::None => f(),
     Option::Some(x) => x,
 };
@@ -28,13 +28,13 @@ let<T> unwrap_or_else: Option<T>, (-> T) -> T = |o, f| match o {
 /// `c` is allowed to change.
 ///
 /// Note: `latch` needs to be equal to `1`, and not merely non-zero for `c` to be able to change.
-let unchanged_until: expr, expr -> Constr = |c, latch| (c' - c) * (1 - latch) = 0;
+pub let unchanged_until: expr, expr -> Constr = |c, latch| (c' - c) * (1 - latch) = 0;
 
 /// Evaluates to a constraint that forces `c` to be either 0 or 1.
-let force_bool: expr -> Constr = |c| c * (1 - c) = 0;
+pub let force_bool: expr -> Constr = |c| c * (1 - c) = 0;
 
 /// Creates a new witness column that is constrained to the values 0 and 1.
-let new_bool: -> expr = constr || {
+pub let new_bool: -> expr = constr || {
     let x;
     force_bool(x);
     x
@@ -43,7 +43,7 @@ let new_bool: -> expr = constr || {
 /// Returns a 2nd degree boolean expression that is 1 if and only if `x` is not zero.
 ///
 /// Introduces one new witness column and one new constraint.
-let is_not_zero: expr -> expr = constr |x| {
+pub let is_not_zero: expr -> expr = constr |x| {
     // The inverse of "x" if it is not zero, otherwise unconstrained.
     let inverse;
 
@@ -56,7 +56,7 @@ let is_not_zero: expr -> expr = constr |x| {
 /// Returns a 2nd degree boolean expression that is 1 if and only if `x` is zero.
 ///
 /// Introduces one new witness column and one new constraint.
-let is_zero: expr -> expr = constr |x| {
+pub let is_zero: expr -> expr = constr |x| {
     1 - is_not_zero(x)
 };
 
@@ -68,7 +68,7 @@ let is_zero: expr -> expr = constr |x| {
 /// This function is useful for combined range checks or building the inputs for function
 /// that is implemented in a lookup.
 /// See binary.asm for an example.
-let cross_product: int[] -> (int -> int)[] = |sizes| cross_product_internal(1, 0, sizes);
+pub let cross_product: int[] -> (int -> int)[] = |sizes| cross_product_internal(1, 0, sizes);
 
 let cross_product_internal: int, int, int[] -> (int -> int)[] = |cycle_len, pos, sizes|
     if pos >= std::array::len(sizes) {
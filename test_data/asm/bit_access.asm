@@ -1,6 +1,6 @@
 use std::array;
 
-machine BitAccess with degree: 32 {
+pub machine BitAccess with degree: 32 {
     reg pc[@pc];
     reg X[<=];
     reg Y[<=];
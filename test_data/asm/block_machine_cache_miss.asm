@@ -1,4 +1,4 @@
-machine Arith with
+pub machine Arith with
     degree: 8,
     latch: latch,
     operation_id: operation_id
@@ -27,7 +27,7 @@ machine Arith with
     operation_id $ [x, y] in [X, SQUARE];
 }
 
-machine Main with degree: 8 {
+pub machine Main with degree: 8 {
     Arith arith;
 
     reg pc[@pc];
@@ -1,7 +1,7 @@
-let N: int = 8;
+pub let N: int = 8;
 
 // calls a constrained machine from a constrained machine
-machine Arith with
+pub machine Arith with
     latch: latch,
     operation_id: operation_id
 {
@@ -15,7 +15,7 @@ machine Arith with
     z = x + y;
 }
 
-machine Main with
+pub machine Main with
     degree: N,
     latch: latch,
     operation_id: operation_id
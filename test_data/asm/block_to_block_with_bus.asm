@@ -13,10 +13,10 @@ use std::prover::challenge;
 // - Final accumulator values are not exposed as public yet
 // In the future, this can be used to test that challenges can be shared between machines.
 
-let ARITH_INTERACTION_ID = 1234;
+pub let ARITH_INTERACTION_ID = 1234;
 
 // calls a constrained machine from a constrained machine
-machine Arith with
+pub machine Arith with
     degree: 8,
     latch: latch,
     operation_id: operation_id,
@@ -76,7 +76,7 @@ machine Arith with
     z = x + y;
 }
 
-machine Main with
+pub machine Main with
     degree: 8,
     latch: latch,
     operation_id: operation_id
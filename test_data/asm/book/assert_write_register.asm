@@ -1,4 +1,4 @@
-machine Machine with degree: 8 {
+pub machine Machine with degree: 8 {
     // ANCHOR: component
 reg pc[@pc];
 reg A;
@@ -1,6 +1,6 @@
 /* ANCHOR: all */
 
-machine Machine with degree: 16 {
+pub machine Machine with degree: 16 {
     reg pc[@pc];
     reg X[<=];
     reg Y[<=];
@@ -1,4 +1,4 @@
-machine HelloWorld with degree: 8 {
+pub machine HelloWorld with degree: 8 {
     reg pc[@pc];
     reg X[<=];
     reg Y[<=];
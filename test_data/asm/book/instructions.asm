@@ -1,4 +1,4 @@
-machine NotUsed {
+pub machine NotUsed {
     reg pc[@pc];
     reg X[<=];
     reg Y[<=];
@@ -11,7 +11,7 @@ instr add X, Y -> Z {
 }
 
 // ANCHOR: submachine
-machine SubMachine with
+pub machine SubMachine with
     latch: latch,
     operation_id: operation_id
 {
@@ -28,7 +28,7 @@ machine SubMachine with
 // ANCHOR_END: submachine
 
 // ANCHOR: main
-machine Main with degree: 32 {
+pub machine Main with degree: 32 {
     reg pc[@pc];
     reg X[<=];
     reg Y[<=];
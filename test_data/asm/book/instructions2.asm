@@ -1,5 +1,5 @@
 // ANCHOR: submachine
-machine SubMachine with
+pub machine SubMachine with
     latch: latch,
     operation_id: operation_id
 {
@@ -17,7 +17,7 @@ machine SubMachine with
 // ANCHOR_END: submachine
 
 // ANCHOR: main
-machine Main with degree: 16 {
+pub machine Main with degree: 16 {
     reg pc[@pc];
     reg X[<=];
     reg Y[<=];
@@ -1,26 +1,28 @@
 use my_module::Other as LocalOther;
 
 // we can define a module at `./submodule.asm`
-mod submodule;
+pub mod submodule;
 
 // we can define a module at `./submodule_in_folder/mod.asm`
-mod submodule_in_folder;
+pub mod submodule_in_folder;
 
 use submodule::Other as SubmoduleOther;
 use submodule_in_folder::Other as FolderSubmoduleOther;
 
+// symbols are private by default: `zero` can only be accessed
+// from this module and its submodules
 let zero: int = 0;
 
 // we can also define modules inline
-mod utils {
+pub mod utils {
     // Each module has a fresh symbol list. Every external symbol needs to be imported,
     // even from the parent module.
     use super::zero;
 
-    let one = zero + 1;
+    pub let one = zero + 1;
 }
 
-machine Main with degree: 8 {
+pub machine Main with degree: 8 {
     // use a machine from another module by relative path
     my_module::Other a;
 
@@ -49,8 +51,8 @@ machine Main with degree: 8 {
     }
 }
 
-mod my_module {
-    machine Other with
+pub mod my_module {
+    pub machine Other with
         latch: latch,
         operation_id: operation_id
     {
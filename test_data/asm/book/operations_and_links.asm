@@ -1,4 +1,4 @@
-machine Main with degree: 16 {
+pub machine Main with degree: 16 {
     Add4 adder;
 
     reg pc[@pc];
@@ -22,7 +22,7 @@ machine Main with degree: 16 {
 }
 
 // ANCHOR: links
-machine Add4 with
+pub machine Add4 with
     latch: latch,
     operation_id: operation_id
 {
@@ -51,7 +51,7 @@ machine Add4 with
 }
 
 // ANCHOR: one_operation
-machine Add with
+pub machine Add with
     latch: latch
 {
     // operation name, with column names as inputs and outputs
@@ -71,7 +71,7 @@ machine Add with
 
 // ANCHOR: many_operations
 // machine declaration must include an operation id column name
-machine AddSub with
+pub machine AddSub with
     latch: latch,
     operation_id: op_id
 {
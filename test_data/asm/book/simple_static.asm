@@ -1,4 +1,4 @@
-machine SimpleStatic with
+pub machine SimpleStatic with
     degree: 8,
     latch: latch,
     operation_id: operation_id
@@ -1,4 +1,4 @@
-machine Other with
+pub machine Other with
     latch: latch,
     operation_id: operation_id,
     degree: 8
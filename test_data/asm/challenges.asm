@@ -1,6 +1,6 @@
 use std::prover::challenge;
 
-machine Main with degree: 4 {
+pub machine Main with degree: 4 {
 
     col fixed foo = [1, 2, 3, 4]*;
 
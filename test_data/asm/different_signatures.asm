@@ -1,4 +1,4 @@
-machine Main with degree: 16 {
+pub machine Main with degree: 16 {
     DifferentSignatures sub;
 
     reg pc[@pc];
@@ -18,7 +18,7 @@ machine Main with degree: 16 {
 }
 
 // A machine exposing functions of different signatures
-machine DifferentSignatures {
+pub machine DifferentSignatures {
 
     reg pc[@pc];
 
@@ -1,15 +1,15 @@
-mod cols {
-    let first: -> expr = {
+pub mod cols {
+    pub let first: -> expr = {
         constr || {
             let fi: col = |i| if i == 0 { 1 } else { 0 };
             fi
         }
     };
-    enum Generic<T> {
+    pub enum Generic<T> {
         A(T),
         B,
     }
-    let<T> f: T -> Generic<T> = |i| {
+    pub let<T> f: T -> Generic<T> = |i| {
         let g: -> Generic<T> = || {
             Generic::A(i)
         };
@@ -18,7 +18,7 @@ mod cols {
 }
 
 
-machine Empty with degree: 16 {
+pub machine Empty with degree: 16 {
     col witness w;
     w - cols::first() = 0;
     let x;
@@ -1,7 +1,7 @@
 use std::machines::large_field::memory::Memory;
 
 // Copy of std::machines::range::Byte2 which sets the correct degree.
-machine Byte2 with
+pub machine Byte2 with
     latch: latch,
     operation_id: operation_id,
     degree: 65536
@@ -13,7 +13,7 @@ machine Byte2 with
     col fixed operation_id = [0]*;
 }
 
-machine Main {
+pub machine Main {
     Arith arith;
 
     col fixed STEP(i) { i };
@@ -47,7 +47,7 @@ machine Main {
     }
 }
 
-machine Arith with
+pub machine Arith with
     latch: latch,
     operation_id: operation_id
 {
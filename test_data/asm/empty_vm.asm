@@ -1,3 +1,3 @@
-machine VM with degree: 4 + 4 {
+pub machine VM with degree: 4 + 4 {
     reg pc[@pc];
 }
@@ -1,22 +1,22 @@
-mod types {
-    enum OptionInt {
+pub mod types {
+    pub enum OptionInt {
         None,
         Some(int, x::Other)
     }
-    mod x {
-        enum Other {
+    pub mod x {
+        pub enum Other {
             A(int)
         }
     }
 }
 
-mod utils {
+pub mod utils {
     use super::types::OptionInt;
     use super::types::x;
-    let o: OptionInt = OptionInt::Some(42, x::Other::A(2));
+    pub let o: OptionInt = OptionInt::Some(42, x::Other::A(2));
 }
 
-machine Empty with degree: 4 {
+pub machine Empty with degree: 4 {
     col witness w;
     w = w * w;
 }
\ No newline at end of file
@@ -3,22 +3,22 @@
 
 use std::check::panic;
 
-enum Result<V, E> {
+pub enum Result<V, E> {
     Ok(V),
     Err(E)
 }
 
 /// A term in an array expression
-enum ArrayTerm {
+pub enum ArrayTerm {
     Repeat(int[]),
     Once(int[])
 }
 
-let MORE_THAN_ONE_REPEATED_ERROR = "unsolvable because more than one term is repeated";
-let NON_REPEATED_SIZE_EXCEEDS_DEGREE_ERROR = "non repeated array terms do not fit in degree";
+pub let MORE_THAN_ONE_REPEATED_ERROR = "unsolvable because more than one term is repeated";
+pub let NON_REPEATED_SIZE_EXCEEDS_DEGREE_ERROR = "non repeated array terms do not fit in degree";
 
 // returns the total size of the repeated array in this array expression
-let compute_length_of_repeated_part: ArrayTerm[], (-> int) -> Result<int, string> = |terms, degree| {
+pub let compute_length_of_repeated_part: ArrayTerm[], (-> int) -> Result<int, string> = |terms, degree| {
     let (_, res) = std::array::fold(terms, (false, Result::Ok(0)), |(found_repeated, l), term| {
         match l {
             Result::Err(e) => (found_repeated, Result::Err(e)),
@@ -46,13 +46,13 @@ let compute_length_of_repeated_part: ArrayTerm[], (-> int) -> Result<int, string
     }
 };
 
-enum OffsetSearch {
+pub enum OffsetSearch {
     Offset(int),
     Value(int),
 }
 
 // returns a function representing the array expression
-let expand: ArrayTerm[], (-> int) -> Result<(int -> int), string> = |terms, degree| {
+pub let expand: ArrayTerm[], (-> int) -> Result<(int -> int), string> = |terms, degree| {
     // return early if all terms are constant with the same value
     let (constant_value, found_repeated_item) = std::array::fold(terms, (Result::Ok(Option::None), Result::Ok(false)), |(r, found), term| {
         let (a, is_repeated) = match term {
@@ -122,19 +122,19 @@ let expand: ArrayTerm[], (-> int) -> Result<(int -> int), string> = |terms, degr
     }
 };
 
-let expand_unwrapped: ArrayTerm[], (-> int) -> (int -> int) = |terms, degree| {
+pub let expand_unwrapped: ArrayTerm[], (-> int) -> (int -> int) = |terms, degree| {
     match expand(terms, degree) {
         Result::Ok(r) => r,
         Result::Err(e) => panic(e)
     }
 };
 
-let repeat: int[] -> ArrayTerm = |a| ArrayTerm::Repeat(a);
+pub let repeat: int[] -> ArrayTerm = |a| ArrayTerm::Repeat(a);
 
-let once: int[] -> ArrayTerm = |a| ArrayTerm::Once(a);
+pub let once: int[] -> ArrayTerm = |a| ArrayTerm::Once(a);
 
 
-machine Main with degree: 2**22 {
+pub machine Main with degree: 2**22 {
     col witness w;
     let LAST: col = match expand([repeat([0]), once([1])], std::prover::degree) {
         Result::Ok(r) => |i| std::convert::fe(r(i)),
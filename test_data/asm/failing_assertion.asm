@@ -1,6 +1,6 @@
 use std::check;
 
-machine Empty with degree: 4 {
+pub machine Empty with degree: 4 {
     let line = |i| i - 7;
     let line_col: col = line;
     col witness w;
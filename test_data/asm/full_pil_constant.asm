@@ -1,4 +1,4 @@
-machine FullConstant with degree: 4 {
+pub machine FullConstant with degree: 4 {
 	pol constant C = [0, 1]*;
 	col commit w;
 	w = C;
@@ -1,6 +1,6 @@
 use std::array;
 
-machine FunctionalInstructions with degree: 32 {
+pub machine FunctionalInstructions with degree: 32 {
     reg pc[@pc];
     reg X[<=];
     reg Y[<=];
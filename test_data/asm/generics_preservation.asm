@@ -1,15 +1,15 @@
-mod cols {
-    enum Generic<T> {
+pub mod cols {
+    pub enum Generic<T> {
         A(T),
         B,
     }
-    let<T> f: -> Generic<T> = || {
+    pub let<T> f: -> Generic<T> = || {
             Generic::B
     };
 }
 
 
-machine Empty with degree: 4 {
+pub machine Empty with degree: 4 {
     let x;
     let fi: col = |i| match cols::f::<int>() {
         cols::Generic::A(_) => 7,
@@ -1,4 +1,4 @@
-machine Intermediate with
+pub machine Intermediate with
     latch: latch,
     operation_id: operation_id,
     degree: 8
@@ -1,7 +1,7 @@
 // Write-once memory with key (ADDR1, ADDR2) and value (v1, v2)
 // This is similar to std::machines::write_once_memory::WriteOnceMemory, but has
 // two address and value columns.
-machine WriteOnceMemory with
+pub machine WriteOnceMemory with
     latch: LATCH
 {
 
@@ -15,7 +15,7 @@ machine WriteOnceMemory with
     let v2;
 }
 
-machine Main with degree: 256 {
+pub machine Main with degree: 256 {
     reg pc[@pc];
     reg X1[<=];
     reg X2[<=];
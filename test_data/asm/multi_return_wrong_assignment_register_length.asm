@@ -1,4 +1,4 @@
-machine MultiAssign with degree: 16 {
+pub machine MultiAssign with degree: 16 {
     reg pc[@pc];
     reg X[<=];
     reg Y[<=];
@@ -1,4 +1,4 @@
-machine Add with
+pub machine Add with
     latch: latch,
     operation_id: operation_id,
     call_selectors: sel,
@@ -18,7 +18,7 @@ machine Add with
     operation sub<0> C, A -> B;
 }
 
-machine Main with degree: 32 {
+pub machine Main with degree: 32 {
     reg pc[@pc];
     reg X[<=];
     reg Y[<=];
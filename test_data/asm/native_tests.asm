@@ -0,0 +1,43 @@
+// Checks that the second input is the square of the first input.
+// Input: x, y
+
+use std::prelude::Query;
+
+pub machine Main with degree: 16 {
+    reg pc[@pc];
+    reg X[<=];
+    reg Y[<=];
+    reg A;
+    reg B;
+
+    instr assert_eq X, Y { X = Y }
+
+    function main {
+        A <=X= ${ Query::Input(0, 1) };
+        B <=X= ${ Query::Input(0, 2) };
+        assert_eq A * A, B;
+        return;
+    }
+}
+
+pub mod test {
+    use std::check::assert;
+    use std::test::run_witgen;
+    use std::test::witness_value;
+    use std::test::expect_witgen_failure;
+    use std::test::expect_constraint_failure;
+
+    pub let test_square = query || {
+        run_witgen([3, 9]);
+        // The registers keep their values until the end of the execution trace.
+        assert(witness_value("main::A", 15) == 3, || "Unexpected value for A");
+        assert(witness_value("main::B", 15) == 9, || "Unexpected value for B")
+    };
+
+    pub let test_wrong_square = query || expect_witgen_failure([3, 10]);
+
+    pub let test_tampered_witness = query || {
+        run_witgen([3, 9]);
+        expect_constraint_failure([("main::B", 15, 10)])
+    };
+}
@@ -4,7 +4,7 @@
 use std::prelude::Query;
 use std::convert::int;
 
-machine Palindrome with degree: 32 {
+pub machine Palindrome with degree: 32 {
     reg pc[@pc];
     reg X[<=];
     reg A;
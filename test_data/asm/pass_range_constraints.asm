@@ -5,7 +5,7 @@
 // into two chunks (which, again, requires witgen to know the range constraints in the
 // calling machine).
 
-machine Mul with
+pub machine Mul with
     latch: latch,
     operation_id: operation_id,
     call_selectors: sel,
@@ -32,7 +32,7 @@ machine Mul with
     z = x * y;
 }
 
-machine Main with
+pub machine Main with
     degree: 16,
     latch: latch,
     operation_id: operation_id
@@ -1,7 +1,7 @@
-mod binary4;
+pub mod binary4;
 use binary4::Binary4;
 
-machine Binary4x with
+pub machine Binary4x with
     latch: latch,
     operation_id: operation_id,
     call_selectors: sel,
@@ -30,7 +30,7 @@ machine Binary4x with
     col witness Y;
 }
 
-machine Main with degree: 256 {
+pub machine Main with degree: 256 {
     reg pc[@pc];
     reg X[<=];
     reg Y[<=];
@@ -1,4 +1,4 @@
-machine Binary with
+pub machine Binary with
     latch: latch
 {
     // fails because incoming permutation links to block machine requires call_selectors
@@ -12,7 +12,7 @@ machine Binary with
     C = A + B;
 }
 
-machine Main with degree: 32 {
+pub machine Main with degree: 32 {
     reg pc[@pc];
     reg X[<=];
     reg Y[<=];
@@ -1,4 +1,4 @@
-machine SubMachine with
+pub machine SubMachine with
     latch: latch,
     operation_id: operation_id
 {
@@ -14,7 +14,7 @@ machine SubMachine with
     z = y + x;
 }
 
-machine Main with degree: 32 {
+pub machine Main with degree: 32 {
     reg pc[@pc];
     reg X[<=];
     reg Y[<=];
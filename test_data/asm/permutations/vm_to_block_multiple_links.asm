@@ -1,4 +1,4 @@
-machine Main with degree: 32 {
+pub machine Main with degree: 32 {
     Arith arith;
 
     reg pc[@pc];
@@ -45,7 +45,7 @@ machine Main with degree: 32 {
     }
 }
 
-machine Arith with
+pub machine Arith with
     latch: latch,
     operation_id: operation_id,
     call_selectors: selectors
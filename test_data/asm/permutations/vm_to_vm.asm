@@ -1,4 +1,4 @@
-machine Binary {
+pub machine Binary {
     reg pc[@pc];
     reg X[<=];
     reg Y[<=];
@@ -15,7 +15,7 @@ machine Binary {
     }
 }
 
-machine Main with degree: 32 {
+pub machine Main with degree: 32 {
     reg pc[@pc];
     reg X[<=];
     reg Y[<=];
@@ -2,7 +2,7 @@ use std::prelude::Query;
 use std::prover::eval;
 use std::prover::challenge;
 
-machine Main with degree: 8 {
+pub machine Main with degree: 8 {
     let is_first: col = std::well_known::is_first;
 
     let count;
@@ -1,4 +1,4 @@
-machine Main with degree: 16 {
+pub machine Main with degree: 16 {
     reg pc[@pc];
     reg X[<=];
     reg Y[<=];
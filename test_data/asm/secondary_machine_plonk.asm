@@ -1,4 +1,4 @@
-machine Main with
+pub machine Main with
     degree: 256
 {
     Pythagoras pythagoras;
@@ -32,7 +32,7 @@ machine Main with
     }
 }
 
-machine Pythagoras with
+pub machine Pythagoras with
     latch: latch
 {
 
@@ -1,10 +1,10 @@
-let new_col_with_hint: -> expr = constr || {
+pub let new_col_with_hint: -> expr = constr || {
     let x;
     std::prelude::set_hint(x, |_| std::prelude::Query::Hint(2));
     x
 };
 
-machine Main with degree: 4 {
+pub machine Main with degree: 4 {
     let x;
     let w = new_col_with_hint();
     x = w;
@@ -5,12 +5,12 @@
 
 use std::machines::large_field::memory::Memory;
 
-mod test_util;
+pub mod test_util;
 use test_util::FakeByte2 as Byte2;
 
-let N: int = 256;
+pub let N: int = 256;
 
-machine MemoryProxy with
+pub machine MemoryProxy with
     latch: latch,
     operation_id: operation_id,
     call_selectors: sel,
@@ -33,7 +33,7 @@ machine MemoryProxy with
     link if used ~> mem.mstore(addr, step, value);
 }
 
-machine Main with degree: N {
+pub machine Main with degree: N {
     reg pc[@pc];
     reg X[<=];
     reg Y[<=];
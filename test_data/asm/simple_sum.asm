@@ -13,7 +13,7 @@ use std::convert::int;
 
 use std::prelude::Query;
 
-machine Main with degree: 16 {
+pub machine Main with degree: 16 {
     reg pc[@pc]; // "@pc" means "pc' = pc + 1" is the default propagation (instead of pc' = pc) and it tracks the line in the program.
     reg X[<=]; // "<=" means it is the default assignment register.
     reg A;
@@ -1,4 +1,4 @@
-machine VM with degree: 8 {
+pub machine VM with degree: 8 {
     reg pc[@pc];
 
     function main {
@@ -1,5 +1,5 @@
 // we don't need to specify an operation_id if we have a single operation
-machine SingleOperation with
+pub machine SingleOperation with
     latch: latch
 {
     operation nothing;
@@ -9,7 +9,7 @@ machine SingleOperation with
     w = w * w;
 }
 
-machine Main with degree: 8 {
+pub machine Main with degree: 8 {
     SingleOperation m;
 
     link => m.nothing();
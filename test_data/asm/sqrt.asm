@@ -1,6 +1,6 @@
 use std::prelude::Query;
 
-machine Sqrt with
+pub machine Sqrt with
     latch: latch,
     operation_id: operation_id,
 {
@@ -39,7 +39,7 @@ machine Sqrt with
 }
 
 
-machine Main with degree: 8 {
+pub machine Main with degree: 8 {
     Sqrt sqrt;
 
     reg pc[@pc];
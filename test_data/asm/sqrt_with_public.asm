@@ -1,4 +1,4 @@
-machine Square with degree: 8 {
+pub machine Square with degree: 8 {
     reg pc[@pc];
     reg X[<=];
     reg Y[<=];
@@ -1,7 +1,7 @@
 /// A version of std::machines::range::Byte2 that actually constrains
 /// to 0..255 because of the limited number of rows.
 /// It is used in tests which happen to only use the first 256 values.
-machine FakeByte2 with
+pub machine FakeByte2 with
     latch: latch,
     operation_id: operation_id,
     degree: 256
@@ -1,16 +1,16 @@
-mod types {
-    enum DoubleOpt<T> {
+pub mod types {
+    pub enum DoubleOpt<T> {
         None,
         Some(T, T)
     }
 
-    trait ArraySum<T> {
+    pub trait ArraySum<T> {
         array_sum: T[4 + 1] -> DoubleOpt<T>,
     }
 }
 
 
-machine Empty with degree: 4 {
+pub machine Empty with degree: 4 {
     col witness w;
     w = w * w;
 }
\ No newline at end of file
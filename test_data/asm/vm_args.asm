@@ -1,9 +1,9 @@
 use std::machines::large_field::shift::Shift;
 use std::machines::large_field::shift::ByteShift;
 
-let N: int = 65536;
+pub let N: int = 65536;
 
-machine Main with degree: N {
+pub machine Main with degree: N {
     reg pc[@pc];
     reg X[<=];
     reg Y[<=];
@@ -37,7 +37,7 @@ machine Main with degree: N {
     }
 }
 
-machine WithArg(shift: Shift) with degree: N {
+pub machine WithArg(shift: Shift) with degree: N {
     reg pc[@pc];
     reg X[<=];
     reg Y[<=];
@@ -1,11 +1,11 @@
 use std::machines::large_field::memory::Memory;
 
-mod test_util;
+pub mod test_util;
 use test_util::FakeByte2 as Byte2;
 
-let N: int = 256;
+pub let N: int = 256;
 
-machine Main with degree: N {
+pub machine Main with degree: N {
     reg pc[@pc];
     reg X[<=];
     reg Y[<=];
@@ -57,7 +57,7 @@ machine Main with degree: N {
     }
 }
 
-machine WithArg(mem: Memory) with degree: N {
+pub machine WithArg(mem: Memory) with degree: N {
     reg pc[@pc];
     reg X[<=];
     reg Y[<=];
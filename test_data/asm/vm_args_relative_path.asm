@@ -1,7 +1,7 @@
 
-let N: int = 16;
+pub let N: int = 16;
 
-machine Main with degree: N {
+pub machine Main with degree: N {
     reg pc[@pc];
     reg X[<=];
     reg Y[<=];
@@ -32,8 +32,8 @@ machine Main with degree: N {
 }
 
 // check that relative paths work in machine parameters
-mod a {
-    machine WithArg(arith: super::b::Arith) with degree: super::N {
+pub mod a {
+    pub machine WithArg(arith: super::b::Arith) with degree: super::N {
         reg pc[@pc];
         reg X[<=];
         reg Y[<=];
@@ -55,8 +55,8 @@ mod a {
     }
 }
 
-mod b {
-    machine Arith with degree: super::N {
+pub mod b {
+    pub machine Arith with degree: super::N {
         reg pc[@pc];
         reg X[<=];
         reg A;
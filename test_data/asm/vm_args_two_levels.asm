@@ -1,11 +1,11 @@
 use std::machines::large_field::memory::Memory;
 
-mod test_util;
+pub mod test_util;
 use test_util::FakeByte2 as Byte2;
 
-let N: int = 256;
+pub let N: int = 256;
 
-machine Main with degree: N {
+pub machine Main with degree: N {
     reg pc[@pc];
     reg X[<=];
     reg Y[<=];
@@ -73,7 +73,7 @@ machine Main with degree: N {
     }
 }
 
-machine Child(mem: Memory) {
+pub machine Child(mem: Memory) {
     reg pc[@pc];
     reg X[<=];
     reg Y[<=];
@@ -110,7 +110,7 @@ machine Child(mem: Memory) {
     }
 }
 
-machine GrandChild(mem: Memory) {
+pub machine GrandChild(mem: Memory) {
     reg pc[@pc];
     reg X[<=];
     reg Y[<=];
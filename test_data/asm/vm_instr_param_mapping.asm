@@ -1,6 +1,6 @@
-let N: int = 64;
+pub let N: int = 64;
 
-machine SubVM with degree: N {
+pub machine SubVM with degree: N {
     reg pc[@pc];
     reg X[<=];
     reg Y[<=];
@@ -16,7 +16,7 @@ machine SubVM with degree: N {
     }
 }
 
-machine AddVM with
+pub machine AddVM with
     degree: N,
     latch: latch,
     operation_id: operation_id
@@ -33,7 +33,7 @@ machine AddVM with
     z = y + x;
 }
 
-machine Main with degree: N {
+pub machine Main with degree: N {
     SubVM subvm;
     AddVM addvm;
 
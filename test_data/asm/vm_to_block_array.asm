@@ -1,4 +1,4 @@
-machine Main with degree: 16 {
+pub machine Main with degree: 16 {
     Arith arith;
 
     reg pc[@pc];
@@ -19,7 +19,7 @@ machine Main with degree: 16 {
     }
 }
 
-machine Arith with
+pub machine Arith with
     latch: latch,
     operation_id: operation_id
 {
@@ -1,6 +1,6 @@
 // calls two functions in a submachine whose interface is different: one is `x, y, z` while the other one is `z, x, y`
 
-machine Arith with
+pub machine Arith with
     degree: 8,
     latch: latch,
     operation_id: operation_id
@@ -17,7 +17,7 @@ machine Arith with
     z = x + y;
 }
 
-machine Main with degree: 8 {
+pub machine Main with degree: 8 {
     Arith arith;
 
     reg pc[@pc];
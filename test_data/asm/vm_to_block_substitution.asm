@@ -0,0 +1,45 @@
+machine Arith with
+    latch: latch,
+    operation_id: operation_id,
+{
+
+    operation square_sum<0> x, y -> z;
+
+    operation mul_sum_diff<1> x, y -> z;
+
+    col witness operation_id;
+    col fixed latch = [1]*;
+    col witness x;
+    col witness y;
+    col witness z;
+    // Determined by linear identities, so the optimizer substitutes them.
+    col witness sum;
+    col witness diff;
+    sum = x + y;
+    diff = x - y;
+    z = (1 - operation_id) * sum * sum + operation_id * sum * diff;
+}
+
+machine Main with degree: 32 {
+    Arith arith;
+
+    reg pc[@pc];
+    reg X[<=];
+    reg Y[<=];
+    reg Z[<=];
+    reg A;
+
+    instr square_sum X, Y -> Z link => Z = arith.square_sum(X, Y);
+    instr mul_sum_diff X, Y -> Z link => Z = arith.mul_sum_diff(X, Y);
+    instr assert_eq X, Y { X = Y }
+
+    function main {
+        A <== square_sum(2, 1);
+        assert_eq A, 9;
+        A <== mul_sum_diff(5, 3);
+        assert_eq A, 16;
+        A <== square_sum(A, 0);
+        assert_eq A, 256;
+        return;
+    }
+}
@@ -1,4 +1,4 @@
-machine Inc with
+pub machine Inc with
     degree: 8,
     latch: latch,
     operation_id: operation_id
@@ -12,7 +12,7 @@ machine Inc with
     y = x + 1;
 }
 
-machine Assert1 with
+pub machine Assert1 with
     degree: 8,
     latch: latch,
     operation_id: operation_id
@@ -32,7 +32,7 @@ machine Assert1 with
     y = 2;
 }
 
-machine Main with degree: 8 {
+pub machine Main with degree: 8 {
     Assert1 assert1;
 
     reg pc[@pc];
@@ -1,4 +1,4 @@
-machine Binary with
+pub machine Binary with
     latch: latch,
     operation_id: operation_id,
 {
@@ -19,7 +19,7 @@ machine Binary with
     [ operation_id, x, y, z ] in [ P_FUNCTION, P_X, P_Y, P_Z ];
 }
 
-machine Arith with
+pub machine Arith with
     latch: latch,
     operation_id: operation_id,
 {
@@ -36,7 +36,7 @@ machine Arith with
     z = (1 - operation_id) * (x + y) + operation_id * (x - y);
 }
 
-machine Main with degree: 32 {
+pub machine Main with degree: 32 {
     Arith arith;
     Binary binary;
 
@@ -1,6 +1,6 @@
-let N: int = 16;
+pub let N: int = 16;
 
-machine Main with degree: N {
+pub machine Main with degree: N {
 
     VM vm;
 
@@ -23,7 +23,7 @@ machine Main with degree: N {
     }
 }
 
-machine VM with degree: N {
+pub machine VM with degree: N {
 
     reg pc[@pc];
     reg X[<=];
@@ -1,4 +1,4 @@
-machine Main with degree: 128 {
+pub machine Main with degree: 128 {
     Pow pow;
 
     reg pc[@pc];
@@ -31,7 +31,7 @@ machine Main with degree: 128 {
 }
 
 // Computes X^Y by multiplying X by itself Y times
-machine Pow {
+pub machine Pow {
 
     reg pc[@pc];
     reg X[<=];
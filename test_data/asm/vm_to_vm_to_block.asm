@@ -1,6 +1,6 @@
-let N: int = 64;
+pub let N: int = 64;
 
-machine Main with degree: N {
+pub machine Main with degree: N {
     Pythagoras pythagoras;
 
     reg pc[@pc];
@@ -27,7 +27,7 @@ machine Main with degree: N {
 }
 
 
-machine Pythagoras with degree: N {
+pub machine Pythagoras with degree: N {
 
     Arith arith;
 
@@ -50,7 +50,7 @@ machine Pythagoras with degree: N {
     }
 }
 
-machine Arith with
+pub machine Arith with
     latch: latch,
     operation_id: operation_id,
 {
@@ -1,6 +1,6 @@
-let N: int = 64;
+pub let N: int = 64;
 
-machine Main with degree: N {
+pub machine Main with degree: N {
     Pythagoras pythagoras;
 
     reg pc[@pc];
@@ -26,7 +26,7 @@ machine Main with degree: N {
     }
 }
 
-machine Pythagoras with degree: N {
+pub machine Pythagoras with degree: N {
 
     Arith arith;
 
@@ -49,7 +49,7 @@ machine Pythagoras with degree: N {
     }
 }
 
-machine Arith with degree: N {
+pub machine Arith with degree: N {
 
     reg pc[@pc];
     reg X[<=];
@@ -1,7 +1,7 @@
 use std::machines::small_field::add_sub::AddSub;
 use std::machines::range::Byte2;
 
-machine Main {
+pub machine Main {
     reg pc[@pc];
     reg A_h[<=];
     reg A_l[<=];
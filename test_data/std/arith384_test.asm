@@ -0,0 +1,260 @@
+use std::machines::large_field::arith384::Arith384;
+use std::machines::range::Byte2;
+use std::machines::large_field::memory::Memory;
+
+pub machine Main with degree: 65536 {
+    reg pc[@pc];
+    reg X[<=];
+    reg ADDR1[<=];
+    reg ADDR2[<=];
+
+    // Increase the time step by 2 in each row, so that the arith384 machine
+    // can read in the given time step and write in the next time step.
+    col fixed STEP(i) { 2 * i };
+    Byte2 byte2;
+    Memory memory(byte2);
+    instr mstore ADDR1, X -> link ~> memory.mstore(ADDR1, STEP, X);
+
+    Arith384 arith384(memory, byte2);
+    instr mul_add ADDR1, ADDR2 -> link ~> arith384.mul_add(ADDR1, ADDR2, STEP);
+    instr mul_sub ADDR1, ADDR2 -> link ~> arith384.mul_sub(ADDR1, ADDR2, STEP);
+
+    col witness val;
+    instr assert_eq ADDR1, X ->
+        link ~> val = memory.mload(ADDR1, STEP)
+    {
+        val = X
+    }
+
+    function main {
+        // Each test writes a, b, c, d and m (12 words each) to the input address
+        // and checks the 12 words of the result at the output address.
+
+        // BLS12-381: a * b
+        mstore 0, 0xa4ff0d2e;
+        mstore 4, 0xd7e77b64;
+        mstore 8, 0x623958a8;
+        mstore 12, 0x0c4705de;
+        mstore 16, 0xc85d3e87;
+        mstore 20, 0x883f678a;
+        mstore 24, 0xbdb24532;
+        mstore 28, 0xc46f8780;
+        mstore 32, 0xef9cfe25;
+        mstore 36, 0x065ccd08;
+        mstore 40, 0xf51a7b45;
+        mstore 44, 0x144d4f7f;
+        mstore 48, 0x5fc36774;
+        mstore 52, 0x3e421b4c;
+        mstore 56, 0x7068ce06;
+        mstore 60, 0x3f28b6f4;
+        mstore 64, 0x1fb3ce3a;
+        mstore 68, 0xb2edd349;
+        mstore 72, 0x8eeed390;
+        mstore 76, 0x3b9acd1c;
+        mstore 80, 0x612a948b;
+        mstore 84, 0xf209d61c;
+        mstore 88, 0x48ab7279;
+        mstore 92, 0x01c25145;
+        mstore 96, 0x00000000;
+        mstore 100, 0x00000000;
+        mstore 104, 0x00000000;
+        mstore 108, 0x00000000;
+        mstore 112, 0x00000000;
+        mstore 116, 0x00000000;
+        mstore 120, 0x00000000;
+        mstore 124, 0x00000000;
+        mstore 128, 0x00000000;
+        mstore 132, 0x00000000;
+        mstore 136, 0x00000000;
+        mstore 140, 0x00000000;
+        mstore 144, 0x00000000;
+        mstore 148, 0x00000000;
+        mstore 152, 0x00000000;
+        mstore 156, 0x00000000;
+        mstore 160, 0x00000000;
+        mstore 164, 0x00000000;
+        mstore 168, 0x00000000;
+        mstore 172, 0x00000000;
+        mstore 176, 0x00000000;
+        mstore 180, 0x00000000;
+        mstore 184, 0x00000000;
+        mstore 188, 0x00000000;
+        mstore 192, 0xffffaaab;
+        mstore 196, 0xb9feffff;
+        mstore 200, 0xb153ffff;
+        mstore 204, 0x1eabfffe;
+        mstore 208, 0xf6b0f624;
+        mstore 212, 0x6730d2a0;
+        mstore 216, 0xf38512bf;
+        mstore 220, 0x64774b84;
+        mstore 224, 0x434bacd7;
+        mstore 228, 0x4b1ba7b6;
+        mstore 232, 0x397fe69a;
+        mstore 236, 0x1a0111ea;
+        mul_add 0, 240;
+        assert_eq 240, 0x50288fbe;
+        assert_eq 244, 0x045bf673;
+        assert_eq 248, 0xf1b43732;
+        assert_eq 252, 0x4490f8ff;
+        assert_eq 256, 0xef68fe96;
+        assert_eq 260, 0x76c295dc;
+        assert_eq 264, 0x05ae6988;
+        assert_eq 268, 0xe4a5cb65;
+        assert_eq 272, 0x34210357;
+        assert_eq 276, 0x9e378296;
+        assert_eq 280, 0xfff858e6;
+        assert_eq 284, 0x194360fd;
+
+        // BLS12-381: a * b - c * d, where the difference is negative
+        mstore 0, 0xe87b9c8f;
+        mstore 4, 0x3c844f05;
+        mstore 8, 0xdfdbc191;
+        mstore 12, 0xb17466c0;
+        mstore 16, 0x988868e6;
+        mstore 20, 0x46378b5b;
+        mstore 24, 0xeec3cf5c;
+        mstore 28, 0xf8815767;
+        mstore 32, 0x64800869;
+        mstore 36, 0xe406dfdd;
+        mstore 40, 0xadc00c0f;
+        mstore 44, 0x0004c457;
+        mstore 48, 0x55277e0e;
+        mstore 52, 0x01a58038;
+        mstore 56, 0x72692ec4;
+        mstore 60, 0x80de7db1;
+        mstore 64, 0x0309f4cc;
+        mstore 68, 0x25c2280b;
+        mstore 72, 0x11052fed;
+        mstore 76, 0xc5168bb4;
+        mstore 80, 0x2be10f32;
+        mstore 84, 0xbce76388;
+        mstore 88, 0xeb6003cf;
+        mstore 92, 0x0004a5d2;
+        mstore 96, 0xffffaaaa;
+        mstore 100, 0xb9feffff;
+        mstore 104, 0xb153ffff;
+        mstore 108, 0x1eabfffe;
+        mstore 112, 0xf6b0f624;
+        mstore 116, 0x6730d2a0;
+        mstore 120, 0xf38512bf;
+        mstore 124, 0x64774b84;
+        mstore 128, 0x434bacd7;
+        mstore 132, 0x4b1ba7b6;
+        mstore 136, 0x397fe69a;
+        mstore 140, 0x1a0111ea;
+        mstore 144, 0xffffaaa9;
+        mstore 148, 0xb9feffff;
+        mstore 152, 0xb153ffff;
+        mstore 156, 0x1eabfffe;
+        mstore 160, 0xf6b0f624;
+        mstore 164, 0x6730d2a0;
+        mstore 168, 0xf38512bf;
+        mstore 172, 0x64774b84;
+        mstore 176, 0x434bacd7;
+        mstore 180, 0x4b1ba7b6;
+        mstore 184, 0x397fe69a;
+        mstore 188, 0x1a0111ea;
+        mstore 192, 0xffffaaab;
+        mstore 196, 0xb9feffff;
+        mstore 200, 0xb153ffff;
+        mstore 204, 0x1eabfffe;
+        mstore 208, 0xf6b0f624;
+        mstore 212, 0x6730d2a0;
+        mstore 216, 0xf38512bf;
+        mstore 220, 0x64774b84;
+        mstore 224, 0x434bacd7;
+        mstore 228, 0x4b1ba7b6;
+        mstore 232, 0x397fe69a;
+        mstore 236, 0x1a0111ea;
+        mul_sub 0, 240;
+        assert_eq 240, 0x6843b029;
+        assert_eq 244, 0xee00df97;
+        assert_eq 248, 0x9eb86f6e;
+        assert_eq 252, 0x09c85252;
+        assert_eq 256, 0x9bbac33a;
+        assert_eq 260, 0x83f26ca3;
+        assert_eq 264, 0xe08ff9a2;
+        assert_eq 268, 0xea140e42;
+        assert_eq 272, 0x8d4c9514;
+        assert_eq 276, 0x7cf7c600;
+        assert_eq 280, 0x357a82fb;
+        assert_eq 284, 0x159bcff6;
+
+        // BN254: a * b + c * d, writing the result over the input
+        mstore 1000, 0x2febd01b;
+        mstore 1004, 0xf0efbca5;
+        mstore 1008, 0xfba8a814;
+        mstore 1012, 0x7ff52902;
+        mstore 1016, 0xe1105f67;
+        mstore 1020, 0x2d5e298a;
+        mstore 1024, 0x72a55909;
+        mstore 1028, 0x1c7ec31c;
+        mstore 1032, 0x00000000;
+        mstore 1036, 0x00000000;
+        mstore 1040, 0x00000000;
+        mstore 1044, 0x00000000;
+        mstore 1048, 0xe352aa3a;
+        mstore 1052, 0xcca49f64;
+        mstore 1056, 0xb0493662;
+        mstore 1060, 0xc2905f8f;
+        mstore 1064, 0x3b016b21;
+        mstore 1068, 0x5516fd2b;
+        mstore 1072, 0x9d32fae0;
+        mstore 1076, 0x1d700db2;
+        mstore 1080, 0x00000000;
+        mstore 1084, 0x00000000;
+        mstore 1088, 0x00000000;
+        mstore 1092, 0x00000000;
+        mstore 1096, 0x655e23ad;
+        mstore 1100, 0x902214f6;
+        mstore 1104, 0x455b14d9;
+        mstore 1108, 0x4ebb6703;
+        mstore 1112, 0xbc8d2a94;
+        mstore 1116, 0x0cf80bb1;
+        mstore 1120, 0x4c059ae0;
+        mstore 1124, 0x274836e9;
+        mstore 1128, 0x00000000;
+        mstore 1132, 0x00000000;
+        mstore 1136, 0x00000000;
+        mstore 1140, 0x00000000;
+        mstore 1144, 0xdf7bb373;
+        mstore 1148, 0x98d8ce68;
+        mstore 1152, 0xb4e91485;
+        mstore 1156, 0x06577990;
+        mstore 1160, 0x7690b757;
+        mstore 1164, 0xe8ea44dc;
+        mstore 1168, 0xf897b86f;
+        mstore 1172, 0x1071a233;
+        mstore 1176, 0x00000000;
+        mstore 1180, 0x00000000;
+        mstore 1184, 0x00000000;
+        mstore 1188, 0x00000000;
+        mstore 1192, 0xd87cfd47;
+        mstore 1196, 0x3c208c16;
+        mstore 1200, 0x6871ca8d;
+        mstore 1204, 0x97816a91;
+        mstore 1208, 0x8181585d;
+        mstore 1212, 0xb85045b6;
+        mstore 1216, 0xe131a029;
+        mstore 1220, 0x30644e72;
+        mstore 1224, 0x00000000;
+        mstore 1228, 0x00000000;
+        mstore 1232, 0x00000000;
+        mstore 1236, 0x00000000;
+        mul_add 1000, 1000;
+        assert_eq 1000, 0x0372b013;
+        assert_eq 1004, 0x8ff93cef;
+        assert_eq 1008, 0x042046c6;
+        assert_eq 1012, 0x78a19f49;
+        assert_eq 1016, 0x4d4084bc;
+        assert_eq 1020, 0x9a10f01d;
+        assert_eq 1024, 0xd59f38fa;
+        assert_eq 1028, 0x23c2ed30;
+        assert_eq 1032, 0x00000000;
+        assert_eq 1036, 0x00000000;
+        assert_eq 1040, 0x00000000;
+        assert_eq 1044, 0x00000000;
+
+        return;
+    }
+}
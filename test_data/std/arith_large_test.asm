@@ -1,6 +1,6 @@
 use std::machines::large_field::arith::Arith;
 
-machine Main with degree: 65536 {
+pub machine Main with degree: 65536 {
     reg pc[@pc];
     reg A0[<=];
     reg A1[<=];
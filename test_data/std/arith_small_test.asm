@@ -2,7 +2,7 @@ use std::machines::small_field::arith::Arith;
 use std::machines::range::Byte;
 use std::machines::range::Byte2;
 
-machine Main with degree: 65536 {
+pub machine Main with degree: 65536 {
     reg pc[@pc];
     reg A0[<=];
     reg A1[<=];
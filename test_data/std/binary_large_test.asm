@@ -1,7 +1,7 @@
 use std::machines::binary::ByteBinary;
 use std::machines::large_field::binary::Binary;
 
-machine Main with degree: 262144 {
+pub machine Main with degree: 262144 {
     reg pc[@pc];
     reg X0[<=];
     reg X1[<=];
@@ -14,6 +14,7 @@ machine Main with degree: 262144 {
     instr and X0, X1 -> X2 link ~> X2 = binary.and(X0, X1);
     instr or X0, X1 -> X2 link ~> X2 = binary.or(X0, X1);
     instr xor X0, X1 -> X2 link ~> X2 = binary.xor(X0, X1);
+    instr bytewise X0, X1 -> X2 link ~> X2 = binary.bytewise(X0, X1);
 
     instr assert_eq X0, X1 {
         X0 = X1
@@ -63,6 +64,16 @@ machine Main with degree: 262144 {
         A <== xor(0xabcdef01, 0);
         assert_eq A, 0xabcdef01;
 
+        // BYTEWISE
+        A <== bytewise(0xabcdef01, 0);
+        assert_eq A, 0x05050701;
+        A <== bytewise(0xffffffff, 0);
+        assert_eq A, 0x08080808;
+        A <== bytewise(0xabcdef01, 0x01010101);
+        assert_eq A, 0xd5b3f780;
+        A <== bytewise(0xab00ef00, 0x02020202);
+        assert_eq A, 0xff00ff00;
+
         return;
     }
 }
@@ -1,7 +1,7 @@
 use std::machines::binary::ByteBinary;
 use std::machines::small_field::binary::Binary;
 
-machine Main {
+pub machine Main {
     reg pc[@pc];
     reg X0_1[<=];
     reg X0_2[<=];
@@ -18,6 +18,7 @@ machine Main {
     instr and X0_1, X0_2, X1_1, X1_2 -> X2_1, X2_2 link ~> (X2_1, X2_2) = binary.and(X0_1, X0_2, X1_1, X1_2);
     instr or X0_1, X0_2, X1_1, X1_2 -> X2_1, X2_2 link ~> (X2_1, X2_2) = binary.or(X0_1, X0_2, X1_1, X1_2);
     instr xor X0_1, X0_2, X1_1, X1_2 -> X2_1, X2_2 link ~> (X2_1, X2_2) = binary.xor(X0_1, X0_2, X1_1, X1_2);
+    instr bytewise X0_1, X0_2, X1_1, X1_2 -> X2_1, X2_2 link ~> (X2_1, X2_2) = binary.bytewise(X0_1, X0_2, X1_1, X1_2);
 
     instr assert_eq X0_1, X0_2, X1_1, X1_2 {
         X0_1 = X1_1,
@@ -68,6 +69,14 @@ machine Main {
         A1, A2 <== xor(0xabcd, 0xef01, 0, 0);
         assert_eq A1, A2, 0xabcd, 0xef01;
 
+        // BYTEWISE
+        A1, A2 <== bytewise(0xabcd, 0xef01, 0, 0);
+        assert_eq A1, A2, 0x0505, 0x0701;
+        A1, A2 <== bytewise(0xabcd, 0xef01, 0x0101, 0x0101);
+        assert_eq A1, A2, 0xd5b3, 0xf780;
+        A1, A2 <== bytewise(0xab00, 0xef00, 0x0202, 0x0202);
+        assert_eq A1, A2, 0xff00, 0xff00;
+
         return;
     }
 }
@@ -4,7 +4,7 @@ use std::protocols::permutation_via_bus::permutation;
 use std::math::fp2::from_base;
 use std::prover::challenge;
 
-machine Main with degree: 8 {
+pub machine Main with degree: 8 {
     let alpha = from_base(challenge(0, 1));
     let beta = from_base(challenge(0, 2));
 
@@ -6,7 +6,7 @@ use std::protocols::permutation_via_bus::compute_next_z_receive_permutation;
 use std::math::fp2::Fp2;
 use std::prover::challenge;
 
-machine Main with degree: 8 {
+pub machine Main with degree: 8 {
 
     let alpha1: expr = challenge(0, 1);
     let alpha2: expr = challenge(0, 2);
@@ -4,7 +4,7 @@ use std::protocols::lookup::lookup;
 use std::math::fp2::from_base;
 use std::prover::challenge;
 
-machine Main with degree: 8 {
+pub machine Main with degree: 8 {
     col fixed random_six = [1, 1, 1, 0, 1, 1, 1, 0];
     col fixed first_seven = [1, 1, 1, 1, 1, 1, 1, 0];
 
@@ -5,7 +5,7 @@ use std::protocols::lookup::compute_next_z;
 use std::math::fp2::Fp2;
 use std::prover::challenge;
 
-machine Main with degree: 8 {
+pub machine Main with degree: 8 {
     col fixed a_sel = [0, 1, 1, 1, 0, 1, 0, 0];
     col fixed b_sel = [1, 1, 0, 1, 1, 1, 1, 0];
 
@@ -5,7 +5,7 @@ use std::protocols::lookup::compute_next_z;
 use std::math::fp2::Fp2;
 use std::prover::challenge;
 
-machine Main with degree: 8 {
+pub machine Main with degree: 8 {
     col fixed a = [1, 1, 4, 1, 1, 2, 1, 1];
     let b;
     query |i| {
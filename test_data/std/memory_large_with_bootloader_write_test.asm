@@ -1,7 +1,7 @@
 use std::machines::range::Byte2;
 use std::machines::large_field::memory_with_bootloader_write::MemoryWithBootloaderWrite;
 
-machine Main with degree: 65536 {
+pub machine Main with degree: 65536 {
     reg pc[@pc];
     reg X[<=];
     reg Y[<=];
@@ -2,7 +2,7 @@ use std::machines::range::Bit12;
 use std::machines::range::Byte2;
 use std::machines::small_field::memory::Memory;
 
-machine Main with degree: 65536 {
+pub machine Main with degree: 65536 {
     reg pc[@pc];
     reg X1[<=];
     reg X2[<=];
@@ -3,7 +3,7 @@ use std::protocols::lookup::lookup;
 use std::math::fp2::from_base;
 use std::prover::challenge;
 
-machine Main with degree: 8 {
+pub machine Main with degree: 8 {
 
     col fixed x = [1, 5, 2, 6, 4, 2, 6, 3];
     col witness y;
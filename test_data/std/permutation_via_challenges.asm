@@ -4,7 +4,7 @@ use std::protocols::permutation::permutation;
 use std::math::fp2::from_base;
 use std::prover::challenge;
 
-machine Main with degree: 8 {
+pub machine Main with degree: 8 {
 
     col fixed first_four = [1, 1, 1, 1, 0, 0, 0, 0];
 
@@ -5,7 +5,7 @@ use std::protocols::permutation::compute_next_z;
 use std::math::fp2::Fp2;
 use std::prover::challenge;
 
-machine Main with degree: 8 {
+pub machine Main with degree: 8 {
 
     col fixed first_four = [1, 1, 1, 1, 0, 0, 0, 0];
 
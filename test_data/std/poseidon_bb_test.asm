@@ -5,7 +5,7 @@ use std::machines::small_field::memory::Memory;
 use std::machines::split::ByteCompare;
 use std::machines::split::split_bb::SplitBB;
 
-machine Main with degree: 65536 {
+pub machine Main with degree: 65536 {
     reg pc[@pc];
     reg X1[<=];
     reg X2[<=];
@@ -1,6 +1,6 @@
 use std::machines::hash::poseidon_bn254::PoseidonBN254;
 
-machine Main with degree: 512 {
+pub machine Main with degree: 512 {
     reg pc[@pc];
     reg X0[<=];
     reg X1[<=];
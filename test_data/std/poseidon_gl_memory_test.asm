@@ -4,7 +4,7 @@ use std::machines::large_field::memory::Memory;
 use std::machines::split::ByteCompare;
 use std::machines::split::split_gl::SplitGL;
 
-machine Main with degree: 65536 {
+pub machine Main with degree: 65536 {
     reg pc[@pc];
     reg X1[<=];
     reg X2[<=];
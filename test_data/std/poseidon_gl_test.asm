@@ -1,6 +1,6 @@
 use std::machines::hash::poseidon_gl::PoseidonGL;
 
-machine Main with degree: 256 {
+pub machine Main with degree: 256 {
     reg pc[@pc];
     reg X0[<=];
     reg X1[<=];
@@ -1,7 +1,7 @@
 use std::machines::large_field::rotate::ByteRotate;
 use std::machines::large_field::rotate::Rotate;
 
-machine Main with degree: 65536 {
+pub machine Main with degree: 65536 {
     reg pc[@pc];
     reg X0[<=];
     reg X1[<=];
@@ -1,7 +1,7 @@
 use std::machines::small_field::rotate::ByteRotate;
 use std::machines::small_field::rotate::Rotate;
 
-machine Main with degree: 65536 {
+pub machine Main with degree: 65536 {
     reg pc[@pc];
     reg X0_1[<=];
     reg X0_2[<=];
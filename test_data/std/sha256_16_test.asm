@@ -0,0 +1,159 @@
+use std::machines::range::Byte2;
+use std::machines::hash::sha256::Sha256_16;
+
+pub machine Main with degree: 256 {
+    reg pc[@pc];
+
+    reg X0[<=];
+    reg X1[<=];
+    reg X2[<=];
+    reg X3[<=];
+    reg X4[<=];
+    reg X5[<=];
+    reg X6[<=];
+    reg X7[<=];
+    reg X8[<=];
+    reg X9[<=];
+    reg X10[<=];
+    reg X11[<=];
+    reg X12[<=];
+    reg X13[<=];
+    reg X14[<=];
+    reg X15[<=];
+    reg X16[<=];
+    reg X17[<=];
+    reg X18[<=];
+    reg X19[<=];
+    reg X20[<=];
+    reg X21[<=];
+    reg X22[<=];
+    reg X23[<=];
+    reg X24[<=];
+    reg X25[<=];
+    reg X26[<=];
+    reg X27[<=];
+    reg X28[<=];
+    reg X29[<=];
+    reg X30[<=];
+    reg X31[<=];
+    reg X32[<=];
+    reg X33[<=];
+    reg X34[<=];
+    reg X35[<=];
+    reg X36[<=];
+    reg X37[<=];
+    reg X38[<=];
+    reg X39[<=];
+    reg X40[<=];
+    reg X41[<=];
+    reg X42[<=];
+    reg X43[<=];
+    reg X44[<=];
+    reg X45[<=];
+    reg X46[<=];
+    reg X47[<=];
+
+    reg Y0[<=];
+    reg Y1[<=];
+    reg Y2[<=];
+    reg Y3[<=];
+    reg Y4[<=];
+    reg Y5[<=];
+    reg Y6[<=];
+    reg Y7[<=];
+    reg Y8[<=];
+    reg Y9[<=];
+    reg Y10[<=];
+    reg Y11[<=];
+    reg Y12[<=];
+    reg Y13[<=];
+    reg Y14[<=];
+    reg Y15[<=];
+
+    reg A0;
+    reg A1;
+    reg A2;
+    reg A3;
+    reg A4;
+    reg A5;
+    reg A6;
+    reg A7;
+    reg A8;
+    reg A9;
+    reg A10;
+    reg A11;
+    reg A12;
+    reg A13;
+    reg A14;
+    reg A15;
+
+    Byte2 byte2;
+    Sha256_16 sha256_16(byte2);
+
+    instr sha256_16 X0, X1, X2, X3, X4, X5, X6, X7, X8, X9, X10, X11, X12, X13, X14, X15, X16, X17, X18, X19, X20, X21, X22, X23, X24, X25, X26, X27, X28, X29, X30, X31, X32, X33, X34, X35, X36, X37, X38, X39, X40, X41, X42, X43, X44, X45, X46, X47 -> Y0, Y1, Y2, Y3, Y4, Y5, Y6, Y7, Y8, Y9, Y10, Y11, Y12, Y13, Y14, Y15 link => (Y0, Y1, Y2, Y3, Y4, Y5, Y6, Y7, Y8, Y9, Y10, Y11, Y12, Y13, Y14, Y15) = sha256_16.sha256_16(X0, X1, X2, X3, X4, X5, X6, X7, X8, X9, X10, X11, X12, X13, X14, X15, X16, X17, X18, X19, X20, X21, X22, X23, X24, X25, X26, X27, X28, X29, X30, X31, X32, X33, X34, X35, X36, X37, X38, X39, X40, X41, X42, X43, X44, X45, X46, X47);
+
+    instr assert_eq X0, X1 {
+        X0 = X1
+    }
+
+    function main {
+        // sha256("abc"), all 32-bit words in 16-bit little endian limbs.
+        A0, A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13, A14, A15 <== sha256_16(0xe667, 0x6a09, 0xae85, 0xbb67, 0xf372, 0x3c6e, 0xf53a, 0xa54f, 0x527f, 0x510e, 0x688c, 0x9b05, 0xd9ab, 0x1f83, 0xcd19, 0x5be0, 0x6380, 0x6162, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x18, 0x0);
+        assert_eq A0, 0x16bf;
+        assert_eq A1, 0xba78;
+        assert_eq A2, 0xcfea;
+        assert_eq A3, 0x8f01;
+        assert_eq A4, 0x40de;
+        assert_eq A5, 0x4141;
+        assert_eq A6, 0x2223;
+        assert_eq A7, 0x5dae;
+        assert_eq A8, 0x61a3;
+        assert_eq A9, 0xb003;
+        assert_eq A10, 0x7a9c;
+        assert_eq A11, 0x9617;
+        assert_eq A12, 0xff61;
+        assert_eq A13, 0xb410;
+        assert_eq A14, 0x15ad;
+        assert_eq A15, 0xf200;
+
+        // First block of sha256("abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq").
+        A0, A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13, A14, A15 <== sha256_16(0xe667, 0x6a09, 0xae85, 0xbb67, 0xf372, 0x3c6e, 0xf53a, 0xa54f, 0x527f, 0x510e, 0x688c, 0x9b05, 0xd9ab, 0x1f83, 0xcd19, 0x5be0, 0x6364, 0x6162, 0x6465, 0x6263, 0x6566, 0x6364, 0x6667, 0x6465, 0x6768, 0x6566, 0x6869, 0x6667, 0x696a, 0x6768, 0x6a6b, 0x6869, 0x6b6c, 0x696a, 0x6c6d, 0x6a6b, 0x6d6e, 0x6b6c, 0x6e6f, 0x6c6d, 0x6f70, 0x6d6e, 0x7071, 0x6e6f, 0x0, 0x8000, 0x0, 0x0);
+        assert_eq A0, 0x55d6;
+        assert_eq A1, 0x85e6;
+        assert_eq A2, 0x1795;
+        assert_eq A3, 0x417a;
+        assert_eq A4, 0x376a;
+        assert_eq A5, 0x3363;
+        assert_eq A6, 0xde5c;
+        assert_eq A7, 0x624c;
+        assert_eq A8, 0x9589;
+        assert_eq A9, 0x76e0;
+        assert_eq A10, 0xf811;
+        assert_eq A11, 0xcac5;
+        assert_eq A12, 0x32c1;
+        assert_eq A13, 0xcc4b;
+        assert_eq A14, 0x533a;
+        assert_eq A15, 0xf20e;
+
+        // Second block, continuing from the state after the first one.
+        A0, A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13, A14, A15 <== sha256_16(0x55d6, 0x85e6, 0x1795, 0x417a, 0x376a, 0x3363, 0xde5c, 0x624c, 0x9589, 0x76e0, 0xf811, 0xcac5, 0x32c1, 0xcc4b, 0x533a, 0xf20e, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x1c0, 0x0);
+        assert_eq A0, 0x6a61;
+        assert_eq A1, 0x248d;
+        assert_eq A2, 0x38b8;
+        assert_eq A3, 0xd206;
+        assert_eq A4, 0x2693;
+        assert_eq A5, 0xe5c0;
+        assert_eq A6, 0x6039;
+        assert_eq A7, 0xc3e;
+        assert_eq A8, 0xe459;
+        assert_eq A9, 0xa33c;
+        assert_eq A10, 0x2167;
+        assert_eq A11, 0x64ff;
+        assert_eq A12, 0xedd4;
+        assert_eq A13, 0xf6ec;
+        assert_eq A14, 0x6c1;
+        assert_eq A15, 0x19db;
+
+        return;
+    }
+}
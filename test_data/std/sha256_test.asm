@@ -0,0 +1,140 @@
+use std::machines::hash::sha256::Sha256;
+use std::machines::range::Byte2;
+use std::machines::large_field::memory::Memory;
+
+pub machine Main with degree: 65536 {
+    reg pc[@pc];
+    reg X[<=];
+    reg ADDR1[<=];
+    reg ADDR2[<=];
+
+    // Increase the time step by 2 in each row, so that the sha256 machine
+    // can read in the given time step and write in the next time step.
+    col fixed STEP(i) { 2 * i };
+    Byte2 byte2;
+    Memory memory(byte2);
+    instr mstore ADDR1, X -> link ~> memory.mstore(ADDR1, STEP, X);
+
+    Sha256 sha256(memory, byte2);
+    instr sha256 ADDR1, ADDR2 -> link ~> sha256.sha256(ADDR1, ADDR2, STEP);
+
+    col witness val;
+    instr assert_eq ADDR1, X ->
+        link ~> val = memory.mload(ADDR1, STEP)
+    {
+        val = X
+    }
+
+    function main {
+
+        // See test vectors at:
+        // https://csrc.nist.gov/projects/cryptographic-standards-and-guidelines/example-values
+
+        // Hash "abc", which fits in a single block:
+        // The initial state
+        mstore 0, 0x6a09e667;
+        mstore 4, 0xbb67ae85;
+        mstore 8, 0x3c6ef372;
+        mstore 12, 0xa54ff53a;
+        mstore 16, 0x510e527f;
+        mstore 20, 0x9b05688c;
+        mstore 24, 0x1f83d9ab;
+        mstore 28, 0x5be0cd19;
+        // The padded message
+        mstore 32, 0x61626380;
+        mstore 36, 0x00000000;
+        mstore 40, 0x00000000;
+        mstore 44, 0x00000000;
+        mstore 48, 0x00000000;
+        mstore 52, 0x00000000;
+        mstore 56, 0x00000000;
+        mstore 60, 0x00000000;
+        mstore 64, 0x00000000;
+        mstore 68, 0x00000000;
+        mstore 72, 0x00000000;
+        mstore 76, 0x00000000;
+        mstore 80, 0x00000000;
+        mstore 84, 0x00000000;
+        mstore 88, 0x00000000;
+        mstore 92, 0x00000018;
+
+        sha256 0, 32;
+
+        assert_eq 0, 0xba7816bf;
+        assert_eq 4, 0x8f01cfea;
+        assert_eq 8, 0x414140de;
+        assert_eq 12, 0x5dae2223;
+        assert_eq 16, 0xb00361a3;
+        assert_eq 20, 0x96177a9c;
+        assert_eq 24, 0xb410ff61;
+        assert_eq 28, 0xf20015ad;
+
+        // Hash "abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq", which takes two blocks,
+        // with the state and the message block at other addresses:
+        mstore 200, 0x6a09e667;
+        mstore 204, 0xbb67ae85;
+        mstore 208, 0x3c6ef372;
+        mstore 212, 0xa54ff53a;
+        mstore 216, 0x510e527f;
+        mstore 220, 0x9b05688c;
+        mstore 224, 0x1f83d9ab;
+        mstore 228, 0x5be0cd19;
+        mstore 100, 0x61626364;
+        mstore 104, 0x62636465;
+        mstore 108, 0x63646566;
+        mstore 112, 0x64656667;
+        mstore 116, 0x65666768;
+        mstore 120, 0x66676869;
+        mstore 124, 0x6768696a;
+        mstore 128, 0x68696a6b;
+        mstore 132, 0x696a6b6c;
+        mstore 136, 0x6a6b6c6d;
+        mstore 140, 0x6b6c6d6e;
+        mstore 144, 0x6c6d6e6f;
+        mstore 148, 0x6d6e6f70;
+        mstore 152, 0x6e6f7071;
+        mstore 156, 0x80000000;
+        mstore 160, 0x00000000;
+
+        sha256 200, 100;
+
+        assert_eq 200, 0x85e655d6;
+        assert_eq 204, 0x417a1795;
+        assert_eq 208, 0x3363376a;
+        assert_eq 212, 0x624cde5c;
+        assert_eq 216, 0x76e09589;
+        assert_eq 220, 0xcac5f811;
+        assert_eq 224, 0xcc4b32c1;
+        assert_eq 228, 0xf20e533a;
+
+        mstore 100, 0x00000000;
+        mstore 104, 0x00000000;
+        mstore 108, 0x00000000;
+        mstore 112, 0x00000000;
+        mstore 116, 0x00000000;
+        mstore 120, 0x00000000;
+        mstore 124, 0x00000000;
+        mstore 128, 0x00000000;
+        mstore 132, 0x00000000;
+        mstore 136, 0x00000000;
+        mstore 140, 0x00000000;
+        mstore 144, 0x00000000;
+        mstore 148, 0x00000000;
+        mstore 152, 0x00000000;
+        mstore 156, 0x00000000;
+        mstore 160, 0x000001c0;
+
+        sha256 200, 100;
+
+        assert_eq 200, 0x248d6a61;
+        assert_eq 204, 0xd20638b8;
+        assert_eq 208, 0xe5c02693;
+        assert_eq 212, 0x0c3e6039;
+        assert_eq 216, 0xa33ce459;
+        assert_eq 220, 0x64ff2167;
+        assert_eq 224, 0xf6ecedd4;
+        assert_eq 228, 0x19db06c1;
+
+        return;
+    }
+}
@@ -1,7 +1,7 @@
 use std::machines::split::ByteCompare;
 use std::machines::split::split_gl::SplitGL;
 
-machine Main with degree: 65536 {
+pub machine Main with degree: 65536 {
     reg pc[@pc];
     reg X0[<=];
     reg X1[<=];
@@ -1,6 +1,6 @@
 use std::machines::write_once_memory::WriteOnceMemory;
 
-machine Main with degree: 256 {
+pub machine Main with degree: 256 {
     WriteOnceMemory memory;
 
     reg pc[@pc];